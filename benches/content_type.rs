@@ -0,0 +1,56 @@
+// benches/content_type.rs
+//
+// Per-request content-type lookup: table scans vs. the precompiled matcher.
+// Run with `cargo bench --bench content_type`.
+
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+
+use axum_jetpack::size_limit::{BufferStrategy, ContentTypeMatcher, SizeLimitConfig};
+
+const CONTENT_TYPES: &[&str] = &[
+    "application/json",
+    "application/json; charset=utf-8",
+    "Application/JSON",
+    "image/png",
+    "video/mp4",
+    "multipart/form-data; boundary=----x",
+    "application/x-unknown",
+];
+
+fn config() -> (SizeLimitConfig, BufferStrategy) {
+    let limits = SizeLimitConfig::default()
+        .with_default_limit("2mb")
+        .with_specific_limit("application/json", "100kb")
+        .with_specific_limit("application/xml", "100kb")
+        .with_specific_limit("multipart/form-data", "20mb")
+        .with_wildcard_limit("image/*", "5mb")
+        .with_wildcard_limit("video/*", "500mb")
+        .with_wildcard_limit("text/*", "256kb");
+    (limits, BufferStrategy::with_defaults())
+}
+
+fn lookup(c: &mut Criterion) {
+    let (limits, strategy) = config();
+    let matcher = ContentTypeMatcher::compile(&limits, &strategy);
+
+    let mut group = c.benchmark_group("content_type_lookup");
+    group.bench_function("scan", |b| {
+        b.iter(|| {
+            for content_type in CONTENT_TYPES {
+                black_box(limits.get_limit_for_content_type(black_box(content_type)));
+                black_box(strategy.should_buffer(black_box(content_type)));
+            }
+        })
+    });
+    group.bench_function("compiled", |b| {
+        b.iter(|| {
+            for content_type in CONTENT_TYPES {
+                black_box(matcher.lookup(black_box(content_type)));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, lookup);
+criterion_main!(benches);
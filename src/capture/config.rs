@@ -0,0 +1,114 @@
+use axum::http::{HeaderMap, HeaderName, header};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+/// Placeholder written instead of the value of a redacted header.
+pub const REDACTED: &str = "[redacted]";
+
+/// A sanitized record of one request and the decision made about it.
+///
+/// Envelopes are written as JSON Lines by [`with_capture`](crate::capture::with_capture)
+/// and read back by [`load_envelopes`](crate::capture::load_envelopes).
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RequestEnvelope {
+    /// Request method (e.g., `"POST"`).
+    pub method: String,
+    /// Route template when known (e.g., `"/users/{id}"`), otherwise the path.
+    /// The query string is never recorded.
+    pub route: String,
+    /// `Content-Type` header, if present.
+    pub content_type: Option<String>,
+    /// Declared `Content-Length`, if present.
+    pub content_length: Option<u64>,
+    /// Body bytes the application actually read.
+    pub body_bytes: u64,
+    /// Request headers, with sensitive values replaced by [`REDACTED`].
+    pub headers: Vec<(String, String)>,
+    /// First bytes of the body (lossy UTF-8), when body prefixes are enabled.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body_prefix: Option<String>,
+    /// Response status code.
+    pub status: u16,
+    /// Time from request to response headers, in milliseconds.
+    pub duration_ms: f64,
+}
+
+impl RequestEnvelope {
+    /// Size used when replaying: the declared length, else the bytes read.
+    pub fn size(&self) -> u64 {
+        self.content_length.unwrap_or(self.body_bytes)
+    }
+}
+
+/// Configuration for [`with_capture`](crate::capture::with_capture).
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::capture::CaptureConfig;
+///
+/// let config = CaptureConfig::new("captures/requests.jsonl")
+///     .with_redacted_header("x-session-token")
+///     .with_body_prefix(64);
+/// ```
+#[derive(Clone, Debug)]
+pub struct CaptureConfig {
+    /// File envelopes are appended to, one JSON object per line.
+    pub path: PathBuf,
+    /// Headers whose values are replaced by [`REDACTED`].
+    pub redacted_headers: Vec<HeaderName>,
+    /// Number of leading body bytes to record; `0` disables body prefixes.
+    pub body_prefix_len: usize,
+}
+
+impl CaptureConfig {
+    /// Creates a configuration writing to `path`.
+    ///
+    /// Credentials (`Authorization`, `Proxy-Authorization`, `Cookie`,
+    /// `X-API-Key`) are redacted by default and body prefixes are off.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            redacted_headers: vec![
+                header::AUTHORIZATION,
+                header::PROXY_AUTHORIZATION,
+                header::COOKIE,
+                HeaderName::from_static("x-api-key"),
+            ],
+            body_prefix_len: 0,
+        }
+    }
+
+    /// Builder method to redact an additional header.
+    ///
+    /// Invalid header names are ignored.
+    pub fn with_redacted_header(mut self, name: &str) -> Self {
+        if let Ok(name) = HeaderName::try_from(name) {
+            self.redacted_headers.push(name);
+        }
+        self
+    }
+
+    /// Builder method to record the first `len` body bytes.
+    ///
+    /// Bodies may contain personal data; enable this only for fixtures that
+    /// stay within the team that could see the traffic anyway.
+    pub fn with_body_prefix(mut self, len: usize) -> Self {
+        self.body_prefix_len = len;
+        self
+    }
+
+    /// Returns the headers as `(name, value)` pairs with sensitive values redacted.
+    pub fn sanitize(&self, headers: &HeaderMap) -> Vec<(String, String)> {
+        headers
+            .iter()
+            .map(|(name, value)| {
+                let value = if self.redacted_headers.contains(name) {
+                    REDACTED.to_string()
+                } else {
+                    String::from_utf8_lossy(value.as_bytes()).into_owned()
+                };
+                (name.as_str().to_string(), value)
+            })
+            .collect()
+    }
+}
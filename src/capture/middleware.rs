@@ -0,0 +1,134 @@
+//! Middleware recording request envelopes to a JSON Lines file.
+
+use axum::{
+    Router,
+    body::Body,
+    extract::{MatchedPath, Request, State},
+    http::{HeaderMap, HeaderName, header},
+    middleware::{self, Next},
+};
+use futures::StreamExt;
+use std::fs::{File, OpenOptions};
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+use crate::capture::{CaptureConfig, RequestEnvelope};
+
+struct Capture {
+    config: CaptureConfig,
+    file: Mutex<File>,
+}
+
+/// Records a sanitized envelope of every request to `config.path`.
+///
+/// Each request is appended as one JSON line once its response headers are
+/// ready. Body sizes (and optional prefixes) reflect what the application
+/// read by then. Replay the file with
+/// [`replay`](crate::capture::replay) to see how a candidate limit
+/// configuration would treat the same traffic.
+///
+/// Writes are synchronous and serialized; enable capture for sampling
+/// windows rather than permanently on high-traffic services.
+///
+/// # Arguments
+/// * `router` - The Axum router to wrap with middleware
+/// * `config` - Output file, redaction and body prefix settings
+///
+/// # Returns
+/// * `Ok(Router)` - A new router recording requests
+/// * `Err(String)` - If the output file cannot be opened
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::capture::{CaptureConfig, with_capture};
+///
+/// let router: Router = Router::new().route("/upload", post(|| async { "ok" }));
+/// let router = with_capture(router, CaptureConfig::new("requests.jsonl")).unwrap();
+/// ```
+pub fn with_capture(router: Router, config: CaptureConfig) -> Result<Router, String> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&config.path)
+        .map_err(|e| format!("Cannot open capture file {}: {}", config.path.display(), e))?;
+    let capture = Arc::new(Capture {
+        config,
+        file: Mutex::new(file),
+    });
+
+    Ok(router.layer(middleware::from_fn_with_state(
+        capture,
+        |State(capture): State<Arc<Capture>>, req: Request<Body>, next: Next| async move {
+            let started = Instant::now();
+            let route = req
+                .extensions()
+                .get::<MatchedPath>()
+                .map(|matched| matched.as_str().to_string())
+                .unwrap_or_else(|| req.uri().path().to_string());
+            let content_type = header_string(req.headers(), header::CONTENT_TYPE);
+            let content_length = header_string(req.headers(), header::CONTENT_LENGTH)
+                .and_then(|length| length.parse().ok());
+            let headers = capture.config.sanitize(req.headers());
+            let method = req.method().to_string();
+
+            // Observe the body as the application reads it
+            let read = Arc::new(AtomicU64::new(0));
+            let prefix = Arc::new(Mutex::new(Vec::new()));
+            let (parts, body) = req.into_parts();
+            let body = {
+                let read = read.clone();
+                let prefix = prefix.clone();
+                let prefix_len = capture.config.body_prefix_len;
+                body.into_data_stream().map(move |chunk| {
+                    if let Ok(chunk) = &chunk {
+                        read.fetch_add(chunk.len() as u64, Ordering::Relaxed);
+                        if let Ok(mut prefix) = prefix.lock()
+                            && prefix.len() < prefix_len
+                        {
+                            let take = (prefix_len - prefix.len()).min(chunk.len());
+                            prefix.extend_from_slice(&chunk[..take]);
+                        }
+                    }
+                    chunk
+                })
+            };
+            let response = next.run(Request::from_parts(parts, Body::from_stream(body))).await;
+
+            let body_prefix = (capture.config.body_prefix_len > 0).then(|| {
+                prefix
+                    .lock()
+                    .map(|prefix| String::from_utf8_lossy(&prefix).into_owned())
+                    .unwrap_or_default()
+            });
+            let envelope = RequestEnvelope {
+                method,
+                route,
+                content_type,
+                content_length,
+                body_bytes: read.load(Ordering::Relaxed),
+                headers,
+                body_prefix,
+                status: response.status().as_u16(),
+                duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+            };
+            // Capture is best-effort: never fail the request over it
+            if let Ok(line) = serde_json::to_string(&envelope)
+                && let Ok(mut file) = capture.file.lock()
+            {
+                let _ = writeln!(file, "{}", line);
+            }
+
+            response
+        },
+    )))
+}
+
+fn header_string(headers: &HeaderMap, name: HeaderName) -> Option<String> {
+    headers
+        .get(name)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string)
+}
@@ -0,0 +1,8 @@
+pub mod config;
+pub mod middleware;
+pub mod replay;
+
+// Public API re-exports
+pub use config::*;
+pub use middleware::*;
+pub use replay::*;
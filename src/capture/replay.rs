@@ -0,0 +1,172 @@
+//! Replaying captured envelopes against a candidate size-limit configuration.
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::Path;
+
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode};
+
+use crate::capture::RequestEnvelope;
+use crate::size_limit::{ContentTypeMatcher, SizeLimitMiddlewareConfig};
+
+/// Reads envelopes written by [`with_capture`](crate::capture::with_capture).
+///
+/// Blank lines are skipped.
+///
+/// # Returns
+/// * `Ok(Vec<RequestEnvelope>)` - The envelopes in file order
+/// * `Err(String)` - If the file cannot be read or a line is not a valid envelope
+pub fn load_envelopes(path: impl AsRef<Path>) -> Result<Vec<RequestEnvelope>, String> {
+    let path = path.as_ref();
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Cannot read capture file {}: {}", path.display(), e))?;
+    contents
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            serde_json::from_str(line).map_err(|e| format!("{}:{}: {}", path.display(), i + 1, e))
+        })
+        .collect()
+}
+
+/// A request the candidate configuration would reject.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReplayRejection {
+    /// The captured request.
+    pub envelope: RequestEnvelope,
+    /// Status the size limiter would answer with.
+    pub status: StatusCode,
+    /// Limit that applied, in bytes.
+    pub limit: usize,
+}
+
+/// Outcome of replaying captured traffic against a configuration.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ReplayReport {
+    /// Number of envelopes replayed.
+    pub total: usize,
+    /// Requests the candidate configuration would reject.
+    pub rejections: Vec<ReplayRejection>,
+    /// Rejected requests that were originally answered successfully.
+    pub newly_rejected: usize,
+    /// Requests originally rejected with 413 that would now be accepted.
+    pub newly_allowed: usize,
+    /// Rejection counts per route.
+    pub rejected_by_route: BTreeMap<String, usize>,
+}
+
+impl ReplayReport {
+    /// Number of requests the candidate configuration would reject.
+    pub fn rejected(&self) -> usize {
+        self.rejections.len()
+    }
+}
+
+impl fmt::Display for ReplayReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} of {} requests rejected ({} newly rejected, {} newly allowed)",
+            self.rejected(),
+            self.total,
+            self.newly_rejected,
+            self.newly_allowed
+        )?;
+        for (route, count) in &self.rejected_by_route {
+            writeln!(f, "  {:>6}  {}", count, route)?;
+        }
+        Ok(())
+    }
+}
+
+/// Replays envelopes against a candidate size-limit configuration.
+///
+/// Applies the same decisions as `with_size_limit` to each recorded request:
+/// body-less method rules first, then the limit (ordered rules, content type,
+/// method caps) against the declared (or read) body size. Header conditions
+/// see the sanitized headers, so rules on redacted headers only match on presence. Nothing is sent anywhere; this only
+/// predicts what the configuration would reject.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::capture::{RequestEnvelope, replay};
+/// use axum_jetpack::size_limit::{SizeLimitConfig, SizeLimitMiddlewareConfig};
+///
+/// let upload = RequestEnvelope {
+///     method: "POST".into(),
+///     route: "/upload".into(),
+///     content_type: Some("application/json".into()),
+///     content_length: Some(150_000),
+///     body_bytes: 150_000,
+///     headers: Vec::new(),
+///     body_prefix: None,
+///     status: 200,
+///     duration_ms: 12.0,
+/// };
+///
+/// let candidate = SizeLimitMiddlewareConfig::new(
+///     SizeLimitConfig::default().with_specific_limit("application/json", "100kb"),
+/// );
+/// let report = replay(&[upload], &candidate);
+/// assert_eq!(report.rejected(), 1);
+/// assert_eq!(report.newly_rejected, 1);
+/// ```
+pub fn replay(envelopes: &[RequestEnvelope], config: &SizeLimitMiddlewareConfig) -> ReplayReport {
+    let matcher = ContentTypeMatcher::compile(&config.size_limits, &config.buffer_strategy);
+    let mut report = ReplayReport {
+        total: envelopes.len(),
+        ..ReplayReport::default()
+    };
+
+    for envelope in envelopes {
+        let method = Method::from_bytes(envelope.method.as_bytes()).unwrap_or(Method::GET);
+        let size = envelope.size();
+        let headers: HeaderMap = envelope
+            .headers
+            .iter()
+            .filter_map(|(name, value)| {
+                Some((HeaderName::try_from(name).ok()?, HeaderValue::try_from(value).ok()?))
+            })
+            .collect();
+        let limit = matcher
+            .lookup_with(
+                &method,
+                &[&envelope.route],
+                envelope
+                    .content_type
+                    .as_deref()
+                    .unwrap_or("application/octet-stream"),
+                &headers,
+            )
+            .limit;
+
+        let status = if config.bodyless_methods.contains(&method) && size > 0 {
+            Some(StatusCode::BAD_REQUEST)
+        } else if size > limit as u64 {
+            Some(StatusCode::PAYLOAD_TOO_LARGE)
+        } else {
+            None
+        };
+
+        match status {
+            Some(status) => {
+                if envelope.status < 400 {
+                    report.newly_rejected += 1;
+                }
+                *report.rejected_by_route.entry(envelope.route.clone()).or_default() += 1;
+                report.rejections.push(ReplayRejection {
+                    envelope: envelope.clone(),
+                    status,
+                    limit,
+                });
+            }
+            None if envelope.status == StatusCode::PAYLOAD_TOO_LARGE.as_u16() => {
+                report.newly_allowed += 1;
+            }
+            None => {}
+        }
+    }
+
+    report
+}
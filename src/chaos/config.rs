@@ -0,0 +1,136 @@
+use axum::http::StatusCode;
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A fault the chaos layer can inject.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Fault {
+    /// Delay the request by this duration before it reaches the handler.
+    Latency(Duration),
+    /// Answer with this status instead of calling the handler.
+    Status(StatusCode),
+    /// Fail the request body after this many bytes, as if the client's
+    /// connection dropped mid-upload.
+    AbortRequestBody {
+        /// Bytes delivered before the abort.
+        after: usize,
+    },
+    /// Cut the response body off after this many bytes; the connection is
+    /// aborted and the declared `Content-Length` is left untouched.
+    TruncateResponse {
+        /// Bytes sent before the cut.
+        after: usize,
+    },
+}
+
+impl Fault {
+    /// Short name used in the [`CHAOS_HEADER`](crate::chaos::CHAOS_HEADER) response header.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Fault::Latency(_) => "latency",
+            Fault::Status(_) => "status",
+            Fault::AbortRequestBody { .. } => "abort-request-body",
+            Fault::TruncateResponse { .. } => "truncate-response",
+        }
+    }
+}
+
+/// A fault injected on matching routes with a given probability.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ChaosRule {
+    /// Route pattern (see [`path_pattern::matches`](crate::path_pattern::matches)).
+    pub pattern: String,
+    /// Probability in `[0.0, 1.0]` that a matching request gets the fault.
+    pub probability: f64,
+    /// The fault to inject.
+    pub fault: Fault,
+}
+
+/// Configuration for [`with_chaos`](crate::chaos::with_chaos).
+///
+/// Every rule matching a request is rolled independently, so a request can
+/// get, e.g., both latency and an error status.
+///
+/// # Examples
+/// ```
+/// use axum::http::StatusCode;
+/// use axum_jetpack::chaos::{ChaosConfig, Fault};
+/// use std::time::Duration;
+///
+/// let config = ChaosConfig::new()
+///     .with_rule("/api/*", 0.1, Fault::Latency(Duration::from_millis(500)))
+///     .with_rule("/api/upload", 0.05, Fault::AbortRequestBody { after: 64 * 1024 })
+///     .with_rule("/api/*", 0.01, Fault::Status(StatusCode::SERVICE_UNAVAILABLE))
+///     .with_seed(42);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct ChaosConfig {
+    /// Fault rules, checked in order.
+    pub rules: Vec<ChaosRule>,
+    /// Seed for reproducible runs; `None` seeds from the clock.
+    pub seed: Option<u64>,
+}
+
+impl ChaosConfig {
+    /// Creates a configuration without rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method to add a fault rule.
+    ///
+    /// # Arguments
+    /// * `pattern` - Route pattern the rule applies to (`"*"` for all)
+    /// * `probability` - Chance per request, clamped to `[0.0, 1.0]`
+    /// * `fault` - The fault to inject
+    pub fn with_rule(mut self, pattern: &str, probability: f64, fault: Fault) -> Self {
+        self.rules.push(ChaosRule {
+            pattern: pattern.to_string(),
+            probability: probability.clamp(0.0, 1.0),
+            fault,
+        });
+        self
+    }
+
+    /// Builder method to make fault selection reproducible.
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+}
+
+/// Small seedable generator (SplitMix64); chaos needs no cryptographic quality.
+#[derive(Debug)]
+pub(crate) struct ChaosRng(Mutex<u64>);
+
+impl ChaosRng {
+    pub(crate) fn new(seed: Option<u64>) -> Self {
+        let seed = seed.unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_nanos() as u64)
+                .unwrap_or(0)
+        });
+        Self(Mutex::new(seed))
+    }
+
+    /// Returns `true` with the given probability.
+    pub(crate) fn roll(&self, probability: f64) -> bool {
+        if probability <= 0.0 {
+            return false;
+        }
+        if probability >= 1.0 {
+            return true;
+        }
+        let Ok(mut state) = self.0.lock() else {
+            return false;
+        };
+        *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = *state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+        // Top 53 bits as a uniform float in [0, 1)
+        ((z >> 11) as f64 / (1u64 << 53) as f64) < probability
+    }
+}
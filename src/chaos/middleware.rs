@@ -0,0 +1,142 @@
+//! Fault-injection middleware for resilience testing.
+
+use axum::{
+    Router,
+    body::{Body, Bytes},
+    extract::{MatchedPath, Request, State},
+    http::{HeaderValue, Response},
+    middleware::{self, Next},
+    response::IntoResponse,
+};
+use futures::{Stream, StreamExt, stream};
+use std::sync::Arc;
+
+use crate::chaos::{ChaosConfig, ChaosRng, Fault};
+
+/// Response header listing the injected faults (e.g., `latency, status`).
+pub const CHAOS_HEADER: &str = "x-chaos-injected";
+
+struct Chaos {
+    config: ChaosConfig,
+    rng: ChaosRng,
+}
+
+/// Injects faults into matching routes for resilience testing.
+///
+/// Rules are matched against the route template (`MatchedPath`) when
+/// available, otherwise the request path. Injected faults are listed in the
+/// [`CHAOS_HEADER`] response header unless the connection is cut.
+///
+/// Apply it innermost (before the other `with_*` functions) so aborted request
+/// bodies exercise the size limiter and other layers like real failures do.
+/// Never enable it in production builds by accident: keep it behind the
+/// `chaos` feature or your own configuration switch.
+///
+/// # Arguments
+/// * `router` - The Axum router to wrap with middleware
+/// * `config` - Fault rules and seed
+///
+/// # Returns
+/// A new router injecting the configured faults.
+///
+/// # Example
+/// ```rust
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::chaos::{ChaosConfig, Fault, with_chaos};
+/// use std::time::Duration;
+///
+/// let router: Router = Router::new().route("/upload", post(|| async { "ok" }));
+/// let router = with_chaos(
+///     router,
+///     ChaosConfig::new().with_rule("/upload", 0.2, Fault::Latency(Duration::from_secs(2))),
+/// );
+/// ```
+pub fn with_chaos(router: Router, config: ChaosConfig) -> Router {
+    let chaos = Arc::new(Chaos {
+        rng: ChaosRng::new(config.seed),
+        config,
+    });
+
+    router.layer(middleware::from_fn_with_state(
+        chaos,
+        |State(chaos): State<Arc<Chaos>>, req: Request<Body>, next: Next| async move {
+            let path = req
+                .extensions()
+                .get::<MatchedPath>()
+                .map(|matched| matched.as_str().to_string())
+                .unwrap_or_else(|| req.uri().path().to_string());
+            let faults: Vec<&Fault> = chaos
+                .config
+                .rules
+                .iter()
+                .filter(|rule| crate::path_pattern::matches(&rule.pattern, &path))
+                .filter(|rule| chaos.rng.roll(rule.probability))
+                .map(|rule| &rule.fault)
+                .collect();
+            if faults.is_empty() {
+                return next.run(req).await;
+            }
+
+            let mut req = req;
+            let mut status = None;
+            let mut truncate = None;
+            for fault in &faults {
+                match fault {
+                    Fault::Latency(delay) => tokio::time::sleep(*delay).await,
+                    Fault::Status(code) => status = status.or(Some(*code)),
+                    Fault::AbortRequestBody { after } => {
+                        let (parts, body) = req.into_parts();
+                        let body = cut_after(body.into_data_stream(), *after, "request body aborted");
+                        req = Request::from_parts(parts, Body::from_stream(body));
+                    }
+                    Fault::TruncateResponse { after } => truncate = Some(*after),
+                }
+            }
+
+            let mut response = match status {
+                Some(code) => (code, "Injected fault").into_response(),
+                None => next.run(req).await,
+            };
+            if let Some(after) = truncate {
+                let (parts, body) = response.into_parts();
+                let body = cut_after(body.into_data_stream(), after, "response truncated");
+                response = Response::from_parts(parts, Body::from_stream(body));
+            }
+
+            let names: Vec<&str> = faults.iter().map(|fault| fault.name()).collect();
+            if let Ok(value) = HeaderValue::from_str(&names.join(", ")) {
+                response.headers_mut().insert(CHAOS_HEADER, value);
+            }
+            response
+        },
+    ))
+}
+
+/// Passes the first `after` bytes through, then fails the stream.
+fn cut_after<S>(
+    body: S,
+    after: usize,
+    reason: &'static str,
+) -> impl Stream<Item = Result<Bytes, axum::Error>> + Send + use<S>
+where
+    S: Stream<Item = Result<Bytes, axum::Error>> + Send + Unpin + 'static,
+{
+    stream::unfold((body, after, false), move |(mut body, remaining, failed)| async move {
+        if failed {
+            return None;
+        }
+        if remaining == 0 {
+            return Some((Err(axum::Error::new(reason)), (body, 0, true)));
+        }
+        match body.next().await? {
+            Ok(mut chunk) => {
+                if chunk.len() > remaining {
+                    chunk.truncate(remaining);
+                }
+                let remaining = remaining - chunk.len();
+                Some((Ok(chunk), (body, remaining, false)))
+            }
+            Err(e) => Some((Err(e), (body, 0, true))),
+        }
+    })
+}
@@ -0,0 +1,6 @@
+pub mod config;
+pub mod middleware;
+
+// Public API re-exports
+pub use config::*;
+pub use middleware::*;
@@ -0,0 +1,105 @@
+//! Client IP extractor and middleware.
+//!
+//! The middleware resolves the client address once per request using a shared
+//! [`ClientIpResolver`] and stores it in the request extensions, where the
+//! [`ClientIp`] extractor (and any other middleware) can pick it up.
+
+use axum::{
+    Router,
+    body::Body,
+    extract::{FromRequestParts, Request, State},
+    http::{StatusCode, request::Parts},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+};
+use std::net::IpAddr;
+use std::sync::Arc;
+
+use crate::client_ip::ClientIpResolver;
+use crate::peer::peer_addr;
+
+/// The resolved address of the client that sent the request.
+///
+/// When [`with_client_ip`] is applied, this is the address determined by the
+/// configured [`ClientIpResolver`]. Without it, the extractor falls back to the
+/// direct peer address from [`ConnectInfo<SocketAddr>`](axum::extract::ConnectInfo)
+/// (or `MockConnectInfo` in tests).
+///
+/// # Example
+/// ```rust
+/// use axum_jetpack::client_ip::ClientIp;
+///
+/// async fn handler(ClientIp(ip): ClientIp) -> String {
+///     format!("Hello, {}", ip)
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClientIp(pub IpAddr);
+
+impl<S> FromRequestParts<S> for ClientIp
+where
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        if let Some(client_ip) = parts.extensions.get::<ClientIp>() {
+            return Ok(*client_ip);
+        }
+
+        peer_addr(parts)
+            .map(|addr| ClientIp(addr.ip()))
+            .ok_or_else(|| {
+                (StatusCode::INTERNAL_SERVER_ERROR, "Client IP unavailable").into_response()
+            })
+    }
+}
+
+/// Applies client IP resolution to an Axum router.
+///
+/// For every request the client address is resolved with `resolver` and stored
+/// as a [`ClientIp`] extension. Requests whose address cannot be determined
+/// (no `ConnectInfo`) pass through without the extension.
+///
+/// # Arguments
+/// * `router` - The Axum router to wrap with middleware
+/// * `resolver` - Resolver holding the trusted proxy networks
+///
+/// # Returns
+/// A new router with client IP resolution applied.
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{Router, routing::get};
+/// use axum_jetpack::client_ip::{ClientIp, ClientIpResolver, with_client_ip};
+/// use std::net::SocketAddr;
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let resolver = ClientIpResolver::new().with_trusted_proxy("10.0.0.0/8")?;
+///
+/// let router = Router::new()
+///     .route("/", get(|ClientIp(ip): ClientIp| async move { ip.to_string() }));
+/// let router = with_client_ip(router, resolver);
+///
+/// // ConnectInfo is required so the peer address is known
+/// let listener = tokio::net::TcpListener::bind("127.0.0.1:3000").await?;
+/// axum::serve(listener, router.into_make_service_with_connect_info::<SocketAddr>()).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn with_client_ip(router: Router, resolver: ClientIpResolver) -> Router {
+    let resolver = Arc::new(resolver);
+
+    router.layer(middleware::from_fn_with_state(
+        resolver,
+        |State(resolver): State<Arc<ClientIpResolver>>, req: Request<Body>, next: Next| async move {
+            let (mut parts, body) = req.into_parts();
+
+            if let Some(ip) = resolver.resolve_parts(&parts) {
+                parts.extensions.insert(ClientIp(ip));
+            }
+
+            next.run(Request::from_parts(parts, body)).await
+        },
+    ))
+}
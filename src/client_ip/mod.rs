@@ -0,0 +1,6 @@
+pub mod resolver;
+pub mod extractor;
+
+// Public API re-exports
+pub use resolver::*;
+pub use extractor::*;
@@ -0,0 +1,259 @@
+use axum::http::{HeaderMap, request::Parts};
+use ipnet::IpNet;
+use std::net::{IpAddr, SocketAddr};
+
+use crate::peer::peer_addr;
+
+/// Headers that may carry the original client address when running behind proxies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ForwardedHeader {
+    /// RFC 7239 `Forwarded` header (`for=` parameters).
+    Forwarded,
+    /// De-facto standard `X-Forwarded-For` header (comma-separated address chain).
+    XForwardedFor,
+    /// `X-Real-IP` header (single address, as set by nginx and others).
+    XRealIp,
+}
+
+/// Resolves the real client IP address of a request.
+///
+/// Forwarding headers are only honored when the direct peer (the TCP connection's
+/// remote address) lies within one of the configured trusted-proxy networks.
+/// Otherwise the peer address itself is the client address, so clients cannot
+/// spoof their IP by sending forwarding headers directly.
+///
+/// For address chains (`Forwarded`, `X-Forwarded-For`) the chain is walked from
+/// right to left, skipping trusted proxies; the first untrusted address is the
+/// client. If every hop is trusted, the left-most address is used.
+///
+/// The same resolver is meant to be shared by every subsystem that keys on the
+/// client address, so they all agree on who the client is.
+///
+/// # Examples
+/// ```
+/// use axum::http::HeaderMap;
+/// use axum_jetpack::client_ip::ClientIpResolver;
+/// use std::net::IpAddr;
+///
+/// let resolver = ClientIpResolver::new()
+///     .with_trusted_proxy("10.0.0.0/8").unwrap();
+///
+/// let mut headers = HeaderMap::new();
+/// headers.insert("x-forwarded-for", "203.0.113.7, 10.0.0.2".parse().unwrap());
+///
+/// // Peer is a trusted proxy: the forwarded chain is honored
+/// let peer: IpAddr = "10.0.0.1".parse().unwrap();
+/// assert_eq!(resolver.resolve(Some(peer), &headers), Some("203.0.113.7".parse().unwrap()));
+///
+/// // Peer is not trusted: the header is ignored
+/// let peer: IpAddr = "198.51.100.1".parse().unwrap();
+/// assert_eq!(resolver.resolve(Some(peer), &headers), Some(peer));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ClientIpResolver {
+    /// Networks whose members are trusted to set forwarding headers.
+    ///
+    /// Default value: empty (forwarding headers are never honored)
+    pub trusted_proxies: Vec<IpNet>,
+
+    /// Forwarding headers to consult, in order of preference.
+    ///
+    /// Default value: `[Forwarded, XForwardedFor, XRealIp]`
+    pub headers: Vec<ForwardedHeader>,
+}
+
+impl Default for ClientIpResolver {
+    /// Creates a resolver with no trusted proxies and all forwarding headers enabled.
+    fn default() -> Self {
+        Self {
+            trusted_proxies: Vec::new(),
+            headers: vec![
+                ForwardedHeader::Forwarded,
+                ForwardedHeader::XForwardedFor,
+                ForwardedHeader::XRealIp,
+            ],
+        }
+    }
+}
+
+impl ClientIpResolver {
+    /// Creates a new resolver with no trusted proxies.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method to add a trusted proxy network.
+    ///
+    /// # Arguments
+    /// * `cidr` - A network in CIDR notation (e.g., "10.0.0.0/8", "fd00::/8")
+    ///   or a single address (e.g., "127.0.0.1")
+    ///
+    /// # Returns
+    /// - `Ok(Self)` for method chaining
+    /// - `Err(String)` if the network cannot be parsed
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::client_ip::ClientIpResolver;
+    ///
+    /// assert!(ClientIpResolver::new().with_trusted_proxy("192.168.0.0/16").is_ok());
+    /// assert!(ClientIpResolver::new().with_trusted_proxy("::1").is_ok());
+    /// assert!(ClientIpResolver::new().with_trusted_proxy("not-a-network").is_err());
+    /// ```
+    pub fn with_trusted_proxy(mut self, cidr: &str) -> Result<Self, String> {
+        self.trusted_proxies.push(parse_network(cidr)?);
+        Ok(self)
+    }
+
+    /// Builder method to add several trusted proxy networks at once.
+    ///
+    /// # Returns
+    /// - `Ok(Self)` for method chaining
+    /// - `Err(String)` naming the first network that cannot be parsed
+    pub fn with_trusted_proxies(mut self, cidrs: &[&str]) -> Result<Self, String> {
+        for cidr in cidrs {
+            self.trusted_proxies.push(parse_network(cidr)?);
+        }
+        Ok(self)
+    }
+
+    /// Builder method to choose which forwarding headers are consulted, in order.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::client_ip::{ClientIpResolver, ForwardedHeader};
+    ///
+    /// // Only trust X-Real-IP set by the edge proxy
+    /// let resolver = ClientIpResolver::new()
+    ///     .with_headers(&[ForwardedHeader::XRealIp]);
+    /// ```
+    pub fn with_headers(mut self, headers: &[ForwardedHeader]) -> Self {
+        self.headers = headers.to_vec();
+        self
+    }
+
+    /// Returns `true` if the address belongs to a trusted proxy network.
+    pub fn is_trusted(&self, ip: &IpAddr) -> bool {
+        self.trusted_proxies.iter().any(|net| net.contains(ip))
+    }
+
+    /// Resolves the client address from the peer address and request headers.
+    ///
+    /// # Arguments
+    /// * `peer` - The remote address of the connection, if known
+    /// * `headers` - The request headers
+    ///
+    /// # Returns
+    /// - `Some(IpAddr)` with the client address
+    /// - `None` if the peer address is unknown
+    pub fn resolve(&self, peer: Option<IpAddr>, headers: &HeaderMap) -> Option<IpAddr> {
+        let peer = peer?;
+
+        if !self.is_trusted(&peer) {
+            return Some(peer);
+        }
+
+        for header in &self.headers {
+            let resolved = match header {
+                ForwardedHeader::Forwarded => self.client_from_chain(forwarded_chain(headers)),
+                ForwardedHeader::XForwardedFor => self.client_from_chain(x_forwarded_for_chain(headers)),
+                ForwardedHeader::XRealIp => headers
+                    .get("x-real-ip")
+                    .and_then(|h| h.to_str().ok())
+                    .and_then(|s| parse_node(s.trim())),
+            };
+
+            if resolved.is_some() {
+                return resolved;
+            }
+        }
+
+        Some(peer)
+    }
+
+    /// Resolves the client address of a request from its parts.
+    ///
+    /// The peer address is taken from axum's [`ConnectInfo<SocketAddr>`](axum::extract::ConnectInfo) extension,
+    /// which is present when the app is served with
+    /// `into_make_service_with_connect_info::<SocketAddr>()`, or else from
+    /// `MockConnectInfo<SocketAddr>` in tests.
+    pub fn resolve_parts(&self, parts: &Parts) -> Option<IpAddr> {
+        let peer = peer_addr(parts).map(|addr| addr.ip());
+        self.resolve(peer, &parts.headers)
+    }
+
+    /// Picks the client out of an address chain (left = original client).
+    fn client_from_chain(&self, chain: Option<Vec<Option<IpAddr>>>) -> Option<IpAddr> {
+        let chain = chain?;
+        let mut leftmost = None;
+
+        for hop in chain.into_iter().rev() {
+            // An unparsable or obfuscated hop means we can no longer tell who the client is
+            let ip = hop?;
+            if !self.is_trusted(&ip) {
+                return Some(ip);
+            }
+            leftmost = Some(ip);
+        }
+
+        leftmost
+    }
+}
+
+/// Parses a CIDR network or a single address into an `IpNet`.
+pub(crate) fn parse_network(cidr: &str) -> Result<IpNet, String> {
+    let cidr = cidr.trim();
+    if let Ok(net) = cidr.parse::<IpNet>() {
+        return Ok(net);
+    }
+    cidr.parse::<IpAddr>()
+        .map(IpNet::from)
+        .map_err(|_| format!("Invalid network '{}'", cidr))
+}
+
+/// Parses a single node, tolerating ports and IPv6 brackets
+/// (`1.2.3.4`, `1.2.3.4:80`, `[2001:db8::1]`, `[2001:db8::1]:443`, `2001:db8::1`).
+fn parse_node(node: &str) -> Option<IpAddr> {
+    let node = node.trim().trim_matches('"');
+
+    if let Ok(ip) = node.parse::<IpAddr>() {
+        return Some(ip);
+    }
+    if let Ok(addr) = node.parse::<SocketAddr>() {
+        return Some(addr.ip());
+    }
+    if let Some(rest) = node.strip_prefix('[') {
+        let end = rest.find(']')?;
+        return rest[..end].parse().ok();
+    }
+    None
+}
+
+/// Collects all `X-Forwarded-For` entries (multiple headers are concatenated in order).
+fn x_forwarded_for_chain(headers: &HeaderMap) -> Option<Vec<Option<IpAddr>>> {
+    let mut chain = Vec::new();
+    for value in headers.get_all("x-forwarded-for") {
+        let value = value.to_str().ok()?;
+        chain.extend(value.split(',').map(parse_node));
+    }
+    (!chain.is_empty()).then_some(chain)
+}
+
+/// Collects the `for=` parameters of all `Forwarded` elements.
+fn forwarded_chain(headers: &HeaderMap) -> Option<Vec<Option<IpAddr>>> {
+    let mut chain = Vec::new();
+    for value in headers.get_all("forwarded") {
+        let value = value.to_str().ok()?;
+        for element in value.split(',') {
+            let node = element.split(';').find_map(|pair| {
+                let (key, value) = pair.split_once('=')?;
+                key.trim().eq_ignore_ascii_case("for").then_some(value)
+            });
+            // Elements without `for=` say nothing about the client and are skipped
+            if let Some(node) = node {
+                chain.push(parse_node(node));
+            }
+        }
+    }
+    (!chain.is_empty()).then_some(chain)
+}
@@ -0,0 +1,207 @@
+use axum::http::{HeaderName, HeaderValue, Method};
+use std::time::Duration;
+use tower_http::cors::{AllowCredentials, AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
+
+/// Response headers emitted by this crate that browsers may need to read
+/// (rate-limit hints on 429, deprecation notices, timings, ranges).
+pub const CRATE_EXPOSED_HEADERS: &[&str] = &[
+    "retry-after",
+    "deprecation",
+    "sunset",
+    "link",
+    "server-timing",
+    "accept-ranges",
+    "content-range",
+];
+
+/// Which origins may read responses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CorsOrigins {
+    /// Any origin (`Access-Control-Allow-Origin: *`). Never combined with credentials.
+    Any,
+    /// An explicit allow list, echoed back when the request origin matches.
+    List(Vec<HeaderValue>),
+    /// No cross-origin access at all.
+    None,
+}
+
+/// An opinionated CORS configuration built from presets.
+///
+/// Presets only produce header combinations browsers accept: credentialed
+/// configurations always use explicit origins and mirror the requested
+/// methods and headers instead of sending `*`.
+///
+/// Apply the resulting layer outermost (after the size limiter, egress quotas
+/// and similar layers) so their 413/429 rejections carry CORS headers too and
+/// browsers let scripts read them.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::cors::CorsConfig;
+/// use std::time::Duration;
+///
+/// // Read-only public API, callable from any page without cookies
+/// let public = CorsConfig::public_api();
+///
+/// // Single-page app on its own origin that sends cookies
+/// let spa = CorsConfig::credentialed_spa(&["https://app.example.com"])
+///     .unwrap()
+///     .with_max_age(Duration::from_secs(600));
+///
+/// // Credentials with a wildcard origin are refused
+/// assert!(CorsConfig::credentialed_spa(&["*"]).is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CorsConfig {
+    /// Origins allowed to read responses.
+    pub origins: CorsOrigins,
+
+    /// Whether cookies and HTTP authentication may be sent cross-origin.
+    pub credentials: bool,
+
+    /// Allowed methods. Empty means "any" (or mirror the request when credentialed).
+    pub methods: Vec<Method>,
+
+    /// Allowed request headers. Empty means "any" (or mirror the request when credentialed).
+    pub allowed_headers: Vec<HeaderName>,
+
+    /// Response headers scripts may read in addition to the CORS-safelisted ones.
+    pub exposed_headers: Vec<HeaderName>,
+
+    /// How long browsers may cache preflight results.
+    pub max_age: Option<Duration>,
+}
+
+impl CorsConfig {
+    /// Public API preset: any origin, no credentials, any method and header.
+    ///
+    /// The crate's own informational headers ([`CRATE_EXPOSED_HEADERS`]) are exposed.
+    pub fn public_api() -> Self {
+        Self {
+            origins: CorsOrigins::Any,
+            credentials: false,
+            methods: Vec::new(),
+            allowed_headers: Vec::new(),
+            exposed_headers: crate_exposed_headers(),
+            max_age: Some(Duration::from_secs(3600)),
+        }
+    }
+
+    /// Credentialed single-page-app preset: explicit origins with cookies allowed.
+    ///
+    /// # Arguments
+    /// * `origins` - Exact origins such as `https://app.example.com`
+    ///
+    /// # Returns
+    /// * `Ok(CorsConfig)` - The configuration
+    /// * `Err(String)` - If no origin is given, an origin is `*` or `null`,
+    ///   or an origin is not a valid header value
+    pub fn credentialed_spa(origins: &[&str]) -> Result<Self, String> {
+        if origins.is_empty() {
+            return Err("A credentialed CORS preset needs at least one origin".to_string());
+        }
+
+        let origins = origins
+            .iter()
+            .map(|origin| {
+                let origin = origin.trim().trim_end_matches('/');
+                if origin == "*" || origin == "null" {
+                    return Err(format!("Origin '{}' cannot be used with credentials", origin));
+                }
+                if !origin.starts_with("http://") && !origin.starts_with("https://") {
+                    return Err(format!("Origin '{}' must include the scheme", origin));
+                }
+                HeaderValue::from_str(origin).map_err(|e| format!("Invalid origin '{}': {}", origin, e))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            origins: CorsOrigins::List(origins),
+            credentials: true,
+            methods: Vec::new(),
+            allowed_headers: Vec::new(),
+            exposed_headers: crate_exposed_headers(),
+            max_age: Some(Duration::from_secs(600)),
+        })
+    }
+
+    /// Deny-all preset: no origin is allowed; preflights get no CORS headers.
+    pub fn deny_all() -> Self {
+        Self {
+            origins: CorsOrigins::None,
+            credentials: false,
+            methods: Vec::new(),
+            allowed_headers: Vec::new(),
+            exposed_headers: Vec::new(),
+            max_age: None,
+        }
+    }
+
+    /// Builder method to restrict the allowed methods.
+    pub fn with_methods(mut self, methods: &[Method]) -> Self {
+        self.methods = methods.to_vec();
+        self
+    }
+
+    /// Builder method to restrict the allowed request headers.
+    pub fn with_allowed_headers(mut self, headers: &[HeaderName]) -> Self {
+        self.allowed_headers = headers.to_vec();
+        self
+    }
+
+    /// Builder method to expose an additional response header.
+    pub fn with_exposed_header(mut self, header: HeaderName) -> Self {
+        if !self.exposed_headers.contains(&header) {
+            self.exposed_headers.push(header);
+        }
+        self
+    }
+
+    /// Builder method to set the preflight cache duration.
+    pub fn with_max_age(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Builds the `tower-http` CORS layer for this configuration.
+    pub fn to_layer(&self) -> CorsLayer {
+        if self.origins == CorsOrigins::None {
+            return CorsLayer::new();
+        }
+
+        let origin = match &self.origins {
+            CorsOrigins::List(origins) => AllowOrigin::list(origins.clone()),
+            _ => AllowOrigin::any(),
+        };
+
+        // `*` is not honored by browsers for credentialed requests; mirror instead
+        let methods = match (self.methods.is_empty(), self.credentials) {
+            (false, _) => AllowMethods::list(self.methods.clone()),
+            (true, true) => AllowMethods::mirror_request(),
+            (true, false) => AllowMethods::any(),
+        };
+        let headers = match (self.allowed_headers.is_empty(), self.credentials) {
+            (false, _) => AllowHeaders::list(self.allowed_headers.clone()),
+            (true, true) => AllowHeaders::mirror_request(),
+            (true, false) => AllowHeaders::any(),
+        };
+
+        let mut layer = CorsLayer::new()
+            .allow_origin(origin)
+            .allow_methods(methods)
+            .allow_headers(headers)
+            .allow_credentials(AllowCredentials::from(self.credentials))
+            .expose_headers(self.exposed_headers.clone());
+        if let Some(max_age) = self.max_age {
+            layer = layer.max_age(max_age);
+        }
+        layer
+    }
+}
+
+fn crate_exposed_headers() -> Vec<HeaderName> {
+    CRATE_EXPOSED_HEADERS
+        .iter()
+        .map(|name| HeaderName::from_static(name))
+        .collect()
+}
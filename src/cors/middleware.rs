@@ -0,0 +1,35 @@
+//! CORS middleware for Axum applications.
+
+use axum::Router;
+
+use crate::cors::CorsConfig;
+
+/// Applies a CORS preset to an Axum router.
+///
+/// Call this last, after the crate's other `with_*` functions, so the CORS
+/// layer is outermost and also decorates rejections such as 413 (size limit)
+/// and 429 (egress quota). Without CORS headers, browsers hide those
+/// responses from scripts, which then only see an opaque network error.
+///
+/// Preflight `OPTIONS` requests are answered directly by the CORS layer and
+/// never reach the inner layers or handlers.
+///
+/// # Arguments
+/// * `router` - The Axum router to wrap with middleware
+/// * `config` - The CORS preset
+///
+/// # Returns
+/// A new router with CORS handling applied.
+///
+/// # Example
+/// ```rust
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::cors::{CorsConfig, with_cors};
+///
+/// let router = Router::new().route("/upload", post(|| async { "ok" }));
+/// // let router = with_size_limit(router, ...);
+/// let router = with_cors(router, CorsConfig::public_api());
+/// ```
+pub fn with_cors(router: Router, config: CorsConfig) -> Router {
+    router.layer(config.to_layer())
+}
@@ -0,0 +1,291 @@
+use axum::http::{Method, request::Parts};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+/// Extracts the client key (API key, tenant, client IP...) used for usage tracking.
+pub type DeprecationKeyExtractor = Arc<dyn Fn(&Parts) -> Option<String> + Send + Sync>;
+
+/// Callback invoked whenever a deprecated endpoint is used.
+pub type DeprecationCallback = Arc<dyn Fn(&DeprecationEvent) + Send + Sync>;
+
+/// Usage counters keyed by `(pattern, client)`.
+pub type DeprecationCounts = HashMap<(String, Option<String>), u64>;
+
+/// A deprecation rule matching a set of routes.
+///
+/// Patterns are matched against the route template (`MatchedPath`, e.g.
+/// `/v1/users/{id}`) when available, otherwise against the request path.
+/// A pattern ending in `/*` matches the prefix and everything below it
+/// (`/v1/*` matches `/v1` and `/v1/users/{id}`).
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::deprecation::DeprecationRule;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let rule = DeprecationRule::new("/v1/*")
+///     .deprecated_at(UNIX_EPOCH + Duration::from_secs(1_735_689_600))
+///     .sunset_at(UNIX_EPOCH + Duration::from_secs(1_767_225_600))
+///     .with_link("https://example.com/docs/migrate-to-v2");
+///
+/// assert!(rule.matches_path("/v1/users/{id}"));
+/// assert!(!rule.matches_path("/v2/users/{id}"));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeprecationRule {
+    /// Route pattern (exact path or `prefix/*`).
+    pub pattern: String,
+
+    /// Methods the rule applies to. Empty means all methods.
+    pub methods: Vec<Method>,
+
+    /// When the endpoint was (or will be) deprecated.
+    ///
+    /// Rendered as `Deprecation: @<unix-seconds>` (RFC 9745). When `None`,
+    /// `Deprecation: true` is sent instead.
+    pub deprecated_at: Option<SystemTime>,
+
+    /// When the endpoint will stop working, rendered as an HTTP-date `Sunset` header (RFC 8594).
+    pub sunset_at: Option<SystemTime>,
+
+    /// Documentation URL, rendered as `Link: <url>; rel="deprecation"`.
+    pub link: Option<String>,
+}
+
+impl DeprecationRule {
+    /// Creates a rule for a route pattern, applying to all methods.
+    pub fn new(pattern: &str) -> Self {
+        Self {
+            pattern: pattern.to_string(),
+            methods: Vec::new(),
+            deprecated_at: None,
+            sunset_at: None,
+            link: None,
+        }
+    }
+
+    /// Builder method to restrict the rule to specific methods.
+    pub fn with_methods(mut self, methods: &[Method]) -> Self {
+        self.methods = methods.to_vec();
+        self
+    }
+
+    /// Builder method to set the deprecation date.
+    pub fn deprecated_at(mut self, at: SystemTime) -> Self {
+        self.deprecated_at = Some(at);
+        self
+    }
+
+    /// Builder method to set the sunset date.
+    pub fn sunset_at(mut self, at: SystemTime) -> Self {
+        self.sunset_at = Some(at);
+        self
+    }
+
+    /// Builder method to set the documentation link.
+    pub fn with_link(mut self, url: &str) -> Self {
+        self.link = Some(url.to_string());
+        self
+    }
+
+    /// Returns `true` if the pattern matches the given route or path.
+    pub fn matches_path(&self, path: &str) -> bool {
+        crate::path_pattern::matches(&self.pattern, path)
+    }
+
+    /// Returns `true` if the rule applies to the given method and route or path.
+    pub fn matches(&self, method: &Method, path: &str) -> bool {
+        (self.methods.is_empty() || self.methods.contains(method)) && self.matches_path(path)
+    }
+}
+
+/// A single use of a deprecated endpoint, passed to the usage callback.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeprecationEvent {
+    /// Pattern of the rule that matched.
+    pub pattern: String,
+    /// Request method.
+    pub method: Method,
+    /// Request path as sent by the client.
+    pub path: String,
+    /// Client key, if the key extractor found one.
+    pub client: Option<String>,
+}
+
+/// Shared, cloneable per-rule and per-client usage counters.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::deprecation::DeprecationUsage;
+///
+/// let usage = DeprecationUsage::new();
+/// usage.record("/v1/*", Some("client-a"));
+/// usage.record("/v1/*", Some("client-a"));
+/// usage.record("/v1/*", None);
+///
+/// assert_eq!(usage.count("/v1/*", Some("client-a")), 2);
+/// assert_eq!(usage.total("/v1/*"), 3);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct DeprecationUsage {
+    counts: Arc<Mutex<DeprecationCounts>>,
+}
+
+impl DeprecationUsage {
+    /// Creates an empty usage store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records one use of a rule by a client.
+    pub fn record(&self, pattern: &str, client: Option<&str>) {
+        if let Ok(mut counts) = self.counts.lock() {
+            *counts
+                .entry((pattern.to_string(), client.map(|c| c.to_string())))
+                .or_insert(0) += 1;
+        }
+    }
+
+    /// Returns how often a client used a rule (`None` = requests without a client key).
+    pub fn count(&self, pattern: &str, client: Option<&str>) -> u64 {
+        self.counts
+            .lock()
+            .ok()
+            .and_then(|counts| {
+                counts
+                    .get(&(pattern.to_string(), client.map(|c| c.to_string())))
+                    .copied()
+            })
+            .unwrap_or(0)
+    }
+
+    /// Returns how often a rule was used across all clients.
+    pub fn total(&self, pattern: &str) -> u64 {
+        self.counts
+            .lock()
+            .map(|counts| {
+                counts
+                    .iter()
+                    .filter(|((p, _), _)| p == pattern)
+                    .map(|(_, count)| count)
+                    .sum()
+            })
+            .unwrap_or(0)
+    }
+
+    /// Returns a snapshot of all counters keyed by `(pattern, client)`.
+    pub fn snapshot(&self) -> DeprecationCounts {
+        self.counts
+            .lock()
+            .map(|counts| counts.clone())
+            .unwrap_or_default()
+    }
+}
+
+/// Configuration for the deprecation header middleware.
+///
+/// Rules are evaluated in order; the first matching rule wins.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::deprecation::{DeprecationConfig, DeprecationRule};
+/// use axum::http::Method;
+///
+/// let config = DeprecationConfig::new()
+///     .with_rule(DeprecationRule::new("/v1/*").with_link("https://example.com/v2"))
+///     .with_rule(DeprecationRule::new("/users/{id}").with_methods(&[Method::PATCH]))
+///     .with_header_key("x-api-key")
+///     .on_use(|event| println!("deprecated call to {} by {:?}", event.path, event.client));
+/// ```
+#[derive(Clone)]
+pub struct DeprecationConfig {
+    /// Ordered rule table.
+    pub rules: Vec<DeprecationRule>,
+
+    /// Extracts the client key used for usage tracking. `None` disables per-client tracking.
+    pub key_extractor: Option<DeprecationKeyExtractor>,
+
+    /// Counters of deprecated endpoint usage.
+    pub usage: DeprecationUsage,
+
+    /// Optional callback fired on every use of a deprecated endpoint (logging, metrics).
+    pub on_use: Option<DeprecationCallback>,
+}
+
+impl Default for DeprecationConfig {
+    /// Creates an empty configuration (no rules, no client tracking).
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            key_extractor: None,
+            usage: DeprecationUsage::new(),
+            on_use: None,
+        }
+    }
+}
+
+impl fmt::Debug for DeprecationConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DeprecationConfig")
+            .field("rules", &self.rules)
+            .field("key_extractor", &self.key_extractor.is_some())
+            .field("usage", &self.usage)
+            .field("on_use", &self.on_use.is_some())
+            .finish()
+    }
+}
+
+impl DeprecationConfig {
+    /// Creates an empty configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method to append a rule.
+    pub fn with_rule(mut self, rule: DeprecationRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Builder method to set a custom client key extractor.
+    pub fn with_key_extractor<F>(mut self, extractor: F) -> Self
+    where
+        F: Fn(&Parts) -> Option<String> + Send + Sync + 'static,
+    {
+        self.key_extractor = Some(Arc::new(extractor));
+        self
+    }
+
+    /// Builder method to key usage tracking on a request header (e.g. an API key).
+    pub fn with_header_key(self, header: &'static str) -> Self {
+        self.with_key_extractor(move |parts| {
+            parts
+                .headers
+                .get(header)
+                .and_then(|h| h.to_str().ok())
+                .map(|s| s.to_string())
+        })
+    }
+
+    /// Builder method to write usage into an existing (shared) store.
+    pub fn with_usage(mut self, usage: DeprecationUsage) -> Self {
+        self.usage = usage;
+        self
+    }
+
+    /// Builder method to register a usage callback.
+    pub fn on_use<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&DeprecationEvent) + Send + Sync + 'static,
+    {
+        self.on_use = Some(Arc::new(callback));
+        self
+    }
+
+    /// Returns the first rule matching the method and route or path.
+    pub fn find_rule(&self, method: &Method, path: &str) -> Option<&DeprecationRule> {
+        self.rules.iter().find(|rule| rule.matches(method, path))
+    }
+}
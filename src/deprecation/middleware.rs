@@ -0,0 +1,127 @@
+//! Deprecation and Sunset header middleware for Axum applications.
+//!
+//! Stamps `Deprecation` (RFC 9745), `Sunset` (RFC 8594) and
+//! `Link: rel="deprecation"` headers on responses of deprecated routes, and
+//! records who is still calling them.
+
+use axum::{
+    Router,
+    body::Body,
+    extract::{MatchedPath, Request, State},
+    http::{HeaderMap, HeaderName, HeaderValue, header},
+    middleware::{self, Next},
+};
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::deprecation::{DeprecationConfig, DeprecationEvent, DeprecationRule};
+
+/// The `Deprecation` response header.
+pub const DEPRECATION: HeaderName = HeaderName::from_static("deprecation");
+
+/// The `Sunset` response header.
+pub const SUNSET: HeaderName = HeaderName::from_static("sunset");
+
+/// Applies deprecation headers to an Axum router.
+///
+/// For every request matching a rule, this middleware:
+/// 1. Records the use in the usage store (per client key, if configured)
+/// 2. Fires the `on_use` callback
+/// 3. Adds `Deprecation`, `Sunset` and `Link` headers to the response,
+///    without overwriting headers the handler already set
+///
+/// Rules are matched against the route template (`MatchedPath`) first and the
+/// raw request path second.
+///
+/// # Arguments
+/// * `router` - The Axum router to wrap with middleware
+/// * `config` - Rule table, client key extraction and usage tracking
+///
+/// # Returns
+/// A new router with deprecation headers applied.
+///
+/// # Example
+/// ```rust
+/// use axum::{Router, routing::get};
+/// use axum_jetpack::deprecation::{DeprecationConfig, DeprecationRule, with_deprecation};
+///
+/// let router = Router::new()
+///     .route("/v1/users", get(|| async { "old" }))
+///     .route("/v2/users", get(|| async { "new" }));
+///
+/// let config = DeprecationConfig::new()
+///     .with_rule(DeprecationRule::new("/v1/*").with_link("https://example.com/migrate"));
+///
+/// let router = with_deprecation(router, config);
+/// ```
+pub fn with_deprecation(router: Router, config: DeprecationConfig) -> Router {
+    let config = Arc::new(config);
+
+    router.layer(middleware::from_fn_with_state(
+        config,
+        |State(config): State<Arc<DeprecationConfig>>, req: Request<Body>, next: Next| async move {
+            let (parts, body) = req.into_parts();
+
+            let matched = parts
+                .extensions
+                .get::<MatchedPath>()
+                .and_then(|matched| config.find_rule(&parts.method, matched.as_str()));
+            let Some(rule) = matched.or_else(|| config.find_rule(&parts.method, parts.uri.path()))
+            else {
+                return next.run(Request::from_parts(parts, body)).await;
+            };
+
+            let client = config.key_extractor.as_ref().and_then(|extract| extract(&parts));
+            config.usage.record(&rule.pattern, client.as_deref());
+
+            if let Some(callback) = &config.on_use {
+                callback(&DeprecationEvent {
+                    pattern: rule.pattern.clone(),
+                    method: parts.method.clone(),
+                    path: parts.uri.path().to_string(),
+                    client,
+                });
+            }
+
+            let rule = rule.clone();
+            let mut response = next.run(Request::from_parts(parts, body)).await;
+            apply_headers(response.headers_mut(), &rule);
+            response
+        },
+    ))
+}
+
+/// Adds the deprecation headers of a rule, keeping any the handler set itself.
+fn apply_headers(headers: &mut HeaderMap, rule: &DeprecationRule) {
+    if !headers.contains_key(DEPRECATION) {
+        let value = match rule.deprecated_at {
+            Some(at) => format!("@{}", unix_seconds(at)),
+            None => "true".to_string(),
+        };
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            headers.insert(DEPRECATION, value);
+        }
+    }
+
+    if let Some(sunset) = rule.sunset_at
+        && !headers.contains_key(SUNSET)
+        && let Ok(value) = HeaderValue::from_str(&httpdate::fmt_http_date(sunset))
+    {
+        headers.insert(SUNSET, value);
+    }
+
+    if let Some(link) = &rule.link
+        && let Ok(value) = HeaderValue::from_str(&format!("<{}>; rel=\"deprecation\"", link))
+    {
+        // Link may legitimately appear several times, so append rather than replace
+        headers.append(header::LINK, value);
+    }
+}
+
+/// Seconds since the Unix epoch (negative for dates before 1970).
+fn unix_seconds(at: SystemTime) -> i64 {
+    match at.duration_since(UNIX_EPOCH) {
+        Ok(duration) => duration.as_secs() as i64,
+        Err(before) => -(before.duration().as_secs() as i64),
+    }
+}
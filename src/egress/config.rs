@@ -0,0 +1,316 @@
+use axum::http::request::Parts;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::peer::peer_ip;
+use crate::size::SizeLimit;
+
+/// Extracts the accounting key (client, tenant, API key...) from a request.
+pub type EgressKeyExtractor = Arc<dyn Fn(&Parts) -> Option<String> + Send + Sync>;
+
+/// Callback invoked once a response body has finished (or was dropped).
+pub type EgressCallback = Arc<dyn Fn(&EgressRecord) + Send + Sync>;
+
+/// A quota on response bytes per key over a fixed time window.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::egress::EgressQuota;
+/// use std::time::Duration;
+///
+/// // 10 GB per key per day
+/// let quota = EgressQuota::new("10GB", Duration::from_secs(24 * 60 * 60));
+/// assert_eq!(quota.max_bytes, 10_000_000_000);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EgressQuota {
+    /// Maximum number of response body bytes per key within one window.
+    pub max_bytes: u64,
+    /// Length of the accounting window.
+    pub window: Duration,
+}
+
+impl EgressQuota {
+    /// Creates a new quota.
+    ///
+    /// # Arguments
+    /// * `max_bytes` - The byte budget per window (human-readable string, `SizeLimit`, or bytes)
+    /// * `window` - The window length
+    pub fn new(max_bytes: impl Into<SizeLimit>, window: Duration) -> Self {
+        Self {
+            max_bytes: max_bytes.into().0 as u64,
+            window,
+        }
+    }
+}
+
+/// Summary of one completed response, passed to the completion callback.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EgressRecord {
+    /// The accounting key the response was attributed to.
+    pub key: String,
+    /// Number of response body bytes sent for this response.
+    pub bytes: u64,
+    /// Whether the body was sent completely (`false` if the client went away early).
+    pub completed: bool,
+}
+
+/// Accumulated usage for one key.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EgressUsage {
+    /// Bytes sent since the key was first recorded; keys with a quota
+    /// window are forgotten some time after their window expires.
+    pub total_bytes: u64,
+    /// Bytes sent within the current quota window.
+    pub window_bytes: u64,
+}
+
+#[derive(Debug)]
+struct LedgerEntry {
+    usage: EgressUsage,
+    window_start: Instant,
+    /// Quota window of the last recording, if any.
+    window: Option<Duration>,
+}
+
+/// Counters of an [`EgressLedger`].
+#[derive(Debug, Default)]
+struct Entries {
+    keys: HashMap<String, LedgerEntry>,
+    /// When expired windows were last swept out.
+    swept: Option<Instant>,
+}
+
+/// Keys kept before expired windows are swept out, at most once per window.
+const SWEEP_THRESHOLD: usize = 1024;
+
+/// Shared, cloneable store of per-key egress counters.
+///
+/// Keep a clone of the ledger to query usage (e.g. from a billing job or admin endpoint).
+///
+/// Keys recorded with a quota window are dropped once their window has
+/// expired. Keys counted without one are kept until [`take`](Self::take) or
+/// [`clear`](Self::clear), so count-only billing jobs should collect them
+/// with `take`.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::egress::EgressLedger;
+///
+/// let ledger = EgressLedger::new();
+/// ledger.record("tenant-a", 1_500, None);
+/// assert_eq!(ledger.usage("tenant-a").total_bytes, 1_500);
+/// assert_eq!(ledger.usage("unknown").total_bytes, 0);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct EgressLedger {
+    entries: Arc<Mutex<Entries>>,
+}
+
+impl EgressLedger {
+    /// Creates an empty ledger.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds sent bytes to a key.
+    ///
+    /// # Arguments
+    /// * `key` - The accounting key
+    /// * `bytes` - Number of bytes sent
+    /// * `window` - Quota window; if the current window has expired, it is reset first
+    pub fn record(&self, key: &str, bytes: u64, window: Option<Duration>) {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let now = Instant::now();
+        // Nothing expires sooner than a window after the last sweep
+        if let Some(window) = window
+            && entries.keys.len() >= SWEEP_THRESHOLD
+            && !entries.keys.contains_key(key)
+            && entries.swept.is_none_or(|swept| now.duration_since(swept) >= window)
+        {
+            entries.keys.retain(|_, entry| {
+                entry.window.is_none_or(|window| now.duration_since(entry.window_start) < window)
+            });
+            entries.swept = Some(now);
+        }
+        let entry = entries.keys.entry(key.to_string()).or_insert_with(|| LedgerEntry {
+            usage: EgressUsage::default(),
+            window_start: now,
+            window,
+        });
+        entry.window = window;
+
+        if let Some(window) = window
+            && now.duration_since(entry.window_start) >= window
+        {
+            entry.window_start = now;
+            entry.usage.window_bytes = 0;
+        }
+
+        entry.usage.total_bytes += bytes;
+        entry.usage.window_bytes += bytes;
+    }
+
+    /// Returns the current usage for a key.
+    pub fn usage(&self, key: &str) -> EgressUsage {
+        let entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        entries.keys.get(key).map(|entry| entry.usage).unwrap_or_default()
+    }
+
+    /// Returns a snapshot of all keys and their usage.
+    pub fn snapshot(&self) -> HashMap<String, EgressUsage> {
+        let entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        entries.keys.iter().map(|(key, entry)| (key.clone(), entry.usage)).collect()
+    }
+
+    /// Returns all keys and their usage, and removes them from the ledger.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::egress::EgressLedger;
+    ///
+    /// let ledger = EgressLedger::new();
+    /// ledger.record("tenant-a", 1_500, None);
+    /// assert_eq!(ledger.take()["tenant-a"].total_bytes, 1_500);
+    /// assert!(ledger.snapshot().is_empty());
+    /// ```
+    pub fn take(&self) -> HashMap<String, EgressUsage> {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        std::mem::take(&mut entries.keys)
+            .into_iter()
+            .map(|(key, entry)| (key, entry.usage))
+            .collect()
+    }
+
+    /// Checks a key against a quota.
+    ///
+    /// # Returns
+    /// - `None` if the key is within its quota
+    /// - `Some(Duration)` with the time until the window resets if the quota is exhausted
+    pub fn check_quota(&self, key: &str, quota: &EgressQuota) -> Option<Duration> {
+        let entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        let entry = entries.keys.get(key)?;
+        let elapsed = entry.window_start.elapsed();
+
+        if elapsed >= quota.window || entry.usage.window_bytes < quota.max_bytes {
+            return None;
+        }
+
+        Some(quota.window.saturating_sub(elapsed))
+    }
+
+    /// Removes all counters.
+    pub fn clear(&self) {
+        let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+        entries.keys.clear();
+    }
+}
+
+/// Configuration for egress (response body) byte accounting.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::egress::{EgressConfig, EgressQuota};
+/// use std::time::Duration;
+///
+/// let config = EgressConfig::default()
+///     .with_header_key("x-tenant-id")
+///     .with_quota(EgressQuota::new("1GB", Duration::from_secs(3600)))
+///     .on_complete(|record| println!("{} sent {} bytes", record.key, record.bytes));
+/// ```
+#[derive(Clone)]
+pub struct EgressConfig {
+    /// Optional quota enforced per key. `None` means count only.
+    pub quota: Option<EgressQuota>,
+
+    /// Extracts the accounting key from the request.
+    ///
+    /// Requests without a key are neither counted nor limited.
+    /// Default: the resolved `ClientIp` extension when the `client-ip` feature is
+    /// enabled and its layer ran, otherwise the peer address from
+    /// `ConnectInfo<SocketAddr>`.
+    pub key_extractor: EgressKeyExtractor,
+
+    /// Callback fired once per response when its body is finished or dropped.
+    pub on_complete: Option<EgressCallback>,
+
+    /// Ledger the counters are written to.
+    pub ledger: EgressLedger,
+}
+
+impl Default for EgressConfig {
+    /// Creates a count-only configuration keyed on the client IP.
+    fn default() -> Self {
+        Self {
+            quota: None,
+            key_extractor: Arc::new(default_key),
+            on_complete: None,
+            ledger: EgressLedger::new(),
+        }
+    }
+}
+
+/// Keys on the client IP, see [`peer_ip`].
+fn default_key(parts: &Parts) -> Option<String> {
+    peer_ip(parts).map(|ip| ip.to_string())
+}
+
+impl fmt::Debug for EgressConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EgressConfig")
+            .field("quota", &self.quota)
+            .field("on_complete", &self.on_complete.is_some())
+            .field("ledger", &self.ledger)
+            .finish()
+    }
+}
+
+impl EgressConfig {
+    /// Creates a new count-only configuration.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method to enforce a quota per key.
+    pub fn with_quota(mut self, quota: EgressQuota) -> Self {
+        self.quota = Some(quota);
+        self
+    }
+
+    /// Builder method to set a custom key extractor.
+    pub fn with_key_extractor<F>(mut self, extractor: F) -> Self
+    where
+        F: Fn(&Parts) -> Option<String> + Send + Sync + 'static,
+    {
+        self.key_extractor = Arc::new(extractor);
+        self
+    }
+
+    /// Builder method to key accounting on a request header (e.g. a tenant id or API key).
+    pub fn with_header_key(self, header: &'static str) -> Self {
+        self.with_key_extractor(move |parts| {
+            parts
+                .headers
+                .get(header)
+                .and_then(|h| h.to_str().ok())
+                .map(|s| s.to_string())
+        })
+    }
+
+    /// Builder method to register a completion callback (access log, billing...).
+    pub fn on_complete<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&EgressRecord) + Send + Sync + 'static,
+    {
+        self.on_complete = Some(Arc::new(callback));
+        self
+    }
+
+    /// Builder method to write into an existing (shared) ledger.
+    pub fn with_ledger(mut self, ledger: EgressLedger) -> Self {
+        self.ledger = ledger;
+        self
+    }
+}
@@ -0,0 +1,199 @@
+//! Egress byte accounting middleware for Axum applications.
+//!
+//! Response bodies are wrapped in a counting body, so the bytes are measured as
+//! they are actually sent rather than trusted from the `Content-Length` header.
+
+use axum::{
+    Router,
+    body::{Body, Bytes, HttpBody},
+    extract::{Request, State},
+    http::{HeaderValue, StatusCode, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+};
+use http_body::{Frame, SizeHint};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll};
+
+use crate::egress::{EgressConfig, EgressRecord};
+
+/// Live byte counter of a response body, inserted into the response extensions.
+///
+/// Outer layers (access logs, billing) can keep a clone and read the final
+/// count once the body has been sent.
+///
+/// # Example
+/// ```rust
+/// use axum_jetpack::egress::EgressBytes;
+///
+/// fn log_response(response: &axum::response::Response) {
+///     if let Some(bytes) = response.extensions().get::<EgressBytes>() {
+///         println!("sent so far: {}", bytes.get());
+///     }
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct EgressBytes(Arc<AtomicU64>);
+
+impl EgressBytes {
+    /// Returns the number of body bytes sent so far.
+    pub fn get(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Response body wrapper that counts data bytes and reports them when done.
+struct CountingBody {
+    inner: Body,
+    key: String,
+    counter: EgressBytes,
+    config: Arc<EgressConfig>,
+    finished: bool,
+}
+
+impl CountingBody {
+    fn finish(&mut self, completed: bool) {
+        if self.finished {
+            return;
+        }
+        self.finished = true;
+
+        let record = EgressRecord {
+            key: std::mem::take(&mut self.key),
+            bytes: self.counter.get(),
+            completed,
+        };
+
+        self.config.ledger.record(
+            &record.key,
+            record.bytes,
+            self.config.quota.map(|quota| quota.window),
+        );
+
+        if let Some(callback) = &self.config.on_complete {
+            callback(&record);
+        }
+    }
+}
+
+impl HttpBody for CountingBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let poll = Pin::new(&mut self.inner).poll_frame(cx);
+
+        match &poll {
+            Poll::Ready(Some(Ok(frame))) => {
+                if let Some(data) = frame.data_ref() {
+                    self.counter.0.fetch_add(data.len() as u64, Ordering::Relaxed);
+                }
+            }
+            Poll::Ready(Some(Err(_))) => self.finish(false),
+            Poll::Ready(None) => self.finish(true),
+            Poll::Pending => {}
+        }
+
+        poll
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
+
+impl Drop for CountingBody {
+    fn drop(&mut self) {
+        // Dropped before the end: the client went away (unless there was nothing left to send)
+        let completed = self.inner.is_end_stream();
+        self.finish(completed);
+    }
+}
+
+/// Applies egress byte accounting (and optional quotas) to an Axum router.
+///
+/// This middleware:
+/// 1. Extracts the accounting key from the request (requests without a key pass through untouched)
+/// 2. Rejects the request with 429 (Too Many Requests) and `Retry-After` if the key
+///    exhausted its quota in the current window
+/// 3. Wraps the response body to count the bytes actually sent
+/// 4. Inserts an [`EgressBytes`] counter into the response extensions
+/// 5. Records the final count in the ledger and fires the completion callback
+///
+/// Quotas are checked when a request arrives; a response that is already being
+/// sent is never cut off, so a key may overshoot its quota by one response.
+///
+/// # Arguments
+/// * `router` - The Axum router to wrap with middleware
+/// * `config` - Key extraction, quota and callback configuration
+///
+/// # Example
+/// ```rust
+/// use axum::{Router, routing::get};
+/// use axum_jetpack::egress::{EgressConfig, EgressLedger, EgressQuota, with_egress_accounting};
+/// use std::time::Duration;
+///
+/// let ledger = EgressLedger::new();
+/// let config = EgressConfig::new()
+///     .with_header_key("x-api-key")
+///     .with_quota(EgressQuota::new("500MB", Duration::from_secs(3600)))
+///     .with_ledger(ledger.clone());
+///
+/// let router = Router::new().route("/download", get(|| async { "data" }));
+/// let router = with_egress_accounting(router, config);
+///
+/// // Later, e.g. in a billing job
+/// let usage = ledger.usage("some-api-key");
+/// ```
+pub fn with_egress_accounting(router: Router, config: EgressConfig) -> Router {
+    let config = Arc::new(config);
+
+    router.layer(middleware::from_fn_with_state(
+        config,
+        |State(config): State<Arc<EgressConfig>>, req: Request<Body>, next: Next| async move {
+            let (parts, body) = req.into_parts();
+
+            let Some(key) = (config.key_extractor)(&parts) else {
+                return next.run(Request::from_parts(parts, body)).await;
+            };
+
+            if let Some(quota) = &config.quota
+                && let Some(retry_after) = config.ledger.check_quota(&key, quota)
+            {
+                let mut response =
+                    (StatusCode::TOO_MANY_REQUESTS, "Egress quota exceeded").into_response();
+                // Round up so clients never retry before the window resets
+                let seconds = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+                response
+                    .headers_mut()
+                    .insert(header::RETRY_AFTER, HeaderValue::from(seconds));
+                return response;
+            }
+
+            let response = next.run(Request::from_parts(parts, body)).await;
+            let (mut parts, body) = response.into_parts();
+
+            let counter = EgressBytes::default();
+            parts.extensions.insert(counter.clone());
+
+            let body = CountingBody {
+                inner: body,
+                key,
+                counter,
+                config: config.clone(),
+                finished: false,
+            };
+
+            Response::from_parts(parts, Body::new(body))
+        },
+    ))
+}
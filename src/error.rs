@@ -0,0 +1,225 @@
+//! Shared error response formatting.
+//!
+//! Guardrails in this crate reject requests with a status code and a short
+//! message. [`ErrorFormat`] decides how that message is rendered, so an API
+//! can keep every rejection in one consistent shape.
+
+use axum::{
+    Json, Router,
+    body::Body,
+    extract::{MatchedPath, Request, State},
+    http::{Extensions, HeaderValue, StatusCode, header},
+    middleware::{self, Next},
+    response::{Html, IntoResponse, Response},
+};
+use serde::Serialize;
+use std::sync::Arc;
+
+/// Body rendering for error responses produced by this crate.
+///
+/// # Examples
+/// ```
+/// use axum::http::StatusCode;
+/// use axum_jetpack::error::ErrorFormat;
+///
+/// let response = ErrorFormat::ProblemDetails.response(StatusCode::BAD_REQUEST, "per_page must be at most 100");
+/// assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+/// assert_eq!(response.headers()["content-type"], "application/problem+json");
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize), serde(rename_all = "snake_case"))]
+pub enum ErrorFormat {
+    /// `text/plain` body containing the message (the crate's historical behavior).
+    #[default]
+    PlainText,
+    /// `application/json` body: `{"error": "<message>", "status": <code>}`.
+    Json,
+    /// RFC 9457 `application/problem+json` body with `type`, `title`, `status` and `detail`.
+    ProblemDetails,
+    /// Minimal `text/html` error page, for browser-facing routes.
+    Html,
+}
+
+#[derive(Serialize)]
+struct JsonError<'a> {
+    error: &'a str,
+    status: u16,
+}
+
+#[derive(Serialize)]
+struct ProblemDetails<'a> {
+    #[serde(rename = "type")]
+    kind: &'a str,
+    title: &'a str,
+    status: u16,
+    detail: &'a str,
+}
+
+impl ErrorFormat {
+    /// Renders an error response.
+    ///
+    /// # Arguments
+    /// * `status` - HTTP status code of the rejection
+    /// * `message` - Human-readable description of what went wrong
+    pub fn response(&self, status: StatusCode, message: &str) -> Response {
+        match self {
+            ErrorFormat::PlainText => (status, message.to_string()).into_response(),
+            ErrorFormat::Json => (
+                status,
+                Json(JsonError {
+                    error: message,
+                    status: status.as_u16(),
+                }),
+            )
+                .into_response(),
+            ErrorFormat::ProblemDetails => {
+                let mut response = (
+                    status,
+                    Json(ProblemDetails {
+                        kind: "about:blank",
+                        title: status.canonical_reason().unwrap_or("Error"),
+                        status: status.as_u16(),
+                        detail: message,
+                    }),
+                )
+                    .into_response();
+                response.headers_mut().insert(
+                    header::CONTENT_TYPE,
+                    HeaderValue::from_static("application/problem+json"),
+                );
+                response
+            }
+            ErrorFormat::Html => {
+                let title = format!(
+                    "{} {}",
+                    status.as_u16(),
+                    status.canonical_reason().unwrap_or("Error")
+                );
+                let page = format!(
+                    "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\"><title>{title}</title></head>\
+                     <body><h1>{title}</h1><p>{}</p></body></html>\n",
+                    escape_html(message)
+                );
+                (status, Html(page)).into_response()
+            }
+        }
+    }
+
+    /// Returns the per-route format resolved by [`with_error_formats`], or `fallback`.
+    ///
+    /// # Arguments
+    /// * `extensions` - Request extensions
+    /// * `fallback` - The subsystem's own configured format
+    pub fn for_request(extensions: &Extensions, fallback: ErrorFormat) -> ErrorFormat {
+        extensions.get::<ErrorFormat>().copied().unwrap_or(fallback)
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Route-pattern to error-format table.
+///
+/// Patterns use [`path_pattern::matches`](crate::path_pattern::matches) and are
+/// checked in order; the first match wins. Routes without a match keep each
+/// subsystem's own configured format.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::error::{ErrorFormat, ErrorFormats};
+///
+/// let formats = ErrorFormats::new()
+///     .with_route("/api/*", ErrorFormat::ProblemDetails)
+///     .with_route("/web/*", ErrorFormat::Html);
+///
+/// assert_eq!(formats.for_path("/api/users"), Some(ErrorFormat::ProblemDetails));
+/// assert_eq!(formats.for_path("/other"), None);
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ErrorFormats {
+    /// Ordered `(pattern, format)` pairs.
+    pub routes: Vec<(String, ErrorFormat)>,
+}
+
+impl ErrorFormats {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method to map a route pattern to a format.
+    pub fn with_route(mut self, pattern: &str, format: ErrorFormat) -> Self {
+        self.routes.push((pattern.to_string(), format));
+        self
+    }
+
+    /// Returns the format of the first pattern matching `path`.
+    pub fn for_path(&self, path: &str) -> Option<ErrorFormat> {
+        self.routes
+            .iter()
+            .find(|(pattern, _)| crate::path_pattern::matches(pattern, path))
+            .map(|(_, format)| *format)
+    }
+}
+
+/// Applies per-route error formats to every subsystem of this crate.
+///
+/// Resolves the format for each request (route template first, then request
+/// path) and stores it in the request extensions, where the size limiter,
+/// extractors and other layers pick it up instead of their own
+/// `error_format` setting.
+///
+/// Call this after the other `with_*` functions so it runs before them.
+///
+/// # Arguments
+/// * `router` - The Axum router to wrap with middleware
+/// * `formats` - Route pattern to format table
+///
+/// # Returns
+/// A new router with per-route error formats.
+///
+/// # Example
+/// ```rust
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::error::{ErrorFormat, ErrorFormats, with_error_formats};
+///
+/// let router: Router = Router::new()
+///     .route("/api/upload", post(|| async { "ok" }))
+///     .route("/web/upload", post(|| async { "ok" }));
+/// // let router = with_size_limit(router, ...);
+/// let router = with_error_formats(
+///     router,
+///     ErrorFormats::new()
+///         .with_route("/api/*", ErrorFormat::ProblemDetails)
+///         .with_route("/web/*", ErrorFormat::Html),
+/// );
+/// ```
+pub fn with_error_formats(router: Router, formats: ErrorFormats) -> Router {
+    let formats = Arc::new(formats);
+
+    router.layer(middleware::from_fn_with_state(
+        formats,
+        |State(formats): State<Arc<ErrorFormats>>, mut req: Request<Body>, next: Next| async move {
+            let matched = req
+                .extensions()
+                .get::<MatchedPath>()
+                .and_then(|matched| formats.for_path(matched.as_str()));
+            if let Some(format) = matched.or_else(|| formats.for_path(req.uri().path())) {
+                req.extensions_mut().insert(format);
+            }
+            next.run(req).await
+        },
+    ))
+}
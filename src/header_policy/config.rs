@@ -0,0 +1,235 @@
+use axum::http::{HeaderMap, HeaderName, HeaderValue, header};
+
+use crate::error::ErrorFormat;
+
+/// What to do when a required response header is missing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MissingHeader {
+    /// Insert this value.
+    Insert(HeaderValue),
+    /// Replace the response with a 500 error: the handler broke the contract.
+    Reject,
+}
+
+/// A response header that must be present.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequiredHeader {
+    /// Header name.
+    pub name: HeaderName,
+    /// Action when the header is missing.
+    pub on_missing: MissingHeader,
+}
+
+/// Routes exempt from some or all required headers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeaderException {
+    /// Route pattern, see [`path_pattern::matches`](crate::path_pattern::matches).
+    pub pattern: String,
+    /// Exempted required headers. Empty means all of them.
+    pub headers: Vec<HeaderName>,
+}
+
+/// Outbound response header policy.
+///
+/// Applied to every response in this order:
+/// 1. Strip listed headers and headers with listed prefixes
+/// 2. Set override values (replacing any existing value)
+/// 3. Enforce required headers, unless the route is exempt
+///
+/// By default, `Server`, `X-Powered-By`, `X-AspNet-Version` and
+/// `X-AspNetMvc-Version` are stripped and nothing is required.
+///
+/// # Examples
+/// ```
+/// use axum::http::{HeaderName, HeaderValue, header};
+/// use axum_jetpack::header_policy::HeaderPolicy;
+///
+/// let policy = HeaderPolicy::new()
+///     .with_stripped_prefix("x-debug-")
+///     .with_override(header::SERVER, HeaderValue::from_static("edge"))
+///     .require(header::X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"))
+///     .require_strict(HeaderName::from_static("x-request-id"))
+///     .with_exception("/metrics", &[HeaderName::from_static("x-request-id")]);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HeaderPolicy {
+    /// Headers removed from every response.
+    pub strip: Vec<HeaderName>,
+
+    /// Lowercase name prefixes removed from every response (e.g. `x-debug-`).
+    pub strip_prefixes: Vec<String>,
+
+    /// Headers set on every response, replacing handler values.
+    pub overrides: Vec<(HeaderName, HeaderValue)>,
+
+    /// Headers every response must carry.
+    pub required: Vec<RequiredHeader>,
+
+    /// Per-route exemptions from required headers.
+    pub exceptions: Vec<HeaderException>,
+
+    /// How 500 rejections for missing strict headers are rendered.
+    pub error_format: ErrorFormat,
+}
+
+impl Default for HeaderPolicy {
+    /// Strips common server-identifying headers; requires nothing.
+    fn default() -> Self {
+        Self {
+            strip: vec![
+                header::SERVER,
+                HeaderName::from_static("x-powered-by"),
+                HeaderName::from_static("x-aspnet-version"),
+                HeaderName::from_static("x-aspnetmvc-version"),
+            ],
+            strip_prefixes: Vec::new(),
+            overrides: Vec::new(),
+            required: Vec::new(),
+            exceptions: Vec::new(),
+            error_format: ErrorFormat::default(),
+        }
+    }
+}
+
+impl HeaderPolicy {
+    /// Creates the default policy.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a policy that strips nothing and requires nothing.
+    pub fn empty() -> Self {
+        Self {
+            strip: Vec::new(),
+            ..Self::default()
+        }
+    }
+
+    /// Builder method to strip a header.
+    pub fn with_stripped_header(mut self, name: HeaderName) -> Self {
+        if !self.strip.contains(&name) {
+            self.strip.push(name);
+        }
+        self
+    }
+
+    /// Builder method to strip every header starting with a prefix.
+    pub fn with_stripped_prefix(mut self, prefix: &str) -> Self {
+        self.strip_prefixes.push(prefix.to_ascii_lowercase());
+        self
+    }
+
+    /// Builder method to set a header on every response.
+    pub fn with_override(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.overrides.retain(|(existing, _)| existing != name);
+        self.overrides.push((name, value));
+        self
+    }
+
+    /// Builder method to require a header, inserting `value` when it is missing.
+    pub fn require(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.required.push(RequiredHeader {
+            name,
+            on_missing: MissingHeader::Insert(value),
+        });
+        self
+    }
+
+    /// Builder method to require a header, failing the response with 500 when it is missing.
+    pub fn require_strict(mut self, name: HeaderName) -> Self {
+        self.required.push(RequiredHeader {
+            name,
+            on_missing: MissingHeader::Reject,
+        });
+        self
+    }
+
+    /// Builder method to exempt a route from required headers.
+    ///
+    /// # Arguments
+    /// * `pattern` - Route pattern (exact or `prefix/*`)
+    /// * `headers` - Exempted headers; an empty slice exempts all of them
+    pub fn with_exception(mut self, pattern: &str, headers: &[HeaderName]) -> Self {
+        self.exceptions.push(HeaderException {
+            pattern: pattern.to_string(),
+            headers: headers.to_vec(),
+        });
+        self
+    }
+
+    /// Builder method to set the error format for missing strict headers.
+    pub fn with_error_format(mut self, format: ErrorFormat) -> Self {
+        self.error_format = format;
+        self
+    }
+
+    /// Returns `true` if `name` is not required on the given route.
+    pub fn is_exempt(&self, path: &str, name: &HeaderName) -> bool {
+        self.exceptions.iter().any(|exception| {
+            crate::path_pattern::matches(&exception.pattern, path)
+                && (exception.headers.is_empty() || exception.headers.contains(name))
+        })
+    }
+
+    /// Applies stripping and overrides, then enforces required headers.
+    ///
+    /// # Arguments
+    /// * `path` - Route template or request path, used for exceptions
+    /// * `headers` - Response headers to modify in place
+    ///
+    /// # Returns
+    /// * `Ok(())` - The headers satisfy the policy
+    /// * `Err(HeaderName)` - A strictly required header is missing
+    pub fn apply(&self, path: &str, headers: &mut HeaderMap) -> Result<(), HeaderName> {
+        self.sanitize(headers);
+        self.enforce(path, headers)
+    }
+
+    /// Strips headers and sets overrides.
+    pub fn sanitize(&self, headers: &mut HeaderMap) {
+        for name in &self.strip {
+            headers.remove(name);
+        }
+        if !self.strip_prefixes.is_empty() {
+            let stripped: Vec<HeaderName> = headers
+                .keys()
+                .filter(|name| {
+                    self.strip_prefixes
+                        .iter()
+                        .any(|prefix| name.as_str().starts_with(prefix.as_str()))
+                })
+                .cloned()
+                .collect();
+            for name in stripped {
+                headers.remove(name);
+            }
+        }
+
+        for (name, value) in &self.overrides {
+            headers.insert(name.clone(), value.clone());
+        }
+    }
+
+    /// Inserts missing required headers that have a default value.
+    ///
+    /// # Returns
+    /// * `Ok(())` - All required headers are present or exempt
+    /// * `Err(HeaderName)` - The first strictly required header that is missing
+    pub fn enforce(&self, path: &str, headers: &mut HeaderMap) -> Result<(), HeaderName> {
+        let mut missing = None;
+        for required in &self.required {
+            if headers.contains_key(&required.name) || self.is_exempt(path, &required.name) {
+                continue;
+            }
+            match &required.on_missing {
+                MissingHeader::Insert(value) => {
+                    headers.insert(required.name.clone(), value.clone());
+                }
+                MissingHeader::Reject => {
+                    missing.get_or_insert_with(|| required.name.clone());
+                }
+            }
+        }
+        missing.map_or(Ok(()), Err)
+    }
+}
@@ -0,0 +1,68 @@
+//! Response header policy middleware for Axum applications.
+
+use axum::{
+    Router,
+    body::Body,
+    extract::{MatchedPath, Request, State},
+    http::StatusCode,
+    middleware::{self, Next},
+};
+use std::sync::Arc;
+
+use crate::error::ErrorFormat;
+use crate::header_policy::HeaderPolicy;
+
+/// Applies an outbound header policy to an Axum router.
+///
+/// Strips server-identifying and internal headers, sets overrides and
+/// enforces required headers on every response. Exceptions are matched
+/// against the route template (`MatchedPath`) when available, otherwise the
+/// request path.
+///
+/// A response missing a strictly required header is replaced with a 500
+/// rendered through the policy's error format; the policy is applied to
+/// that response too, so it never leaks stripped headers.
+///
+/// Apply it outermost (after the other `with_*` functions) so rejections from
+/// inner layers are covered as well.
+///
+/// # Arguments
+/// * `router` - The Axum router to wrap with middleware
+/// * `policy` - The header policy
+///
+/// # Returns
+/// A new router enforcing the header policy.
+///
+/// # Example
+/// ```rust
+/// use axum::{Router, routing::get};
+/// use axum_jetpack::header_policy::{HeaderPolicy, with_header_policy};
+///
+/// let router = Router::new().route("/", get(|| async { ([("x-powered-by", "php")], "hi") }));
+/// let router = with_header_policy(router, HeaderPolicy::new());
+/// ```
+pub fn with_header_policy(router: Router, policy: HeaderPolicy) -> Router {
+    let policy = Arc::new(policy);
+
+    router.layer(middleware::from_fn_with_state(
+        policy,
+        |State(policy): State<Arc<HeaderPolicy>>, req: Request<Body>, next: Next| async move {
+            let path = req
+                .extensions()
+                .get::<MatchedPath>()
+                .map(|matched| matched.as_str().to_string())
+                .unwrap_or_else(|| req.uri().path().to_string());
+            let error_format = ErrorFormat::for_request(req.extensions(), policy.error_format);
+
+            let mut response = next.run(req).await;
+            if let Err(missing) = policy.apply(&path, response.headers_mut()) {
+                let message = format!("Missing required response header '{}'", missing);
+                response = error_format.response(StatusCode::INTERNAL_SERVER_ERROR, &message);
+                // The error response cannot carry the missing header either; apply the rest
+                policy.sanitize(response.headers_mut());
+                let _ = policy.enforce(&path, response.headers_mut());
+            }
+            response
+        },
+    ))
+}
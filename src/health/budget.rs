@@ -0,0 +1,37 @@
+use crate::health::HealthCheck;
+use crate::size_limit::{BufferBudget, SizeLimit};
+
+impl HealthCheck {
+    /// Creates a non-critical check reporting a [`BufferBudget`] running low.
+    ///
+    /// The check fails, degrading the service, while less than `min_available`
+    /// of the budget is free, so load balancers can steer uploads elsewhere
+    /// before requests start being refused.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::health::{HealthCheck, HealthRegistry};
+    /// use axum_jetpack::size_limit::{BufferBudget, SizeLimitMiddlewareConfig};
+    ///
+    /// let budget = BufferBudget::new("512mb");
+    /// let config = SizeLimitMiddlewareConfig::default().with_buffer_budget(budget.clone());
+    /// let registry = HealthRegistry::new().with_check(HealthCheck::buffer_budget(budget, "64mb"));
+    /// ```
+    pub fn buffer_budget(budget: BufferBudget, min_available: impl Into<SizeLimit>) -> Self {
+        let min_available = min_available.into().0;
+        Self::new("buffer_budget", move || {
+            let (available, capacity) = (budget.available(), budget.capacity());
+            async move {
+                if available >= min_available.min(capacity) {
+                    return Ok(());
+                }
+                Err(format!(
+                    "{} of {} available",
+                    SizeLimit::bytes(available),
+                    SizeLimit::bytes(capacity)
+                ))
+            }
+        })
+        .non_critical()
+    }
+}
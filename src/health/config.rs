@@ -0,0 +1,193 @@
+use futures::future::{BoxFuture, join_all};
+use serde::Serialize;
+use std::fmt;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Default time a single check may take before it counts as failed.
+pub const DEFAULT_CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// An async health check returning `Err(reason)` when unhealthy.
+pub type HealthCheckFn = Arc<dyn Fn() -> BoxFuture<'static, Result<(), String>> + Send + Sync>;
+
+/// Outcome of a check or of the whole registry.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    /// Everything works.
+    Healthy,
+    /// A non-critical check failed; the service still accepts traffic.
+    Degraded,
+    /// A critical check failed; the service should not receive traffic.
+    Unhealthy,
+}
+
+/// A named health check with timeout and criticality.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::health::HealthCheck;
+/// use std::time::Duration;
+///
+/// let db = HealthCheck::new("database", || async { Ok(()) })
+///     .with_timeout(Duration::from_millis(500));
+///
+/// let cache = HealthCheck::new("cache", || async { Err("connection refused".to_string()) })
+///     .non_critical();
+/// ```
+#[derive(Clone)]
+pub struct HealthCheck {
+    /// Name reported in the JSON output.
+    pub name: String,
+
+    /// Whether a failure makes the service unready (`true`) or only degraded.
+    /// Default: `true`.
+    pub critical: bool,
+
+    /// Maximum duration of one run. Default: [`DEFAULT_CHECK_TIMEOUT`].
+    pub timeout: Duration,
+
+    /// The check itself.
+    pub check: HealthCheckFn,
+}
+
+impl fmt::Debug for HealthCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HealthCheck")
+            .field("name", &self.name)
+            .field("critical", &self.critical)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl HealthCheck {
+    /// Creates a critical check with the default timeout.
+    pub fn new<F, Fut>(name: &str, check: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<(), String>> + Send + 'static,
+    {
+        Self {
+            name: name.to_string(),
+            critical: true,
+            timeout: DEFAULT_CHECK_TIMEOUT,
+            check: Arc::new(move || Box::pin(check())),
+        }
+    }
+
+    /// Builder method to set the timeout.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Builder method to mark the check as non-critical (failures only degrade).
+    pub fn non_critical(mut self) -> Self {
+        self.critical = false;
+        self
+    }
+
+    /// Runs the check once, enforcing its timeout.
+    pub async fn run(&self) -> CheckReport {
+        let started = Instant::now();
+        let result = match tokio::time::timeout(self.timeout, (self.check)()).await {
+            Ok(result) => result,
+            Err(_) => Err(format!("timed out after {} ms", self.timeout.as_millis())),
+        };
+
+        let status = match (&result, self.critical) {
+            (Ok(()), _) => HealthStatus::Healthy,
+            (Err(_), true) => HealthStatus::Unhealthy,
+            (Err(_), false) => HealthStatus::Degraded,
+        };
+
+        CheckReport {
+            name: self.name.clone(),
+            status,
+            critical: self.critical,
+            duration_ms: started.elapsed().as_millis() as u64,
+            error: result.err(),
+        }
+    }
+}
+
+/// Result of a single check, as reported in JSON.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct CheckReport {
+    /// Check name.
+    pub name: String,
+    /// Check outcome.
+    pub status: HealthStatus,
+    /// Whether the check is critical.
+    pub critical: bool,
+    /// How long the check took.
+    pub duration_ms: u64,
+    /// Failure reason, if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Aggregated result of all checks.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct HealthReport {
+    /// Worst status over all checks (`healthy` when there are none).
+    pub status: HealthStatus,
+    /// Per-check results, in registration order.
+    pub checks: Vec<CheckReport>,
+}
+
+/// A registry of named health checks.
+///
+/// Checks run concurrently, each bounded by its own timeout. The overall status
+/// is `unhealthy` if any critical check fails, `degraded` if only non-critical
+/// checks fail, and `healthy` otherwise.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::health::{HealthCheck, HealthRegistry, HealthStatus};
+///
+/// # #[tokio::main]
+/// # async fn main() {
+/// let registry = HealthRegistry::new()
+///     .with_check(HealthCheck::new("database", || async { Ok(()) }))
+///     .with_check(HealthCheck::new("cache", || async { Err("down".to_string()) }).non_critical());
+///
+/// let report = registry.run().await;
+/// assert_eq!(report.status, HealthStatus::Degraded);
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct HealthRegistry {
+    /// Registered checks.
+    pub checks: Vec<HealthCheck>,
+}
+
+impl HealthRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method to register a check.
+    pub fn with_check(mut self, check: HealthCheck) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    /// Runs all checks concurrently and aggregates the results.
+    pub async fn run(&self) -> HealthReport {
+        let checks = join_all(self.checks.iter().map(|check| check.run())).await;
+
+        let status = if checks.iter().any(|c| c.status == HealthStatus::Unhealthy) {
+            HealthStatus::Unhealthy
+        } else if checks.iter().any(|c| c.status == HealthStatus::Degraded) {
+            HealthStatus::Degraded
+        } else {
+            HealthStatus::Healthy
+        };
+
+        HealthReport { status, checks }
+    }
+}
@@ -0,0 +1,8 @@
+#[cfg(feature = "size-limit")]
+mod budget;
+pub mod config;
+pub mod routes;
+
+// Public API re-exports
+pub use config::*;
+pub use routes::*;
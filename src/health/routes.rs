@@ -0,0 +1,73 @@
+//! Ready-made `/healthz` and `/readyz` routes.
+
+use axum::{
+    Json, Router,
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use std::sync::Arc;
+
+use crate::health::{HealthRegistry, HealthReport, HealthStatus};
+
+/// Path of the liveness endpoint.
+pub const LIVENESS_PATH: &str = "/healthz";
+
+/// Path of the readiness endpoint.
+pub const READINESS_PATH: &str = "/readyz";
+
+/// Creates a router serving liveness and readiness endpoints.
+///
+/// * `GET /healthz` - Liveness: answers `200 {"status":"healthy","checks":[]}`
+///   as long as the process can serve requests. It deliberately runs no
+///   checks, so a failing dependency never gets the process restarted.
+/// * `GET /readyz` - Readiness: runs all checks and returns the JSON report,
+///   with `200` when healthy or degraded and `503` when unhealthy.
+///
+/// The returned router can be merged into any application router; it uses no
+/// application state.
+///
+/// # Arguments
+/// * `registry` - Checks run by `/readyz`
+///
+/// # Returns
+/// A router with the two health routes.
+///
+/// # Example
+/// ```rust
+/// use axum::{Router, routing::get};
+/// use axum_jetpack::health::{HealthCheck, HealthRegistry, health_routes};
+///
+/// let registry = HealthRegistry::new()
+///     .with_check(HealthCheck::new("database", || async { Ok(()) }));
+///
+/// let app: Router = Router::new()
+///     .route("/", get(|| async { "hello" }))
+///     .merge(health_routes(registry));
+/// ```
+pub fn health_routes<S>(registry: HealthRegistry) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    Router::new()
+        .route(LIVENESS_PATH, get(liveness))
+        .route(READINESS_PATH, get(readiness))
+        .with_state(Arc::new(registry))
+}
+
+async fn liveness() -> Json<HealthReport> {
+    Json(HealthReport {
+        status: HealthStatus::Healthy,
+        checks: Vec::new(),
+    })
+}
+
+async fn readiness(State(registry): State<Arc<HealthRegistry>>) -> Response {
+    let report = registry.run().await;
+    let status = match report.status {
+        HealthStatus::Unhealthy => StatusCode::SERVICE_UNAVAILABLE,
+        _ => StatusCode::OK,
+    };
+    (status, Json(report)).into_response()
+}
@@ -1 +1,34 @@
+pub mod error;
+pub mod path_pattern;
+#[cfg(any(feature = "size-limit", feature = "client-ip", feature = "egress"))]
+mod peer;
+pub mod size;
+
+#[cfg(feature = "size-limit")]
 pub mod size_limit;
+#[cfg(feature = "path-rewrite")]
+pub mod path_rewrite;
+#[cfg(feature = "client-ip")]
+pub mod client_ip;
+#[cfg(feature = "egress")]
+pub mod egress;
+#[cfg(feature = "deprecation")]
+pub mod deprecation;
+#[cfg(feature = "server-timing")]
+pub mod server_timing;
+#[cfg(feature = "range")]
+pub mod range;
+#[cfg(feature = "cors")]
+pub mod cors;
+#[cfg(feature = "pagination")]
+pub mod pagination;
+#[cfg(feature = "limited-query")]
+pub mod query;
+#[cfg(feature = "health")]
+pub mod health;
+#[cfg(feature = "header-policy")]
+pub mod header_policy;
+#[cfg(feature = "capture")]
+pub mod capture;
+#[cfg(feature = "chaos")]
+pub mod chaos;
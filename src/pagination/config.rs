@@ -0,0 +1,82 @@
+use crate::error::ErrorFormat;
+
+/// Bounds and defaults for the [`Pagination`](crate::pagination::Pagination) extractor.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::error::ErrorFormat;
+/// use axum_jetpack::pagination::PaginationConfig;
+///
+/// let config = PaginationConfig::new()
+///     .with_default_per_page(25)
+///     .with_max_per_page(200)
+///     .with_max_page(1_000)
+///     .with_error_format(ErrorFormat::ProblemDetails);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaginationConfig {
+    /// Page size used when the client does not send `per_page`. Default: 20.
+    pub default_per_page: u64,
+
+    /// Largest accepted `per_page`. Larger values are rejected with 400. Default: 100.
+    pub max_per_page: u64,
+
+    /// Largest accepted `page`, bounding deep offset scans. `None` means unbounded.
+    pub max_page: Option<u64>,
+
+    /// Longest accepted `cursor` in bytes. Default: 512.
+    pub max_cursor_len: usize,
+
+    /// How 400 rejections are rendered.
+    pub error_format: ErrorFormat,
+}
+
+impl Default for PaginationConfig {
+    /// 20 items per page, at most 100, unbounded pages, plain-text errors.
+    fn default() -> Self {
+        Self {
+            default_per_page: 20,
+            max_per_page: 100,
+            max_page: None,
+            max_cursor_len: 512,
+            error_format: ErrorFormat::PlainText,
+        }
+    }
+}
+
+impl PaginationConfig {
+    /// Creates a configuration with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method to set the default page size.
+    pub fn with_default_per_page(mut self, per_page: u64) -> Self {
+        self.default_per_page = per_page;
+        self
+    }
+
+    /// Builder method to set the maximum page size.
+    pub fn with_max_per_page(mut self, per_page: u64) -> Self {
+        self.max_per_page = per_page;
+        self
+    }
+
+    /// Builder method to set the maximum page number.
+    pub fn with_max_page(mut self, page: u64) -> Self {
+        self.max_page = Some(page);
+        self
+    }
+
+    /// Builder method to set the maximum cursor length.
+    pub fn with_max_cursor_len(mut self, len: usize) -> Self {
+        self.max_cursor_len = len;
+        self
+    }
+
+    /// Builder method to set the error format for rejections.
+    pub fn with_error_format(mut self, format: ErrorFormat) -> Self {
+        self.error_format = format;
+        self
+    }
+}
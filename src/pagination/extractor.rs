@@ -0,0 +1,210 @@
+//! Pagination extractor and middleware.
+//!
+//! The middleware makes a [`PaginationConfig`] available to the [`Pagination`]
+//! extractor; without it the extractor uses the default bounds.
+
+use axum::{
+    Router,
+    extract::{FromRequestParts, Query},
+    http::{StatusCode, request::Parts},
+    response::Response,
+};
+use std::sync::Arc;
+
+use crate::error::ErrorFormat;
+use crate::pagination::{PaginationConfig, Paginated};
+
+/// Query parameter carrying the 1-based page number.
+pub const PAGE_PARAM: &str = "page";
+/// Query parameter carrying the page size.
+pub const PER_PAGE_PARAM: &str = "per_page";
+/// Query parameter carrying an opaque cursor.
+pub const CURSOR_PARAM: &str = "cursor";
+
+/// Validated pagination parameters of a request.
+///
+/// Reads `page`, `per_page` and `cursor` from the query string. `page` and
+/// `cursor` are mutually exclusive: offset-paginated endpoints use
+/// [`offset`](Self::offset), cursor-paginated ones use [`cursor`](Self::cursor).
+/// Values outside the configured bounds are rejected with 400 rendered through
+/// the configured [`ErrorFormat`](crate::error::ErrorFormat).
+///
+/// # Example
+/// ```rust
+/// use axum_jetpack::pagination::{Pagination, Paginated};
+///
+/// async fn list(pagination: Pagination) -> Paginated<String> {
+///     let total = 1_234;
+///     let items = format!("items {}..{}", pagination.offset(), pagination.offset() + pagination.per_page);
+///     pagination.page_response(items, Some(total))
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pagination {
+    /// 1-based page number (1 when a cursor is used).
+    pub page: u64,
+    /// Number of items per page.
+    pub per_page: u64,
+    /// Opaque cursor sent by the client, if any.
+    pub cursor: Option<String>,
+    /// Request path, used to build `Link` headers.
+    pub(crate) path: String,
+    /// Non-pagination query parameters, preserved in `Link` headers.
+    pub(crate) query: Vec<(String, String)>,
+}
+
+impl Pagination {
+    /// Number of items to skip for offset pagination.
+    pub fn offset(&self) -> u64 {
+        (self.page - 1).saturating_mul(self.per_page)
+    }
+
+    /// Alias for [`per_page`](Self::per_page), for use as a query `LIMIT`.
+    pub fn limit(&self) -> u64 {
+        self.per_page
+    }
+
+    /// Returns the client's cursor, if cursor pagination is used.
+    pub fn cursor(&self) -> Option<&str> {
+        self.cursor.as_deref()
+    }
+
+    /// Wraps a page of an offset-paginated collection for the response.
+    ///
+    /// # Arguments
+    /// * `body` - The page content
+    /// * `total` - Total number of items, if known (enables `last` and `X-Total-Count`)
+    pub fn page_response<T>(&self, body: T, total: Option<u64>) -> Paginated<T> {
+        Paginated::for_page(self, body, total)
+    }
+
+    /// Wraps a page of a cursor-paginated collection for the response.
+    ///
+    /// # Arguments
+    /// * `body` - The page content
+    /// * `next_cursor` - Cursor of the following page; `None` on the last page
+    pub fn cursor_response<T>(&self, body: T, next_cursor: Option<String>) -> Paginated<T> {
+        Paginated::for_cursor(self, body, next_cursor)
+    }
+
+    /// Parses and validates pagination from a query parameter list.
+    ///
+    /// # Returns
+    /// * `Ok(Pagination)` - Valid parameters
+    /// * `Err(String)` - A message describing the violated bound
+    pub fn from_query(
+        path: &str,
+        params: Vec<(String, String)>,
+        config: &PaginationConfig,
+    ) -> Result<Self, String> {
+        let mut page = None;
+        let mut per_page = None;
+        let mut cursor = None;
+        let mut query = Vec::new();
+
+        for (key, value) in params {
+            match key.as_str() {
+                PAGE_PARAM => page = Some(parse_number(PAGE_PARAM, &value)?),
+                PER_PAGE_PARAM => per_page = Some(parse_number(PER_PAGE_PARAM, &value)?),
+                CURSOR_PARAM => cursor = Some(value),
+                _ => query.push((key, value)),
+            }
+        }
+
+        if page.is_some() && cursor.is_some() {
+            return Err("page and cursor cannot be combined".to_string());
+        }
+
+        let page = page.unwrap_or(1);
+        if page == 0 {
+            return Err("page must be at least 1".to_string());
+        }
+        if let Some(max) = config.max_page
+            && page > max
+        {
+            return Err(format!("page must be at most {}", max));
+        }
+
+        let per_page = per_page.unwrap_or(config.default_per_page);
+        if per_page == 0 {
+            return Err("per_page must be at least 1".to_string());
+        }
+        if per_page > config.max_per_page {
+            return Err(format!("per_page must be at most {}", config.max_per_page));
+        }
+
+        if let Some(cursor) = &cursor
+            && (cursor.is_empty() || cursor.len() > config.max_cursor_len)
+        {
+            return Err(format!(
+                "cursor must be between 1 and {} bytes",
+                config.max_cursor_len
+            ));
+        }
+
+        Ok(Self {
+            page,
+            per_page,
+            cursor,
+            path: path.to_string(),
+            query,
+        })
+    }
+}
+
+fn parse_number(name: &str, value: &str) -> Result<u64, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{} must be a positive integer", name))
+}
+
+impl<S> FromRequestParts<S> for Pagination
+where
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let config = parts
+            .extensions
+            .get::<Arc<PaginationConfig>>()
+            .cloned()
+            .unwrap_or_default();
+        let error_format = ErrorFormat::for_request(&parts.extensions, config.error_format);
+
+        let params = match Query::<Vec<(String, String)>>::from_request_parts(parts, state).await {
+            Ok(Query(params)) => params,
+            Err(_) => {
+                return Err(error_format.response(StatusCode::BAD_REQUEST, "Invalid query string"));
+            }
+        };
+
+        Self::from_query(parts.uri.path(), params, &config)
+            .map_err(|message| error_format.response(StatusCode::BAD_REQUEST, &message))
+    }
+}
+
+/// Makes a pagination configuration available to the [`Pagination`] extractor.
+///
+/// # Arguments
+/// * `router` - The Axum router to wrap
+/// * `config` - Bounds, defaults and error format
+///
+/// # Returns
+/// A new router whose handlers extract [`Pagination`] with these bounds.
+///
+/// # Example
+/// ```rust
+/// use axum::{Router, routing::get};
+/// use axum_jetpack::pagination::{Pagination, PaginationConfig, with_pagination};
+///
+/// async fn list(pagination: Pagination) -> String {
+///     format!("offset {} limit {}", pagination.offset(), pagination.limit())
+/// }
+///
+/// let router = Router::new().route("/items", get(list));
+/// let router = with_pagination(router, PaginationConfig::new().with_max_per_page(50));
+/// ```
+pub fn with_pagination(router: Router, config: PaginationConfig) -> Router {
+    router.layer(axum::Extension(Arc::new(config)))
+}
@@ -0,0 +1,8 @@
+pub mod config;
+pub mod extractor;
+pub mod response;
+
+// Public API re-exports
+pub use config::*;
+pub use extractor::*;
+pub use response::*;
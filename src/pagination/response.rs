@@ -0,0 +1,134 @@
+//! `Paginated<T>` response helper emitting RFC 8288 `Link` headers.
+
+use axum::{
+    http::{HeaderName, HeaderValue, header},
+    response::{IntoResponse, Response},
+};
+
+use crate::pagination::{CURSOR_PARAM, PAGE_PARAM, PER_PAGE_PARAM, Pagination};
+
+/// Response header carrying the total number of items, when known.
+pub const X_TOTAL_COUNT: HeaderName = HeaderName::from_static("x-total-count");
+
+/// A page of results plus navigation links.
+///
+/// Renders the wrapped body unchanged and adds a `Link` header with `first`,
+/// `prev`, `next` and `last` relations (as applicable) plus `X-Total-Count`
+/// when the total is known. Links are relative to the request path and keep
+/// the request's other query parameters.
+///
+/// Created with [`Pagination::page_response`] or [`Pagination::cursor_response`].
+#[derive(Clone, Debug)]
+pub struct Paginated<T> {
+    /// The page content.
+    pub body: T,
+    /// `(relation, url)` pairs rendered into the `Link` header.
+    pub links: Vec<(&'static str, String)>,
+    /// Total item count, if known.
+    pub total: Option<u64>,
+}
+
+impl<T> Paginated<T> {
+    pub(crate) fn for_page(pagination: &Pagination, body: T, total: Option<u64>) -> Self {
+        let page = pagination.page;
+        let per_page = pagination.per_page;
+        let last = total.map(|total| total.div_ceil(per_page).max(1));
+        let link = |page: u64| {
+            build_url(
+                pagination,
+                &[
+                    (PAGE_PARAM, page.to_string()),
+                    (PER_PAGE_PARAM, per_page.to_string()),
+                ],
+            )
+        };
+
+        let mut links = vec![("first", link(1))];
+        if page > 1 {
+            // Clamp so a page past the end still links back into the collection
+            let prev = last.map_or(page - 1, |last| (page - 1).min(last));
+            links.push(("prev", link(prev)));
+        }
+        if last.is_none_or(|last| page < last) {
+            links.push(("next", link(page + 1)));
+        }
+        if let Some(last) = last {
+            links.push(("last", link(last)));
+        }
+
+        Self { body, links, total }
+    }
+
+    pub(crate) fn for_cursor(pagination: &Pagination, body: T, next_cursor: Option<String>) -> Self {
+        let per_page = pagination.per_page.to_string();
+        let mut links = vec![("first", build_url(pagination, &[(PER_PAGE_PARAM, per_page.clone())]))];
+        if let Some(cursor) = next_cursor {
+            links.push((
+                "next",
+                build_url(pagination, &[(CURSOR_PARAM, cursor), (PER_PAGE_PARAM, per_page)]),
+            ));
+        }
+
+        Self {
+            body,
+            links,
+            total: None,
+        }
+    }
+
+    /// Builder method to set the total item count (sent as `X-Total-Count`).
+    pub fn with_total(mut self, total: u64) -> Self {
+        self.total = Some(total);
+        self
+    }
+
+    /// Renders the `Link` header value.
+    pub fn link_header(&self) -> String {
+        self.links
+            .iter()
+            .map(|(rel, url)| format!("<{}>; rel=\"{}\"", url, rel))
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+impl<T: IntoResponse> IntoResponse for Paginated<T> {
+    fn into_response(self) -> Response {
+        let link = self.link_header();
+        let mut response = self.body.into_response();
+        if let Ok(value) = HeaderValue::from_str(&link) {
+            response.headers_mut().insert(header::LINK, value);
+        }
+        if let Some(total) = self.total {
+            response.headers_mut().insert(X_TOTAL_COUNT, HeaderValue::from(total));
+        }
+        response
+    }
+}
+
+/// Builds `path?<preserved query>&<pagination params>`.
+fn build_url(pagination: &Pagination, params: &[(&str, String)]) -> String {
+    let query = pagination
+        .query
+        .iter()
+        .map(|(k, v)| (k.as_str(), v.as_str()))
+        .chain(params.iter().map(|(k, v)| (*k, v.as_str())))
+        .map(|(k, v)| format!("{}={}", encode(k), encode(v)))
+        .collect::<Vec<_>>()
+        .join("&");
+    format!("{}?{}", pagination.path, query)
+}
+
+/// Percent-encodes everything except RFC 3986 unreserved characters.
+fn encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
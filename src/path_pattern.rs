@@ -0,0 +1,33 @@
+//! Route pattern matching shared by the crate's per-route options.
+
+/// Returns `true` if a route pattern matches a route template or request path.
+///
+/// A pattern is either an exact path (`/users/{id}`) or a prefix ending in
+/// `/*`, which matches the prefix itself and everything below it on segment
+/// boundaries (`/api/*` matches `/api` and `/api/users`, but not `/apix`).
+/// A lone `*` matches every path.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::path_pattern::matches;
+///
+/// assert!(matches("/api/*", "/api"));
+/// assert!(matches("/api/*", "/api/users/{id}"));
+/// assert!(!matches("/api/*", "/apix"));
+/// assert!(matches("/health", "/health"));
+/// assert!(matches("*", "/anything"));
+/// ```
+pub fn matches(pattern: &str, path: &str) -> bool {
+    if pattern == "*" {
+        return true;
+    }
+    match pattern.strip_suffix("/*") {
+        Some(prefix) => {
+            path == prefix
+                || path
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        }
+        None => path == pattern,
+    }
+}
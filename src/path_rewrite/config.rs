@@ -0,0 +1,307 @@
+use regex::Regex;
+
+/// A single path rewrite rule.
+///
+/// Rules are applied in the order they were added to a [`PathRewriteConfig`].
+/// Each rule either leaves the path untouched or produces a new path.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::path_rewrite::RewriteRule;
+///
+/// let rule = RewriteRule::strip_prefix("/api");
+/// assert_eq!(rule.apply("/api/users"), Some("/users".to_string()));
+/// assert_eq!(rule.apply("/apiary"), None); // Only whole segments are stripped
+///
+/// let rule = RewriteRule::regex(r"^/v1/(.*)$", "/v2/$1").unwrap();
+/// assert_eq!(rule.apply("/v1/orders"), Some("/v2/orders".to_string()));
+/// ```
+#[derive(Clone, Debug)]
+pub enum RewriteRule {
+    /// Removes a leading path prefix.
+    ///
+    /// The prefix only matches whole path segments, so stripping `/api`
+    /// rewrites `/api/users` to `/users` and `/api` to `/`, but leaves `/apiary` alone.
+    StripPrefix(String),
+
+    /// Replaces the path using a regular expression with capture substitution.
+    ///
+    /// The replacement string supports the usual `$1` / `${name}` syntax of the `regex` crate.
+    Regex {
+        /// Pattern matched against the request path (without query string).
+        pattern: Regex,
+        /// Replacement applied to the first match.
+        replacement: String,
+    },
+}
+
+impl RewriteRule {
+    /// Creates a rule that strips a leading path prefix.
+    ///
+    /// A trailing slash on the prefix is ignored (`"/api/"` behaves like `"/api"`).
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::path_rewrite::RewriteRule;
+    ///
+    /// let rule = RewriteRule::strip_prefix("/api/");
+    /// assert_eq!(rule.apply("/api"), Some("/".to_string()));
+    /// ```
+    pub fn strip_prefix(prefix: &str) -> Self {
+        RewriteRule::StripPrefix(prefix.trim_end_matches('/').to_string())
+    }
+
+    /// Creates a regex substitution rule.
+    ///
+    /// # Arguments
+    /// * `pattern` - Regular expression matched against the path
+    /// * `replacement` - Replacement string (supports `$1`, `${name}` captures)
+    ///
+    /// # Returns
+    /// - `Ok(RewriteRule)` if the pattern compiles
+    /// - `Err(regex::Error)` if the pattern is invalid
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::path_rewrite::RewriteRule;
+    ///
+    /// assert!(RewriteRule::regex(r"^/old/(?P<rest>.*)$", "/new/${rest}").is_ok());
+    /// assert!(RewriteRule::regex(r"^/old/(", "/new").is_err());
+    /// ```
+    pub fn regex(pattern: &str, replacement: &str) -> Result<Self, regex::Error> {
+        Ok(RewriteRule::Regex {
+            pattern: Regex::new(pattern)?,
+            replacement: replacement.to_string(),
+        })
+    }
+
+    /// Applies this rule to a path.
+    ///
+    /// # Returns
+    /// - `Some(String)` with the rewritten path if the rule matched and changed the path
+    /// - `None` if the rule did not apply
+    pub fn apply(&self, path: &str) -> Option<String> {
+        let rewritten = match self {
+            RewriteRule::StripPrefix(prefix) => {
+                if prefix.is_empty() {
+                    return None;
+                }
+                let rest = path.strip_prefix(prefix.as_str())?;
+                if rest.is_empty() {
+                    "/".to_string()
+                } else if rest.starts_with('/') {
+                    rest.to_string()
+                } else {
+                    // Prefix matched only part of a segment (e.g. "/api" vs "/apiary")
+                    return None;
+                }
+            }
+            RewriteRule::Regex { pattern, replacement } => {
+                if !pattern.is_match(path) {
+                    return None;
+                }
+                pattern.replace(path, replacement.as_str()).into_owned()
+            }
+        };
+
+        (rewritten != path).then_some(rewritten)
+    }
+}
+
+/// How trailing slashes are normalized after the rewrite rules ran.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum TrailingSlash {
+    /// Leave trailing slashes as they are.
+    #[default]
+    Keep,
+    /// Remove trailing slashes (`/users/` → `/users`). The root path `/` is never changed.
+    Trim,
+    /// Ensure a trailing slash (`/users` → `/users/`).
+    Append,
+}
+
+impl TrailingSlash {
+    /// Applies the normalization to a path.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::path_rewrite::TrailingSlash;
+    ///
+    /// assert_eq!(TrailingSlash::Trim.normalize("/users//"), "/users");
+    /// assert_eq!(TrailingSlash::Trim.normalize("/"), "/");
+    /// assert_eq!(TrailingSlash::Append.normalize("/users"), "/users/");
+    /// assert_eq!(TrailingSlash::Keep.normalize("/users/"), "/users/");
+    /// ```
+    pub fn normalize(&self, path: &str) -> String {
+        match self {
+            TrailingSlash::Keep => path.to_string(),
+            TrailingSlash::Trim => {
+                let trimmed = path.trim_end_matches('/');
+                if trimmed.is_empty() {
+                    "/".to_string()
+                } else {
+                    trimmed.to_string()
+                }
+            }
+            TrailingSlash::Append => {
+                if path.ends_with('/') {
+                    path.to_string()
+                } else {
+                    format!("{}/", path)
+                }
+            }
+        }
+    }
+}
+
+/// Configuration for the path rewrite layer.
+///
+/// Rules are applied in order, followed by trailing-slash normalization. This
+/// forms one *pass*. Passes are repeated until the path no longer changes, so
+/// rules may build on each other's output. If a path is seen twice (a rewrite
+/// cycle) or `max_passes` is exceeded, rewriting fails with a loop error.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::path_rewrite::{PathRewriteConfig, RewriteRule, TrailingSlash};
+///
+/// let config = PathRewriteConfig::new()
+///     .with_strip_prefix("/api")
+///     .with_rule(RewriteRule::regex(r"^/v1/(.*)$", "/v2/$1").unwrap())
+///     .with_trailing_slash(TrailingSlash::Trim);
+///
+/// assert_eq!(config.rewrite("/api/v1/users/").unwrap(), "/v2/users");
+/// assert_eq!(config.rewrite("/health").unwrap(), "/health");
+/// ```
+#[derive(Clone, Debug)]
+pub struct PathRewriteConfig {
+    /// Rewrite rules, applied in insertion order.
+    pub rules: Vec<RewriteRule>,
+
+    /// Trailing-slash normalization applied at the end of each pass.
+    ///
+    /// Default value: [`TrailingSlash::Keep`]
+    pub trailing_slash: TrailingSlash,
+
+    /// Maximum number of passes before rewriting is considered a loop.
+    ///
+    /// Default value: 10
+    pub max_passes: usize,
+
+    /// Whether to also expose the original path as an `X-Original-Path` request header.
+    ///
+    /// The [`OriginalPath`](crate::path_rewrite::OriginalPath) extension is always inserted;
+    /// the header is useful when the request is forwarded to another service.
+    /// Any client-supplied `X-Original-Path` header is overwritten.
+    ///
+    /// Default value: `false`
+    pub original_path_header: bool,
+}
+
+impl Default for PathRewriteConfig {
+    /// Creates an empty configuration (no rules, keep trailing slashes, 10 passes).
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            trailing_slash: TrailingSlash::Keep,
+            max_passes: 10,
+            original_path_header: false,
+        }
+    }
+}
+
+impl PathRewriteConfig {
+    /// Creates a new, empty `PathRewriteConfig`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method to append a rewrite rule.
+    pub fn with_rule(mut self, rule: RewriteRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Builder method to append a prefix-stripping rule.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::path_rewrite::PathRewriteConfig;
+    ///
+    /// let config = PathRewriteConfig::new().with_strip_prefix("/service-a");
+    /// assert_eq!(config.rewrite("/service-a/items").unwrap(), "/items");
+    /// ```
+    pub fn with_strip_prefix(self, prefix: &str) -> Self {
+        self.with_rule(RewriteRule::strip_prefix(prefix))
+    }
+
+    /// Builder method to set trailing-slash normalization.
+    pub fn with_trailing_slash(mut self, trailing_slash: TrailingSlash) -> Self {
+        self.trailing_slash = trailing_slash;
+        self
+    }
+
+    /// Builder method to set the maximum number of rewrite passes.
+    pub fn with_max_passes(mut self, max_passes: usize) -> Self {
+        self.max_passes = max_passes;
+        self
+    }
+
+    /// Builder method to enable the `X-Original-Path` request header.
+    pub fn with_original_path_header(mut self, enabled: bool) -> Self {
+        self.original_path_header = enabled;
+        self
+    }
+
+    /// Rewrites a path according to the configured rules.
+    ///
+    /// # Arguments
+    /// * `path` - The request path (without query string)
+    ///
+    /// # Returns
+    /// - `Ok(String)` - The rewritten path (equal to the input if nothing matched)
+    /// - `Err(String)` - An error message if a rewrite loop was detected
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::path_rewrite::{PathRewriteConfig, RewriteRule};
+    ///
+    /// // A rule that matches its own output never settles
+    /// let config = PathRewriteConfig::new()
+    ///     .with_rule(RewriteRule::regex("^/(.*)$", "/x/$1").unwrap());
+    ///
+    /// assert!(config.rewrite("/a").is_err());
+    /// ```
+    pub fn rewrite(&self, path: &str) -> Result<String, String> {
+        let mut seen = vec![path.to_string()];
+        let mut current = path.to_string();
+
+        for _ in 0..self.max_passes {
+            let mut next = current.clone();
+            for rule in &self.rules {
+                if let Some(rewritten) = rule.apply(&next) {
+                    next = rewritten;
+                }
+            }
+            next = self.trailing_slash.normalize(&next);
+
+            // Fixed point reached
+            if next == current {
+                return Ok(current);
+            }
+
+            // A path we already produced means the rules cycle
+            if seen.contains(&next) {
+                return Err(format!("Rewrite loop detected at '{}'", next));
+            }
+
+            seen.push(next.clone());
+            current = next;
+        }
+
+        Err(format!(
+            "Rewrite of '{}' did not settle within {} passes",
+            path, self.max_passes
+        ))
+    }
+}
@@ -0,0 +1,194 @@
+//! Path rewriting layer for Axum applications.
+//!
+//! Axum performs routing before any middleware added with `Router::layer` runs,
+//! so a URI rewrite inside such middleware has no effect on which handler is
+//! selected. This layer must therefore wrap the *whole* router (or the proxy
+//! service) so the rewrite happens before routing.
+
+use axum::{
+    Router,
+    body::Body,
+    extract::{OriginalUri, Request},
+    http::{HeaderName, HeaderValue, StatusCode, Uri, uri::PathAndQuery},
+    response::{IntoResponse, Response},
+};
+use futures::future::Either;
+use std::future::{Ready, ready};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+use crate::path_rewrite::PathRewriteConfig;
+
+/// Name of the request header carrying the original path (when enabled).
+pub const X_ORIGINAL_PATH: HeaderName = HeaderName::from_static("x-original-path");
+
+/// The request path as received, before any rewrite rules were applied.
+///
+/// Inserted into the request extensions by [`PathRewrite`] so handlers can
+/// still see what the client asked for.
+///
+/// # Example
+/// ```rust
+/// use axum::Extension;
+/// use axum_jetpack::path_rewrite::OriginalPath;
+///
+/// async fn handler(Extension(OriginalPath(path)): Extension<OriginalPath>) -> String {
+///     format!("You requested {}", path)
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OriginalPath(pub String);
+
+/// Tower layer that rewrites request paths according to a [`PathRewriteConfig`].
+///
+/// # Example
+/// ```rust
+/// use axum::{Router, routing::get};
+/// use axum_jetpack::path_rewrite::{PathRewriteConfig, PathRewriteLayer};
+/// use tower::Layer;
+///
+/// let router: Router = Router::new().route("/users", get(|| async { "users" }));
+///
+/// // Wrap the whole router so the rewrite happens before routing
+/// let app = PathRewriteLayer::new(PathRewriteConfig::new().with_strip_prefix("/api"))
+///     .layer(router);
+/// ```
+#[derive(Clone, Debug)]
+pub struct PathRewriteLayer {
+    config: Arc<PathRewriteConfig>,
+}
+
+impl PathRewriteLayer {
+    /// Creates a new path rewrite layer.
+    ///
+    /// # Arguments
+    /// * `config` - Rewrite rules and normalization settings
+    pub fn new(config: PathRewriteConfig) -> Self {
+        Self {
+            config: Arc::new(config),
+        }
+    }
+}
+
+impl<S> Layer<S> for PathRewriteLayer {
+    type Service = PathRewrite<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        PathRewrite {
+            inner,
+            config: self.config.clone(),
+        }
+    }
+}
+
+/// Service that rewrites request paths before calling the inner service.
+///
+/// For every request it:
+/// 1. Records the original path in the [`OriginalPath`] extension (and axum's
+///    [`OriginalUri`], unless an outer layer already set it)
+/// 2. Applies the configured rules until the path settles
+/// 3. Replaces the request URI, preserving the query string
+/// 4. Returns 508 (Loop Detected) if the rules never settle
+#[derive(Clone, Debug)]
+pub struct PathRewrite<S> {
+    inner: S,
+    config: Arc<PathRewriteConfig>,
+}
+
+impl<S> Service<Request<Body>> for PathRewrite<S>
+where
+    S: Service<Request<Body>, Response = Response>,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Either<Ready<Result<Response, S::Error>>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<Body>) -> Self::Future {
+        let original = req.uri().path().to_string();
+
+        let rewritten = match self.config.rewrite(&original) {
+            Ok(path) => path,
+            Err(_) => {
+                return Either::Left(ready(Ok(
+                    (StatusCode::LOOP_DETECTED, "Rewrite loop detected").into_response()
+                )));
+            }
+        };
+
+        if req.extensions().get::<OriginalUri>().is_none() {
+            let uri = req.uri().clone();
+            req.extensions_mut().insert(OriginalUri(uri));
+        }
+
+        if self.config.original_path_header
+            && let Ok(value) = HeaderValue::from_str(&original)
+        {
+            req.headers_mut().insert(X_ORIGINAL_PATH, value);
+        }
+
+        if rewritten != original {
+            match replace_path(req.uri(), &rewritten) {
+                Some(uri) => *req.uri_mut() = uri,
+                None => {
+                    // Replacement produced characters that are not valid in a URI
+                    return Either::Left(ready(Ok(
+                        (StatusCode::INTERNAL_SERVER_ERROR, "Invalid rewritten path").into_response()
+                    )));
+                }
+            }
+        }
+
+        req.extensions_mut().insert(OriginalPath(original));
+
+        Either::Right(self.inner.call(req))
+    }
+}
+
+/// Applies path rewriting to an Axum router.
+///
+/// Convenience wrapper around [`PathRewriteLayer`]. The returned service is no
+/// longer a `Router`, so it has to be served with
+/// `ServiceExt::<Request>::into_make_service`.
+///
+/// # Arguments
+/// * `router` - The Axum router to wrap
+/// * `config` - Rewrite rules and normalization settings
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{Router, extract::Request, routing::get, ServiceExt};
+/// use axum_jetpack::path_rewrite::{PathRewriteConfig, TrailingSlash, with_path_rewrite};
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let router = Router::new().route("/users", get(|| async { "users" }));
+///
+/// let config = PathRewriteConfig::new()
+///     .with_strip_prefix("/api")
+///     .with_trailing_slash(TrailingSlash::Trim);
+/// let app = with_path_rewrite(router, config);
+///
+/// let listener = tokio::net::TcpListener::bind("127.0.0.1:3000").await?;
+/// axum::serve(listener, ServiceExt::<Request>::into_make_service(app)).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn with_path_rewrite(router: Router, config: PathRewriteConfig) -> PathRewrite<Router> {
+    PathRewriteLayer::new(config).layer(router)
+}
+
+/// Builds a new URI with the given path, keeping scheme, authority and query.
+fn replace_path(uri: &Uri, path: &str) -> Option<Uri> {
+    let path_and_query = match uri.query() {
+        Some(query) => format!("{}?{}", path, query),
+        None => path.to_string(),
+    };
+
+    let mut parts = uri.clone().into_parts();
+    parts.path_and_query = Some(PathAndQuery::try_from(path_and_query).ok()?);
+    Uri::from_parts(parts).ok()
+}
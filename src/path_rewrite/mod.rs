@@ -0,0 +1,6 @@
+pub mod config;
+pub mod layer;
+
+// Public API re-exports
+pub use config::*;
+pub use layer::*;
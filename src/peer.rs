@@ -0,0 +1,31 @@
+//! Who sent a request, as seen by the modules keying on it.
+
+use axum::extract::{ConnectInfo, connect_info::MockConnectInfo};
+use axum::http::request::Parts;
+use std::net::SocketAddr;
+#[cfg(any(feature = "size-limit", feature = "egress"))]
+use std::net::IpAddr;
+
+#[cfg(all(feature = "client-ip", any(feature = "size-limit", feature = "egress")))]
+use crate::client_ip::ClientIp;
+
+/// The peer address of the connection, from `ConnectInfo<SocketAddr>`.
+pub(crate) fn peer_addr(parts: &Parts) -> Option<SocketAddr> {
+    let extensions = &parts.extensions;
+    extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|info| info.0)
+        // `MockConnectInfo` stands in for the connect info in tests
+        .or_else(|| extensions.get::<MockConnectInfo<SocketAddr>>().map(|info| info.0))
+}
+
+/// The client IP: the address resolved by the `client_ip` module when the
+/// `client-ip` feature is enabled and its layer ran, else the peer address.
+#[cfg(any(feature = "size-limit", feature = "egress"))]
+pub(crate) fn peer_ip(parts: &Parts) -> Option<IpAddr> {
+    #[cfg(feature = "client-ip")]
+    if let Some(ClientIp(ip)) = parts.extensions.get::<ClientIp>() {
+        return Some(*ip);
+    }
+    peer_addr(parts).map(|peer| peer.ip())
+}
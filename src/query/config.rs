@@ -0,0 +1,133 @@
+use crate::error::ErrorFormat;
+
+/// Limits enforced on the raw query string before it is deserialized.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::error::ErrorFormat;
+/// use axum_jetpack::query::QueryLimits;
+///
+/// let limits = QueryLimits::new()
+///     .with_max_length(1024)
+///     .with_max_params(16)
+///     .with_max_value_length(128)
+///     .with_error_format(ErrorFormat::Json);
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QueryLimits {
+    /// Maximum length of the raw query string in bytes (414 when exceeded). Default: 2048.
+    pub max_length: usize,
+
+    /// Maximum number of `key=value` pairs (400 when exceeded). Default: 32.
+    pub max_params: usize,
+
+    /// Maximum length of a single raw (still percent-encoded) value in bytes
+    /// (400 when exceeded). Default: 256.
+    pub max_value_length: usize,
+
+    /// How rejections are rendered.
+    pub error_format: ErrorFormat,
+}
+
+impl Default for QueryLimits {
+    /// 2 KiB query, 32 parameters, 256-byte values, plain-text errors.
+    fn default() -> Self {
+        Self {
+            max_length: 2048,
+            max_params: 32,
+            max_value_length: 256,
+            error_format: ErrorFormat::PlainText,
+        }
+    }
+}
+
+impl QueryLimits {
+    /// Creates limits with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method to set the maximum query string length.
+    pub fn with_max_length(mut self, length: usize) -> Self {
+        self.max_length = length;
+        self
+    }
+
+    /// Builder method to set the maximum number of parameters.
+    pub fn with_max_params(mut self, count: usize) -> Self {
+        self.max_params = count;
+        self
+    }
+
+    /// Builder method to set the maximum length of a single value.
+    pub fn with_max_value_length(mut self, length: usize) -> Self {
+        self.max_value_length = length;
+        self
+    }
+
+    /// Builder method to set the error format for rejections.
+    pub fn with_error_format(mut self, format: ErrorFormat) -> Self {
+        self.error_format = format;
+        self
+    }
+
+    /// Checks a raw query string against the limits.
+    ///
+    /// # Returns
+    /// * `Ok(())` - The query is within all limits
+    /// * `Err(QueryLimitError)` - The first violated limit
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::query::{QueryLimitError, QueryLimits};
+    ///
+    /// let limits = QueryLimits::new().with_max_params(2);
+    /// assert!(limits.check("a=1&b=2").is_ok());
+    /// assert_eq!(limits.check("a=1&b=2&c=3"), Err(QueryLimitError::TooManyParams(2)));
+    /// ```
+    pub fn check(&self, query: &str) -> Result<(), QueryLimitError> {
+        if query.len() > self.max_length {
+            return Err(QueryLimitError::TooLong(self.max_length));
+        }
+
+        let mut count = 0;
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            count += 1;
+            if count > self.max_params {
+                return Err(QueryLimitError::TooManyParams(self.max_params));
+            }
+            let value = pair.split_once('=').map_or("", |(_, value)| value);
+            if value.len() > self.max_value_length {
+                return Err(QueryLimitError::ValueTooLong(self.max_value_length));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A violated query limit.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum QueryLimitError {
+    /// The query string exceeds the maximum length.
+    TooLong(usize),
+    /// The query has more parameters than allowed.
+    TooManyParams(usize),
+    /// A value exceeds the maximum length.
+    ValueTooLong(usize),
+}
+
+impl std::fmt::Display for QueryLimitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueryLimitError::TooLong(max) => write!(f, "Query string exceeds {} bytes", max),
+            QueryLimitError::TooManyParams(max) => {
+                write!(f, "Query string has more than {} parameters", max)
+            }
+            QueryLimitError::ValueTooLong(max) => {
+                write!(f, "Query parameter value exceeds {} bytes", max)
+            }
+        }
+    }
+}
+
+impl std::error::Error for QueryLimitError {}
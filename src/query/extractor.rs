@@ -0,0 +1,141 @@
+//! Query extractors that enforce [`QueryLimits`] before deserializing.
+
+use axum::{
+    Router,
+    extract::{FromRequestParts, Query},
+    http::{StatusCode, request::Parts},
+    response::Response,
+};
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
+
+use crate::error::ErrorFormat;
+use crate::query::{QueryLimitError, QueryLimits};
+
+/// Query extractor that checks length, parameter count and value length
+/// before deserializing into `T`.
+///
+/// Limits come from [`with_query_limits`]; without it the defaults of
+/// [`QueryLimits`] apply. An overlong query is rejected with 414, other
+/// violations and deserialization failures with 400, all rendered through
+/// the configured [`ErrorFormat`](crate::error::ErrorFormat).
+///
+/// # Example
+/// ```rust
+/// use axum_jetpack::query::LimitedQuery;
+/// use serde::Deserialize;
+///
+/// #[derive(Deserialize)]
+/// struct Search {
+///     q: String,
+///     lang: Option<String>,
+/// }
+///
+/// async fn search(LimitedQuery(search): LimitedQuery<Search>) -> String {
+///     format!("searching for {}", search.q)
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LimitedQuery<T>(pub T);
+
+impl<T, S> FromRequestParts<S> for LimitedQuery<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let limits = limits(parts);
+        let query = parts.uri.query().unwrap_or_default();
+
+        if let Err(e) = limits.check(query) {
+            let status = match e {
+                QueryLimitError::TooLong(_) => StatusCode::URI_TOO_LONG,
+                _ => StatusCode::BAD_REQUEST,
+            };
+            return Err(error_format(parts, &limits).response(status, &e.to_string()));
+        }
+
+        Query::<T>::try_from_uri(&parts.uri)
+            .map(|Query(value)| LimitedQuery(value))
+            .map_err(|e| error_format(parts, &limits).response(StatusCode::BAD_REQUEST, &e.body_text()))
+    }
+}
+
+/// [`LimitedQuery`] that additionally runs `validator` rules on the result.
+///
+/// Validation failures are rejected with 422 Unprocessable Entity.
+///
+/// # Example
+/// ```rust
+/// use axum_jetpack::query::ValidatedQuery;
+/// use serde::Deserialize;
+/// use validator::Validate;
+///
+/// #[derive(Deserialize, Validate)]
+/// struct Search {
+///     #[validate(length(min = 2, max = 64))]
+///     q: String,
+/// }
+///
+/// async fn search(ValidatedQuery(search): ValidatedQuery<Search>) -> String {
+///     search.q
+/// }
+/// ```
+#[cfg(feature = "validation")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ValidatedQuery<T>(pub T);
+
+#[cfg(feature = "validation")]
+impl<T, S> FromRequestParts<S> for ValidatedQuery<T>
+where
+    T: DeserializeOwned + validator::Validate,
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let LimitedQuery(value) = LimitedQuery::<T>::from_request_parts(parts, state).await?;
+
+        value.validate().map_err(|e| {
+            error_format(parts, &limits(parts))
+                .response(StatusCode::UNPROCESSABLE_ENTITY, &e.to_string())
+        })?;
+        Ok(ValidatedQuery(value))
+    }
+}
+
+fn limits(parts: &Parts) -> Arc<QueryLimits> {
+    parts
+        .extensions
+        .get::<Arc<QueryLimits>>()
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Per-route format from `with_error_formats`, else the configured one.
+fn error_format(parts: &Parts, limits: &QueryLimits) -> ErrorFormat {
+    ErrorFormat::for_request(&parts.extensions, limits.error_format)
+}
+
+/// Makes query limits available to [`LimitedQuery`] (and `ValidatedQuery`).
+///
+/// # Arguments
+/// * `router` - The Axum router to wrap
+/// * `limits` - Limits and error format
+///
+/// # Returns
+/// A new router whose handlers extract queries with these limits.
+///
+/// # Example
+/// ```rust
+/// use axum::{Router, routing::get};
+/// use axum_jetpack::query::{QueryLimits, with_query_limits};
+///
+/// let router: Router = Router::new().route("/search", get(|| async { "ok" }));
+/// let router = with_query_limits(router, QueryLimits::new().with_max_params(8));
+/// ```
+pub fn with_query_limits(router: Router, limits: QueryLimits) -> Router {
+    router.layer(axum::Extension(Arc::new(limits)))
+}
@@ -0,0 +1,6 @@
+pub mod config;
+pub mod extractor;
+
+// Public API re-exports
+pub use config::*;
+pub use extractor::*;
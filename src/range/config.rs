@@ -0,0 +1,188 @@
+use axum::http::{HeaderMap, header};
+
+/// Default largest response the range middleware buffers to serve a range (8 MiB).
+pub const DEFAULT_MAX_BUFFERED: usize = 8 * 1024 * 1024;
+
+/// An inclusive byte range within a representation of known length.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ByteRange {
+    /// First byte offset (inclusive).
+    pub start: u64,
+    /// Last byte offset (inclusive).
+    pub end: u64,
+}
+
+impl ByteRange {
+    /// Number of bytes covered by the range.
+    pub fn len(&self) -> u64 {
+        self.end - self.start + 1
+    }
+
+    /// A range always covers at least one byte.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Formats the `Content-Range` value for this range, e.g. `bytes 0-499/1234`.
+    pub fn content_range(&self, total: u64) -> String {
+        format!("bytes {}-{}/{}", self.start, self.end, total)
+    }
+}
+
+/// How a request's `Range` header applies to a representation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RangeOutcome {
+    /// No (usable) range requested: serve the full representation with 200.
+    Full,
+    /// Serve a single range with 206 Partial Content.
+    Partial(ByteRange),
+    /// The range lies outside the representation: 416 Range Not Satisfiable.
+    Unsatisfiable,
+}
+
+/// Parses a `Range` header value against a representation length.
+///
+/// Only single `bytes` ranges are served. Syntactically invalid headers, other
+/// units and multi-range requests fall back to [`RangeOutcome::Full`], which
+/// RFC 9110 permits (a server may always ignore `Range`).
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::range::{ByteRange, RangeOutcome, parse_range};
+///
+/// assert_eq!(parse_range("bytes=0-99", 1000), RangeOutcome::Partial(ByteRange { start: 0, end: 99 }));
+/// assert_eq!(parse_range("bytes=900-", 1000), RangeOutcome::Partial(ByteRange { start: 900, end: 999 }));
+/// assert_eq!(parse_range("bytes=-100", 1000), RangeOutcome::Partial(ByteRange { start: 900, end: 999 }));
+/// assert_eq!(parse_range("bytes=0-5000", 1000), RangeOutcome::Partial(ByteRange { start: 0, end: 999 }));
+/// assert_eq!(parse_range("bytes=1000-", 1000), RangeOutcome::Unsatisfiable);
+/// assert_eq!(parse_range("bytes=0-1,5-9", 1000), RangeOutcome::Full);
+/// assert_eq!(parse_range("items=0-1", 1000), RangeOutcome::Full);
+/// ```
+pub fn parse_range(value: &str, total: u64) -> RangeOutcome {
+    let Some(spec) = value.trim().strip_prefix("bytes=") else {
+        return RangeOutcome::Full;
+    };
+    if spec.contains(',') {
+        return RangeOutcome::Full;
+    }
+    let Some((first, last)) = spec.trim().split_once('-') else {
+        return RangeOutcome::Full;
+    };
+    let (first, last) = (first.trim(), last.trim());
+
+    if first.is_empty() {
+        // Suffix range: the last N bytes
+        let Ok(suffix) = last.parse::<u64>() else {
+            return RangeOutcome::Full;
+        };
+        if suffix == 0 || total == 0 {
+            return RangeOutcome::Unsatisfiable;
+        }
+        return RangeOutcome::Partial(ByteRange {
+            start: total.saturating_sub(suffix),
+            end: total - 1,
+        });
+    }
+
+    let Ok(start) = first.parse::<u64>() else {
+        return RangeOutcome::Full;
+    };
+    let end = if last.is_empty() {
+        None
+    } else {
+        match last.parse::<u64>() {
+            Ok(end) if end >= start => Some(end),
+            _ => return RangeOutcome::Full,
+        }
+    };
+
+    if start >= total {
+        return RangeOutcome::Unsatisfiable;
+    }
+    let end = end.map_or(total - 1, |end| end.min(total - 1));
+    RangeOutcome::Partial(ByteRange { start, end })
+}
+
+/// Evaluates `If-Range` against the representation's validators.
+///
+/// Returns `true` when the range may be served: either no `If-Range` was sent,
+/// or it matches the strong `ETag` or the exact `Last-Modified` date. Weak
+/// entity tags never match, as required by RFC 9110.
+///
+/// # Arguments
+/// * `request` - Request headers
+/// * `etag` - The representation's current `ETag` value (with quotes)
+/// * `last_modified` - The representation's current `Last-Modified` value
+pub fn if_range_matches(request: &HeaderMap, etag: Option<&str>, last_modified: Option<&str>) -> bool {
+    let Some(condition) = request.get(header::IF_RANGE).and_then(|v| v.to_str().ok()) else {
+        return true;
+    };
+    let condition = condition.trim();
+
+    if condition.starts_with('"') || condition.starts_with("W/") {
+        return !condition.starts_with("W/")
+            && etag.is_some_and(|etag| !etag.starts_with("W/") && etag == condition);
+    }
+    last_modified.is_some_and(|date| date == condition)
+}
+
+/// Determines the range outcome for a request, honoring `If-Range`.
+///
+/// # Arguments
+/// * `request` - Request headers (`Range`, `If-Range`)
+/// * `total` - Length of the full representation
+/// * `etag` - The representation's `ETag`, if any
+/// * `last_modified` - The representation's `Last-Modified`, if any
+pub fn evaluate_range(
+    request: &HeaderMap,
+    total: u64,
+    etag: Option<&str>,
+    last_modified: Option<&str>,
+) -> RangeOutcome {
+    let Some(range) = request.get(header::RANGE).and_then(|v| v.to_str().ok()) else {
+        return RangeOutcome::Full;
+    };
+    if !if_range_matches(request, etag, last_modified) {
+        return RangeOutcome::Full;
+    }
+    parse_range(range, total)
+}
+
+/// Configuration for the range request middleware.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::range::RangeConfig;
+/// use axum_jetpack::size::SizeLimit;
+///
+/// let config = RangeConfig::new().with_max_buffered(SizeLimit::mib(32.0));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RangeConfig {
+    /// Largest response body (from its exact size hint) the middleware will
+    /// buffer to cut a range from. Larger or unsized bodies are served in full;
+    /// use a [`RangeSource`](crate::range::RangeSource) in the handler for those.
+    /// Default: 8 MiB.
+    pub max_buffered: usize,
+}
+
+impl Default for RangeConfig {
+    fn default() -> Self {
+        Self {
+            max_buffered: DEFAULT_MAX_BUFFERED,
+        }
+    }
+}
+
+impl RangeConfig {
+    /// Creates a configuration with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method to set the largest body buffered for range slicing.
+    pub fn with_max_buffered(mut self, limit: impl Into<crate::size::SizeLimit>) -> Self {
+        self.max_buffered = limit.into().0;
+        self
+    }
+}
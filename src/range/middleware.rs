@@ -0,0 +1,110 @@
+//! Range request middleware for Axum applications.
+//!
+//! Serves single byte ranges of buffered responses. Large downloads should use
+//! [`Ranged`](crate::range::Ranged) with a [`RangeSource`](crate::range::RangeSource)
+//! in the handler instead, which the middleware leaves untouched.
+
+use axum::{
+    Router,
+    body::{Body, HttpBody, to_bytes},
+    extract::{Request, State},
+    http::{HeaderValue, Method, StatusCode, header},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+};
+use std::sync::Arc;
+
+use crate::range::{RangeConfig, RangeOutcome, evaluate_range, source::unsatisfiable};
+
+/// Applies `Range`/`If-Range` handling to an Axum router.
+///
+/// For `GET` requests, a `200 OK` response is eligible when its body has an
+/// exact size no larger than [`RangeConfig::max_buffered`] and the handler did
+/// not already handle ranges (`Accept-Ranges` or `Content-Range` set). Eligible
+/// responses advertise `Accept-Ranges: bytes`; when a range is requested, the
+/// body is buffered and answered with `206` or `416`.
+///
+/// `If-Range` is compared against the response's own `ETag` and
+/// `Last-Modified` headers. Place this layer inside any compression layer so
+/// ranges refer to the bytes actually sent.
+///
+/// # Arguments
+/// * `router` - The Axum router to wrap with middleware
+/// * `config` - Buffering limit for range slicing
+///
+/// # Returns
+/// A new router serving byte ranges.
+///
+/// # Example
+/// ```rust
+/// use axum::{Router, routing::get};
+/// use axum_jetpack::range::{RangeConfig, with_range_requests};
+///
+/// let router = Router::new().route("/report.csv", get(|| async { "a,b\n1,2\n" }));
+/// let router = with_range_requests(router, RangeConfig::new());
+/// ```
+pub fn with_range_requests(router: Router, config: RangeConfig) -> Router {
+    let config = Arc::new(config);
+
+    router.layer(middleware::from_fn_with_state(
+        config,
+        |State(config): State<Arc<RangeConfig>>, req: Request<Body>, next: Next| async move {
+            if req.method() != Method::GET {
+                return next.run(req).await;
+            }
+            let request_headers = req.headers().clone();
+
+            let response = next.run(req).await;
+            let headers = response.headers();
+            let eligible = response.status() == StatusCode::OK
+                && !headers.contains_key(header::ACCEPT_RANGES)
+                && !headers.contains_key(header::CONTENT_RANGE);
+            let size = response.body().size_hint().exact();
+            let Some(total) = size.filter(|&size| eligible && size <= config.max_buffered as u64)
+            else {
+                return response;
+            };
+
+            let (mut parts, body) = response.into_parts();
+            parts
+                .headers
+                .insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+
+            let outcome = evaluate_range(
+                &request_headers,
+                total,
+                parts.headers.get(header::ETAG).and_then(|v| v.to_str().ok()),
+                parts
+                    .headers
+                    .get(header::LAST_MODIFIED)
+                    .and_then(|v| v.to_str().ok()),
+            );
+
+            match outcome {
+                RangeOutcome::Full => Response::from_parts(parts, body),
+                RangeOutcome::Unsatisfiable => unsatisfiable(total, parts.headers),
+                RangeOutcome::Partial(range) => {
+                    let Ok(bytes) = to_bytes(body, config.max_buffered).await else {
+                        return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to read response")
+                            .into_response();
+                    };
+                    // The size hint lied; serve what we have rather than a corrupt slice
+                    if bytes.len() as u64 != total {
+                        parts.headers.remove(header::CONTENT_LENGTH);
+                        return Response::from_parts(parts, Body::from(bytes));
+                    }
+
+                    parts.status = StatusCode::PARTIAL_CONTENT;
+                    if let Ok(value) = HeaderValue::from_str(&range.content_range(total)) {
+                        parts.headers.insert(header::CONTENT_RANGE, value);
+                    }
+                    parts
+                        .headers
+                        .insert(header::CONTENT_LENGTH, HeaderValue::from(range.len()));
+                    let slice = bytes.slice(range.start as usize..=range.end as usize);
+                    Response::from_parts(parts, Body::from(slice))
+                }
+            }
+        },
+    ))
+}
@@ -0,0 +1,8 @@
+pub mod config;
+pub mod middleware;
+pub mod source;
+
+// Public API re-exports
+pub use config::*;
+pub use middleware::*;
+pub use source::{FileSource, RangeSource, Ranged};
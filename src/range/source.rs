@@ -0,0 +1,256 @@
+//! Seekable response sources and the `Ranged` response builder.
+
+use axum::{
+    body::{Body, Bytes},
+    http::{HeaderMap, HeaderValue, StatusCode, header},
+    response::{IntoResponse, Response},
+};
+use std::future::Future;
+use std::io::{self, SeekFrom};
+use std::path::Path;
+use tokio::io::{AsyncReadExt, AsyncSeekExt};
+
+use crate::range::{ByteRange, RangeOutcome, evaluate_range};
+
+/// Chunk size used when streaming file ranges.
+const FILE_CHUNK_SIZE: usize = 64 * 1024;
+
+/// A response source of known length that can produce any byte range.
+pub trait RangeSource: Send + 'static {
+    /// Total length of the representation in bytes.
+    fn len(&self) -> u64;
+
+    /// Returns `true` if the representation is empty.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Produces a body containing only the given range.
+    fn open_range(self, range: ByteRange) -> impl Future<Output = io::Result<Body>> + Send;
+
+    /// Produces a body containing the full representation.
+    fn open_full(self) -> impl Future<Output = io::Result<Body>> + Send
+    where
+        Self: Sized,
+    {
+        async move {
+            match self.len() {
+                0 => Ok(Body::empty()),
+                len => self.open_range(ByteRange { start: 0, end: len - 1 }).await,
+            }
+        }
+    }
+}
+
+impl RangeSource for Bytes {
+    fn len(&self) -> u64 {
+        Bytes::len(self) as u64
+    }
+
+    async fn open_range(self, range: ByteRange) -> io::Result<Body> {
+        Ok(Body::from(self.slice(range.start as usize..=range.end as usize)))
+    }
+
+    async fn open_full(self) -> io::Result<Body> {
+        Ok(Body::from(self))
+    }
+}
+
+impl RangeSource for Vec<u8> {
+    fn len(&self) -> u64 {
+        Vec::len(self) as u64
+    }
+
+    async fn open_range(self, range: ByteRange) -> io::Result<Body> {
+        Bytes::from(self).open_range(range).await
+    }
+
+    async fn open_full(self) -> io::Result<Body> {
+        Ok(Body::from(self))
+    }
+}
+
+/// A file opened for range reads.
+///
+/// # Examples
+/// ```no_run
+/// use axum_jetpack::range::FileSource;
+///
+/// # async fn example() -> std::io::Result<()> {
+/// let source = FileSource::open("assets/video.mp4").await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct FileSource {
+    file: tokio::fs::File,
+    len: u64,
+}
+
+impl FileSource {
+    /// Opens a file and reads its length.
+    pub async fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_file(tokio::fs::File::open(path).await?).await
+    }
+
+    /// Wraps an already opened file.
+    pub async fn from_file(file: tokio::fs::File) -> io::Result<Self> {
+        let len = file.metadata().await?.len();
+        Ok(Self { file, len })
+    }
+}
+
+impl RangeSource for FileSource {
+    fn len(&self) -> u64 {
+        self.len
+    }
+
+    async fn open_range(mut self, range: ByteRange) -> io::Result<Body> {
+        self.file.seek(SeekFrom::Start(range.start)).await?;
+
+        let stream = futures::stream::unfold(
+            (self.file, range.len()),
+            |(mut file, remaining)| async move {
+                if remaining == 0 {
+                    return None;
+                }
+                let mut buf = vec![0u8; remaining.min(FILE_CHUNK_SIZE as u64) as usize];
+                match file.read(&mut buf).await {
+                    // The file shrank underneath us; end the body early
+                    Ok(0) => None,
+                    Ok(n) => {
+                        buf.truncate(n);
+                        Some((Ok(Bytes::from(buf)), (file, remaining - n as u64)))
+                    }
+                    Err(e) => Some((Err(e), (file, 0))),
+                }
+            },
+        );
+        Ok(Body::from_stream(stream))
+    }
+}
+
+/// Builds a response honoring `Range` and `If-Range` for a seekable source.
+///
+/// Produces `200` with the full body, `206` with `Content-Range` for a
+/// satisfiable single range, or `416` with `Content-Range: bytes */<len>`.
+/// `Accept-Ranges: bytes` is always set, and the configured `ETag`,
+/// `Last-Modified` and `Content-Type` are sent on every response.
+///
+/// # Example
+/// ```rust
+/// use axum::{http::HeaderMap, response::Response};
+/// use axum_jetpack::range::{FileSource, Ranged};
+///
+/// async fn download(headers: HeaderMap) -> Response {
+///     match FileSource::open("report.pdf").await {
+///         Ok(source) => Ranged::new(source)
+///             .with_content_type("application/pdf")
+///             .with_etag("\"v42\"")
+///             .respond(&headers)
+///             .await,
+///         Err(_) => axum::http::StatusCode::NOT_FOUND.into_response(),
+///     }
+/// }
+/// # use axum::response::IntoResponse;
+/// ```
+#[derive(Debug)]
+pub struct Ranged<S> {
+    source: S,
+    content_type: Option<String>,
+    etag: Option<String>,
+    last_modified: Option<String>,
+}
+
+impl<S: RangeSource> Ranged<S> {
+    /// Wraps a source.
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            content_type: None,
+            etag: None,
+            last_modified: None,
+        }
+    }
+
+    /// Builder method to set the `Content-Type`.
+    pub fn with_content_type(mut self, content_type: &str) -> Self {
+        self.content_type = Some(content_type.to_string());
+        self
+    }
+
+    /// Builder method to set the `ETag` (including quotes, e.g. `"abc"`).
+    pub fn with_etag(mut self, etag: &str) -> Self {
+        self.etag = Some(etag.to_string());
+        self
+    }
+
+    /// Builder method to set the `Last-Modified` HTTP-date.
+    pub fn with_last_modified(mut self, date: &str) -> Self {
+        self.last_modified = Some(date.to_string());
+        self
+    }
+
+    /// Builds the response for the given request headers.
+    pub async fn respond(self, request: &HeaderMap) -> Response {
+        let total = self.source.len();
+        let outcome = evaluate_range(
+            request,
+            total,
+            self.etag.as_deref(),
+            self.last_modified.as_deref(),
+        );
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::ACCEPT_RANGES, HeaderValue::from_static("bytes"));
+        for (name, value) in [
+            (header::CONTENT_TYPE, &self.content_type),
+            (header::ETAG, &self.etag),
+            (header::LAST_MODIFIED, &self.last_modified),
+        ] {
+            if let Some(value) = value
+                && let Ok(value) = HeaderValue::from_str(value)
+            {
+                headers.insert(name, value);
+            }
+        }
+
+        let result = match outcome {
+            RangeOutcome::Full => self
+                .source
+                .open_full()
+                .await
+                .map(|body| (StatusCode::OK, total, body)),
+            RangeOutcome::Partial(range) => {
+                if let Ok(value) = HeaderValue::from_str(&range.content_range(total)) {
+                    headers.insert(header::CONTENT_RANGE, value);
+                }
+                self.source
+                    .open_range(range)
+                    .await
+                    .map(|body| (StatusCode::PARTIAL_CONTENT, range.len(), body))
+            }
+            RangeOutcome::Unsatisfiable => {
+                return unsatisfiable(total, headers);
+            }
+        };
+
+        match result {
+            Ok((status, length, body)) => {
+                headers.insert(header::CONTENT_LENGTH, HeaderValue::from(length));
+                (status, headers, body).into_response()
+            }
+            Err(_) => (StatusCode::INTERNAL_SERVER_ERROR, "Failed to read content").into_response(),
+        }
+    }
+}
+
+/// Builds a `416 Range Not Satisfiable` response for a representation length.
+pub(crate) fn unsatisfiable(total: u64, mut headers: HeaderMap) -> Response {
+    if let Ok(value) = HeaderValue::from_str(&format!("bytes */{}", total)) {
+        headers.insert(header::CONTENT_RANGE, value);
+    }
+    headers.remove(header::CONTENT_LENGTH);
+    headers.remove(header::CONTENT_TYPE);
+    (StatusCode::RANGE_NOT_SATISFIABLE, headers, "Range not satisfiable").into_response()
+}
@@ -0,0 +1,104 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Metric name used by the size limiter for the time spent reading the request body.
+pub const BODY_READ_METRIC: &str = "body-read";
+
+/// Configuration for the Server-Timing middleware.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::server_timing::ServerTimingConfig;
+///
+/// // Measure the inner stack as "app" and queueing from the proxy's X-Request-Start header
+/// let config = ServerTimingConfig::new()
+///     .with_queue_header("x-request-start");
+///
+/// // Only forward metrics recorded by inner layers and handlers
+/// let config = ServerTimingConfig::new().without_app_metric();
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ServerTimingConfig {
+    /// Name of the metric measuring everything inside the middleware
+    /// (inner layers and the handler). `None` disables it.
+    /// Default: `"app"`.
+    pub app_metric: Option<String>,
+
+    /// Header set by a load balancer or reverse proxy with the time the request
+    /// was received (e.g. `X-Request-Start: t=1700000000.123`). When present,
+    /// the time between that moment and the middleware is recorded as `queue`.
+    pub queue_header: Option<String>,
+}
+
+impl Default for ServerTimingConfig {
+    /// Records the `app` metric; queue time is disabled.
+    fn default() -> Self {
+        Self {
+            app_metric: Some("app".to_string()),
+            queue_header: None,
+        }
+    }
+}
+
+impl ServerTimingConfig {
+    /// Creates a configuration with default values.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method to rename the metric measuring the inner stack.
+    pub fn with_app_metric(mut self, name: &str) -> Self {
+        self.app_metric = Some(name.to_string());
+        self
+    }
+
+    /// Builder method to disable the metric measuring the inner stack.
+    pub fn without_app_metric(mut self) -> Self {
+        self.app_metric = None;
+        self
+    }
+
+    /// Builder method to record queue time from a request-start header.
+    pub fn with_queue_header(mut self, header: &str) -> Self {
+        self.queue_header = Some(header.to_ascii_lowercase());
+        self
+    }
+}
+
+/// Parses a request-start header value into a point in time.
+///
+/// Accepts the formats used by common proxies, with or without a `t=` prefix:
+/// seconds with fractional part (nginx `t=${msec}`), milliseconds or
+/// microseconds since the Unix epoch.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::server_timing::parse_request_start;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let expected = UNIX_EPOCH + Duration::from_millis(1_700_000_000_123);
+/// assert_eq!(parse_request_start("t=1700000000.123"), Some(expected));
+/// assert_eq!(parse_request_start("1700000000123"), Some(expected));
+/// assert_eq!(parse_request_start("t=1700000000123000"), Some(expected));
+/// assert_eq!(parse_request_start("soon"), None);
+/// ```
+pub fn parse_request_start(value: &str) -> Option<SystemTime> {
+    let value = value.trim();
+    let value = value.strip_prefix("t=").unwrap_or(value);
+    let number: f64 = value.parse().ok()?;
+    if !number.is_finite() || number <= 0.0 {
+        return None;
+    }
+
+    // Pick the unit from the magnitude: anything after 2001 in seconds has 10 digits
+    let seconds = if number >= 1e14 {
+        number / 1_000_000.0
+    } else if number >= 1e11 {
+        number / 1_000.0
+    } else {
+        number
+    };
+
+    // Round to whole microseconds to keep the float conversion stable
+    let micros = (seconds * 1_000_000.0).round() as u64;
+    Some(UNIX_EPOCH + Duration::from_micros(micros))
+}
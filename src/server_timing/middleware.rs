@@ -0,0 +1,101 @@
+//! Server-Timing middleware for Axum applications.
+//!
+//! Collects per-request timing metrics from this middleware, inner layers and
+//! handlers and emits them in the `Server-Timing` response header.
+
+use axum::{
+    Router,
+    body::Body,
+    extract::{Request, State},
+    http::{HeaderName, HeaderValue},
+    middleware::{self, Next},
+};
+use std::sync::Arc;
+use std::time::{Instant, SystemTime};
+
+use crate::server_timing::{ServerTiming, ServerTimingConfig, parse_request_start};
+
+/// The `Server-Timing` response header.
+pub const SERVER_TIMING: HeaderName = HeaderName::from_static("server-timing");
+
+/// Applies Server-Timing collection to an Axum router.
+///
+/// The middleware:
+/// 1. Inserts a [`ServerTiming`] collector into the request extensions
+///    (reusing one inserted by an outer layer)
+/// 2. Records `queue` time if a request-start header is configured and present
+/// 3. Measures the inner stack as the `app` metric
+/// 4. Appends all metrics to the `Server-Timing` response header
+///
+/// Inner layers record their own phases; the size limiter records
+/// `body-read` when it buffers or checks a request body.
+///
+/// Server-Timing is visible to clients, so only expose it where that is acceptable.
+///
+/// # Arguments
+/// * `router` - The Axum router to wrap with middleware
+/// * `config` - Which built-in metrics to record
+///
+/// # Returns
+/// A new router emitting the `Server-Timing` header.
+///
+/// # Example
+/// ```rust
+/// use axum::{Router, routing::get};
+/// use axum_jetpack::server_timing::{ServerTiming, ServerTimingConfig, with_server_timing};
+/// use std::time::Duration;
+///
+/// async fn handler(timing: ServerTiming) -> &'static str {
+///     timing.record("db", Duration::from_millis(3));
+///     "ok"
+/// }
+///
+/// let router = Router::new().route("/", get(handler));
+/// let router = with_server_timing(router, ServerTimingConfig::new());
+/// ```
+pub fn with_server_timing(router: Router, config: ServerTimingConfig) -> Router {
+    let config = Arc::new(config);
+
+    router.layer(middleware::from_fn_with_state(
+        config,
+        |State(config): State<Arc<ServerTimingConfig>>, mut req: Request<Body>, next: Next| async move {
+            let started = Instant::now();
+
+            let timing = match req.extensions().get::<ServerTiming>() {
+                Some(timing) => timing.clone(),
+                None => {
+                    let timing = ServerTiming::new();
+                    req.extensions_mut().insert(timing.clone());
+                    timing
+                }
+            };
+
+            if let Some(header) = &config.queue_header
+                && let Some(received) = req
+                    .headers()
+                    .get(header.as_str())
+                    .and_then(|h| h.to_str().ok())
+                    .and_then(parse_request_start)
+            {
+                // Clock skew between proxy and server can make this negative; clamp to zero
+                let queued = SystemTime::now().duration_since(received).unwrap_or_default();
+                timing.record("queue", queued);
+            }
+
+            let mut response = next.run(req).await;
+
+            if let Some(name) = &config.app_metric {
+                timing.record(name, started.elapsed());
+            }
+
+            if let Some(value) = timing.header_value()
+                && let Ok(value) = HeaderValue::from_str(&value)
+            {
+                // Keep any Server-Timing entries the handler set directly
+                response.headers_mut().append(SERVER_TIMING, value);
+            }
+
+            response
+        },
+    ))
+}
@@ -0,0 +1,167 @@
+//! The per-request timing collector shared between layers and handlers.
+
+use axum::{extract::FromRequestParts, http::request::Parts};
+use std::convert::Infallible;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// A single `Server-Timing` metric.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TimingMetric {
+    /// Metric name (must be an HTTP token, e.g. `db` or `body-read`).
+    pub name: String,
+    /// Measured duration, rendered as `dur=<milliseconds>`.
+    pub duration: Option<Duration>,
+    /// Optional human-readable description, rendered as `desc="..."`.
+    pub description: Option<String>,
+}
+
+impl fmt::Display for TimingMetric {
+    /// Formats the metric as a `Server-Timing` entry, e.g. `db;dur=12.5;desc="query"`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name)?;
+        if let Some(duration) = self.duration {
+            write!(f, ";dur={:.1}", duration.as_secs_f64() * 1000.0)?;
+        }
+        if let Some(description) = &self.description {
+            let escaped = description.replace('\\', "\\\\").replace('"', "\\\"");
+            write!(f, ";desc=\"{}\"", escaped)?;
+        }
+        Ok(())
+    }
+}
+
+/// Collects timing metrics for one request.
+///
+/// The Server-Timing middleware inserts a `ServerTiming` into the request
+/// extensions. Inner layers and handlers append metrics to it, and the
+/// middleware renders everything into the `Server-Timing` response header.
+/// Clones share the same metric list.
+///
+/// Used as an extractor it never fails: without the middleware, a detached
+/// collector is returned whose metrics are simply discarded.
+///
+/// # Examples
+/// ```rust
+/// use axum_jetpack::server_timing::ServerTiming;
+/// use std::time::Duration;
+///
+/// async fn handler(timing: ServerTiming) -> &'static str {
+///     timing.record("db", Duration::from_millis(12));
+///
+///     let _cache = timing.start("cache"); // recorded when dropped
+///     "ok"
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ServerTiming {
+    metrics: Arc<Mutex<Vec<TimingMetric>>>,
+}
+
+impl ServerTiming {
+    /// Creates an empty collector.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a metric.
+    pub fn push(&self, metric: TimingMetric) {
+        if let Ok(mut metrics) = self.metrics.lock() {
+            metrics.push(metric);
+        }
+    }
+
+    /// Records a named duration.
+    pub fn record(&self, name: &str, duration: Duration) {
+        self.push(TimingMetric {
+            name: name.to_string(),
+            duration: Some(duration),
+            description: None,
+        });
+    }
+
+    /// Records a named duration with a description.
+    pub fn record_with_description(&self, name: &str, duration: Duration, description: &str) {
+        self.push(TimingMetric {
+            name: name.to_string(),
+            duration: Some(duration),
+            description: Some(description.to_string()),
+        });
+    }
+
+    /// Records a metric without a duration (e.g. a cache hit marker).
+    pub fn mark(&self, name: &str) {
+        self.push(TimingMetric {
+            name: name.to_string(),
+            duration: None,
+            description: None,
+        });
+    }
+
+    /// Starts a timer that records its elapsed time under `name` when dropped.
+    pub fn start(&self, name: &str) -> TimingGuard {
+        TimingGuard {
+            timing: self.clone(),
+            name: name.to_string(),
+            started: Instant::now(),
+        }
+    }
+
+    /// Returns a copy of the metrics recorded so far.
+    pub fn metrics(&self) -> Vec<TimingMetric> {
+        self.metrics
+            .lock()
+            .map(|metrics| metrics.clone())
+            .unwrap_or_default()
+    }
+
+    /// Renders all metrics as a `Server-Timing` header value, or `None` if there are none.
+    pub fn header_value(&self) -> Option<String> {
+        let metrics = self.metrics();
+        if metrics.is_empty() {
+            return None;
+        }
+        Some(
+            metrics
+                .iter()
+                .map(|metric| metric.to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        )
+    }
+}
+
+impl<S> FromRequestParts<S> for ServerTiming
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<ServerTiming>()
+            .cloned()
+            .unwrap_or_default())
+    }
+}
+
+/// Records the elapsed time of a phase when dropped. See [`ServerTiming::start`].
+#[derive(Debug)]
+pub struct TimingGuard {
+    timing: ServerTiming,
+    name: String,
+    started: Instant,
+}
+
+impl TimingGuard {
+    /// Stops the timer now and records the metric.
+    pub fn stop(self) {}
+}
+
+impl Drop for TimingGuard {
+    fn drop(&mut self) {
+        self.timing.record(&self.name, self.started.elapsed());
+    }
+}
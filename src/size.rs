@@ -0,0 +1,1321 @@
+/// Represents units for expressing data sizes.
+///
+/// This enum supports both decimal (metric) and binary (IEC) units,
+/// as well as bit-based units for network bandwidth.
+///
+/// # Decimal (Metric) Units
+/// - Based on powers of 10 (1,000)
+/// - Commonly used for storage, networking, and data transfer
+/// - Follows SI (International System of Units) conventions
+/// - Examples: KB, MB, GB
+///
+/// # Binary (IEC) Units
+/// - Based on powers of 2 (1,024)
+/// - Traditionally used for memory and file sizes
+/// - Officially defined by IEC 60027-2
+/// - Examples: KiB, MiB, GiB
+///
+/// # Bit Units
+/// - Based on bits rather than bytes
+/// - Commonly used for network bandwidth (e.g., "100 Mbps")
+/// - 1 byte = 8 bits
+/// - Examples: kbit, Mbit, Gbit
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::size::SizeUnit;
+///
+/// // Decimal units
+/// assert_eq!(SizeUnit::parse("MB"), Some(SizeUnit::Megabytes));
+/// assert_eq!(SizeUnit::parse("gigabyte"), Some(SizeUnit::Gigabytes));
+///
+/// // Binary units
+/// assert_eq!(SizeUnit::parse("MiB"), Some(SizeUnit::Mebibytes));
+/// assert_eq!(SizeUnit::parse("gibibytes"), Some(SizeUnit::Gibibytes));
+///
+/// // Bit units
+/// assert_eq!(SizeUnit::parse("Mbit"), Some(SizeUnit::Megabits));
+/// assert_eq!(SizeUnit::parse("gigabits"), Some(SizeUnit::Gigabits));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeUnit {
+    /// Bytes (base unit)
+    /// - 1 byte = 8 bits
+    /// - Symbol: `B`, `byte`, `bytes`
+    Bytes,
+
+    /// Kilobytes (decimal)
+    /// - 1 kilobyte = 1,000 bytes
+    /// - Symbol: `KB`, `kilobyte`, `kilobytes`
+    Kilobytes,
+
+    /// Megabytes (decimal)
+    /// - 1 megabyte = 1,000,000 bytes
+    /// - Symbol: `MB`, `megabyte`, `megabytes`
+    Megabytes,
+
+    /// Gigabytes (decimal)
+    /// - 1 gigabyte = 1,000,000,000 bytes
+    /// - Symbol: `GB`, `gigabyte`, `gigabytes`
+    Gigabytes,
+
+    /// Terabytes (decimal)
+    /// - 1 terabyte = 1,000,000,000,000 bytes
+    /// - Symbol: `TB`, `terabyte`, `terabytes`
+    Terabytes,
+
+    /// Petabytes (decimal)
+    /// - 1 petabyte = 1,000,000,000,000,000 bytes
+    /// - Symbol: `PB`, `petabyte`, `petabytes`
+    Petabytes,
+
+    /// Kibibytes (binary)
+    /// - 1 kibibyte = 1,024 bytes
+    /// - Symbol: `KiB`, `kibibyte`, `kibibytes`
+    Kibibytes,
+
+    /// Mebibytes (binary)
+    /// - 1 mebibyte = 1,048,576 bytes (1,024 × 1,024)
+    /// - Symbol: `MiB`, `mebibyte`, `mebibytes`
+    Mebibytes,
+
+    /// Gibibytes (binary)
+    /// - 1 gibibyte = 1,073,741,824 bytes (1,024 × 1,024 × 1,024)
+    /// - Symbol: `GiB`, `gibibyte`, `gibibytes`
+    Gibibytes,
+
+    /// Tebibytes (binary)
+    /// - 1 tebibyte = 1,099,511,627,776 bytes (1,024⁴)
+    /// - Symbol: `TiB`, `tebibyte`, `tebibytes`
+    Tebibytes,
+
+    /// Pebibytes (binary)
+    /// - 1 pebibyte = 1,125,899,906,842,624 bytes (1,024⁵)
+    /// - Symbol: `PiB`, `pebibyte`, `pebibytes`
+    Pebibytes,
+
+    /// Kilobits (bit-based)
+    /// - 1 kilobit = 1,000 bits = 125 bytes
+    /// - Symbol: `kbit`, `kilobit`, `kilobits`
+    Kilobits,
+
+    /// Megabits (bit-based)
+    /// - 1 megabit = 1,000,000 bits = 125,000 bytes
+    /// - Symbol: `Mbit`, `megabit`, `megabits`
+    Megabits,
+
+    /// Gigabits (bit-based)
+    /// - 1 gigabit = 1,000,000,000 bits = 125,000,000 bytes
+    /// - Symbol: `Gbit`, `gigabit`, `gigabits`
+    Gigabits,
+
+    /// Terabits (bit-based)
+    /// - 1 terabit = 1,000,000,000,000 bits = 125,000,000,000 bytes
+    /// - Symbol: `Tbit`, `terabit`, `terabits`
+    Terabits,
+}
+
+/// Accepted spellings of each unit, lowercase.
+const UNIT_NAMES: &[(&str, SizeUnit)] = &[
+    // Byte units
+    ("b", SizeUnit::Bytes),
+    ("byte", SizeUnit::Bytes),
+    ("bytes", SizeUnit::Bytes),
+    // Decimal (metric) units
+    ("kb", SizeUnit::Kilobytes),
+    ("kilobyte", SizeUnit::Kilobytes),
+    ("kilobytes", SizeUnit::Kilobytes),
+    ("mb", SizeUnit::Megabytes),
+    ("megabyte", SizeUnit::Megabytes),
+    ("megabytes", SizeUnit::Megabytes),
+    ("gb", SizeUnit::Gigabytes),
+    ("gigabyte", SizeUnit::Gigabytes),
+    ("gigabytes", SizeUnit::Gigabytes),
+    ("tb", SizeUnit::Terabytes),
+    ("terabyte", SizeUnit::Terabytes),
+    ("terabytes", SizeUnit::Terabytes),
+    ("pb", SizeUnit::Petabytes),
+    ("petabyte", SizeUnit::Petabytes),
+    ("petabytes", SizeUnit::Petabytes),
+    // Binary (IEC) units
+    ("kib", SizeUnit::Kibibytes),
+    ("kibibyte", SizeUnit::Kibibytes),
+    ("kibibytes", SizeUnit::Kibibytes),
+    ("mib", SizeUnit::Mebibytes),
+    ("mebibyte", SizeUnit::Mebibytes),
+    ("mebibytes", SizeUnit::Mebibytes),
+    ("gib", SizeUnit::Gibibytes),
+    ("gibibyte", SizeUnit::Gibibytes),
+    ("gibibytes", SizeUnit::Gibibytes),
+    ("tib", SizeUnit::Tebibytes),
+    ("tebibyte", SizeUnit::Tebibytes),
+    ("tebibytes", SizeUnit::Tebibytes),
+    ("pib", SizeUnit::Pebibytes),
+    ("pebibyte", SizeUnit::Pebibytes),
+    ("pebibytes", SizeUnit::Pebibytes),
+    // Bit units
+    ("kbit", SizeUnit::Kilobits),
+    ("kilobit", SizeUnit::Kilobits),
+    ("kilobits", SizeUnit::Kilobits),
+    ("mbit", SizeUnit::Megabits),
+    ("megabit", SizeUnit::Megabits),
+    ("megabits", SizeUnit::Megabits),
+    ("gbit", SizeUnit::Gigabits),
+    ("gigabit", SizeUnit::Gigabits),
+    ("gigabits", SizeUnit::Gigabits),
+    ("tbit", SizeUnit::Terabits),
+    ("terabit", SizeUnit::Terabits),
+    ("terabits", SizeUnit::Terabits),
+];
+
+impl SizeUnit {
+    /// Parses a string representation of a size unit.
+    ///
+    /// This function is case-insensitive and accepts various common
+    /// abbreviations and full names for each unit.
+    ///
+    /// # Arguments
+    /// * `s` - The string to parse (e.g., "MB", "megabyte", "MiB", "Mbit")
+    ///
+    /// # Returns
+    /// - `Some(SizeUnit)` if the string represents a valid unit
+    /// - `None` if the string doesn't match any known unit
+    ///
+    /// # Supported Formats
+    ///
+    /// | Unit Type | Short Forms | Long Forms |
+    /// |-----------|-------------|------------|
+    /// | Bytes | `"b"` | `"byte"`, `"bytes"` |
+    /// | Kilobytes | `"kb"` | `"kilobyte"`, `"kilobytes"` |
+    /// | Megabytes | `"mb"` | `"megabyte"`, `"megabytes"` |
+    /// | Gigabytes | `"gb"` | `"gigabyte"`, `"gigabytes"` |
+    /// | Terabytes | `"tb"` | `"terabyte"`, `"terabytes"` |
+    /// | Petabytes | `"pb"` | `"petabyte"`, `"petabytes"` |
+    /// | Kibibytes | `"kib"` | `"kibibyte"`, `"kibibytes"` |
+    /// | Mebibytes | `"mib"` | `"mebibyte"`, `"mebibytes"` |
+    /// | Gibibytes | `"gib"` | `"gibibyte"`, `"gibibytes"` |
+    /// | Tebibytes | `"tib"` | `"tebibyte"`, `"tebibytes"` |
+    /// | Pebibytes | `"pib"` | `"pebibyte"`, `"pebibytes"` |
+    /// | Kilobits | `"kbit"` | `"kilobit"`, `"kilobits"` |
+    /// | Megabits | `"mbit"` | `"megabit"`, `"megabits"` |
+    /// | Gigabits | `"gbit"` | `"gigabit"`, `"gigabits"` |
+    /// | Terabits | `"tbit"` | `"terabit"`, `"terabits"` |
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeUnit;
+    ///
+    /// // Case-insensitive parsing
+    /// assert_eq!(SizeUnit::parse("MB"), Some(SizeUnit::Megabytes));
+    /// assert_eq!(SizeUnit::parse("mb"), Some(SizeUnit::Megabytes));
+    /// assert_eq!(SizeUnit::parse("Mb"), Some(SizeUnit::Megabytes));
+    ///
+    /// // Full names work too
+    /// assert_eq!(SizeUnit::parse("megabyte"), Some(SizeUnit::Megabytes));
+    /// assert_eq!(SizeUnit::parse("megabytes"), Some(SizeUnit::Megabytes));
+    ///
+    /// // Binary units
+    /// assert_eq!(SizeUnit::parse("MiB"), Some(SizeUnit::Mebibytes));
+    /// assert_eq!(SizeUnit::parse("mebibyte"), Some(SizeUnit::Mebibytes));
+    ///
+    /// // Bit units
+    /// assert_eq!(SizeUnit::parse("Mbit"), Some(SizeUnit::Megabits));
+    /// assert_eq!(SizeUnit::parse("megabit"), Some(SizeUnit::Megabits));
+    ///
+    /// // Unknown units return None
+    /// assert_eq!(SizeUnit::parse("EB"), None); // Exabytes not supported
+    /// assert_eq!(SizeUnit::parse("foo"), None);
+    /// ```
+    pub fn parse(s: &str) -> Option<Self> {
+        let name = s.to_lowercase();
+        UNIT_NAMES.iter().find(|(unit_name, _)| *unit_name == name).map(|(_, unit)| *unit)
+    }
+
+    /// ASCII case-insensitive lookup usable in constant evaluation.
+    pub(crate) const fn parse_ascii(name: &[u8]) -> Option<Self> {
+        let mut i = 0;
+        while i < UNIT_NAMES.len() {
+            if name.eq_ignore_ascii_case(UNIT_NAMES[i].0.as_bytes()) {
+                return Some(UNIT_NAMES[i].1);
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// Converts a value in this unit to bytes.
+    ///
+    /// # Arguments
+    /// * `value` - The numeric value to convert (e.g., 1.5 for "1.5MB")
+    ///
+    /// # Returns
+    /// The equivalent number of bytes as `usize`.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeUnit;
+    ///
+    /// // Decimal units
+    /// assert_eq!(SizeUnit::Kilobytes.to_bytes(1.0), 1_000);
+    /// assert_eq!(SizeUnit::Megabytes.to_bytes(2.5), 2_500_000);
+    /// assert_eq!(SizeUnit::Gigabytes.to_bytes(0.5), 500_000_000);
+    /// assert_eq!(SizeUnit::Terabytes.to_bytes(2.0), 2_000_000_000_000);
+    ///
+    /// // Binary units
+    /// assert_eq!(SizeUnit::Kibibytes.to_bytes(1.0), 1_024);
+    /// assert_eq!(SizeUnit::Mebibytes.to_bytes(1.0), 1_048_576);
+    /// assert_eq!(SizeUnit::Gibibytes.to_bytes(1.0), 1_073_741_824);
+    /// assert_eq!(SizeUnit::Tebibytes.to_bytes(1.0), 1_099_511_627_776);
+    ///
+    /// // Bit units
+    /// assert_eq!(SizeUnit::Kilobits.to_bytes(8.0), 1_000); // 8 kilobits = 1,000 bytes
+    /// assert_eq!(SizeUnit::Megabits.to_bytes(1.0), 125_000);
+    /// assert_eq!(SizeUnit::Gigabits.to_bytes(1.0), 125_000_000);
+    ///
+    /// // Bytes (no conversion needed)
+    /// assert_eq!(SizeUnit::Bytes.to_bytes(1024.0), 1024);
+    /// ```
+    pub const fn to_bytes(&self, value: f64) -> usize {
+        match self {
+            // Byte units (no conversion)
+            SizeUnit::Bytes => value as usize,
+
+            // Decimal units (powers of 10)
+            SizeUnit::Kilobytes => (value * 1000.0) as usize,
+            SizeUnit::Megabytes => (value * 1_000_000.0) as usize,
+            SizeUnit::Gigabytes => (value * 1_000_000_000.0) as usize,
+            SizeUnit::Terabytes => (value * 1e12) as usize,
+            SizeUnit::Petabytes => (value * 1e15) as usize,
+
+            // Binary units (powers of 2)
+            SizeUnit::Kibibytes => (value * 1024.0) as usize,
+            SizeUnit::Mebibytes => (value * 1_048_576.0) as usize,
+            SizeUnit::Gibibytes => (value * 1_073_741_824.0) as usize,
+            SizeUnit::Tebibytes => (value * 1_099_511_627_776.0) as usize,
+            SizeUnit::Pebibytes => (value * 1_125_899_906_842_624.0) as usize,
+
+            // Bit units (1 byte = 8 bits)
+            SizeUnit::Kilobits => (value * 125.0) as usize,     // 1 kilobit = 125 bytes
+            SizeUnit::Megabits => (value * 125_000.0) as usize, // 1 megabit = 125,000 bytes
+            SizeUnit::Gigabits => (value * 125_000_000.0) as usize, // 1 gigabit = 125,000,000 bytes
+            SizeUnit::Terabits => (value * 125_000_000_000.0) as usize, // 1 terabit = 125,000,000,000 bytes
+        }
+    }
+}
+
+impl std::str::FromStr for SizeUnit {
+    type Err = SizeParseError;
+
+    /// Parses a unit; see [`SizeUnit::parse`] for the accepted spellings.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeUnit;
+    ///
+    /// let unit: SizeUnit = "GiB".parse()?;
+    /// assert_eq!(unit, SizeUnit::Gibibytes);
+    /// assert!("parsecs".parse::<SizeUnit>().is_err());
+    /// # Ok::<(), axum_jetpack::size::SizeParseError>(())
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SizeUnit::parse(s.trim()).ok_or_else(|| SizeParseError {
+            input: s.to_string(),
+            reason: format!("Unknown unit '{}'", s.trim()),
+        })
+    }
+}
+
+impl TryFrom<&str> for SizeUnit {
+    type Error = SizeParseError;
+
+    /// Same as [`str::parse`]; see the [`FromStr`](std::str::FromStr) implementation.
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// Parses a human-readable size string into bytes.
+///
+/// This function supports strings like "1MB", "100kb", "2.5GB", "1.5 MiB", etc.
+/// It handles both decimal and binary units, and accepts comma or period as
+/// decimal separator.
+///
+/// # Arguments
+/// * `size_str` - A human-readable size string (e.g., "1.5MB", "100kb", "2,5 GB")
+///
+/// # Returns
+/// - `Ok(usize)` - The size in bytes if parsing succeeds
+/// - `Err(String)` - An error message if parsing fails
+///
+/// # Supported Formats
+/// - Numbers: Can be integers or decimals (e.g., "1024", "1.5", "2,5")
+/// - Units: See `SizeUnit::from_str()` for supported units
+/// - Whitespace: Optional space between number and unit (e.g., "1 MB" or "1MB")
+/// - Case: Case-insensitive (e.g., "mb", "MB", "Mb" all work)
+/// - Decimal separator: Both period (.) and comma (,) are accepted
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::size::parse_human_size;
+///
+/// // Decimal units
+/// assert_eq!(parse_human_size("1KB").unwrap(), 1_000);
+/// assert_eq!(parse_human_size("2.5MB").unwrap(), 2_500_000);
+/// assert_eq!(parse_human_size("1 GB").unwrap(), 1_000_000_000);
+/// assert_eq!(parse_human_size("2TB").unwrap(), 2_000_000_000_000);
+/// assert_eq!(parse_human_size("1 PB").unwrap(), 1_000_000_000_000_000);
+///
+/// // Binary units
+/// assert_eq!(parse_human_size("1KiB").unwrap(), 1_024);
+/// assert_eq!(parse_human_size("2 MiB").unwrap(), 2_097_152);
+/// assert_eq!(parse_human_size("1.5GiB").unwrap(), 1_610_612_736);
+/// assert_eq!(parse_human_size("1TiB").unwrap(), 1_099_511_627_776);
+///
+/// // Bit units
+/// assert_eq!(parse_human_size("1Mbit").unwrap(), 125_000);
+/// assert_eq!(parse_human_size("10 Gbit").unwrap(), 1_250_000_000);
+///
+/// // Bytes (no unit specified)
+/// assert_eq!(parse_human_size("1024").unwrap(), 1_024);
+/// assert_eq!(parse_human_size("4096").unwrap(), 4_096);
+///
+/// // International decimal format (comma separator)
+/// assert_eq!(parse_human_size("1,5MB").unwrap(), 1_500_000);
+/// assert_eq!(parse_human_size("2,5 GB").unwrap(), 2_500_000_000);
+///
+/// // Error cases
+/// assert!(parse_human_size("").is_err()); // Empty string
+/// assert!(parse_human_size("abc").is_err()); // No number
+/// assert!(parse_human_size("1XB").is_err()); // Unknown unit
+/// assert!(parse_human_size("1.2.3MB").is_err()); // Invalid number
+/// ```
+pub fn parse_human_size(size_str: &str) -> Result<usize, String> {
+    parse_bytes(size_str).map_err(|error| error.reason)
+}
+
+/// Parses a human-readable size like [`parse_human_size`], keeping the input
+/// in the error for [`SizeLimit`]'s typed parsers.
+fn parse_bytes(size_str: &str) -> Result<usize, SizeParseError> {
+    let error = |reason: String| SizeParseError {
+        input: size_str.to_string(),
+        reason,
+    };
+
+    // Normalize input: trim whitespace and convert to lowercase
+    let size_str = size_str.trim().to_lowercase();
+
+    // Check for empty input
+    if size_str.is_empty() {
+        return Err(error("Empty size string".to_string()));
+    }
+
+    // Find where the number part ends
+    let mut num_end = 0;
+    let chars: Vec<char> = size_str.chars().collect();
+
+    for (i, c) in chars.iter().enumerate() {
+        // Accept digits, period, or comma as part of the number
+        if c.is_ascii_digit() || *c == '.' || *c == ',' {
+            num_end = i + 1;
+        } else {
+            // Stop at first non-numeric character (start of unit)
+            break;
+        }
+    }
+
+    // Ensure we found a number
+    if num_end == 0 {
+        return Err(error("No number found".to_string()));
+    }
+
+    // Extract and parse the number part
+    let num_part = &size_str[..num_end];
+    // Replace comma with period for consistent parsing
+    let num = num_part.replace(',', ".").parse::<f64>()
+        .map_err(|e| error(format!("Invalid number '{}': {}", num_part, e)))?;
+
+    // Extract and parse the unit part (if any)
+    let unit_part = size_str[num_end..].trim();
+    let unit = if unit_part.is_empty() {
+        // Default to bytes if no unit specified
+        SizeUnit::Bytes
+    } else {
+        SizeUnit::parse(unit_part)
+            .ok_or_else(|| error(format!("Unknown unit '{}'", unit_part)))?
+    };
+
+    // Convert to bytes
+    Ok(unit.to_bytes(num))
+}
+
+/// Error returned when a human-readable size cannot be parsed.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::size::SizeLimit;
+///
+/// let error = SizeLimit::parse("12 parsecs").unwrap_err();
+/// assert_eq!(error.input, "12 parsecs");
+/// assert_eq!(error.to_string(), "Invalid size '12 parsecs': Unknown unit 'parsecs'");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SizeParseError {
+    /// The string that failed to parse.
+    pub input: String,
+    /// Why it failed.
+    pub reason: String,
+}
+
+impl std::fmt::Display for SizeParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid size '{}': {}", self.input, self.reason)
+    }
+}
+
+impl std::error::Error for SizeParseError {}
+
+/// A type-safe wrapper for size limits in bytes.
+///
+/// This struct provides a convenient way to work with size limits
+/// and supports conversion from various formats (strings, numbers).
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::size::SizeLimit;
+///
+/// // From string (human-readable format)
+/// let limit1: SizeLimit = "2MB".into();
+/// assert_eq!(limit1.0, 2_000_000);
+///
+/// // From number (bytes)
+/// let limit2 = SizeLimit::from(1024);
+/// assert_eq!(limit2.0, 1024);
+///
+/// // Using constructor methods
+/// let limit3 = SizeLimit::mb(1.5);
+/// assert_eq!(limit3.0, 1_500_000);
+///
+/// let limit4 = SizeLimit::mib(2.0);
+/// assert_eq!(limit4.0, 2_097_152);
+///
+/// // Using constants (binary units)
+/// assert_eq!(SizeLimit::KB.0, 1024);
+/// assert_eq!(SizeLimit::MB.0, 1_048_576);
+/// assert_eq!(SizeLimit::GB.0, 1_073_741_824);
+///
+/// // No limit at all
+/// assert!(SizeLimit::unlimited().is_unlimited());
+/// assert_eq!(SizeLimit::from(None).as_option(), None);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SizeLimit(pub usize);
+
+impl std::fmt::Display for SizeLimit {
+    /// Formats the limit for humans.
+    ///
+    /// Uses binary units when they represent the value exactly and decimal
+    /// units don't, so values print the way they were most likely
+    /// configured; unlimited prints as `unlimited`.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// assert_eq!(SizeLimit::mb(10.0).to_string(), "10 MB");
+    /// assert_eq!(SizeLimit::mib(1.5).to_string(), "1.5 MiB");
+    /// assert_eq!(SizeLimit::bytes(999).to_string(), "999 B");
+    /// assert_eq!(SizeLimit::unlimited().to_string(), "unlimited");
+    /// ```
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_unlimited() {
+            f.write_str("unlimited")
+        } else if !is_exact(self.0, 1000) && is_exact(self.0, 1024) {
+            f.write_str(&self.format_binary())
+        } else {
+            f.write_str(&self.format_decimal())
+        }
+    }
+}
+
+impl From<Option<usize>> for SizeLimit {
+    /// Creates a `SizeLimit` from an optional byte count; `None` means unlimited.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// assert_eq!(SizeLimit::from(Some(1024)).0, 1024);
+    /// assert!(SizeLimit::from(None).is_unlimited());
+    /// ```
+    fn from(bytes: Option<usize>) -> Self {
+        bytes.map_or(SizeLimit::UNLIMITED, SizeLimit)
+    }
+}
+
+impl From<usize> for SizeLimit {
+    /// Creates a `SizeLimit` from a raw byte count.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// let limit = SizeLimit::from(1024);
+    /// assert_eq!(limit.0, 1024);
+    /// ```
+    fn from(bytes: usize) -> Self {
+        SizeLimit(bytes)
+    }
+}
+
+impl From<&str> for SizeLimit {
+    /// Creates a `SizeLimit` from a human-readable string.
+    ///
+    /// `"unlimited"` (any case) creates [`SizeLimit::unlimited`].
+    ///
+    /// # Panics
+    /// Panics if the string cannot be parsed. Use [`SizeLimit::try_from_str`]
+    /// (or the `try_with_*` builders of `SizeLimitConfig`) for sizes read
+    /// from configuration files.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// let limit: SizeLimit = "10MB".into();
+    /// assert_eq!(limit.0, 10_000_000);
+    /// ```
+    fn from(s: &str) -> Self {
+        SizeLimit::parse(s).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl std::str::FromStr for SizeLimit {
+    type Err = SizeParseError;
+
+    /// Parses a human-readable size; see [`SizeLimit::parse`].
+    ///
+    /// This is the fallible conversion to use with `?`, clap's value parsers
+    /// or serde's `FromStr`-based helpers; [`SizeLimit::try_from_str`] is the
+    /// same as a constructor.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::{SizeLimit, SizeParseError};
+    ///
+    /// fn upload_limit(setting: &str) -> Result<SizeLimit, SizeParseError> {
+    ///     let limit: SizeLimit = setting.parse()?;
+    ///     Ok(limit)
+    /// }
+    ///
+    /// assert_eq!(upload_limit("250MB").unwrap().0, 250_000_000);
+    /// assert!(upload_limit("250 elephants").is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SizeLimit::parse(s)
+    }
+}
+
+impl From<String> for SizeLimit {
+    /// Creates a `SizeLimit` from a `String`.
+    ///
+    /// # Panics
+    /// Panics if the string cannot be parsed; see [`SizeLimit::try_from_str`].
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// let s = String::from("100KB");
+    /// let limit: SizeLimit = s.into();
+    /// assert_eq!(limit.0, 100_000);
+    /// ```
+    fn from(s: String) -> Self {
+        s.as_str().into()
+    }
+}
+
+impl SizeLimit {
+    /// Binary kilobyte constant (1,024 bytes).
+    /// Note: This uses binary units despite the name "KB".
+    pub const KB: SizeLimit = SizeLimit(1024);
+
+    /// Binary megabyte constant (1,048,576 bytes).
+    /// Note: This uses binary units despite the name "MB".
+    pub const MB: SizeLimit = SizeLimit(1024 * 1024);
+
+    /// Binary gigabyte constant (1,073,741,824 bytes).
+    /// Note: This uses binary units despite the name "GB".
+    pub const GB: SizeLimit = SizeLimit(1024 * 1024 * 1024);
+
+    /// Kibibyte constant (1,024 bytes).
+    pub const KIB: SizeLimit = SizeLimit(1024);
+
+    /// Mebibyte constant (1,048,576 bytes).
+    pub const MIB: SizeLimit = SizeLimit(1024 * 1024);
+
+    /// Gibibyte constant (1,073,741,824 bytes).
+    pub const GIB: SizeLimit = SizeLimit(1024 * 1024 * 1024);
+
+    /// Tebibyte constant (1,099,511,627,776 bytes).
+    pub const TIB: SizeLimit = SizeLimit(1024 * 1024 * 1024 * 1024);
+
+    /// Pebibyte constant (1,125,899,906,842,624 bytes).
+    pub const PIB: SizeLimit = SizeLimit(1024 * 1024 * 1024 * 1024 * 1024);
+
+    /// No limit. Stored as `usize::MAX`; the middleware passes such bodies
+    /// through without counting them.
+    pub const UNLIMITED: SizeLimit = SizeLimit(usize::MAX);
+
+    /// Creates a `SizeLimit` that does not limit at all.
+    ///
+    /// Use it to opt specific content types, methods or rules out of
+    /// limiting while a default limit applies elsewhere.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// let backups = SizeLimit::unlimited();
+    ///
+    /// assert!(backups.is_unlimited());
+    /// assert_eq!(backups.as_option(), None);
+    /// ```
+    pub fn unlimited() -> Self {
+        SizeLimit::UNLIMITED
+    }
+
+    /// Returns `true` if this is [`SizeLimit::unlimited`].
+    pub fn is_unlimited(&self) -> bool {
+        self.0 == usize::MAX
+    }
+
+    /// The limit in bytes, or `None` if unlimited.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// assert_eq!(SizeLimit::kb(1.0).as_option(), Some(1_000));
+    /// assert_eq!(SizeLimit::unlimited().as_option(), None);
+    /// ```
+    pub fn as_option(&self) -> Option<usize> {
+        (!self.is_unlimited()).then_some(self.0)
+    }
+
+    /// Formats the limit with binary units (KiB, MiB, ...), up to two decimals.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// assert_eq!(SizeLimit::bytes(1_572_864).format_binary(), "1.5 MiB");
+    /// assert_eq!(SizeLimit::bytes(1_000_000).format_binary(), "976.56 KiB");
+    /// assert_eq!(SizeLimit::bytes(512).format_binary(), "512 B");
+    /// ```
+    pub fn format_binary(&self) -> String {
+        format_scaled(self.0, 1024.0, &["B", "KiB", "MiB", "GiB", "TiB", "PiB"])
+    }
+
+    /// Formats the limit with decimal units (KB, MB, ...), up to two decimals.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// assert_eq!(SizeLimit::bytes(1_572_864).format_decimal(), "1.57 MB");
+    /// assert_eq!(SizeLimit::gb(2.5).format_decimal(), "2.5 GB");
+    /// ```
+    pub fn format_decimal(&self) -> String {
+        format_scaled(self.0, 1000.0, &["B", "KB", "MB", "GB", "TB", "PB"])
+    }
+
+    /// Parses a human-readable size such as `"10MB"`, or `"unlimited"`.
+    ///
+    /// # Returns
+    /// * `Ok(SizeLimit)` - The parsed limit
+    /// * `Err(SizeParseError)` - If the string is not a valid size
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// assert_eq!(SizeLimit::parse("1.5 MiB").unwrap().0, 1_572_864);
+    /// assert!(SizeLimit::parse("Unlimited").unwrap().is_unlimited());
+    /// assert!(SizeLimit::parse("lots").is_err());
+    /// ```
+    pub fn parse(s: &str) -> Result<Self, SizeParseError> {
+        if s.trim().eq_ignore_ascii_case("unlimited") {
+            return Ok(SizeLimit::UNLIMITED);
+        }
+        parse_bytes(s).map(SizeLimit)
+    }
+
+    /// Converts a human-readable size like the `From<&str>` and
+    /// `From<String>` conversions do, returning an error instead of
+    /// panicking on invalid input.
+    ///
+    /// `TryFrom<&str>` cannot offer this: the standard library derives it
+    /// from `From<&str>`, so `SizeLimit::try_from` panics like `into()`.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// assert_eq!(SizeLimit::try_from_str("10MB").unwrap().0, 10_000_000);
+    /// assert_eq!(SizeLimit::try_from_str(String::from("64 KiB")).unwrap().0, 65_536);
+    ///
+    /// let error = SizeLimit::try_from_str("junk").unwrap_err();
+    /// assert_eq!(error.input, "junk");
+    /// ```
+    pub fn try_from_str(s: impl AsRef<str>) -> Result<Self, SizeParseError> {
+        Self::parse(s.as_ref())
+    }
+
+    /// Parses a human-readable size during constant evaluation.
+    ///
+    /// Accepts the same syntax as [`parse`](Self::parse) but panics on
+    /// invalid input, so in a `const` context a typo fails the build. This is
+    /// what [`size_limits!`](crate::size_limits) uses.
+    ///
+    /// # Panics
+    /// Panics if the string is not a valid size.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// const UPLOAD: SizeLimit = SizeLimit::parse_const("1.5 GiB");
+    /// assert_eq!(UPLOAD.0, 1_610_612_736);
+    /// ```
+    ///
+    /// ```compile_fail
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// const UPLOAD: SizeLimit = SizeLimit::parse_const("10 mega");
+    /// ```
+    pub const fn parse_const(s: &str) -> SizeLimit {
+        let s = s.as_bytes().trim_ascii();
+        if s.is_empty() {
+            panic!("Invalid size: empty size string");
+        }
+        if s.eq_ignore_ascii_case(b"unlimited") {
+            return SizeLimit::UNLIMITED;
+        }
+
+        // Number: digits with an optional '.' or ',' decimal separator
+        let mut mantissa: u64 = 0;
+        let mut decimals = 0;
+        let mut digits = 0;
+        let mut separator = false;
+        let mut i = 0;
+        while i < s.len() && (s[i].is_ascii_digit() || s[i] == b'.' || s[i] == b',') {
+            if s[i].is_ascii_digit() {
+                mantissa = mantissa.saturating_mul(10).saturating_add((s[i] - b'0') as u64);
+                digits += 1;
+                if separator {
+                    decimals += 1;
+                }
+            } else if separator {
+                panic!("Invalid size: invalid number");
+            } else {
+                separator = true;
+            }
+            i += 1;
+        }
+        if i == 0 {
+            panic!("Invalid size: no number found");
+        }
+        if digits == 0 {
+            panic!("Invalid size: invalid number");
+        }
+
+        let (_, unit) = s.split_at(i);
+        let unit = unit.trim_ascii();
+        let unit = if unit.is_empty() {
+            SizeUnit::Bytes
+        } else {
+            match SizeUnit::parse_ascii(unit) {
+                Some(unit) => unit,
+                None => panic!("Invalid size: unknown unit"),
+            }
+        };
+
+        let mut value = mantissa as f64;
+        while decimals > 0 {
+            value /= 10.0;
+            decimals -= 1;
+        }
+        SizeLimit(unit.to_bytes(value))
+    }
+
+    /// Creates a `SizeLimit` from a raw byte count.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// let limit = SizeLimit::bytes(1024);
+    /// assert_eq!(limit.0, 1024);
+    /// ```
+    pub fn bytes(bytes: usize) -> Self {
+        SizeLimit(bytes)
+    }
+
+    /// Creates a `SizeLimit` from decimal kilobytes.
+    ///
+    /// # Arguments
+    /// * `kb` - Number of kilobytes (1 KB = 1,000 bytes)
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// let limit = SizeLimit::kb(2.5);
+    /// assert_eq!(limit.0, 2_500); // 2.5 × 1,000
+    /// ```
+    pub fn kb(kb: f64) -> Self {
+        SizeLimit((kb * 1000.0) as usize)
+    }
+
+    /// Creates a `SizeLimit` from decimal megabytes.
+    ///
+    /// # Arguments
+    /// * `mb` - Number of megabytes (1 MB = 1,000,000 bytes)
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// let limit = SizeLimit::mb(1.5);
+    /// assert_eq!(limit.0, 1_500_000); // 1.5 × 1,000,000
+    /// ```
+    pub fn mb(mb: f64) -> Self {
+        SizeLimit((mb * 1_000_000.0) as usize)
+    }
+
+    /// Creates a `SizeLimit` from decimal gigabytes.
+    ///
+    /// # Arguments
+    /// * `gb` - Number of gigabytes (1 GB = 1,000,000,000 bytes)
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// let limit = SizeLimit::gb(0.5);
+    /// assert_eq!(limit.0, 500_000_000); // 0.5 × 1,000,000,000
+    /// ```
+    pub fn gb(gb: f64) -> Self {
+        SizeLimit((gb * 1_000_000_000.0) as usize)
+    }
+
+    /// Creates a `SizeLimit` from decimal terabytes.
+    ///
+    /// # Arguments
+    /// * `tb` - Number of terabytes (1 TB = 1,000,000,000,000 bytes)
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// let limit = SizeLimit::tb(2.0);
+    /// assert_eq!(limit.0, 2_000_000_000_000);
+    /// ```
+    pub fn tb(tb: f64) -> Self {
+        SizeLimit(SizeUnit::Terabytes.to_bytes(tb))
+    }
+
+    /// Creates a `SizeLimit` from decimal petabytes.
+    ///
+    /// # Arguments
+    /// * `pb` - Number of petabytes (1 PB = 1,000,000,000,000,000 bytes)
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// let limit = SizeLimit::pb(0.5);
+    /// assert_eq!(limit.0, 500_000_000_000_000);
+    /// ```
+    pub fn pb(pb: f64) -> Self {
+        SizeLimit(SizeUnit::Petabytes.to_bytes(pb))
+    }
+
+    /// Creates a `SizeLimit` from binary kibibytes.
+    ///
+    /// # Arguments
+    /// * `kib` - Number of kibibytes (1 KiB = 1,024 bytes)
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// let limit = SizeLimit::kib(2.0);
+    /// assert_eq!(limit.0, 2_048); // 2 × 1,024
+    /// ```
+    pub fn kib(kib: f64) -> Self {
+        SizeLimit((kib * 1024.0) as usize)
+    }
+
+    /// Creates a `SizeLimit` from binary mebibytes.
+    ///
+    /// # Arguments
+    /// * `mib` - Number of mebibytes (1 MiB = 1,048,576 bytes)
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// let limit = SizeLimit::mib(1.5);
+    /// assert_eq!(limit.0, 1_572_864); // 1.5 × 1,048,576
+    /// ```
+    pub fn mib(mib: f64) -> Self {
+        SizeLimit((mib * 1_048_576.0) as usize)
+    }
+
+    /// Creates a `SizeLimit` from binary gibibytes.
+    ///
+    /// # Arguments
+    /// * `gib` - Number of gibibytes (1 GiB = 1,073,741,824 bytes)
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// let limit = SizeLimit::gib(0.25);
+    /// assert_eq!(limit.0, 268_435_456); // 0.25 × 1,073,741,824
+    /// ```
+    pub fn gib(gib: f64) -> Self {
+        SizeLimit((gib * 1_073_741_824.0) as usize)
+    }
+
+    /// Creates a `SizeLimit` from binary tebibytes.
+    ///
+    /// # Arguments
+    /// * `tib` - Number of tebibytes (1 TiB = 1,099,511,627,776 bytes)
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// let limit = SizeLimit::tib(1.0);
+    /// assert_eq!(limit.0, 1_099_511_627_776);
+    /// ```
+    pub fn tib(tib: f64) -> Self {
+        SizeLimit(SizeUnit::Tebibytes.to_bytes(tib))
+    }
+
+    /// Creates a `SizeLimit` from binary pebibytes.
+    ///
+    /// # Arguments
+    /// * `pib` - Number of pebibytes (1 PiB = 1,125,899,906,842,624 bytes)
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// let limit = SizeLimit::pib(1.0);
+    /// assert_eq!(limit.0, 1_125_899_906_842_624);
+    /// ```
+    pub fn pib(pib: f64) -> Self {
+        SizeLimit(SizeUnit::Pebibytes.to_bytes(pib))
+    }
+
+    /// Creates a `SizeLimit` from kilobits.
+    ///
+    /// # Arguments
+    /// * `kbit` - Number of kilobits (1 kbit = 125 bytes)
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// let limit = SizeLimit::kbit(8.0);
+    /// assert_eq!(limit.0, 1_000); // 8 × 125 = 1,000 bytes
+    /// ```
+    pub fn kbit(kbit: f64) -> Self {
+        SizeLimit((kbit * 125.0) as usize)
+    }
+
+    /// Creates a `SizeLimit` from megabits.
+    ///
+    /// # Arguments
+    /// * `mbit` - Number of megabits (1 Mbit = 125,000 bytes)
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// let limit = SizeLimit::mbit(10.0);
+    /// assert_eq!(limit.0, 1_250_000); // 10 × 125,000
+    /// ```
+    pub fn mbit(mbit: f64) -> Self {
+        SizeLimit((mbit * 125_000.0) as usize)
+    }
+
+    /// Creates a `SizeLimit` from gigabits.
+    ///
+    /// # Arguments
+    /// * `gbit` - Number of gigabits (1 Gbit = 125,000,000 bytes)
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// let limit = SizeLimit::gbit(1.0);
+    /// assert_eq!(limit.0, 125_000_000);
+    /// ```
+    pub fn gbit(gbit: f64) -> Self {
+        SizeLimit((gbit * 125_000_000.0) as usize)
+    }
+
+    /// Creates a `SizeLimit` from terabits.
+    ///
+    /// # Arguments
+    /// * `tbit` - Number of terabits (1 Tbit = 125,000,000,000 bytes)
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// let limit = SizeLimit::tbit(1.0);
+    /// assert_eq!(limit.0, 125_000_000_000);
+    /// ```
+    pub fn tbit(tbit: f64) -> Self {
+        SizeLimit(SizeUnit::Terabits.to_bytes(tbit))
+    }
+
+    /// Creates a `SizeLimit` as a percentage of a memory total.
+    ///
+    /// # Arguments
+    /// * `total` - Memory total in bytes
+    /// * `percent` - Percentage of `total` (e.g., `20.0` for 20%)
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// let limit = SizeLimit::percent_of(2_000_000_000, 20.0);
+    /// assert_eq!(limit.0, 400_000_000);
+    /// ```
+    pub fn percent_of(total: usize, percent: f64) -> Self {
+        SizeLimit((total as f64 * percent / 100.0) as usize)
+    }
+
+    /// Creates a `SizeLimit` as a percentage of the detected memory limit.
+    ///
+    /// The value is computed once, when called; build the configuration at
+    /// startup (and again on reload) so one config adapts to differently
+    /// sized containers. See [`detect_memory_limit`] for the detection order.
+    ///
+    /// # Arguments
+    /// * `percent` - Percentage of detected memory (e.g., `20.0` for 20%)
+    ///
+    /// # Returns
+    /// * `Ok(SizeLimit)` - The computed limit
+    /// * `Err(SizeParseError)` - If the percentage is out of range or memory cannot be detected
+    ///
+    /// # Examples
+    /// ```no_run
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// let default_limit = SizeLimit::percent_of_memory(5.0).unwrap_or(SizeLimit::mib(16.0));
+    /// ```
+    pub fn percent_of_memory(percent: f64) -> Result<Self, SizeParseError> {
+        let error = |reason: String| SizeParseError {
+            input: format!("{}%", percent),
+            reason,
+        };
+        if !(percent > 0.0 && percent <= 100.0) {
+            return Err(error(format!("Percentage must be in (0, 100], got {}", percent)));
+        }
+        let total = detect_memory_limit()
+            .ok_or_else(|| error("Unable to detect available memory".to_string()))?;
+        Ok(Self::percent_of(total, percent))
+    }
+
+    /// Parses a size that may be relative to memory.
+    ///
+    /// Accepts everything [`parse_human_size`] does, plus percentages such as
+    /// `"20%"`, which are resolved against `total`.
+    ///
+    /// # Arguments
+    /// * `s` - Size string (e.g., `"10MB"` or `"20%"`)
+    /// * `total` - Memory total in bytes used for percentages
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size::SizeLimit;
+    ///
+    /// assert_eq!(SizeLimit::parse_relative("25%", 1_000).unwrap().0, 250);
+    /// assert_eq!(SizeLimit::parse_relative("1KB", 1_000).unwrap().0, 1_000);
+    /// assert!(SizeLimit::parse_relative("150%", 1_000).is_err());
+    /// ```
+    pub fn parse_relative(s: &str, total: usize) -> Result<Self, SizeParseError> {
+        let error = |reason: String| SizeParseError {
+            input: s.to_string(),
+            reason,
+        };
+        match s.trim().strip_suffix('%') {
+            Some(percent) => {
+                let percent = percent.trim().replace(',', ".").parse::<f64>()
+                    .map_err(|e| error(format!("Invalid percentage: {}", e)))?;
+                if !(percent > 0.0 && percent <= 100.0) {
+                    return Err(error("Percentage must be in (0, 100]".to_string()));
+                }
+                Ok(Self::percent_of(total, percent))
+            }
+            None => parse_bytes(s).map(SizeLimit),
+        }
+    }
+}
+
+/// Returns `true` if `bytes` has at most two decimals in the unit
+/// [`format_scaled`] picks for `base`.
+fn is_exact(bytes: usize, base: usize) -> bool {
+    let mut divisor: u128 = 1;
+    while (bytes as u128) >= divisor * base as u128 && divisor < (base as u128).pow(5) {
+        divisor *= base as u128;
+    }
+    (bytes as u128 * 100).is_multiple_of(divisor)
+}
+
+/// Formats `bytes` in the largest unit keeping the value at or above 1,
+/// with at most two decimals and no trailing zeros.
+fn format_scaled(bytes: usize, base: f64, units: &[&str]) -> String {
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    // Compare the rounded value, so 999,999 bytes print as "1 MB", not "1000 KB"
+    while (value * 100.0).round() / 100.0 >= base && unit + 1 < units.len() {
+        value /= base;
+        unit += 1;
+    }
+    let rounded = format!("{:.2}", value);
+    let rounded = rounded.trim_end_matches('0').trim_end_matches('.');
+    format!("{} {}", rounded, units[unit])
+}
+
+/// cgroup v1 reports "no limit" as a value close to `i64::MAX`.
+const CGROUP_V1_UNLIMITED: u64 = 1 << 60;
+
+/// Detects the memory available to this process, in bytes.
+///
+/// Checks, in order:
+/// 1. the cgroup v2 limit (`/sys/fs/cgroup/memory.max`)
+/// 2. the cgroup v1 limit (`/sys/fs/cgroup/memory/memory.limit_in_bytes`)
+/// 3. `MemAvailable` from `/proc/meminfo`
+///
+/// Unlimited cgroups fall through to the next source. Returns `None` when
+/// no source is readable (e.g., on non-Linux platforms).
+pub fn detect_memory_limit() -> Option<usize> {
+    let read = |path: &str| std::fs::read_to_string(path).ok();
+
+    read("/sys/fs/cgroup/memory.max")
+        .and_then(|s| parse_cgroup_limit(&s))
+        .or_else(|| read("/sys/fs/cgroup/memory/memory.limit_in_bytes").and_then(|s| parse_cgroup_limit(&s)))
+        .or_else(|| read("/proc/meminfo").and_then(|s| parse_meminfo_available(&s)))
+}
+
+fn parse_cgroup_limit(contents: &str) -> Option<usize> {
+    let value = contents.trim().parse::<u64>().ok()?;
+    if value == 0 || value >= CGROUP_V1_UNLIMITED {
+        return None;
+    }
+    usize::try_from(value).ok()
+}
+
+fn parse_meminfo_available(contents: &str) -> Option<usize> {
+    contents.lines().find_map(|line| {
+        let kib = line.strip_prefix("MemAvailable:")?.trim().strip_suffix("kB")?;
+        kib.trim().parse::<usize>().ok().map(|kib| kib * 1024)
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::disallowed_methods)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_human_size() {
+        // Test bytes (no unit)
+        assert_eq!(parse_human_size("1024").unwrap(), 1024);
+
+        // Test decimal units
+        assert_eq!(parse_human_size("1KB").unwrap(), 1000);
+        assert_eq!(parse_human_size("1kb").unwrap(), 1000); // Case insensitive
+        assert_eq!(parse_human_size("1.5MB").unwrap(), 1_500_000);
+        assert_eq!(parse_human_size("2.5 GB").unwrap(), 2_500_000_000); // With space
+        assert_eq!(parse_human_size("2TB").unwrap(), 2_000_000_000_000);
+        assert_eq!(parse_human_size("1 petabyte").unwrap(), 1_000_000_000_000_000);
+
+        // Test binary units
+        assert_eq!(parse_human_size("1KiB").unwrap(), 1024);
+        assert_eq!(parse_human_size("1MiB").unwrap(), 1_048_576);
+        assert_eq!(parse_human_size("1GiB").unwrap(), 1_073_741_824);
+        assert_eq!(parse_human_size("1TiB").unwrap(), 1_099_511_627_776);
+        assert_eq!(parse_human_size("1PiB").unwrap(), 1_125_899_906_842_624);
+
+        // Test bit units
+        assert_eq!(parse_human_size("1Mbit").unwrap(), 125_000);
+        assert_eq!(parse_human_size("10Mbit").unwrap(), 1_250_000);
+        assert_eq!(parse_human_size("1Gbit").unwrap(), 125_000_000);
+        assert_eq!(parse_human_size("1Tbit").unwrap(), 125_000_000_000);
+
+        // Test international decimal format (comma separator)
+        assert_eq!(parse_human_size("1,5MB").unwrap(), 1_500_000);
+
+        // Test error cases
+        assert!(parse_human_size("").is_err()); // Empty string
+        assert!(parse_human_size("abc").is_err()); // No number
+        assert!(parse_human_size("1XB").is_err()); // Unknown unit
+    }
+
+    #[test]
+    fn test_size_limit_from_str() {
+        // Test From<&str> implementation
+        let limit: SizeLimit = "2MB".into();
+        assert_eq!(limit.0, 2_000_000);
+
+        let limit: SizeLimit = "1.5GB".into();
+        assert_eq!(limit.0, 1_500_000_000);
+
+        let limit: SizeLimit = "100Mbit".into();
+        assert_eq!(limit.0, 12_500_000); // 100 × 125,000
+
+        // Test formatting
+        assert_eq!(SizeLimit::bytes(1_572_864).to_string(), "1.5 MiB");
+        assert_eq!(SizeLimit::bytes(999_999).format_decimal(), "1 MB");
+        assert_eq!(SizeLimit::bytes(0).to_string(), "0 B");
+        assert_eq!(SizeLimit::tb(3.0).to_string(), "3 TB");
+
+        // Test FromStr / TryFrom implementations
+        assert_eq!("1.5GB".parse::<SizeLimit>().map(|limit| limit.0), Ok(1_500_000_000));
+        assert!("1.5 GX".parse::<SizeLimit>().is_err());
+        assert_eq!(SizeUnit::try_from("TiB"), Ok(SizeUnit::Tebibytes));
+        assert_eq!(SizeUnit::try_from("XB").map_err(|e| e.input), Err("XB".to_string()));
+
+        // Invalid strings are errors, not panics
+        let error = SizeLimit::try_from_str("junk").unwrap_err();
+        assert_eq!(error.input, "junk");
+        assert_eq!(error.reason, "No number found");
+        assert_eq!(SizeLimit::try_from_str(String::from("1 GX")).unwrap_err().reason, "Unknown unit 'gx'");
+        assert_eq!(SizeLimit::parse("1.2.3MB").unwrap_err().input, "1.2.3MB");
+        assert_eq!(parse_human_size("1 GX"), Err("Unknown unit 'gx'".to_string()));
+        assert_eq!(SizeLimit::parse_relative("150%", 1_000).unwrap_err().input, "150%");
+        assert!(SizeLimit::percent_of_memory(0.0).is_err());
+    }
+
+    #[test]
+    fn test_memory_sources() {
+        // cgroup v2 reports "max" when unlimited
+        assert_eq!(parse_cgroup_limit("max\n"), None);
+        assert_eq!(parse_cgroup_limit("536870912\n"), Some(536_870_912));
+        // cgroup v1 reports a huge value when unlimited
+        assert_eq!(parse_cgroup_limit("9223372036854771712\n"), None);
+
+        let meminfo = "MemTotal:       16000000 kB\nMemFree:         1000000 kB\nMemAvailable:    8000000 kB\n";
+        assert_eq!(parse_meminfo_available(meminfo), Some(8_000_000 * 1024));
+        assert_eq!(parse_meminfo_available("MemTotal: 1 kB\n"), None);
+    }
+}
\ No newline at end of file
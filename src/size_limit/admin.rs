@@ -0,0 +1,96 @@
+//! Admin routes exposing the live size limits (feature `size-limit-admin`).
+
+use axum::{
+    Json, Router,
+    body::Body,
+    extract::{Request, State},
+    http::{StatusCode, request::Parts},
+    middleware::{self, Next},
+    response::Response,
+    routing::get,
+};
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::error::ErrorFormat;
+use crate::size_limit::{BufferStrategy, RejectionStats, SizeLimitConfig, SizeLimitHandle};
+
+/// Path of the effective configuration endpoint.
+pub const ADMIN_LIMITS_PATH: &str = "/limits";
+
+/// Path of the rejection statistics endpoint.
+pub const ADMIN_STATS_PATH: &str = "/limits/stats";
+
+/// Decides whether a request may use the admin routes.
+pub type AdminAuth = Arc<dyn Fn(&Parts) -> bool + Send + Sync>;
+
+/// Body of `GET /limits`.
+#[derive(Serialize)]
+struct EffectiveLimits {
+    limits: SizeLimitConfig,
+    buffering: BufferStrategy,
+}
+
+/// Creates a router exposing the limits behind a [`SizeLimitHandle`].
+///
+/// * `GET /limits` - The effective configuration as JSON:
+///   `{"limits": {...}, "buffering": {...}}`, in the serde form of
+///   [`SizeLimitConfig`] and [`BufferStrategy`].
+/// * `GET /limits/stats` - [`RejectionStats`] as JSON:
+///   `{"total": 3, "by_content_type": {"image/png": 3}}`.
+///
+/// Every request must pass `auth` first; others get `401 Unauthorized`. Mount
+/// the router under an internal prefix with [`Router::nest`].
+///
+/// # Arguments
+/// * `handle` - Handle returned by [`with_size_limit_handle`](crate::size_limit::with_size_limit_handle)
+/// * `auth` - Returns `true` for requests allowed to read the admin routes
+///
+/// # Returns
+/// A router with the two admin routes.
+///
+/// # Example
+/// ```rust
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::size_limit::{SizeLimitMiddlewareConfig, admin_router, with_size_limit_handle};
+///
+/// let api: Router = Router::new().route("/upload", post(|| async { "ok" }));
+/// let (api, limits) = with_size_limit_handle(api, SizeLimitMiddlewareConfig::default());
+///
+/// let app = api.nest(
+///     "/admin",
+///     admin_router(limits, |parts| {
+///         parts.headers.get("authorization").is_some_and(|value| value == "Bearer s3cret")
+///     }),
+/// );
+/// ```
+pub fn admin_router<S>(handle: SizeLimitHandle, auth: impl Fn(&Parts) -> bool + Send + Sync + 'static) -> Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let auth: AdminAuth = Arc::new(auth);
+    Router::new()
+        .route(ADMIN_LIMITS_PATH, get(effective_limits))
+        .route(ADMIN_STATS_PATH, get(rejection_stats))
+        .with_state(handle)
+        .layer(middleware::from_fn_with_state(auth, authorize))
+}
+
+async fn authorize(State(auth): State<AdminAuth>, req: Request<Body>, next: Next) -> Response {
+    let (parts, body) = req.into_parts();
+    if !auth(&parts) {
+        return ErrorFormat::Json.response(StatusCode::UNAUTHORIZED, "Unauthorized");
+    }
+    next.run(Request::from_parts(parts, body)).await
+}
+
+async fn effective_limits(State(handle): State<SizeLimitHandle>) -> Json<EffectiveLimits> {
+    Json(EffectiveLimits {
+        limits: handle.config(),
+        buffering: handle.buffer_strategy(),
+    })
+}
+
+async fn rejection_stats(State(handle): State<SizeLimitHandle>) -> Json<RejectionStats> {
+    Json(handle.stats())
+}
@@ -0,0 +1,91 @@
+//! Exact ingress byte counts.
+//!
+//! The size limiter counts the body bytes it reads from the client and
+//! leaves the count in the request and response extensions as an
+//! [`ObservedBodySize`], optionally also in a response header (see
+//! [`with_body_size_header`](crate::size_limit::SizeLimitMiddlewareConfig::with_body_size_header)),
+//! so access logs and billing need not trust `Content-Length`.
+
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    extract::Request,
+};
+use http_body::{Frame, SizeHint};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::task::{Context, Poll, ready};
+
+/// Body bytes the size limiter read from the client.
+///
+/// In the request extensions the count grows as the handler reads the body.
+/// In the response extensions it is final: it includes bytes drained after
+/// the handler stopped reading, or after a rejection.
+///
+/// # Examples
+/// ```
+/// use axum::{Router, body::Bytes, extract::Extension, routing::post};
+/// use axum_jetpack::size_limit::{ObservedBodySize, RouterExt};
+///
+/// async fn upload(Extension(size): Extension<ObservedBodySize>, body: Bytes) -> String {
+///     assert_eq!(size.bytes(), body.len() as u64);
+///     format!("received {} bytes", size.bytes())
+/// }
+///
+/// let router: Router = Router::new().route("/upload", post(upload)).size_limit("10mb");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ObservedBodySize {
+    received: Arc<AtomicU64>,
+}
+
+impl ObservedBodySize {
+    /// Body bytes read so far.
+    pub fn bytes(&self) -> u64 {
+        self.received.load(Ordering::Relaxed)
+    }
+
+    /// Wraps the body of `req` to count the bytes read from it, leaving the
+    /// count in its extensions.
+    pub(crate) fn attach(req: Request<Body>) -> (Request<Body>, Self) {
+        let size = Self::default();
+        let (mut parts, body) = req.into_parts();
+        parts.extensions.insert(size.clone());
+        let body = match body.is_end_stream() {
+            true => body,
+            false => Body::new(CountingBody {
+                inner: body,
+                received: size.received.clone(),
+            }),
+        };
+        (Request::from_parts(parts, body), size)
+    }
+}
+
+/// A body passed through unchanged while its bytes are counted.
+struct CountingBody {
+    inner: Body,
+    received: Arc<AtomicU64>,
+}
+
+impl HttpBody for CountingBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, axum::Error>>> {
+        let this = self.get_mut();
+        let frame = ready!(Pin::new(&mut this.inner).poll_frame(cx));
+        if let Some(data) = frame.as_ref().and_then(|frame| frame.as_ref().ok()?.data_ref()) {
+            this.received.fetch_add(data.len() as u64, Ordering::Relaxed);
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
@@ -0,0 +1,109 @@
+//! A process-wide cap on buffered request bodies.
+//!
+//! Per-request limits bound each body, not how many of them sit in memory at
+//! once: 1000 concurrent 10 MB uploads still buffer 10 GB. A [`BufferBudget`]
+//! caps the total. Requests take their share of it before their body is
+//! buffered and give it back once their response is ready; when it runs out
+//! they are refused, or wait for room.
+
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+use crate::size_limit::{SizeLimit, SizeLimitError};
+
+/// Budget is counted in blocks of this many bytes, so the largest share a
+/// request can take (`u32::MAX` permits) is far beyond any budget.
+const BLOCK: usize = 1024;
+
+/// What happens to a request whose body does not fit in the budget.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OnExhausted {
+    /// Refuse with 503 (Service Unavailable).
+    #[default]
+    Unavailable,
+    /// Refuse with 429 (Too Many Requests).
+    TooManyRequests,
+    /// Wait up to this long for room, then refuse with 503.
+    Wait(Duration),
+}
+
+/// Memory shared by the buffered bodies of concurrent requests.
+///
+/// Clones share the budget, so one budget given to several size limiters
+/// caps them all together.
+///
+/// Each buffered request takes its declared `Content-Length`, or its whole
+/// limit when the length is unknown, capped at the budget itself. Streamed
+/// requests take nothing.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::size_limit::{BufferBudget, OnExhausted, SizeLimitMiddlewareConfig};
+/// use std::time::Duration;
+///
+/// let budget = BufferBudget::new("512mb").with_on_exhausted(OnExhausted::Wait(Duration::from_secs(2)));
+/// assert_eq!(budget.capacity(), 512_000_000);
+///
+/// let config = SizeLimitMiddlewareConfig::default().with_buffer_budget(budget);
+/// ```
+#[derive(Clone, Debug)]
+pub struct BufferBudget {
+    semaphore: Arc<Semaphore>,
+    blocks: u32,
+    on_exhausted: OnExhausted,
+}
+
+impl BufferBudget {
+    /// Creates a budget.
+    ///
+    /// # Arguments
+    /// * `capacity` - Bytes of request bodies buffered at once, at most
+    pub fn new(capacity: impl Into<SizeLimit>) -> Self {
+        let blocks = blocks(capacity.into().0);
+        Self {
+            semaphore: Arc::new(Semaphore::new(blocks as usize)),
+            blocks,
+            on_exhausted: OnExhausted::default(),
+        }
+    }
+
+    /// Builder method to choose what happens when the budget runs out.
+    pub fn with_on_exhausted(mut self, on_exhausted: OnExhausted) -> Self {
+        self.on_exhausted = on_exhausted;
+        self
+    }
+
+    /// The budget in bytes, rounded up to whole kilobytes (1024 bytes).
+    pub fn capacity(&self) -> usize {
+        self.blocks as usize * BLOCK
+    }
+
+    /// Bytes not taken by buffered requests right now.
+    pub fn available(&self) -> usize {
+        self.semaphore.available_permits() * BLOCK
+    }
+
+    /// Takes room for a body of `bytes`, held until the permit is dropped.
+    pub(crate) async fn reserve(&self, bytes: usize) -> Result<OwnedSemaphorePermit, SizeLimitError> {
+        let blocks = blocks(bytes).min(self.blocks);
+        let semaphore = self.semaphore.clone();
+        match self.on_exhausted {
+            OnExhausted::Unavailable => semaphore
+                .try_acquire_many_owned(blocks)
+                .map_err(|_| SizeLimitError::Overloaded),
+            OnExhausted::TooManyRequests => semaphore
+                .try_acquire_many_owned(blocks)
+                .map_err(|_| SizeLimitError::TooManyRequests),
+            OnExhausted::Wait(timeout) => match tokio::time::timeout(timeout, semaphore.acquire_many_owned(blocks)).await {
+                Ok(Ok(permit)) => Ok(permit),
+                _ => Err(SizeLimitError::Overloaded),
+            },
+        }
+    }
+}
+
+/// Blocks needed for `bytes`.
+fn blocks(bytes: usize) -> u32 {
+    u32::try_from(bytes.div_ceil(BLOCK)).unwrap_or(u32::MAX)
+}
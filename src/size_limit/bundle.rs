@@ -0,0 +1,80 @@
+//! Named groups of content types for limit and buffering configuration.
+
+/// A group of related content types.
+///
+/// Bundles expand to exact types and glob patterns, so one call configures
+/// every member: [`SizeLimitConfig::with_bundle_limit`](crate::size_limit::SizeLimitConfig::with_bundle_limit)
+/// for limits, [`BufferStrategy::with_buffered_bundle`](crate::size_limit::BufferStrategy::with_buffered_bundle)
+/// and [`BufferStrategy::with_streamed_bundle`](crate::size_limit::BufferStrategy::with_streamed_bundle)
+/// for buffering.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::size_limit::{Bundle, SizeLimitConfig};
+///
+/// let config = SizeLimitConfig::default()
+///     .with_bundle_limit(Bundle::Images, "10mb")
+///     .with_bundle_limit(Bundle::Archives, "500mb");
+///
+/// assert_eq!(config.get_limit_for_content_type("image/webp"), 10_000_000);
+/// assert_eq!(config.get_limit_for_content_type("application/zip"), 500_000_000);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Bundle {
+    /// Every `image/*` type.
+    Images,
+    /// PDF, plain text, Markdown, CSV, RTF and word processor documents.
+    Documents,
+    /// ZIP, tar, gzip, bzip2, xz, zstd, 7z and RAR archives.
+    Archives,
+    /// Every `audio/*` and `video/*` type.
+    Media,
+    /// Microsoft Office and OpenDocument formats.
+    Office,
+}
+
+impl Bundle {
+    /// Content types and glob patterns in the bundle, lowercase.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size_limit::Bundle;
+    ///
+    /// assert_eq!(Bundle::Media.content_types(), &["audio/*", "video/*"]);
+    /// ```
+    pub fn content_types(&self) -> &'static [&'static str] {
+        match self {
+            Bundle::Images => &["image/*"],
+            Bundle::Documents => &[
+                "application/pdf",
+                "text/plain",
+                "text/markdown",
+                "text/csv",
+                "application/rtf",
+                "application/msword",
+                "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+                "application/vnd.oasis.opendocument.text",
+            ],
+            Bundle::Archives => &[
+                "application/zip",
+                "application/x-tar",
+                "application/gzip",
+                "application/x-gzip",
+                "application/x-bzip2",
+                "application/x-xz",
+                "application/zstd",
+                "application/x-7z-compressed",
+                "application/vnd.rar",
+                "application/x-rar-compressed",
+            ],
+            Bundle::Media => &["audio/*", "video/*"],
+            Bundle::Office => &[
+                "application/msword",
+                "application/vnd.ms-excel",
+                "application/vnd.ms-powerpoint",
+                "application/vnd.openxmlformats-officedocument.*",
+                "application/vnd.oasis.opendocument.*",
+            ],
+        }
+    }
+}
@@ -0,0 +1,210 @@
+//! Concurrent upload cap per key.
+//!
+//! [`UploadConcurrencyLayer`] bounds how many uploads a client, API key or
+//! tenant (see [`KeyExtractor`]) may have in flight at once, e.g. 3, and
+//! answers 429 (Too Many Requests) beyond that, so one client cannot tie up
+//! every worker with slow uploads.
+
+use axum::{
+    body::{Body, HttpBody},
+    extract::Request,
+    http::header,
+    response::Response,
+};
+use futures::future::BoxFuture;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+use crate::error::ErrorFormat;
+use crate::size_limit::{HeaderKey, KeyExtractor, PeerIp, SizeLimit, SizeLimitError};
+
+/// Layer capping the uploads each key has in flight.
+///
+/// A request counts as an upload if it has a body of at least
+/// [`with_min_size`](Self::with_min_size) bytes, or of unknown length; it
+/// stays in flight until its response is ready. Requests without a key, or
+/// without such a body, pass through uncounted.
+///
+/// # Examples
+/// ```
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::size_limit::{RouterExt, UploadConcurrencyLayer};
+///
+/// // At most 3 uploads of 1 MB or more per API key at a time
+/// let router: Router = Router::new()
+///     .route("/upload", post(|| async { "ok" }))
+///     .size_limit("1gb")
+///     .layer(UploadConcurrencyLayer::new(3).with_header_key("x-api-key").with_min_size("1mb"));
+/// ```
+#[derive(Clone)]
+pub struct UploadConcurrencyLayer {
+    max: usize,
+    min_size: u64,
+    key: Arc<dyn KeyExtractor>,
+    error_format: ErrorFormat,
+    in_flight: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+impl UploadConcurrencyLayer {
+    /// Creates a layer allowing `max` uploads in flight per client IP
+    /// ([`PeerIp`]).
+    ///
+    /// # Arguments
+    /// * `max` - Uploads a key may have in flight at once
+    pub fn new(max: usize) -> Self {
+        Self {
+            max,
+            min_size: 1,
+            key: Arc::new(PeerIp),
+            error_format: ErrorFormat::default(),
+            in_flight: Arc::default(),
+        }
+    }
+
+    /// Builder method to only count bodies of at least `size` bytes, so
+    /// small requests are never held up. Any body counts by default.
+    pub fn with_min_size(mut self, size: impl Into<SizeLimit>) -> Self {
+        self.min_size = (size.into().0 as u64).max(1);
+        self
+    }
+
+    /// Builder method to count uploads by a custom key.
+    pub fn with_key(mut self, key: impl KeyExtractor) -> Self {
+        self.key = Arc::new(key);
+        self
+    }
+
+    /// Builder method to count uploads by a header, e.g. `x-api-key`.
+    pub fn with_header_key(self, header: &'static str) -> Self {
+        self.with_key(HeaderKey::new(header))
+    }
+
+    /// Builder method to set how the 429 rejection is rendered.
+    pub fn with_error_format(mut self, format: ErrorFormat) -> Self {
+        self.error_format = format;
+        self
+    }
+
+    /// Uploads currently in flight for `key`.
+    pub fn in_flight(&self, key: &str) -> usize {
+        let in_flight = self.in_flight.lock().unwrap_or_else(PoisonError::into_inner);
+        in_flight.get(key).copied().unwrap_or(0)
+    }
+
+    /// Whether a request carries a body large enough to count.
+    fn is_upload(&self, req: &Request<Body>) -> bool {
+        if req.body().is_end_stream() {
+            return false;
+        }
+        let declared = req
+            .headers()
+            .get(header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok());
+        let hint = req.body().size_hint();
+        // Bodies of unknown length may be of any size
+        match declared.or(hint.exact()) {
+            Some(size) => size >= self.min_size,
+            None => hint.upper().is_none_or(|upper| upper >= self.min_size),
+        }
+    }
+
+    /// Takes a slot for `key`, unless it has `max` uploads in flight.
+    fn acquire(&self, key: String) -> Option<InFlight> {
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(PoisonError::into_inner);
+        if in_flight.get(&key).copied().unwrap_or(0) >= self.max {
+            return None;
+        }
+        *in_flight.entry(key.clone()).or_insert(0) += 1;
+        Some(InFlight {
+            key,
+            in_flight: self.in_flight.clone(),
+        })
+    }
+}
+
+impl fmt::Debug for UploadConcurrencyLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UploadConcurrencyLayer")
+            .field("max", &self.max)
+            .field("min_size", &self.min_size)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> Layer<S> for UploadConcurrencyLayer {
+    type Service = UploadConcurrencyService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        UploadConcurrencyService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// Service created by [`UploadConcurrencyLayer`].
+#[derive(Clone, Debug)]
+pub struct UploadConcurrencyService<S> {
+    inner: S,
+    layer: UploadConcurrencyLayer,
+}
+
+impl<S> Service<Request<Body>> for UploadConcurrencyService<S>
+where
+    S: Service<Request<Body>, Response = Response>,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        if !self.layer.is_upload(&req) {
+            return Box::pin(self.inner.call(req));
+        }
+        let (parts, body) = req.into_parts();
+        let slot = match self.layer.key.key(&parts) {
+            Some(key) => match self.layer.acquire(key) {
+                Some(slot) => Some(slot),
+                None => {
+                    let error_format = ErrorFormat::for_request(&parts.extensions, self.layer.error_format);
+                    let response = SizeLimitError::TooManyRequests.into_response(error_format);
+                    return Box::pin(async move { Ok(response) });
+                }
+            },
+            None => None,
+        };
+        let future = self.inner.call(Request::from_parts(parts, body));
+        Box::pin(async move {
+            // Held until the response is ready
+            let _slot = slot;
+            future.await
+        })
+    }
+}
+
+/// An upload in flight, counted until dropped.
+struct InFlight {
+    key: String,
+    in_flight: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        let mut in_flight = self.in_flight.lock().unwrap_or_else(PoisonError::into_inner);
+        if let Some(count) = in_flight.get_mut(&self.key) {
+            *count -= 1;
+            if *count == 0 {
+                in_flight.remove(&self.key);
+            }
+        }
+    }
+}
@@ -1,5 +1,7 @@
+use axum::http::{Method, header, request::Parts};
 use std::collections::HashMap;
-use crate::size_limit::{parse_human_size, SizeLimit};
+use crate::size_limit::{parse_human_size, Bundle, ContentTypeName, ContentTypePattern, LimitRule, LimitSource, SizeLimit, SizeParseError};
+use crate::size_limit::glob;
 
 /// Configuration for size limits based on content type.
 ///
@@ -46,9 +48,150 @@ pub struct SizeLimitConfig {
     /// - `"application/*"` → matches all application types
     /// - `"text/*"` → matches all text types
     ///
-    /// Wildcards must follow the format `"type/*"` (asterisk after slash).
-    /// The map keys should be lowercase.
+    /// - `"application/vnd.*"` → matches all vendor types
+    /// - `"*/zip"` → matches `application/zip`, `multipart/zip`, ...
+    ///
+    /// Keys are glob patterns: `*` matches any run of characters and `?` a
+    /// single one. When several match, the most specific (most literal
+    /// characters) wins. The map keys should be lowercase.
     pub wildcard_limits: HashMap<String, usize>,
+
+    /// Regex limits for content types, checked in order (feature `content-type-regex`).
+    ///
+    /// Patterns are matched against the lowercase content type without
+    /// parameters, after exact matches and before wildcards. Examples:
+    /// - `^application/vnd\..*\+json$` → every vendor JSON type
+    #[cfg(feature = "content-type-regex")]
+    pub regex_limits: Vec<(regex::Regex, usize)>,
+
+    /// Caps per HTTP method.
+    ///
+    /// A method cap combines with the content-type rules: the effective limit
+    /// is the smaller of the two. Examples:
+    /// - `PATCH` → 64KB, even for content types allowed more
+    /// - `PUT` → 50MB
+    ///
+    /// Methods without an entry are limited by content type only.
+    pub method_limits: HashMap<Method, usize>,
+
+    /// Ordered rules matching on content type, method, path and headers.
+    ///
+    /// Evaluated first-match-wins before every other setting; a matching
+    /// rule's limit is used as is. Requests matching no rule fall back to the
+    /// content-type maps, method caps and default limit. Only
+    /// [`get_limit_for_request`](Self::get_limit_for_request) (and the
+    /// middleware) can evaluate path and header conditions.
+    pub rules: Vec<LimitRule>,
+
+    /// Allowlist mode: reject content types without an explicit limit.
+    ///
+    /// When `true`, requests whose content type matches no entry in
+    /// `specific_limits`, `wildcard_limits` or `regex_limits` are rejected
+    /// with 415 Unsupported Media Type instead of getting `default_limit`.
+    /// Rules do not extend the allowlist. Defaults to `false`.
+    pub allowlist_mode: bool,
+
+    /// Handling of requests without a `Content-Type` header.
+    ///
+    /// Defaults to [`MissingContentType::AsOctetStream`], which limits them
+    /// like genuine `application/octet-stream` uploads.
+    pub missing_content_type: MissingContentType,
+
+    /// Largest single chunk (data frame) a body may deliver, in bytes.
+    ///
+    /// A chunk larger than this is rejected with 413 Payload Too Large even
+    /// when the body as a whole would fit its limit. Defaults to
+    /// [`DEFAULT_MAX_CHUNK_SIZE`] (16MB); `None` disables the check, e.g.
+    /// behind proxies or HTTP/3 stacks that deliver larger frames.
+    pub max_chunk_size: Option<usize>,
+
+    /// Headers describing the limit on 413 responses.
+    ///
+    /// None by default, so limits are not disclosed unless asked for.
+    pub rejection_headers: RejectionHeaders,
+}
+
+/// Default for [`SizeLimitConfig::max_chunk_size`]: 16MB (16,000,000 bytes).
+pub const DEFAULT_MAX_CHUNK_SIZE: usize = 16_000_000;
+
+/// Machine-readable headers added to 413 (Payload Too Large) responses, so
+/// clients and SDKs can adapt without parsing the error body.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::size_limit::{RejectionHeaders, SizeLimitConfig};
+/// use std::time::Duration;
+///
+/// let config = SizeLimitConfig::default()
+///     .with_rejection_headers(RejectionHeaders::all().with_retry_after(Duration::from_secs(60)));
+/// assert!(config.rejection_headers.max_body_size);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RejectionHeaders {
+    /// Sends `X-Max-Body-Size: <bytes>` with the limit that applied.
+    pub max_body_size: bool,
+    /// Sends `Upload-Limit: max-size=<bytes>`, as in resumable uploads.
+    pub upload_limit: bool,
+    /// Sends `Retry-After: <seconds>`, for limits that may be lifted.
+    pub retry_after: Option<u64>,
+}
+
+impl RejectionHeaders {
+    /// Sends `X-Max-Body-Size` and `Upload-Limit`.
+    pub fn all() -> Self {
+        Self {
+            max_body_size: true,
+            upload_limit: true,
+            retry_after: None,
+        }
+    }
+
+    /// Builder method to send `Retry-After`.
+    pub fn with_retry_after(mut self, after: std::time::Duration) -> Self {
+        self.retry_after = Some(after.as_secs());
+        self
+    }
+
+    /// Adds the configured headers to a rejection.
+    ///
+    /// # Arguments
+    /// * `headers` - Headers of the 413 response
+    /// * `limit` - The limit in bytes that applied
+    pub fn apply(&self, headers: &mut axum::http::HeaderMap, limit: usize) {
+        if self.max_body_size {
+            headers.insert(X_MAX_BODY_SIZE, limit.into());
+        }
+        if self.upload_limit
+            && let Ok(value) = format!("max-size={}", limit).parse()
+        {
+            headers.insert(UPLOAD_LIMIT, value);
+        }
+        if let Some(seconds) = self.retry_after {
+            headers.insert(header::RETRY_AFTER, seconds.into());
+        }
+    }
+}
+
+/// The `X-Max-Body-Size` header.
+pub const X_MAX_BODY_SIZE: header::HeaderName = header::HeaderName::from_static("x-max-body-size");
+
+/// The `Upload-Limit` header.
+pub const UPLOAD_LIMIT: header::HeaderName = header::HeaderName::from_static("upload-limit");
+
+/// The `Max-Upload-Size` header, listing limits per content type.
+pub const MAX_UPLOAD_SIZE: header::HeaderName = header::HeaderName::from_static("max-upload-size");
+
+/// How requests without a `Content-Type` header are limited.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum MissingContentType {
+    /// Treat the body as `application/octet-stream`.
+    #[default]
+    AsOctetStream,
+    /// Apply this limit (in bytes) instead of the content-type limit; rules
+    /// and method caps still apply.
+    Limit(usize),
+    /// Reject with 415 Unsupported Media Type.
+    Reject,
 }
 
 impl Default for SizeLimitConfig {
@@ -58,6 +201,12 @@ impl Default for SizeLimitConfig {
     /// - `default_limit`: 1 megabyte (1,000,000 bytes)
     /// - `specific_limits`: Empty (no specific limits)
     /// - `wildcard_limits`: Empty (no wildcard limits)
+    /// - `method_limits`: Empty (no method caps)
+    /// - `rules`: Empty (no ordered rules)
+    /// - `allowlist_mode`: `false` (unlisted types get the default limit)
+    /// - `missing_content_type`: `AsOctetStream`
+    /// - `max_chunk_size`: 16MB ([`DEFAULT_MAX_CHUNK_SIZE`])
+    /// - `rejection_headers`: none
     ///
     /// # Examples
     /// ```
@@ -72,6 +221,14 @@ impl Default for SizeLimitConfig {
             default_limit: parse_human_size("1mb").unwrap_or(1_000_000),
             specific_limits: HashMap::new(),
             wildcard_limits: HashMap::new(),
+            #[cfg(feature = "content-type-regex")]
+            regex_limits: Vec::new(),
+            method_limits: HashMap::new(),
+            rules: Vec::new(),
+            allowlist_mode: false,
+            missing_content_type: MissingContentType::AsOctetStream,
+            max_chunk_size: Some(DEFAULT_MAX_CHUNK_SIZE),
+            rejection_headers: RejectionHeaders::default(),
         }
     }
 }
@@ -81,8 +238,9 @@ impl SizeLimitConfig {
     ///
     /// The lookup follows this priority order:
     /// 1. **Exact match**: Check if the content type exists in `specific_limits`
-    /// 2. **Wildcard match**: Check if a wildcard pattern matches in `wildcard_limits`
-    /// 3. **Default**: Return `default_limit`
+    /// 2. **Regex match**: Check `regex_limits` in order (feature `content-type-regex`)
+    /// 3. **Wildcard match**: The most specific glob in `wildcard_limits` that matches
+    /// 4. **Default**: Return `default_limit`
     ///
     /// # Arguments
     /// * `content_type` - The Content-Type header value (e.g., "application/json; charset=utf-8")
@@ -112,25 +270,141 @@ impl SizeLimitConfig {
     /// assert_eq!(config.get_limit_for_content_type("application/json; charset=utf-8"), 100_000);
     /// ```
     pub fn get_limit_for_content_type(&self, content_type: &str) -> usize {
-        // Normalize the content type: convert to lowercase and strip parameters
-        let ct_lower = content_type.to_lowercase();
-        let ct_trimmed = ct_lower.split(';').next().unwrap_or(&ct_lower).trim();
+        // Normalize the content type: strip parameters and lowercase (without allocating)
+        glob::with_essence(content_type, |essence| self.resolve_content_type(essence).0)
+    }
 
+    /// Looks up a normalized content type, returning the limit, the part of
+    /// the configuration it came from and the matching pattern.
+    pub(crate) fn resolve_content_type(&self, content_type: &str) -> (usize, LimitSource, Option<&str>) {
         // 1. Check for exact match in specific limits
-        if let Some(limit) = self.specific_limits.get(ct_trimmed) {
-            return *limit;
+        if let Some((pattern, limit)) = self.specific_limits.get_key_value(content_type) {
+            return (*limit, LimitSource::Specific, Some(pattern.as_str()));
         }
 
-        // 2. Check for wildcard match
-        if let Some(slash_pos) = ct_trimmed.find('/') {
-            let wildcard = format!("{}/*", &ct_trimmed[..slash_pos]);
-            if let Some(limit) = self.wildcard_limits.get(&wildcard) {
-                return *limit;
+        // 2. Check regex patterns, in order
+        #[cfg(feature = "content-type-regex")]
+        if let Some((pattern, limit)) = self.regex_limits.iter().find(|(pattern, _)| pattern.is_match(content_type)) {
+            return (*limit, LimitSource::Regex, Some(pattern.as_str()));
+        }
+
+        // 3. Check for wildcard match, most specific pattern first
+        let wildcards = self.wildcard_limits.iter().map(|(pattern, limit)| (pattern.as_str(), *limit));
+        if let Some((pattern, limit)) = glob::best_match(wildcards, content_type) {
+            return (limit, LimitSource::Wildcard, Some(pattern));
+        }
+
+        // 4. Fall back to default limit
+        (self.default_limit, LimitSource::Default, None)
+    }
+
+    /// Returns `true` if the content type has an explicit limit.
+    ///
+    /// A content type is listed when it matches an entry in `specific_limits`,
+    /// `regex_limits` or `wildcard_limits`. In [allowlist mode](Self::allowlist_mode)
+    /// only listed types are accepted.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size_limit::SizeLimitConfig;
+    ///
+    /// let config = SizeLimitConfig::default()
+    ///     .with_specific_limit("application/json", "100kb")
+    ///     .with_wildcard_limit("image/*", "5mb");
+    ///
+    /// assert!(config.is_content_type_listed("application/json; charset=utf-8"));
+    /// assert!(config.is_content_type_listed("image/png"));
+    /// assert!(!config.is_content_type_listed("application/xml"));
+    /// ```
+    pub fn is_content_type_listed(&self, content_type: &str) -> bool {
+        glob::with_essence(content_type, |essence| {
+            #[cfg(feature = "content-type-regex")]
+            if self.regex_limits.iter().any(|(pattern, _)| pattern.is_match(essence)) {
+                return true;
             }
+            self.specific_limits.contains_key(essence)
+                || glob::best_match(self.wildcard_limits.keys().map(|pattern| (pattern.as_str(), ())), essence).is_some()
+        })
+    }
+
+    /// Determines the size limit for a method and content type.
+    ///
+    /// The content-type limit (see [`get_limit_for_content_type`](Self::get_limit_for_content_type))
+    /// capped by the method's limit, if one is configured.
+    ///
+    /// # Arguments
+    /// * `method` - The request method
+    /// * `content_type` - The Content-Type header value
+    ///
+    /// # Examples
+    /// ```
+    /// use axum::http::Method;
+    /// use axum_jetpack::size_limit::SizeLimitConfig;
+    ///
+    /// let config = SizeLimitConfig::default()
+    ///     .with_default_limit("100mb")
+    ///     .with_specific_limit("application/json", "1mb")
+    ///     .with_method_limit(Method::PATCH, "64kb");
+    ///
+    /// assert_eq!(config.get_limit_for_method_and_content_type(&Method::PATCH, "application/json"), 64_000);
+    /// assert_eq!(config.get_limit_for_method_and_content_type(&Method::POST, "application/json"), 1_000_000);
+    /// ```
+    pub fn get_limit_for_method_and_content_type(&self, method: &Method, content_type: &str) -> usize {
+        let limit = self.get_limit_for_content_type(content_type);
+        match self.method_limits.get(method) {
+            Some(cap) => limit.min(*cap),
+            None => limit,
         }
+    }
 
-        // 3. Fall back to default limit
-        self.default_limit
+    /// Determines the size limit for a request.
+    ///
+    /// The first matching entry of [`rules`](Self::rules) wins; otherwise the
+    /// limit comes from the method and `Content-Type` (see
+    /// [`get_limit_for_method_and_content_type`](Self::get_limit_for_method_and_content_type)).
+    /// Requests without a `Content-Type` are handled as
+    /// [`missing_content_type`](Self::missing_content_type) says; when they are
+    /// rejected, this returns 0.
+    ///
+    /// # Arguments
+    /// * `parts` - The request head
+    ///
+    /// # Returns
+    /// The size limit in bytes for the request.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum::http::Request;
+    /// use axum_jetpack::size_limit::{LimitRule, SizeLimitConfig};
+    ///
+    /// let config = SizeLimitConfig::default()
+    ///     .with_rule(LimitRule::new("20mb").for_content_type("image/*").for_path("/admin/*"))
+    ///     .with_rule(LimitRule::new("2mb").for_content_type("image/*"));
+    ///
+    /// let (admin, _) = Request::post("/admin/logo").header("content-type", "image/png").body(()).unwrap().into_parts();
+    /// let (user, _) = Request::post("/avatar").header("content-type", "image/png").body(()).unwrap().into_parts();
+    /// assert_eq!(config.get_limit_for_request(&admin), 20_000_000);
+    /// assert_eq!(config.get_limit_for_request(&user), 2_000_000);
+    /// ```
+    pub fn get_limit_for_request(&self, parts: &Parts) -> usize {
+        if let Some(rule) = self.rules.iter().find(|rule| rule.matches_parts(parts)) {
+            return rule.limit;
+        }
+        let content_type = parts.headers.get(header::CONTENT_TYPE);
+        let missing_limit = match (content_type, self.missing_content_type) {
+            (None, MissingContentType::Limit(limit)) => limit,
+            (None, MissingContentType::Reject) => return 0,
+            _ => {
+                let content_type = content_type
+                    .and_then(|h| h.to_str().ok())
+                    .unwrap_or("application/octet-stream");
+                return self.get_limit_for_method_and_content_type(&parts.method, content_type);
+            }
+        };
+        match self.method_limits.get(&parts.method) {
+            Some(cap) => missing_limit.min(*cap),
+            None => missing_limit,
+        }
     }
 
     /// Builder method to set the default size limit.
@@ -166,10 +440,13 @@ impl SizeLimitConfig {
     /// Builder method to set a size limit for a specific MIME type.
     ///
     /// This limit applies only when the content type exactly matches
-    /// the provided MIME type (case-insensitive).
+    /// the provided MIME type (case-insensitive). A limit of `0` forbids
+    /// bodies of that type; the middleware rejects them with
+    /// [`SizeLimitError::BodyNotAllowed`](crate::size_limit::SizeLimitError::BodyNotAllowed).
     ///
     /// # Arguments
-    /// * `mime_type` - The exact MIME type to limit (e.g., "application/json")
+    /// * `mime_type` - The exact MIME type to limit (e.g., "application/json"), or
+    ///   with the `mime` feature a `mime::Mime` such as `mime::APPLICATION_JSON`
     /// * `limit` - The size limit (human-readable string, `SizeLimit`, or bytes)
     ///
     /// # Returns
@@ -186,19 +463,21 @@ impl SizeLimitConfig {
     /// assert_eq!(config.get_limit_for_content_type("application/json"), 100_000);
     /// assert_eq!(config.get_limit_for_content_type("application/xml"), 500_000);
     /// ```
-    pub fn with_specific_limit(mut self, mime_type: &str, limit: impl Into<SizeLimit>) -> Self {
-        self.specific_limits.insert(mime_type.to_lowercase(), limit.into().0);
+    pub fn with_specific_limit(mut self, mime_type: impl ContentTypeName, limit: impl Into<SizeLimit>) -> Self {
+        self.specific_limits.insert(mime_type.to_content_type().to_lowercase(), limit.into().0);
         self
     }
 
     /// Builder method to set a size limit for a wildcard MIME type pattern.
     ///
     /// This limit applies to all content types that match the wildcard pattern.
-    /// Patterns must follow the format `"type/*"` where `type` is the MIME type
-    /// (e.g., "image", "video", "application").
+    /// Patterns are globs: `*` matches any run of characters and `?` a single
+    /// one, so `"image/*"`, `"application/vnd.*"` and `"*/zip"` all work. When
+    /// several patterns match, the most specific one wins.
     ///
     /// # Arguments
-    /// * `wildcard` - The wildcard pattern (e.g., "image/*", "application/*")
+    /// * `wildcard` - The wildcard pattern (e.g., "image/*", "application/vnd.*", "*/zip"), or
+    ///   with the `mime` feature a `mime::Name` top-level type such as `mime::IMAGE`
     /// * `limit` - The size limit (human-readable string, `SizeLimit`, or bytes)
     ///
     /// # Returns
@@ -215,9 +494,256 @@ impl SizeLimitConfig {
     /// assert_eq!(config.get_limit_for_content_type("image/jpeg"), 5_000_000);
     /// assert_eq!(config.get_limit_for_content_type("image/png"), 5_000_000);
     /// assert_eq!(config.get_limit_for_content_type("video/mp4"), 100_000_000);
+    ///
+    /// // More specific patterns win over `type/*`
+    /// let config = SizeLimitConfig::default()
+    ///     .with_wildcard_limit("application/*", "1mb")
+    ///     .with_wildcard_limit("application/vnd.*", "10mb")
+    ///     .with_wildcard_limit("*/zip", "50mb");
+    ///
+    /// assert_eq!(config.get_limit_for_content_type("application/vnd.api+json"), 10_000_000);
+    /// assert_eq!(config.get_limit_for_content_type("application/pdf"), 1_000_000);
+    /// assert_eq!(config.get_limit_for_content_type("multipart/zip"), 50_000_000);
+    /// ```
+    pub fn with_wildcard_limit(mut self, wildcard: impl ContentTypePattern, limit: impl Into<SizeLimit>) -> Self {
+        self.wildcard_limits.insert(wildcard.to_content_type_pattern().to_lowercase(), limit.into().0);
+        self
+    }
+
+    /// Builder method to set a size limit for every content type in a bundle.
+    ///
+    /// Exact members become specific limits and patterns become wildcard
+    /// limits, exactly as if added one by one.
+    ///
+    /// # Arguments
+    /// * `bundle` - The group of content types (e.g., `Bundle::Images`)
+    /// * `limit` - The size limit (human-readable string, `SizeLimit`, or bytes)
+    ///
+    /// # Returns
+    /// `Self` for method chaining.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size_limit::{Bundle, SizeLimitConfig};
+    ///
+    /// let config = SizeLimitConfig::default()
+    ///     .with_bundle_limit(Bundle::Office, "20mb");
+    ///
+    /// assert_eq!(config.get_limit_for_content_type("application/vnd.ms-excel"), 20_000_000);
+    /// assert_eq!(
+    ///     config.get_limit_for_content_type("application/vnd.openxmlformats-officedocument.spreadsheetml.sheet"),
+    ///     20_000_000
+    /// );
+    /// ```
+    pub fn with_bundle_limit(mut self, bundle: Bundle, limit: impl Into<SizeLimit>) -> Self {
+        let limit = limit.into();
+        for content_type in bundle.content_types() {
+            self = if glob::is_glob(content_type) {
+                self.with_wildcard_limit(content_type, limit)
+            } else {
+                self.with_specific_limit(content_type, limit)
+            };
+        }
+        self
+    }
+
+    /// Builder method to add a regex content-type limit.
+    ///
+    /// The pattern is compiled once, here, and matched against the lowercase
+    /// content type without parameters. Regex limits are checked in the order
+    /// they are added, after exact matches and before wildcards.
+    ///
+    /// # Arguments
+    /// * `pattern` - Regular expression (e.g., `^application/vnd\..*\+json$`)
+    /// * `limit` - The maximum size for matching content types
+    ///
+    /// # Returns
+    /// - `Ok(Self)` for method chaining if the pattern compiles
+    /// - `Err(regex::Error)` if the pattern is invalid
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size_limit::SizeLimitConfig;
+    ///
+    /// let config = SizeLimitConfig::default()
+    ///     .with_default_limit("10mb")
+    ///     .with_regex_limit(r"^application/vnd\..*\+json$", "256kb")
+    ///     .unwrap();
+    ///
+    /// assert_eq!(config.get_limit_for_content_type("application/vnd.api+json"), 256_000);
+    /// assert_eq!(config.get_limit_for_content_type("Application/VND.github.v3+json; charset=utf-8"), 256_000);
+    /// assert_eq!(config.get_limit_for_content_type("application/json"), 10_000_000);
+    /// ```
+    #[cfg(feature = "content-type-regex")]
+    pub fn with_regex_limit(mut self, pattern: &str, limit: impl Into<SizeLimit>) -> Result<Self, regex::Error> {
+        self.regex_limits.push((regex::Regex::new(pattern)?, limit.into().0));
+        Ok(self)
+    }
+
+    /// Builder method to enable or disable allowlist mode.
+    ///
+    /// In allowlist mode, content types without a specific, regex or wildcard
+    /// limit are rejected with 415 Unsupported Media Type.
+    ///
+    /// # Arguments
+    /// * `enabled` - Whether to reject unlisted content types
+    ///
+    /// # Returns
+    /// `Self` for method chaining.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size_limit::SizeLimitConfig;
+    ///
+    /// // Only JSON and images are accepted
+    /// let config = SizeLimitConfig::default()
+    ///     .with_specific_limit("application/json", "100kb")
+    ///     .with_wildcard_limit("image/*", "5mb")
+    ///     .with_allowlist_mode(true);
+    ///
+    /// assert!(config.allowlist_mode);
+    /// ```
+    pub fn with_allowlist_mode(mut self, enabled: bool) -> Self {
+        self.allowlist_mode = enabled;
+        self
+    }
+
+    /// Builder method to set the limit for requests without a `Content-Type`.
+    ///
+    /// Header-less bodies then get `limit` instead of the
+    /// `application/octet-stream` limit, so they can be treated differently
+    /// from genuine binary uploads.
+    ///
+    /// # Arguments
+    /// * `limit` - The size limit for requests without a `Content-Type`
+    ///
+    /// # Returns
+    /// `Self` for method chaining.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum::http::Request;
+    /// use axum_jetpack::size_limit::SizeLimitConfig;
+    ///
+    /// let config = SizeLimitConfig::default()
+    ///     .with_specific_limit("application/octet-stream", "100mb")
+    ///     .with_missing_content_type_limit("16kb");
+    ///
+    /// let (parts, _) = Request::post("/upload").body(()).unwrap().into_parts();
+    /// assert_eq!(config.get_limit_for_request(&parts), 16_000);
+    /// ```
+    pub fn with_missing_content_type_limit(mut self, limit: impl Into<SizeLimit>) -> Self {
+        self.missing_content_type = MissingContentType::Limit(limit.into().0);
+        self
+    }
+
+    /// Builder method to reject requests without a `Content-Type` with 415.
+    ///
+    /// # Returns
+    /// `Self` for method chaining.
+    ///
+    /// # Examples
     /// ```
-    pub fn with_wildcard_limit(mut self, wildcard: &str, limit: impl Into<SizeLimit>) -> Self {
-        self.wildcard_limits.insert(wildcard.to_lowercase(), limit.into().0);
+    /// use axum_jetpack::size_limit::{MissingContentType, SizeLimitConfig};
+    ///
+    /// let config = SizeLimitConfig::default().reject_missing_content_type();
+    /// assert_eq!(config.missing_content_type, MissingContentType::Reject);
+    /// ```
+    pub fn reject_missing_content_type(mut self) -> Self {
+        self.missing_content_type = MissingContentType::Reject;
+        self
+    }
+
+    /// Builder method to set the largest chunk a body may deliver.
+    ///
+    /// # Arguments
+    /// * `limit` - The maximum size of a single data frame
+    ///
+    /// # Returns
+    /// `Self` for method chaining.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size_limit::SizeLimitConfig;
+    ///
+    /// let config = SizeLimitConfig::default().with_max_chunk_size("1mb");
+    /// assert_eq!(config.max_chunk_size, Some(1_000_000));
+    /// ```
+    pub fn with_max_chunk_size(mut self, limit: impl Into<SizeLimit>) -> Self {
+        self.max_chunk_size = Some(limit.into().0);
+        self
+    }
+
+    /// Builder method to accept chunks of any size, leaving only the body
+    /// limit.
+    ///
+    /// # Returns
+    /// `Self` for method chaining.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size_limit::SizeLimitConfig;
+    ///
+    /// let config = SizeLimitConfig::default().without_max_chunk_size();
+    /// assert_eq!(config.max_chunk_size, None);
+    /// ```
+    pub fn without_max_chunk_size(mut self) -> Self {
+        self.max_chunk_size = None;
+        self
+    }
+
+    /// Builder method to describe the limit on 413 responses.
+    ///
+    /// # Arguments
+    /// * `headers` - Which headers to send (see [`RejectionHeaders`])
+    ///
+    /// # Returns
+    /// `Self` for method chaining.
+    pub fn with_rejection_headers(mut self, headers: RejectionHeaders) -> Self {
+        self.rejection_headers = headers;
+        self
+    }
+
+    /// Builder method to cap the size limit for an HTTP method.
+    ///
+    /// The cap applies on top of the content-type rules: the smaller limit wins.
+    ///
+    /// # Arguments
+    /// * `method` - The HTTP method (e.g., `Method::PATCH`)
+    /// * `limit` - The maximum size for requests with this method
+    ///
+    /// # Returns
+    /// `Self` for method chaining.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum::http::Method;
+    /// use axum_jetpack::size_limit::SizeLimitConfig;
+    ///
+    /// let config = SizeLimitConfig::default()
+    ///     .with_default_limit("100mb")
+    ///     .with_method_limit(Method::PATCH, "64kb")
+    ///     .with_method_limit(Method::PUT, "50mb");
+    ///
+    /// assert_eq!(config.method_limits[&Method::PUT], 50_000_000);
+    /// ```
+    pub fn with_method_limit(mut self, method: Method, limit: impl Into<SizeLimit>) -> Self {
+        self.method_limits.insert(method, limit.into().0);
+        self
+    }
+
+    /// Builder method to append an ordered limit rule.
+    ///
+    /// Rules are evaluated in the order they are added; the first match wins.
+    /// Add specific rules (e.g., images on `/admin`) before general ones.
+    ///
+    /// # Arguments
+    /// * `rule` - The rule to append
+    ///
+    /// # Returns
+    /// `Self` for method chaining.
+    pub fn with_rule(mut self, rule: LimitRule) -> Self {
+        self.rules.push(rule);
         self
     }
 
@@ -225,7 +751,7 @@ impl SizeLimitConfig {
     ///
     /// This creates a configuration with default values:
     /// - `default_limit`: 1 megabyte (1,000,000 bytes)
-    /// - Empty `specific_limits` and `wildcard_limits`
+    /// - Empty `specific_limits`, `wildcard_limits`, `method_limits` and `rules`
     ///
     /// # Examples
     /// ```
@@ -273,7 +799,23 @@ impl SizeLimitConfig {
         self.wildcard_limits.clear();
     }
 
-    /// Clears all limits (specific, wildcard, and resets default to 1MB).
+    /// Clears all method caps from the configuration.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum::http::Method;
+    /// use axum_jetpack::size_limit::SizeLimitConfig;
+    ///
+    /// let mut config = SizeLimitConfig::default().with_method_limit(Method::PATCH, "64kb");
+    ///
+    /// config.clear_method_limits();
+    /// assert!(config.method_limits.is_empty());
+    /// ```
+    pub fn clear_method_limits(&mut self) {
+        self.method_limits.clear();
+    }
+
+    /// Clears all limits (rules, specific, wildcard, method, missing content type, and resets default to 1MB).
     ///
     /// # Examples
     /// ```
@@ -288,11 +830,75 @@ impl SizeLimitConfig {
     /// assert_eq!(config.default_limit, 1_000_000); // Back to 1MB (1,000,000 bytes)
     /// assert!(config.specific_limits.is_empty());
     /// assert!(config.wildcard_limits.is_empty());
+    /// assert!(config.method_limits.is_empty());
+    /// assert!(config.rules.is_empty());
     /// ```
     pub fn clear_all_limits(&mut self) {
         self.default_limit = parse_human_size("1mb").unwrap_or(1_000_000);
         self.specific_limits.clear();
         self.wildcard_limits.clear();
+        #[cfg(feature = "content-type-regex")]
+        self.regex_limits.clear();
+        self.method_limits.clear();
+        self.rules.clear();
+        self.missing_content_type = MissingContentType::AsOctetStream;
+    }
+}
+
+// Fallible builders for limits read from configuration files
+impl SizeLimitConfig {
+    /// Fallible version of [`with_default_limit`](Self::with_default_limit).
+    ///
+    /// # Returns
+    /// * `Ok(Self)` for method chaining
+    /// * `Err(SizeParseError)` if `limit` is not a valid size
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size_limit::{SizeLimitConfig, SizeParseError};
+    ///
+    /// let config = SizeLimitConfig::default()
+    ///     .try_with_default_limit("10mb")?
+    ///     .try_with_specific_limit("application/json", "100kb")?
+    ///     .try_with_wildcard_limit("image/*", "5mb")?;
+    /// assert_eq!(config.default_limit, 10_000_000);
+    ///
+    /// // Typos surface as errors instead of panics
+    /// assert!(SizeLimitConfig::default().try_with_default_limit("10 mega").is_err());
+    /// # Ok::<(), SizeParseError>(())
+    /// ```
+    pub fn try_with_default_limit(self, limit: &str) -> Result<Self, SizeParseError> {
+        Ok(self.with_default_limit(SizeLimit::parse(limit)?))
+    }
+
+    /// Fallible version of [`with_specific_limit`](Self::with_specific_limit).
+    pub fn try_with_specific_limit(self, mime_type: &str, limit: &str) -> Result<Self, SizeParseError> {
+        Ok(self.with_specific_limit(mime_type, SizeLimit::parse(limit)?))
+    }
+
+    /// Fallible version of [`with_wildcard_limit`](Self::with_wildcard_limit).
+    pub fn try_with_wildcard_limit(self, wildcard: &str, limit: &str) -> Result<Self, SizeParseError> {
+        Ok(self.with_wildcard_limit(wildcard, SizeLimit::parse(limit)?))
+    }
+
+    /// Fallible version of [`with_bundle_limit`](Self::with_bundle_limit).
+    pub fn try_with_bundle_limit(self, bundle: Bundle, limit: &str) -> Result<Self, SizeParseError> {
+        Ok(self.with_bundle_limit(bundle, SizeLimit::parse(limit)?))
+    }
+
+    /// Fallible version of [`with_method_limit`](Self::with_method_limit).
+    pub fn try_with_method_limit(self, method: Method, limit: &str) -> Result<Self, SizeParseError> {
+        Ok(self.with_method_limit(method, SizeLimit::parse(limit)?))
+    }
+
+    /// Fallible version of [`with_missing_content_type_limit`](Self::with_missing_content_type_limit).
+    pub fn try_with_missing_content_type_limit(self, limit: &str) -> Result<Self, SizeParseError> {
+        Ok(self.with_missing_content_type_limit(SizeLimit::parse(limit)?))
+    }
+
+    /// Fallible version of [`with_max_chunk_size`](Self::with_max_chunk_size).
+    pub fn try_with_max_chunk_size(self, limit: &str) -> Result<Self, SizeParseError> {
+        Ok(self.with_max_chunk_size(SizeLimit::parse(limit)?))
     }
 }
 
@@ -313,4 +919,100 @@ impl SizeLimitConfig {
     pub fn with_default(limit: impl Into<SizeLimit>) -> Self {
         Self::default().with_default_limit(limit)
     }
+
+    /// Preset for locked-down JSON APIs: small limits everywhere.
+    ///
+    /// | Content type | Limit |
+    /// |---|---|
+    /// | default | 64KB |
+    /// | `application/json` | 100KB |
+    /// | `application/x-www-form-urlencoded` | 16KB |
+    /// | `multipart/form-data` | 1MB |
+    /// | `text/*` | 64KB |
+    ///
+    /// Combine with [`with_allowlist_mode`](Self::with_allowlist_mode) to also
+    /// refuse every other content type.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size_limit::SizeLimitConfig;
+    ///
+    /// let config = SizeLimitConfig::strict();
+    /// assert_eq!(config.get_limit_for_content_type("application/json"), 100_000);
+    /// assert_eq!(config.get_limit_for_content_type("video/mp4"), 64_000);
+    /// ```
+    pub fn strict() -> Self {
+        Self::default()
+            .with_default_limit("64kb")
+            .with_specific_limit("application/json", "100kb")
+            .with_specific_limit("application/x-www-form-urlencoded", "16kb")
+            .with_specific_limit("multipart/form-data", "1mb")
+            .with_wildcard_limit("text/*", "64kb")
+    }
+
+    /// Preset for general-purpose applications: generous limits.
+    ///
+    /// | Content type | Limit |
+    /// |---|---|
+    /// | default | 100MB |
+    /// | `application/json` | 10MB |
+    /// | `application/x-www-form-urlencoded` | 1MB |
+    /// | `multipart/form-data` | 1GB |
+    /// | `text/*` | 10MB |
+    /// | `image/*` | 50MB |
+    /// | `video/*` | 1GB |
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size_limit::SizeLimitConfig;
+    ///
+    /// let config = SizeLimitConfig::permissive();
+    /// assert_eq!(config.get_limit_for_content_type("image/png"), 50_000_000);
+    /// ```
+    pub fn permissive() -> Self {
+        Self::default()
+            .with_default_limit("100mb")
+            .with_specific_limit("application/json", "10mb")
+            .with_specific_limit("application/x-www-form-urlencoded", "1mb")
+            .with_specific_limit("multipart/form-data", "1gb")
+            .with_wildcard_limit("text/*", "10mb")
+            .with_wildcard_limit("image/*", "50mb")
+            .with_wildcard_limit("video/*", "1gb")
+    }
+
+    /// Preset for media uploads: tiny structured payloads, large media.
+    ///
+    /// | Content type | Limit |
+    /// |---|---|
+    /// | default | 1MB |
+    /// | `application/json` | 256KB |
+    /// | `application/x-www-form-urlencoded` | 64KB |
+    /// | `multipart/form-data` | 5GB |
+    /// | `application/octet-stream` | 5GB |
+    /// | `image/*` | 25MB |
+    /// | `audio/*` | 500MB |
+    /// | `video/*` | 5GB |
+    ///
+    /// Pair it with [`BufferStrategy::with_defaults`](crate::size_limit::BufferStrategy::with_defaults),
+    /// which streams media instead of buffering it.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size_limit::SizeLimitConfig;
+    ///
+    /// let config = SizeLimitConfig::media_server();
+    /// assert_eq!(config.get_limit_for_content_type("application/json"), 256_000);
+    /// assert_eq!(config.get_limit_for_content_type("video/mp4"), 5_000_000_000);
+    /// ```
+    pub fn media_server() -> Self {
+        Self::default()
+            .with_default_limit("1mb")
+            .with_specific_limit("application/json", "256kb")
+            .with_specific_limit("application/x-www-form-urlencoded", "64kb")
+            .with_specific_limit("multipart/form-data", "5gb")
+            .with_specific_limit("application/octet-stream", "5gb")
+            .with_wildcard_limit("image/*", "25mb")
+            .with_wildcard_limit("audio/*", "500mb")
+            .with_wildcard_limit("video/*", "5gb")
+    }
 }
\ No newline at end of file
@@ -0,0 +1,189 @@
+//! Per-connection cumulative body quota.
+//!
+//! Per-request limits do not stop a client that keeps one keep-alive
+//! connection open and sends request after request just under the limit.
+//! [`ConnectionUsage`] gives every accepted connection its own byte counter,
+//! and [`with_connection_quota`] caps the total request-body bytes accepted
+//! over that connection, answering `Connection: close` once it is used up.
+
+use axum::{
+    Router,
+    body::{Body, HttpBody},
+    extract::{ConnectInfo, FromRequestParts, Request, State, connect_info::Connected},
+    http::{HeaderValue, header},
+    middleware::{self, Next},
+    response::Response,
+    serve::IncomingStream,
+};
+use futures::StreamExt;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use tokio::net::TcpListener;
+
+use crate::error::ErrorFormat;
+use crate::size_limit::SizeLimitError;
+
+/// Connection info carrying a per-connection body byte counter.
+///
+/// Use it as the connect info type of the server so each connection gets a
+/// fresh counter:
+///
+/// ```rust,no_run
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::size_limit::{ConnectionQuota, ConnectionUsage, SizeLimit, with_connection_quota};
+///
+/// # async fn run() -> Result<(), Box<dyn std::error::Error>> {
+/// let router = Router::new().route("/upload", post(|| async { "ok" }));
+/// let router = with_connection_quota(router, ConnectionQuota::new(SizeLimit::mib(256.0)));
+///
+/// let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await?;
+/// axum::serve(listener, router.into_make_service_with_connect_info::<ConnectionUsage>()).await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct ConnectionUsage {
+    /// Peer address of the connection.
+    pub peer: SocketAddr,
+    bytes: Arc<AtomicUsize>,
+}
+
+impl ConnectionUsage {
+    /// Creates a usage counter for a connection from `peer`.
+    pub fn new(peer: SocketAddr) -> Self {
+        Self {
+            peer,
+            bytes: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Total request-body bytes received on this connection so far.
+    pub fn bytes(&self) -> usize {
+        self.bytes.load(Ordering::Relaxed)
+    }
+}
+
+impl Connected<IncomingStream<'_, TcpListener>> for ConnectionUsage {
+    fn connect_info(stream: IncomingStream<'_, TcpListener>) -> Self {
+        Self::new(*stream.remote_addr())
+    }
+}
+
+impl Connected<SocketAddr> for ConnectionUsage {
+    fn connect_info(peer: SocketAddr) -> Self {
+        Self::new(peer)
+    }
+}
+
+/// Configuration for [`with_connection_quota`].
+#[derive(Clone, Debug)]
+pub struct ConnectionQuota {
+    /// Maximum request-body bytes accepted over a single connection.
+    pub max_bytes: usize,
+    /// How the 413 rejection is rendered.
+    pub error_format: ErrorFormat,
+}
+
+impl ConnectionQuota {
+    /// Creates a quota of `max_bytes` per connection.
+    ///
+    /// # Arguments
+    /// * `max_bytes` - Cumulative body bytes allowed per connection
+    pub fn new(max_bytes: impl Into<crate::size::SizeLimit>) -> Self {
+        Self {
+            max_bytes: max_bytes.into().0,
+            error_format: ErrorFormat::default(),
+        }
+    }
+
+    /// Builder method to set how the rejection is rendered.
+    pub fn with_error_format(mut self, format: ErrorFormat) -> Self {
+        self.error_format = format;
+        self
+    }
+}
+
+/// Caps cumulative request-body bytes per keep-alive connection.
+///
+/// Requires the server to be started with
+/// `into_make_service_with_connect_info::<ConnectionUsage>()`; requests
+/// without [`ConnectionUsage`] pass through unchecked. For each request:
+///
+/// 1. If the connection has already used its quota, or the declared
+///    `Content-Length` would exceed it, respond 413 with `Connection: close`
+/// 2. Otherwise count body bytes as the handler reads them; reading past the
+///    quota fails, and the response is sent with `Connection: close`
+///
+/// The peer address is also exposed as `ConnectInfo<SocketAddr>` so
+/// extractors such as `ClientIp` keep working; apply this layer last so it
+/// runs before them.
+///
+/// # Arguments
+/// * `router` - The Axum router to wrap with middleware
+/// * `quota` - The per-connection quota
+///
+/// # Returns
+/// A new router with per-connection quota enforcement.
+pub fn with_connection_quota(router: Router, quota: ConnectionQuota) -> Router {
+    let quota = Arc::new(quota);
+
+    router.layer(middleware::from_fn_with_state(
+        quota,
+        |State(quota): State<Arc<ConnectionQuota>>, req: Request<Body>, next: Next| async move {
+            let (mut parts, body) = req.into_parts();
+            // The extractor also honors `MockConnectInfo` in tests
+            let Ok(ConnectInfo(usage)) =
+                ConnectInfo::<ConnectionUsage>::from_request_parts(&mut parts, &()).await
+            else {
+                return next.run(Request::from_parts(parts, body)).await;
+            };
+            if parts.extensions.get::<ConnectInfo<SocketAddr>>().is_none() {
+                parts.extensions.insert(ConnectInfo(usage.peer));
+            }
+            let req = Request::from_parts(parts, body);
+
+            let declared = req
+                .headers()
+                .get(header::CONTENT_LENGTH)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse::<usize>().ok())
+                .unwrap_or(0);
+            if usage.bytes().saturating_add(declared) > quota.max_bytes {
+                let error_format = ErrorFormat::for_request(req.extensions(), quota.error_format);
+                return close(SizeLimitError::PayloadTooLarge.into_response(error_format));
+            }
+
+            if req.body().is_end_stream() {
+                return next.run(req).await;
+            }
+
+            let (parts, body) = req.into_parts();
+            let counter = usage.bytes.clone();
+            let max_bytes = quota.max_bytes;
+            let counted = body.into_data_stream().map(move |chunk| {
+                let chunk = chunk?;
+                let total = counter.fetch_add(chunk.len(), Ordering::Relaxed) + chunk.len();
+                if total > max_bytes {
+                    return Err(axum::Error::new(SizeLimitError::PayloadTooLarge));
+                }
+                Ok(chunk)
+            });
+            let req = Request::from_parts(parts, Body::from_stream(counted));
+
+            let response = next.run(req).await;
+            if usage.bytes() >= quota.max_bytes {
+                close(response)
+            } else {
+                response
+            }
+        },
+    ))
+}
+
+fn close(mut response: Response) -> Response {
+    response
+        .headers_mut()
+        .insert(header::CONNECTION, HeaderValue::from_static("close"));
+    response
+}
@@ -0,0 +1,70 @@
+//! Content types accepted by the configuration builders.
+//!
+//! [`SizeLimitConfig`](crate::size_limit::SizeLimitConfig) and
+//! [`BufferStrategy`](crate::size_limit::BufferStrategy) take content types
+//! as strings, or with the `mime` feature as [`mime::Mime`] values, which are
+//! validated when parsed and can be shared as constants (`mime::IMAGE_PNG`).
+
+/// An exact content type, such as `"application/json"`.
+///
+/// Implemented for strings and, with the `mime` feature, [`mime::Mime`]
+/// (parameters such as `charset` are dropped).
+pub trait ContentTypeName {
+    /// Returns the content type as a string.
+    fn to_content_type(&self) -> String;
+}
+
+/// An exact content type or a glob such as `"image/*"`.
+///
+/// Implemented for everything that is a [`ContentTypeName`] and, with the
+/// `mime` feature, [`mime::Name`]: a top-level type such as [`mime::IMAGE`]
+/// stands for all its subtypes (`"image/*"`).
+pub trait ContentTypePattern {
+    /// Returns the pattern as a string.
+    fn to_content_type_pattern(&self) -> String;
+}
+
+impl ContentTypeName for str {
+    fn to_content_type(&self) -> String {
+        self.to_string()
+    }
+}
+
+impl ContentTypeName for String {
+    fn to_content_type(&self) -> String {
+        self.clone()
+    }
+}
+
+impl<T: ContentTypeName + ?Sized> ContentTypeName for &T {
+    fn to_content_type(&self) -> String {
+        (**self).to_content_type()
+    }
+}
+
+impl<T: ContentTypeName + ?Sized> ContentTypePattern for T {
+    fn to_content_type_pattern(&self) -> String {
+        self.to_content_type()
+    }
+}
+
+#[cfg(feature = "mime")]
+impl ContentTypeName for mime::Mime {
+    fn to_content_type(&self) -> String {
+        self.essence_str().to_string()
+    }
+}
+
+#[cfg(feature = "mime")]
+impl ContentTypePattern for mime::Name<'_> {
+    fn to_content_type_pattern(&self) -> String {
+        format!("{}/*", self.as_str())
+    }
+}
+
+#[cfg(feature = "mime")]
+impl ContentTypePattern for &mime::Name<'_> {
+    fn to_content_type_pattern(&self) -> String {
+        (**self).to_content_type_pattern()
+    }
+}
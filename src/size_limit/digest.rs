@@ -0,0 +1,119 @@
+//! Body digests computed while the body is read.
+//!
+//! With [`with_body_digest`](crate::size_limit::SizeLimitMiddlewareConfig::with_body_digest)
+//! the size limiter hashes the bytes as they pass through and leaves a
+//! [`BodyDigest`] in the request and response extensions, so integrity checks
+//! and deduplication need no second pass over the body.
+
+use std::sync::{Arc, Mutex, OnceLock, PoisonError};
+
+/// A hash function fed with the body as it arrives.
+///
+/// With the `body-digest` feature it is implemented for every
+/// [`sha2::Digest`] hash, e.g. [`Sha256`].
+pub trait BodyHasher: Send + 'static {
+    /// Feeds a chunk of the body.
+    fn update(&mut self, data: &[u8]);
+
+    /// Returns the digest of everything fed.
+    fn finish(self: Box<Self>) -> Vec<u8>;
+}
+
+#[cfg(feature = "body-digest")]
+impl<D: sha2::Digest + Send + 'static> BodyHasher for D {
+    fn update(&mut self, data: &[u8]) {
+        sha2::Digest::update(self, data);
+    }
+
+    fn finish(self: Box<Self>) -> Vec<u8> {
+        self.finalize().to_vec()
+    }
+}
+
+/// SHA-256, the usual [`BodyHasher`].
+#[cfg(feature = "body-digest")]
+pub use sha2::Sha256;
+
+/// Creates a [`BodyHasher`] for each request.
+pub type HasherFactory = Arc<dyn Fn() -> Box<dyn BodyHasher> + Send + Sync>;
+
+/// Digest of a request body.
+///
+/// Set once the body was read to its end within the limit: before the
+/// handler runs for buffered bodies, once the handler has read them for
+/// streamed ones. Bodies that were rejected or not read to the end have no
+/// digest.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "body-digest")]
+/// # {
+/// use axum::{Router, body::Bytes, extract::Extension, routing::post};
+/// use axum_jetpack::size_limit::{BodyDigest, Sha256, SizeLimitMiddlewareConfig, with_size_limit};
+///
+/// async fn upload(Extension(digest): Extension<BodyDigest>, _body: Bytes) -> String {
+///     digest.hex().unwrap_or_default()
+/// }
+///
+/// let config = SizeLimitMiddlewareConfig::default().with_body_digest::<Sha256>();
+/// let router = with_size_limit(Router::new().route("/upload", post(upload)), config);
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct BodyDigest {
+    digest: Arc<OnceLock<Vec<u8>>>,
+}
+
+impl BodyDigest {
+    /// The digest, once the body has been read to its end.
+    pub fn get(&self) -> Option<&[u8]> {
+        self.digest.get().map(Vec::as_slice)
+    }
+
+    /// The digest in lowercase hex, once the body has been read to its end.
+    pub fn hex(&self) -> Option<String> {
+        self.get()
+            .map(|digest| digest.iter().map(|byte| format!("{:02x}", byte)).collect())
+    }
+}
+
+/// Hashes one body as its size is checked, shared by the clones of its
+/// budget.
+#[derive(Clone)]
+pub(crate) struct DigestMeter {
+    /// `None` once the digest is set.
+    hasher: Arc<Mutex<Option<Box<dyn BodyHasher>>>>,
+    digest: BodyDigest,
+}
+
+impl DigestMeter {
+    /// Starts hashing a body, returning the digest to hand out.
+    pub(crate) fn new(hasher: Box<dyn BodyHasher>) -> (Self, BodyDigest) {
+        let digest = BodyDigest::default();
+        let meter = Self {
+            hasher: Arc::new(Mutex::new(Some(hasher))),
+            digest: digest.clone(),
+        };
+        (meter, digest)
+    }
+
+    /// Feeds a chunk of the body.
+    pub(crate) fn update(&self, data: &[u8]) {
+        if let Some(hasher) = self.hasher.lock().unwrap_or_else(PoisonError::into_inner).as_mut() {
+            hasher.update(data);
+        }
+    }
+
+    /// Sets the digest of the body read so far, which ended within its limit.
+    pub(crate) fn finish(&self) {
+        if let Some(hasher) = self.hasher.lock().unwrap_or_else(PoisonError::into_inner).take() {
+            let _ = self.digest.digest.set(hasher.finish());
+        }
+    }
+}
+
+impl std::fmt::Debug for DigestMeter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DigestMeter").field("digest", &self.digest).finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,76 @@
+//! Detection of clients disconnecting mid-body.
+
+use axum::{
+    body::{Body, Bytes},
+    extract::{MatchedPath, Request},
+    http::{Method, header},
+};
+use futures::{StreamExt, stream};
+use std::sync::Arc;
+
+/// Callback fired when a client stops sending a request body midway.
+pub type DisconnectHook = Arc<dyn Fn(&DisconnectInfo) + Send + Sync>;
+
+/// Context passed to a [`DisconnectHook`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DisconnectInfo {
+    /// Request method.
+    pub method: Method,
+    /// Route template when known, otherwise the request path.
+    pub route: String,
+    /// Body bytes received before the disconnect.
+    pub bytes_received: u64,
+    /// Declared `Content-Length`, if any.
+    pub expected: Option<u64>,
+    /// Transport error reported by the body, or a note about early termination.
+    pub reason: String,
+}
+
+/// Wraps the request body so `hook` fires if the body fails or ends before
+/// its declared `Content-Length`.
+///
+/// The hook fires at most once, at the moment the body is read, so cleanup
+/// can start before the handler or any timeout notices.
+pub(crate) fn watch_disconnect(req: Request<Body>, hook: DisconnectHook) -> Request<Body> {
+    let (parts, body) = req.into_parts();
+    let info = DisconnectInfo {
+        method: parts.method.clone(),
+        route: parts
+            .extensions
+            .get::<MatchedPath>()
+            .map(|matched| matched.as_str().to_string())
+            .unwrap_or_else(|| parts.uri.path().to_string()),
+        bytes_received: 0,
+        expected: parts
+            .headers
+            .get(header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse().ok()),
+        reason: String::new(),
+    };
+
+    let watched = stream::unfold(
+        (body.into_data_stream(), Some((info, hook))),
+        |(mut body, mut watch)| async move {
+            let next = body.next().await;
+            match (&next, watch.as_mut()) {
+                (Some(Ok(chunk)), Some((info, _))) => info.bytes_received += chunk.len() as u64,
+                (Some(Err(e)), Some(_)) => {
+                    if let Some((mut info, hook)) = watch.take() {
+                        info.reason = e.to_string();
+                        hook(&info);
+                    }
+                }
+                (None, Some((info, _))) if info.expected.is_some_and(|n| info.bytes_received < n) => {
+                    if let Some((mut info, hook)) = watch.take() {
+                        info.reason = "body ended before Content-Length".to_string();
+                        hook(&info);
+                    }
+                }
+                _ => {}
+            }
+            next.map(|chunk: Result<Bytes, axum::Error>| (chunk, (body, watch)))
+        },
+    );
+    Request::from_parts(parts, Body::from_stream(watched))
+}
@@ -0,0 +1,148 @@
+//! A discovery endpoint publishing the limits to clients.
+//!
+//! [`limits_endpoint`] serves a [`LimitTable`] as JSON, so client SDKs can
+//! learn the limits instead of hard-coding them. Unlike the
+//! [admin routes](crate::size_limit::admin_router) it is meant to be public:
+//! it only lists limits, not the full configuration.
+
+use axum::{
+    Json,
+    routing::{MethodRouter, get},
+};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::size_limit::{LimitSource, SizeLimit, SizeLimitConfig};
+
+/// A limit in bytes and in human form.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct LimitEntry {
+    /// The limit in bytes; `null` when unlimited.
+    pub bytes: Option<usize>,
+    /// The limit for humans, e.g. `"1.5 MiB"` or `"unlimited"`.
+    pub human: String,
+}
+
+impl From<usize> for LimitEntry {
+    fn from(limit: usize) -> Self {
+        let limit = SizeLimit(limit);
+        Self {
+            bytes: limit.as_option(),
+            human: limit.to_string(),
+        }
+    }
+}
+
+/// The limit of a content type or content-type pattern.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct ContentTypeLimit {
+    /// Exact content type, glob (`image/*`) or regex.
+    pub content_type: String,
+    /// `"specific"`, `"regex"` or `"wildcard"`.
+    pub kind: &'static str,
+    /// The limit.
+    #[serde(flatten)]
+    pub limit: LimitEntry,
+}
+
+/// The limits of a [`SizeLimitConfig`], as served by [`limits_endpoint`].
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::size_limit::{LimitTable, SizeLimitConfig};
+///
+/// let table = LimitTable::new(&SizeLimitConfig::default().with_specific_limit("application/json", "100kb"));
+/// assert_eq!(table.default_limit.human, "1 MB");
+/// assert_eq!(table.content_types[0].content_type, "application/json");
+/// assert_eq!(table.content_types[0].limit.bytes, Some(100_000));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct LimitTable {
+    /// Limit of content types without their own entry, unless
+    /// `allowlist_mode` rejects them.
+    pub default_limit: LimitEntry,
+    /// Whether content types without an entry are rejected.
+    pub allowlist_mode: bool,
+    /// Content-type limits in lookup order.
+    pub content_types: Vec<ContentTypeLimit>,
+    /// Caps per HTTP method, applied on top of the content-type limits.
+    pub methods: BTreeMap<String, LimitEntry>,
+}
+
+impl LimitTable {
+    /// Collects the limits of a configuration.
+    ///
+    /// Ordered rules are left out: they match on request details clients
+    /// cannot be expected to evaluate.
+    pub fn new(config: &SizeLimitConfig) -> Self {
+        Self {
+            default_limit: config.default_limit.into(),
+            allowlist_mode: config.allowlist_mode,
+            content_types: config
+                .rules()
+                .filter_map(|rule| {
+                    let kind = match rule.source {
+                        LimitSource::Specific => "specific",
+                        LimitSource::Regex => "regex",
+                        LimitSource::Wildcard => "wildcard",
+                        LimitSource::Default => return None,
+                    };
+                    Some(ContentTypeLimit {
+                        content_type: rule.pattern,
+                        kind,
+                        limit: rule.limit.into(),
+                    })
+                })
+                .collect(),
+            methods: config
+                .method_limits
+                .iter()
+                .map(|(method, limit)| (method.to_string(), (*limit).into()))
+                .collect(),
+        }
+    }
+}
+
+/// Creates a handler serving the limits of `config` as JSON.
+///
+/// Responds to `GET` with a [`LimitTable`]:
+///
+/// ```json
+/// {
+///   "default_limit": { "bytes": 2000000, "human": "2 MB" },
+///   "allowlist_mode": false,
+///   "content_types": [
+///     { "content_type": "application/json", "kind": "specific", "bytes": 100000, "human": "100 KB" },
+///     { "content_type": "image/*", "kind": "wildcard", "bytes": 5242880, "human": "5 MiB" }
+///   ],
+///   "methods": { "PATCH": { "bytes": 64000, "human": "64 KB" } }
+/// }
+/// ```
+///
+/// The table is built once, when the handler is created.
+///
+/// # Arguments
+/// * `config` - The limits to publish, usually those given to the middleware
+///
+/// # Returns
+/// A method router to mount with [`Router::route`](axum::Router::route).
+///
+/// # Example
+/// ```rust
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::size_limit::{SizeLimitConfig, limits_endpoint, with_size_limit_simple};
+///
+/// let limits = SizeLimitConfig::default().with_specific_limit("application/json", "100kb");
+///
+/// let app: Router = Router::new()
+///     .route("/upload", post(|| async { "ok" }))
+///     .route("/.well-known/limits", limits_endpoint(&limits));
+/// let app = with_size_limit_simple(app, limits);
+/// ```
+pub fn limits_endpoint<S>(config: &SizeLimitConfig) -> MethodRouter<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    let table = LimitTable::new(config);
+    get(move || async move { Json(table) })
+}
@@ -0,0 +1,266 @@
+//! Declarative request policies.
+//!
+//! A [`JetpackPolicy`] says everything about how a request body is handled:
+//! its size limit, whether it is buffered or streamed, how long the request
+//! may take and how rejections are rendered. A [`PolicyTable`] maps method,
+//! path and content type to policies with ordered [`PolicyRule`]s, and
+//! [`with_jetpack_policy`] applies the table as one layer, instead of
+//! splitting the same decision across a [`SizeLimitConfig`](crate::size_limit::SizeLimitConfig)
+//! and a [`BufferStrategy`](crate::size_limit::BufferStrategy).
+
+use axum::{
+    Router,
+    body::Body,
+    extract::{Request, State},
+    http::{Method, StatusCode, request::Parts},
+    middleware::{self, Next},
+};
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::error::ErrorFormat;
+use crate::size_limit::{
+    Buffering, Decision, LimitPolicy, LimitRule, RequestContext, SizeLimit, SizeLimitMiddlewareConfig,
+    with_size_limit_policy,
+};
+
+/// How a request is handled.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::error::ErrorFormat;
+/// use axum_jetpack::size_limit::JetpackPolicy;
+/// use std::time::Duration;
+///
+/// let uploads = JetpackPolicy::streamed("2gb").with_timeout(Duration::from_secs(600));
+/// let api = JetpackPolicy::buffered("256kb")
+///     .with_timeout(Duration::from_secs(10))
+///     .with_error_format(ErrorFormat::ProblemDetails);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct JetpackPolicy {
+    /// Size limit in bytes.
+    pub limit: usize,
+    /// How the body is read.
+    pub buffering: Buffering,
+    /// Time allowed for the whole request, body and handler included; `None`
+    /// for no timeout.
+    pub timeout: Option<Duration>,
+    /// Error format for rejections; `None` keeps the default.
+    pub error_format: Option<ErrorFormat>,
+}
+
+impl JetpackPolicy {
+    /// Creates a policy reading the whole body into memory first.
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum body size
+    pub fn buffered(limit: impl Into<SizeLimit>) -> Self {
+        Self {
+            limit: limit.into().0,
+            buffering: Buffering::Buffer,
+            timeout: None,
+            error_format: None,
+        }
+    }
+
+    /// Creates a policy passing the body through in chunks.
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum body size
+    pub fn streamed(limit: impl Into<SizeLimit>) -> Self {
+        Self {
+            buffering: Buffering::Stream,
+            ..Self::buffered(limit)
+        }
+    }
+
+    /// Builder method to limit how long the request may take.
+    ///
+    /// Requests still running after `timeout` get 408 (Request Timeout).
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Builder method to set the error format of rejections.
+    pub fn with_error_format(mut self, format: ErrorFormat) -> Self {
+        self.error_format = Some(format);
+        self
+    }
+}
+
+impl Default for JetpackPolicy {
+    /// Buffered, 1MB (the default limit of
+    /// [`SizeLimitConfig`](crate::size_limit::SizeLimitConfig)), no timeout.
+    fn default() -> Self {
+        Self::buffered(1_000_000)
+    }
+}
+
+/// A policy applied to requests matching all of the rule's conditions.
+///
+/// Conditions left unset match everything.
+#[derive(Clone, Debug)]
+pub struct PolicyRule {
+    /// Conditions; the rule's limit is unused.
+    conditions: LimitRule,
+    /// Policy for matching requests.
+    policy: JetpackPolicy,
+}
+
+impl PolicyRule {
+    /// Creates a rule matching every request.
+    ///
+    /// # Arguments
+    /// * `policy` - The policy for matching requests
+    pub fn new(policy: JetpackPolicy) -> Self {
+        Self {
+            conditions: LimitRule::new(policy.limit),
+            policy,
+        }
+    }
+
+    /// Builder method to match a method; call repeatedly for several.
+    pub fn for_method(mut self, method: Method) -> Self {
+        self.conditions = self.conditions.for_method(method);
+        self
+    }
+
+    /// Builder method to match a route pattern (e.g., `"/admin/*"`).
+    pub fn for_path(mut self, pattern: &str) -> Self {
+        self.conditions = self.conditions.for_path(pattern);
+        self
+    }
+
+    /// Builder method to match a content type or glob (`"image/*"`, `"application/vnd.*"`).
+    pub fn for_content_type(mut self, content_type: &str) -> Self {
+        self.conditions = self.conditions.for_content_type(content_type);
+        self
+    }
+
+    /// Returns `true` if the rule matches a request head.
+    pub fn matches(&self, parts: &Parts) -> bool {
+        self.conditions.matches_parts(parts)
+    }
+}
+
+/// Ordered rules mapping requests to policies.
+///
+/// Rules are checked in order and the first match wins; requests matching
+/// none get the default policy.
+///
+/// # Examples
+/// ```
+/// use axum::http::Method;
+/// use axum_jetpack::size_limit::{JetpackPolicy, PolicyRule, PolicyTable};
+/// use std::time::Duration;
+///
+/// let table = PolicyTable::new(JetpackPolicy::buffered("1mb").with_timeout(Duration::from_secs(30)))
+///     .with_rule(PolicyRule::new(JetpackPolicy::streamed("2gb")).for_path("/videos/*"))
+///     .with_rule(PolicyRule::new(JetpackPolicy::streamed("20mb")).for_content_type("image/*"))
+///     .with_rule(PolicyRule::new(JetpackPolicy::buffered("64kb")).for_method(Method::PATCH));
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct PolicyTable {
+    /// Policy for requests matching no rule.
+    default: JetpackPolicy,
+    /// Ordered rules.
+    rules: Vec<PolicyRule>,
+}
+
+impl PolicyTable {
+    /// Creates a table applying `default` to every request.
+    pub fn new(default: JetpackPolicy) -> Self {
+        Self {
+            default,
+            rules: Vec::new(),
+        }
+    }
+
+    /// Builder method to append a rule.
+    pub fn with_rule(mut self, rule: PolicyRule) -> Self {
+        self.rules.push(rule);
+        self
+    }
+
+    /// Returns the policy for a request head.
+    pub fn resolve(&self, parts: &Parts) -> &JetpackPolicy {
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(parts))
+            .map_or(&self.default, |rule| &rule.policy)
+    }
+}
+
+/// Uses the policy resolved by [`with_jetpack_policy`], or resolves it.
+impl LimitPolicy for PolicyTable {
+    async fn decide(&self, ctx: RequestContext<'_>) -> Decision {
+        let policy = match ctx.parts.extensions.get::<JetpackPolicy>() {
+            Some(policy) => policy,
+            None => self.resolve(ctx.parts),
+        };
+        Decision::Accept {
+            limit: policy.limit,
+            buffering: policy.buffering,
+        }
+    }
+}
+
+/// Applies a policy table to a router.
+///
+/// Every request is matched against the table once; its policy decides the
+/// size limit and buffering, a timeout covering the body and the handler,
+/// and the error format of rejections (formats set with
+/// [`with_error_formats`](crate::error::with_error_formats) take precedence).
+/// The resolved [`JetpackPolicy`] is also stored in the request extensions.
+///
+/// # Arguments
+/// * `router` - The Axum router to wrap with middleware
+/// * `table` - Rules mapping requests to policies
+///
+/// # Returns
+/// A new router with the policies applied.
+///
+/// # Example
+/// ```rust
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::size_limit::{JetpackPolicy, PolicyRule, PolicyTable, with_jetpack_policy};
+/// use std::time::Duration;
+///
+/// let router: Router = Router::new()
+///     .route("/videos/upload", post(|| async { "ok" }))
+///     .route("/api/users", post(|| async { "ok" }));
+///
+/// let table = PolicyTable::new(JetpackPolicy::buffered("256kb").with_timeout(Duration::from_secs(10)))
+///     .with_rule(PolicyRule::new(JetpackPolicy::streamed("2gb")).for_path("/videos/*"));
+/// let router = with_jetpack_policy(router, table);
+/// ```
+pub fn with_jetpack_policy(router: Router, table: PolicyTable) -> Router {
+    let config = SizeLimitMiddlewareConfig::default();
+    let default_format = config.error_format;
+    let router = with_size_limit_policy(router, config, table.clone());
+
+    router.layer(middleware::from_fn_with_state(
+        Arc::new(table),
+        move |State(table): State<Arc<PolicyTable>>, req: Request<Body>, next: Next| async move {
+            let (mut parts, body) = req.into_parts();
+            let policy = *table.resolve(&parts);
+            parts.extensions.insert(policy);
+            if let Some(format) = policy.error_format
+                && parts.extensions.get::<ErrorFormat>().is_none()
+            {
+                parts.extensions.insert(format);
+            }
+            let error_format = ErrorFormat::for_request(&parts.extensions, default_format);
+            let req = Request::from_parts(parts, body);
+
+            match policy.timeout {
+                Some(timeout) => tokio::time::timeout(timeout, next.run(req))
+                    .await
+                    .unwrap_or_else(|_| error_format.response(StatusCode::REQUEST_TIMEOUT, "Request timeout")),
+                None => next.run(req).await,
+            }
+        },
+    ))
+}
@@ -0,0 +1,149 @@
+//! Size limit configuration from environment variables.
+//!
+//! Containerized deployments can tune limits without rebuilding the image or
+//! mounting a config file. Variables are read under a prefix
+//! ([`SIZE_LIMIT_ENV_PREFIX`] by default):
+//!
+//! | Variable | Effect |
+//! |----------|--------|
+//! | `JETPACK_SIZE_LIMIT_DEFAULT=10mb` | Default limit |
+//! | `JETPACK_SIZE_LIMIT_TYPE_application__json=100kb` | Limit for `application/json` (`__` stands for `/`) |
+//! | `JETPACK_SIZE_LIMIT_TYPE_image__*=5mb` | Wildcard limit for `image/*` |
+//! | `JETPACK_SIZE_LIMIT_METHOD_PATCH=64kb` | Cap for `PATCH` requests |
+//! | `JETPACK_SIZE_LIMIT_ALLOWLIST=true` | Allowlist mode |
+//! | `JETPACK_SIZE_LIMIT_MISSING_CONTENT_TYPE=16kb` | Limit for bodies without a `Content-Type`; also `reject` or `as_octet_stream` |
+//! | `JETPACK_SIZE_LIMIT_MAX_CHUNK=1mb` | Largest single chunk; `off` disables the check |
+
+use axum::http::Method;
+
+use crate::size_limit::SizeLimitConfig;
+
+/// Default prefix of the variables read by [`SizeLimitConfig::from_env`].
+pub const SIZE_LIMIT_ENV_PREFIX: &str = "JETPACK_SIZE_LIMIT_";
+
+impl SizeLimitConfig {
+    /// Builds a configuration from `JETPACK_SIZE_LIMIT_*` environment variables.
+    ///
+    /// Unset variables keep their [`Default`] values. See
+    /// [`from_env_vars`](Self::from_env_vars) for the variable names.
+    ///
+    /// # Returns
+    /// The configuration, or a message naming the offending variable
+    pub fn from_env() -> Result<Self, String> {
+        Self::from_env_with_prefix(SIZE_LIMIT_ENV_PREFIX)
+    }
+
+    /// Builds a configuration from environment variables under a custom prefix.
+    ///
+    /// # Arguments
+    /// * `prefix` - Variable name prefix, e.g. `"UPLOADS_LIMIT_"`
+    pub fn from_env_with_prefix(prefix: &str) -> Result<Self, String> {
+        let mut vars = Vec::new();
+        for (name, value) in std::env::vars_os() {
+            let Some(name) = name.to_str().filter(|name| name.starts_with(prefix)) else {
+                continue;
+            };
+            let value = value
+                .into_string()
+                .map_err(|_| format!("Environment variable {} is not valid UTF-8", name))?;
+            vars.push((name.to_string(), value));
+        }
+        Self::from_env_vars(prefix, vars)
+    }
+
+    /// Builds a configuration from `(name, value)` pairs, as if they were
+    /// environment variables.
+    ///
+    /// Recognized names, after `prefix`:
+    /// * `DEFAULT` - default limit
+    /// * `TYPE_<content type>` - limit for a content type, with `__` standing
+    ///   for `/`; names containing `*` set a wildcard limit
+    /// * `METHOD_<method>` - per-method cap
+    /// * `ALLOWLIST` - `true` or `false`
+    /// * `MISSING_CONTENT_TYPE` - a size, `reject` or `as_octet_stream`
+    /// * `MAX_CHUNK` - largest single chunk, or `off`
+    ///
+    /// Variables without the prefix are ignored; unknown names under it are
+    /// an error, so typos don't go unnoticed.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size_limit::{SIZE_LIMIT_ENV_PREFIX, SizeLimitConfig};
+    ///
+    /// let config = SizeLimitConfig::from_env_vars(SIZE_LIMIT_ENV_PREFIX, [
+    ///     ("JETPACK_SIZE_LIMIT_DEFAULT", "10mb"),
+    ///     ("JETPACK_SIZE_LIMIT_TYPE_application__json", "100kb"),
+    ///     ("JETPACK_SIZE_LIMIT_TYPE_image__*", "5mb"),
+    ///     ("PATH", "/usr/bin"),
+    /// ]).unwrap();
+    ///
+    /// assert_eq!(config.default_limit, 10_000_000);
+    /// assert_eq!(config.get_limit_for_content_type("application/json"), 100_000);
+    /// assert_eq!(config.get_limit_for_content_type("image/png"), 5_000_000);
+    ///
+    /// let err = SizeLimitConfig::from_env_vars(SIZE_LIMIT_ENV_PREFIX, [("JETPACK_SIZE_LIMIT_DEFALT", "1mb")]);
+    /// assert!(err.is_err());
+    /// ```
+    pub fn from_env_vars<I, K, V>(prefix: &str, vars: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut vars: Vec<(String, String)> = vars
+            .into_iter()
+            .filter(|(name, _)| name.as_ref().starts_with(prefix))
+            .map(|(name, value)| (name.as_ref().to_string(), value.as_ref().trim().to_string()))
+            .collect();
+        // Apply in a stable order so the first error reported is deterministic
+        vars.sort();
+
+        let mut config = SizeLimitConfig::default();
+        for (name, value) in &vars {
+            config = apply_var(config, &name[prefix.len()..], value).map_err(|e| format!("{}: {}", name, e))?;
+        }
+        Ok(config)
+    }
+}
+
+fn apply_var(config: SizeLimitConfig, key: &str, value: &str) -> Result<SizeLimitConfig, String> {
+    if let Some(content_type) = key.strip_prefix("TYPE_") {
+        let content_type = content_type.replace("__", "/").to_ascii_lowercase();
+        if !content_type.contains('/') {
+            return Err(format!("'{}' is not a content type (write '/' as '__')", content_type));
+        }
+        let config = if content_type.contains('*') {
+            config.try_with_wildcard_limit(&content_type, value)
+        } else {
+            config.try_with_specific_limit(&content_type, value)
+        };
+        return config.map_err(|e| e.to_string());
+    }
+    if let Some(method) = key.strip_prefix("METHOD_") {
+        let method = Method::from_bytes(method.to_ascii_uppercase().as_bytes())
+            .map_err(|_| format!("Invalid method '{}'", method))?;
+        return config.try_with_method_limit(method, value).map_err(|e| e.to_string());
+    }
+    match key {
+        "DEFAULT" => config.try_with_default_limit(value).map_err(|e| e.to_string()),
+        "ALLOWLIST" => match value.to_ascii_lowercase().as_str() {
+            "true" | "1" | "yes" | "on" => Ok(config.with_allowlist_mode(true)),
+            "false" | "0" | "no" | "off" => Ok(config.with_allowlist_mode(false)),
+            _ => Err(format!("expected true or false, got '{}'", value)),
+        },
+        "MISSING_CONTENT_TYPE" => match value.to_ascii_lowercase().as_str() {
+            "reject" => Ok(config.reject_missing_content_type()),
+            "as_octet_stream" => {
+                let mut config = config;
+                config.missing_content_type = Default::default();
+                Ok(config)
+            }
+            _ => config.try_with_missing_content_type_limit(value).map_err(|e| e.to_string()),
+        },
+        "MAX_CHUNK" => match value.to_ascii_lowercase().as_str() {
+            "off" | "none" => Ok(config.without_max_chunk_size()),
+            _ => config.try_with_max_chunk_size(value).map_err(|e| e.to_string()),
+        },
+        _ => Err("unknown size limit variable".to_string()),
+    }
+}
@@ -0,0 +1,127 @@
+//! Rejections produced by the size limit middleware.
+
+use axum::{http::StatusCode, response::Response};
+use std::fmt;
+
+use crate::error::ErrorFormat;
+
+/// Why the size limit middleware refused a request.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SizeLimitError {
+    /// The body exceeds the limit for its content type (413).
+    PayloadTooLarge,
+    /// A body was sent on a method configured as body-less (400).
+    UnexpectedBody,
+    /// The content type is not allowed (415).
+    UnsupportedMediaType,
+    /// A body was sent where its limit is zero, i.e. no body is allowed (413).
+    BodyNotAllowed,
+    /// The body failed to arrive, e.g. the client disconnected midway (400).
+    BodyReadFailed,
+    /// The body does not match its `Content-Length`, or its framing is
+    /// ambiguous (400; strict mode only).
+    ContentLengthMismatch,
+    /// A body was sent without `Content-Length` where one is required (411).
+    LengthRequired,
+    /// The body could not be read (500).
+    Internal,
+    /// The shared [`BufferBudget`](crate::size_limit::BufferBudget) has no
+    /// room for the body (503).
+    Overloaded,
+    /// The shared [`BufferBudget`](crate::size_limit::BufferBudget) has no
+    /// room for the body, reported as 429, or the key has too many uploads in
+    /// flight (see [`UploadConcurrencyLayer`](crate::size_limit::UploadConcurrencyLayer)).
+    TooManyRequests,
+    /// The body arrived too slowly: a chunk or the whole body missed its
+    /// deadline (408).
+    BodyTimeout,
+    /// The key of the request used up its
+    /// [`ByteQuota`](crate::size_limit::ByteQuota) (429).
+    QuotaExceeded,
+    /// A compressed body expanded more than the ratio of its
+    /// [`ExpansionGuardLayer`](crate::size_limit::ExpansionGuardLayer) (413).
+    ExpansionRatioExceeded,
+    /// A text field of a multipart body is over its
+    /// [`MultipartLimits`](crate::size_limit::MultipartLimits) (413).
+    FieldTooLarge,
+    /// A file part of a multipart body is over its
+    /// [`MultipartLimits`](crate::size_limit::MultipartLimits) (413).
+    FileTooLarge,
+    /// A multipart body whose part headers cannot be delimited (400).
+    MalformedMultipart,
+}
+
+impl SizeLimitError {
+    /// HTTP status code of the rejection.
+    pub fn status(&self) -> StatusCode {
+        match self {
+            SizeLimitError::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            SizeLimitError::UnexpectedBody => StatusCode::BAD_REQUEST,
+            SizeLimitError::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            SizeLimitError::BodyNotAllowed => StatusCode::PAYLOAD_TOO_LARGE,
+            SizeLimitError::BodyReadFailed => StatusCode::BAD_REQUEST,
+            SizeLimitError::ContentLengthMismatch => StatusCode::BAD_REQUEST,
+            SizeLimitError::LengthRequired => StatusCode::LENGTH_REQUIRED,
+            SizeLimitError::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+            SizeLimitError::Overloaded => StatusCode::SERVICE_UNAVAILABLE,
+            SizeLimitError::TooManyRequests => StatusCode::TOO_MANY_REQUESTS,
+            SizeLimitError::BodyTimeout => StatusCode::REQUEST_TIMEOUT,
+            SizeLimitError::QuotaExceeded => StatusCode::TOO_MANY_REQUESTS,
+            SizeLimitError::ExpansionRatioExceeded => StatusCode::PAYLOAD_TOO_LARGE,
+            SizeLimitError::FieldTooLarge | SizeLimitError::FileTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            SizeLimitError::MalformedMultipart => StatusCode::BAD_REQUEST,
+        }
+    }
+
+    /// Renders the rejection in the given format.
+    ///
+    /// The error is also stored in the response extensions, so outer layers
+    /// can tell size limit rejections from handler responses.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum::http::StatusCode;
+    /// use axum_jetpack::error::ErrorFormat;
+    /// use axum_jetpack::size_limit::SizeLimitError;
+    ///
+    /// let response = SizeLimitError::PayloadTooLarge.into_response(ErrorFormat::Json);
+    /// assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    /// assert_eq!(response.extensions().get(), Some(&SizeLimitError::PayloadTooLarge));
+    /// ```
+    pub fn into_response(self, format: ErrorFormat) -> Response {
+        self.render(format, &self.to_string())
+    }
+
+    /// Renders the rejection with a more specific message.
+    pub(crate) fn render(self, format: ErrorFormat, message: &str) -> Response {
+        let mut response = format.response(self.status(), message);
+        response.extensions_mut().insert(self);
+        response
+    }
+}
+
+impl fmt::Display for SizeLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SizeLimitError::PayloadTooLarge => write!(f, "Payload too large"),
+            SizeLimitError::UnexpectedBody => write!(f, "Request body not allowed"),
+            SizeLimitError::UnsupportedMediaType => write!(f, "Unsupported media type"),
+            SizeLimitError::BodyNotAllowed => write!(f, "Request body not allowed for this content type"),
+            SizeLimitError::BodyReadFailed => write!(f, "Failed to read request body"),
+            SizeLimitError::ContentLengthMismatch => write!(f, "Request body does not match Content-Length"),
+            SizeLimitError::LengthRequired => write!(f, "Content-Length required"),
+            SizeLimitError::Internal => write!(f, "Internal error"),
+            SizeLimitError::Overloaded | SizeLimitError::TooManyRequests => {
+                write!(f, "Too many request bodies in flight, try again later")
+            }
+            SizeLimitError::BodyTimeout => write!(f, "Request body timed out"),
+            SizeLimitError::QuotaExceeded => write!(f, "Upload quota exceeded"),
+            SizeLimitError::ExpansionRatioExceeded => write!(f, "Compressed payload expands too much"),
+            SizeLimitError::FieldTooLarge => write!(f, "Form field too large"),
+            SizeLimitError::FileTooLarge => write!(f, "Uploaded file too large"),
+            SizeLimitError::MalformedMultipart => write!(f, "Malformed multipart body"),
+        }
+    }
+}
+
+impl std::error::Error for SizeLimitError {}
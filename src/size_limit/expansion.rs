@@ -0,0 +1,273 @@
+//! Compression-ratio guard for compressed request bodies.
+//!
+//! A few kilobytes of gzip can inflate to gigabytes. A decompressed-size
+//! limit stops such a bomb only once the limit is reached;
+//! [`ExpansionGuardLayer`] wraps the decompression layer of your choice
+//! (e.g. tower-http's `RequestDecompressionLayer`) and kills the body as soon
+//! as the decoded bytes outgrow the compressed bytes read by a set ratio.
+
+use axum::{
+    BoxError,
+    body::{Body, Bytes, HttpBody},
+    extract::Request,
+    http::{self, header},
+    response::Response,
+};
+use futures::future::BoxFuture;
+use http_body::{Frame, SizeHint};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::task::{Context, Poll, ready};
+use tower::{Layer, Service};
+
+use crate::error::ErrorFormat;
+use crate::size_limit::{SizeLimit, SizeLimitError};
+
+/// Decoded bytes always allowed, whatever the ratio, so small bodies with
+/// a large header overhead pass.
+const DEFAULT_GRACE: u64 = 64 * 1024;
+
+/// Layer rejecting compressed bodies that expand more than a ratio.
+///
+/// Wraps a decompression layer: bodies are counted on both of its sides,
+/// and once the decoded bytes exceed the grace allowance and `max_ratio`
+/// times the compressed bytes read so far, reading fails and the request is
+/// answered with 413 ([`SizeLimitError::ExpansionRatioExceeded`]). Requests
+/// without a `Content-Encoding` are not checked. The check is incremental:
+/// it needs neither the whole body nor a decompressed-size limit.
+///
+/// # Examples
+/// ```
+/// use axum::{Router, body::Body, extract::Request, routing::post};
+/// use axum_jetpack::size_limit::{ExpansionGuardLayer, RouterExt};
+/// use tower::util::MapRequestLayer;
+///
+/// // Stands in for a real decoder, e.g. tower-http's `RequestDecompressionLayer::new()`
+/// let decoder = MapRequestLayer::new(|req: Request<Body>| req);
+///
+/// // The size limiter inside the decoder caps the decompressed size
+/// let router: Router = Router::new()
+///     .route("/upload", post(|| async { "ok" }))
+///     .size_limit("10mb")
+///     .layer(ExpansionGuardLayer::new(100, decoder));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ExpansionGuardLayer<L> {
+    decoder: L,
+    max_ratio: u64,
+    grace: u64,
+    error_format: ErrorFormat,
+}
+
+impl<L> ExpansionGuardLayer<L> {
+    /// Creates a guard around `decoder`.
+    ///
+    /// # Arguments
+    /// * `max_ratio` - Most decoded bytes allowed per compressed byte, e.g. `100`
+    /// * `decoder` - The layer decompressing request bodies
+    pub fn new(max_ratio: u64, decoder: L) -> Self {
+        Self {
+            decoder,
+            max_ratio: max_ratio.max(1),
+            grace: DEFAULT_GRACE,
+            error_format: ErrorFormat::default(),
+        }
+    }
+
+    /// Builder method to set how many decoded bytes pass regardless of the
+    /// ratio. 64 KiB by default.
+    pub fn with_grace(mut self, grace: impl Into<SizeLimit>) -> Self {
+        self.grace = grace.into().0 as u64;
+        self
+    }
+
+    /// Builder method to set how the 413 rejection is rendered.
+    pub fn with_error_format(mut self, format: ErrorFormat) -> Self {
+        self.error_format = format;
+        self
+    }
+}
+
+impl<L, S> Layer<S> for ExpansionGuardLayer<L>
+where
+    L: Layer<DecodedService<S>>,
+{
+    type Service = ExpansionGuardService<L::Service>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ExpansionGuardService {
+            inner: self.decoder.layer(DecodedService { inner }),
+            max_ratio: self.max_ratio,
+            grace: self.grace,
+            error_format: self.error_format,
+        }
+    }
+}
+
+/// Service created by [`ExpansionGuardLayer`], counting compressed bytes
+/// before the decoder.
+#[derive(Clone, Debug)]
+pub struct ExpansionGuardService<S> {
+    inner: S,
+    max_ratio: u64,
+    grace: u64,
+    error_format: ErrorFormat,
+}
+
+impl<S, B> Service<Request<Body>> for ExpansionGuardService<S>
+where
+    S: Service<Request<Body>, Response = http::Response<B>>,
+    S::Future: Send + 'static,
+    B: HttpBody<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let encoded = req
+            .headers()
+            .get(header::CONTENT_ENCODING)
+            .is_some_and(|encoding| !encoding.as_bytes().eq_ignore_ascii_case(b"identity"));
+        if !encoded {
+            let future = self.inner.call(req);
+            return Box::pin(async move { Ok(future.await?.map(Body::new)) });
+        }
+
+        let meter = Arc::new(ExpansionMeter {
+            compressed: AtomicU64::new(0),
+            decoded: AtomicU64::new(0),
+            max_ratio: self.max_ratio,
+            grace: self.grace,
+            exceeded: AtomicBool::new(false),
+        });
+        let error_format = ErrorFormat::for_request(req.extensions(), self.error_format);
+        let (mut parts, body) = req.into_parts();
+        parts.extensions.insert(meter.clone());
+        let body = Body::new(MeteredBody {
+            inner: body,
+            meter: meter.clone(),
+            side: Side::Compressed,
+        });
+        let future = self.inner.call(Request::from_parts(parts, body));
+        Box::pin(async move {
+            let response = future.await?;
+            // The handler's response is void once the body was cut off
+            match meter.exceeded.load(Ordering::Relaxed) {
+                true => Ok(SizeLimitError::ExpansionRatioExceeded.into_response(error_format)),
+                false => Ok(response.map(Body::new)),
+            }
+        })
+    }
+}
+
+/// Service behind the decoder, checking decoded bytes against the
+/// compressed ones.
+#[derive(Clone, Debug)]
+pub struct DecodedService<S> {
+    inner: S,
+}
+
+impl<S, B> Service<Request<B>> for DecodedService<S>
+where
+    S: Service<Request<Body>>,
+    B: HttpBody<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<B>) -> Self::Future {
+        let (parts, body) = req.into_parts();
+        let body = match parts.extensions.get::<Arc<ExpansionMeter>>() {
+            Some(meter) => Body::new(MeteredBody {
+                inner: Body::new(body),
+                meter: meter.clone(),
+                side: Side::Decoded,
+            }),
+            None => Body::new(body),
+        };
+        self.inner.call(Request::from_parts(parts, body))
+    }
+}
+
+/// Byte counts of one body on both sides of the decoder.
+#[derive(Debug)]
+struct ExpansionMeter {
+    compressed: AtomicU64,
+    decoded: AtomicU64,
+    max_ratio: u64,
+    grace: u64,
+    exceeded: AtomicBool,
+}
+
+impl ExpansionMeter {
+    /// Counts `len` decoded bytes, returning `false` once they outgrow the
+    /// compressed bytes by more than the ratio.
+    fn decoded(&self, len: u64) -> bool {
+        let decoded = self.decoded.fetch_add(len, Ordering::Relaxed) + len;
+        let allowed = self.compressed.load(Ordering::Relaxed).saturating_mul(self.max_ratio);
+        if decoded > self.grace && decoded > allowed {
+            self.exceeded.store(true, Ordering::Relaxed);
+        }
+        !self.exceeded.load(Ordering::Relaxed)
+    }
+}
+
+/// Which side of the decoder a [`MeteredBody`] is on.
+#[derive(Clone, Copy, Debug)]
+enum Side {
+    Compressed,
+    Decoded,
+}
+
+/// A body counted on one side of the decoder.
+struct MeteredBody {
+    inner: Body,
+    meter: Arc<ExpansionMeter>,
+    side: Side,
+}
+
+impl HttpBody for MeteredBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, axum::Error>>> {
+        let this = self.get_mut();
+        let frame = ready!(Pin::new(&mut this.inner).poll_frame(cx));
+        let len = match &frame {
+            Some(Ok(frame)) => frame.data_ref().map_or(0, Bytes::len) as u64,
+            _ => return Poll::Ready(frame),
+        };
+        match this.side {
+            Side::Compressed => {
+                this.meter.compressed.fetch_add(len, Ordering::Relaxed);
+            }
+            Side::Decoded => {
+                if !this.meter.decoded(len) {
+                    return Poll::Ready(Some(Err(axum::Error::new(SizeLimitError::ExpansionRatioExceeded))));
+                }
+            }
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
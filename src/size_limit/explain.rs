@@ -0,0 +1,194 @@
+//! Introspection of the effective content-type limits.
+//!
+//! [`SizeLimitConfig::rules`] lists the content-type table in the order it is
+//! consulted, and [`SizeLimitConfig::explain`] tells which entry decided the
+//! limit of a content type, so "why did this get 1MB?" can be answered from a
+//! debugger, a log line or an admin endpoint instead of the source code.
+
+use std::fmt;
+
+use crate::size_limit::glob;
+use crate::size_limit::{SizeLimit, SizeLimitConfig};
+
+/// Which part of a [`SizeLimitConfig`] a limit comes from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LimitSource {
+    /// An exact entry in `specific_limits`.
+    Specific,
+    /// A pattern in `regex_limits` (feature `content-type-regex`).
+    Regex,
+    /// A glob in `wildcard_limits`.
+    Wildcard,
+    /// `default_limit`.
+    Default,
+}
+
+impl fmt::Display for LimitSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LimitSource::Specific => write!(f, "specific limit"),
+            LimitSource::Regex => write!(f, "regex limit"),
+            LimitSource::Wildcard => write!(f, "wildcard limit"),
+            LimitSource::Default => write!(f, "default limit"),
+        }
+    }
+}
+
+/// One entry of the content-type table, as returned by [`SizeLimitConfig::rules`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EffectiveRule {
+    /// Content type, regex source or glob; `*/*` for the default limit.
+    pub pattern: String,
+    /// Limit in bytes.
+    pub limit: usize,
+    /// Where the entry is configured.
+    pub source: LimitSource,
+}
+
+impl fmt::Display for EffectiveRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} → {} ({})", self.pattern, SizeLimit(self.limit), self.source)
+    }
+}
+
+/// Why a content type gets its limit, as returned by [`SizeLimitConfig::explain`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Explanation {
+    /// The content type, lowercased and without parameters.
+    pub content_type: String,
+    /// Limit in bytes; `0` when the type is rejected in allowlist mode.
+    pub limit: usize,
+    /// The deciding entry.
+    pub source: LimitSource,
+    /// The deciding content type, regex or glob; `None` for the default limit.
+    pub pattern: Option<String>,
+    /// `true` if allowlist mode rejects the type with 415.
+    pub rejected: bool,
+}
+
+impl fmt::Display for Explanation {
+    /// Formats the explanation as one sentence.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size_limit::SizeLimitConfig;
+    ///
+    /// let config = SizeLimitConfig::default()
+    ///     .with_wildcard_limit("image/*", "5mb")
+    ///     .with_wildcard_limit("image/svg*", "100kb");
+    ///
+    /// assert_eq!(
+    ///     config.explain("image/svg+xml").to_string(),
+    ///     "image/svg+xml: 100 KB from wildcard limit 'image/svg*' (most specific matching pattern)",
+    /// );
+    /// assert_eq!(
+    ///     config.explain("video/mp4").to_string(),
+    ///     "video/mp4: 1 MB from default limit (no specific, regex or wildcard limit matches)",
+    /// );
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.rejected {
+            return write!(
+                f,
+                "{}: rejected with 415 (allowlist mode and no specific, regex or wildcard limit matches)",
+                self.content_type
+            );
+        }
+        write!(f, "{}: {} from {}", self.content_type, SizeLimit(self.limit), self.source)?;
+        match (&self.pattern, self.source) {
+            (Some(pattern), LimitSource::Wildcard) => write!(f, " '{}' (most specific matching pattern)", pattern),
+            (Some(pattern), LimitSource::Regex) => write!(f, " '{}' (first matching pattern)", pattern),
+            (Some(pattern), _) => write!(f, " '{}' (exact match)", pattern),
+            (None, _) => write!(f, " (no specific, regex or wildcard limit matches)"),
+        }
+    }
+}
+
+impl SizeLimitConfig {
+    /// Lists the content-type limits in the order they are consulted.
+    ///
+    /// Exact types come first (alphabetically), then regex patterns in
+    /// order, then wildcards from most to least specific, and finally the
+    /// default limit as `*/*`. Method caps, the ordered `rules` and
+    /// missing-content-type handling are request-level settings and are not
+    /// included.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size_limit::SizeLimitConfig;
+    ///
+    /// let config = SizeLimitConfig::default()
+    ///     .with_specific_limit("application/json", "100kb")
+    ///     .with_wildcard_limit("image/*", "5mb");
+    ///
+    /// let table: Vec<String> = config.rules().map(|rule| rule.to_string()).collect();
+    /// assert_eq!(table, [
+    ///     "application/json → 100 KB (specific limit)",
+    ///     "image/* → 5 MB (wildcard limit)",
+    ///     "*/* → 1 MB (default limit)",
+    /// ]);
+    /// ```
+    pub fn rules(&self) -> impl Iterator<Item = EffectiveRule> {
+        let entry = |pattern: &str, limit: usize, source: LimitSource| EffectiveRule {
+            pattern: pattern.to_string(),
+            limit,
+            source,
+        };
+
+        let mut specific: Vec<_> = self.specific_limits.iter().collect();
+        specific.sort();
+        let mut wildcards: Vec<_> = self.wildcard_limits.iter().collect();
+        wildcards.sort_by(|(a, _), (b, _)| glob::specificity(b).cmp(&glob::specificity(a)).then(a.cmp(b)));
+
+        let mut rules: Vec<EffectiveRule> = specific
+            .into_iter()
+            .map(|(pattern, limit)| entry(pattern, *limit, LimitSource::Specific))
+            .collect();
+        #[cfg(feature = "content-type-regex")]
+        rules.extend(
+            self.regex_limits
+                .iter()
+                .map(|(pattern, limit)| entry(pattern.as_str(), *limit, LimitSource::Regex)),
+        );
+        rules.extend(
+            wildcards
+                .into_iter()
+                .map(|(pattern, limit)| entry(pattern, *limit, LimitSource::Wildcard)),
+        );
+        rules.push(entry("*/*", self.default_limit, LimitSource::Default));
+        rules.into_iter()
+    }
+
+    /// Explains which entry decides the limit of a content type.
+    ///
+    /// Follows the same lookup as [`get_limit_for_content_type`](Self::get_limit_for_content_type),
+    /// and reports allowlist rejections.
+    ///
+    /// # Arguments
+    /// * `content_type` - The Content-Type header value
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size_limit::{LimitSource, SizeLimitConfig};
+    ///
+    /// let config = SizeLimitConfig::default().with_specific_limit("application/json", "100kb");
+    ///
+    /// let explanation = config.explain("Application/JSON; charset=utf-8");
+    /// assert_eq!(explanation.content_type, "application/json");
+    /// assert_eq!(explanation.limit, 100_000);
+    /// assert_eq!(explanation.source, LimitSource::Specific);
+    /// ```
+    pub fn explain(&self, content_type: &str) -> Explanation {
+        let content_type = content_type.to_lowercase();
+        let content_type = content_type.split(';').next().unwrap_or(&content_type).trim().to_string();
+        let (limit, source, pattern) = self.resolve_content_type(&content_type);
+        let rejected = self.allowlist_mode && source == LimitSource::Default;
+        Explanation {
+            limit: if rejected { 0 } else { limit },
+            source,
+            pattern: pattern.map(str::to_string),
+            rejected,
+            content_type,
+        }
+    }
+}
@@ -0,0 +1,59 @@
+//! Method-call syntax for applying the size limiter.
+
+use axum::{Router, routing::MethodRouter};
+use std::convert::Infallible;
+
+use crate::size_limit::{SizeLimit, SizeLimitConfig, SizeLimitLayer, SizeLimitMiddlewareConfig};
+
+/// Adds size limiting methods to [`Router`] and [`MethodRouter`].
+///
+/// On a [`Router`] the limiter covers every route added so far; on a
+/// [`MethodRouter`] only that route, so individual routes can get their own
+/// limits. Limiters nest: a request must pass both a router-wide limiter and
+/// its route's limiter, so a route-level limit can tighten a router-wide one
+/// but not relax it.
+///
+/// # Examples
+/// ```
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::size_limit::{RouterExt, SizeLimitConfig, SizeLimitMiddlewareConfig};
+///
+/// let router: Router = Router::new()
+///     .route("/avatar", post(|| async { "ok" }).size_limit("5mb"))
+///     .route("/comments", post(|| async { "ok" }))
+///     .size_limit_config(SizeLimitMiddlewareConfig::new(
+///         SizeLimitConfig::default().with_specific_limit("application/json", "64kb"),
+///     ));
+/// ```
+pub trait RouterExt: Sized {
+    /// Limits every request body to `limit`, with the default buffer strategy.
+    ///
+    /// # Arguments
+    /// * `limit` - Maximum body size for any content type
+    fn size_limit(self, limit: impl Into<SizeLimit>) -> Self {
+        let limits = SizeLimitConfig::default().with_default_limit(limit);
+        self.size_limit_config(SizeLimitMiddlewareConfig::with_default_buffer_strategy(limits))
+    }
+
+    /// Applies size limiting with a full middleware configuration, like
+    /// [`with_size_limit`](crate::size_limit::with_size_limit).
+    fn size_limit_config(self, config: SizeLimitMiddlewareConfig) -> Self;
+}
+
+impl<S> RouterExt for Router<S>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    fn size_limit_config(self, config: SizeLimitMiddlewareConfig) -> Self {
+        self.layer(SizeLimitLayer::new(config))
+    }
+}
+
+impl<S> RouterExt for MethodRouter<S, Infallible>
+where
+    S: Clone + Send + Sync + 'static,
+{
+    fn size_limit_config(self, config: SizeLimitMiddlewareConfig) -> Self {
+        self.layer(SizeLimitLayer::new(config))
+    }
+}
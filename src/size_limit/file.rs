@@ -0,0 +1,252 @@
+//! Loading size limit configuration from TOML and YAML (feature `config-file`).
+//!
+//! Both formats share one schema, documented on
+//! [`SizeLimitConfig::from_toml_str`] and
+//! [`SizeLimitMiddlewareConfig::from_toml_str`]. Unknown keys are rejected,
+//! and errors name the offending key.
+
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::fmt::Display;
+use std::path::Path;
+
+use crate::error::ErrorFormat;
+use crate::size_limit::serialization::parse_method;
+use crate::size_limit::{BufferStrategy, SizeLimitConfig, SizeLimitMiddlewareConfig};
+
+impl SizeLimitConfig {
+    /// Parses a configuration from TOML.
+    ///
+    /// Every key is optional; sizes are byte counts or human-readable strings:
+    ///
+    /// ```toml
+    /// default_limit = "2mb"
+    /// allowlist_mode = false
+    /// missing_content_type = "as_octet_stream"   # "reject" or { limit = "16kb" }
+    /// max_chunk_size = "16mb"
+    /// rejection_headers = { max_body_size = true, upload_limit = true, retry_after = 60 }
+    ///
+    /// [specific_limits]
+    /// "application/json" = "100kb"
+    ///
+    /// [wildcard_limits]
+    /// "image/*" = "10mb"
+    ///
+    /// [method_limits]
+    /// PATCH = "64kb"
+    ///
+    /// [[rules]]
+    /// limit = "50mb"
+    /// content_type = "image/*"
+    /// path = "/admin/*"
+    /// methods = ["POST"]
+    /// ```
+    ///
+    /// # Returns
+    /// The configuration, or a message naming the offending key
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size_limit::SizeLimitConfig;
+    ///
+    /// let config = SizeLimitConfig::from_toml_str(r#"
+    ///     default_limit = "2mb"
+    ///
+    ///     [specific_limits]
+    ///     "application/json" = "100kb"
+    /// "#).unwrap();
+    /// assert_eq!(config.get_limit_for_content_type("application/json"), 100_000);
+    ///
+    /// let err = SizeLimitConfig::from_toml_str("[wildcard_limits]\n\"image/*\" = \"lots\"").unwrap_err();
+    /// assert!(err.contains("wildcard_limits.image/*"));
+    /// ```
+    pub fn from_toml_str(toml: &str) -> Result<Self, String> {
+        parse_toml(toml)
+    }
+
+    /// Reads and parses a TOML configuration file.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the file
+    pub fn from_toml_file(path: impl AsRef<Path>) -> Result<Self, String> {
+        parse_toml(&read(path.as_ref())?)
+    }
+
+    /// Parses a configuration from YAML, using the same keys as TOML.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size_limit::SizeLimitConfig;
+    ///
+    /// let config = SizeLimitConfig::from_yaml_str("
+    /// default_limit: 2mb
+    /// wildcard_limits:
+    ///   image/*: 10mb
+    /// ").unwrap();
+    /// assert_eq!(config.get_limit_for_content_type("image/png"), 10_000_000);
+    /// ```
+    pub fn from_yaml_str(yaml: &str) -> Result<Self, String> {
+        parse_yaml(yaml)
+    }
+
+    /// Reads and parses a YAML configuration file.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the file
+    pub fn from_yaml_file(path: impl AsRef<Path>) -> Result<Self, String> {
+        parse_yaml(&read(path.as_ref())?)
+    }
+}
+
+/// File form of a [`SizeLimitMiddlewareConfig`].
+#[derive(Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct MiddlewareFile {
+    limits: SizeLimitConfig,
+    buffering: BufferStrategy,
+    error_format: ErrorFormat,
+    fast_path_methods: Option<Vec<String>>,
+    exempt_methods: Vec<String>,
+    exempt_paths: Vec<String>,
+    bodyless_methods: Vec<String>,
+    strict_content_length: bool,
+    require_content_length: bool,
+}
+
+impl MiddlewareFile {
+    fn into_config(self) -> Result<SizeLimitMiddlewareConfig, String> {
+        let methods = |field: &str, methods: Vec<String>| {
+            methods
+                .iter()
+                .map(|method| parse_method(method).map_err(|e| format!("{}: {}", field, e)))
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let mut config = SizeLimitMiddlewareConfig::new(self.limits)
+            .with_buffer_strategy(self.buffering)
+            .with_error_format(self.error_format);
+        if let Some(fast_path) = self.fast_path_methods {
+            config.fast_path_methods = methods("fast_path_methods", fast_path)?;
+        }
+        config.exempt_methods = methods("exempt_methods", self.exempt_methods)?;
+        config.exempt_paths = self.exempt_paths;
+        config.bodyless_methods = methods("bodyless_methods", self.bodyless_methods)?;
+        config.strict_content_length = self.strict_content_length;
+        config.require_content_length = self.require_content_length;
+        Ok(config)
+    }
+}
+
+impl SizeLimitMiddlewareConfig {
+    /// Parses a middleware configuration from TOML.
+    ///
+    /// Limits use the [`SizeLimitConfig::from_toml_str`] schema under
+    /// `limits`; the remaining settings sit at the top level. Keys left out
+    /// keep their [`Default`] values:
+    ///
+    /// ```toml
+    /// error_format = "problem_details"   # "plain_text", "json", "problem_details" or "html"
+    /// fast_path_methods = ["HEAD", "OPTIONS"]
+    /// exempt_methods = ["CONNECT"]
+    /// exempt_paths = ["/health", "/internal/*"]
+    /// bodyless_methods = ["GET", "DELETE"]
+    /// strict_content_length = true
+    /// require_content_length = false
+    ///
+    /// [limits]
+    /// default_limit = "1mb"
+    ///
+    /// [limits.specific_limits]
+    /// "application/json" = "100kb"
+    ///
+    /// [buffering]
+    /// buffered_types = ["application/json", "text/*"]
+    /// streamed_types = ["video/*", "image/*"]
+    /// ```
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::error::ErrorFormat;
+    /// use axum_jetpack::size_limit::SizeLimitMiddlewareConfig;
+    ///
+    /// let config = SizeLimitMiddlewareConfig::from_toml_str(r#"
+    ///     error_format = "json"
+    ///
+    ///     [limits]
+    ///     default_limit = "1mb"
+    ///
+    ///     [buffering]
+    ///     buffered_types = ["application/json"]
+    /// "#).unwrap();
+    /// assert_eq!(config.error_format, ErrorFormat::Json);
+    /// assert!(config.buffer_strategy.should_buffer("application/json"));
+    /// ```
+    pub fn from_toml_str(toml: &str) -> Result<Self, String> {
+        parse_toml::<MiddlewareFile>(toml)?.into_config()
+    }
+
+    /// Reads and parses a TOML middleware configuration file.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the file
+    pub fn from_toml_file(path: impl AsRef<Path>) -> Result<Self, String> {
+        parse_toml::<MiddlewareFile>(&read(path.as_ref())?)?.into_config()
+    }
+
+    /// Parses a middleware configuration from YAML, using the same keys as TOML.
+    pub fn from_yaml_str(yaml: &str) -> Result<Self, String> {
+        parse_yaml::<MiddlewareFile>(yaml)?.into_config()
+    }
+
+    /// Reads and parses a YAML middleware configuration file.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the file
+    pub fn from_yaml_file(path: impl AsRef<Path>) -> Result<Self, String> {
+        parse_yaml::<MiddlewareFile>(&read(path.as_ref())?)?.into_config()
+    }
+}
+
+impl SizeLimitMiddlewareConfig {
+    /// Reads a middleware configuration file, choosing the format by
+    /// extension: `.yaml`/`.yml` are parsed as YAML, anything else as TOML.
+    ///
+    /// # Arguments
+    /// * `path` - Path to the file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, String> {
+        let path = path.as_ref();
+        parse_middleware_file(path, &read(path)?)
+    }
+}
+
+/// Parses the contents of a middleware configuration file in the format
+/// implied by its extension.
+pub(crate) fn parse_middleware_file(path: &Path, contents: &str) -> Result<SizeLimitMiddlewareConfig, String> {
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("yaml") || ext.eq_ignore_ascii_case("yml") => {
+            SizeLimitMiddlewareConfig::from_yaml_str(contents)
+        }
+        _ => SizeLimitMiddlewareConfig::from_toml_str(contents),
+    }
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    std::fs::read_to_string(path).map_err(|e| format!("Failed to read '{}': {}", path.display(), e))
+}
+
+fn parse_toml<T: DeserializeOwned>(toml: &str) -> Result<T, String> {
+    serde_path_to_error::deserialize(toml::Deserializer::new(toml)).map_err(|e| describe("TOML", e))
+}
+
+fn parse_yaml<T: DeserializeOwned>(yaml: &str) -> Result<T, String> {
+    serde_path_to_error::deserialize(serde_yaml::Deserializer::from_str(yaml)).map_err(|e| describe("YAML", e))
+}
+
+/// Formats a parse error with the path of the key it occurred at.
+fn describe<E: Display>(format: &str, err: serde_path_to_error::Error<E>) -> String {
+    let path = err.path().to_string();
+    if path == "." {
+        format!("Invalid {} size limit config: {}", format, err.inner())
+    } else {
+        format!("Invalid {} size limit config at '{}': {}", format, path, err.inner())
+    }
+}
@@ -0,0 +1,119 @@
+//! Glob patterns for content types (`application/vnd.*`, `*/zip`).
+//!
+//! `*` matches any run of characters (including `/`), `?` matches exactly one.
+//! When several patterns match, the most specific one — the one with the most
+//! literal characters — wins, so `application/vnd.*` beats `application/*`.
+
+/// Returns `true` if the pattern contains glob metacharacters.
+pub(crate) fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?'])
+}
+
+/// Returns `true` if the pattern is the plain `type/*` form.
+pub(crate) fn is_top_level_wildcard(pattern: &str) -> bool {
+    pattern
+        .strip_suffix("/*")
+        .is_some_and(|top| !top.is_empty() && !is_glob(top))
+}
+
+/// Number of literal (non-wildcard) characters; higher is more specific.
+pub(crate) fn specificity(pattern: &str) -> usize {
+    pattern.chars().filter(|c| *c != '*' && *c != '?').count()
+}
+
+/// Matches `text` against a glob `pattern`, ignoring ASCII case.
+///
+/// Works on bytes without allocating; content types are ASCII, so `?`
+/// matching one byte is the same as matching one character.
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let (pattern, text) = (pattern.as_bytes(), text.as_bytes());
+    let (mut p, mut t) = (0, 0);
+    // Position of the last `*` and the text position it is currently matched up to
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        if p < pattern.len() && (pattern[p] == b'?' || pattern[p].eq_ignore_ascii_case(&text[t])) {
+            p += 1;
+            t += 1;
+        } else if p < pattern.len() && pattern[p] == b'*' {
+            star = Some((p, t));
+            p += 1;
+        } else if let Some((star_p, star_t)) = star {
+            // Let the last `*` absorb one more character and retry
+            p = star_p + 1;
+            t = star_t + 1;
+            star = Some((star_p, star_t + 1));
+        } else {
+            return false;
+        }
+    }
+    pattern[p..].iter().all(|c| *c == b'*')
+}
+
+/// Longest content type that is lowercased on the stack; longer (unusual)
+/// values fall back to a heap allocation.
+const STACK_LOWERCASE_LEN: usize = 128;
+
+/// Calls `f` with the essence of a `Content-Type` value: parameters
+/// (`; charset=...`) stripped, trimmed and lowercased.
+///
+/// Values that are already lowercase are passed through as-is; others are
+/// lowercased into a stack buffer, so the request path does not allocate.
+pub(crate) fn with_essence<R>(content_type: &str, f: impl FnOnce(&str) -> R) -> R {
+    let essence = content_type.split(';').next().unwrap_or(content_type).trim();
+    if !essence.bytes().any(|b| b.is_ascii_uppercase()) {
+        return f(essence);
+    }
+    if essence.len() <= STACK_LOWERCASE_LEN {
+        let mut buf = [0u8; STACK_LOWERCASE_LEN];
+        let lower = &mut buf[..essence.len()];
+        lower.copy_from_slice(essence.as_bytes());
+        lower.make_ascii_lowercase();
+        // ASCII lowercasing keeps UTF-8 valid
+        if let Ok(lower) = std::str::from_utf8(lower) {
+            return f(lower);
+        }
+    }
+    f(&essence.to_ascii_lowercase())
+}
+
+/// Returns the most specific pattern matching `text`, with its value.
+///
+/// Ties are broken by pattern order (lexicographic) so the result does not
+/// depend on map iteration order.
+pub(crate) fn best_match<'a, T>(
+    patterns: impl Iterator<Item = (&'a str, T)>,
+    text: &str,
+) -> Option<(&'a str, T)> {
+    patterns
+        .filter(|(pattern, _)| is_glob(pattern) && glob_match(pattern, text))
+        .max_by(|(a, _), (b, _)| specificity(a).cmp(&specificity(b)).then_with(|| b.cmp(a)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("image/*", "image/png"));
+        assert!(glob_match("application/vnd.*", "application/vnd.api+json"));
+        assert!(glob_match("*/zip", "application/zip"));
+        assert!(glob_match("*/*+json", "application/problem+json"));
+        assert!(glob_match("text/?sv", "text/csv"));
+        assert!(!glob_match("application/vnd.*", "application/json"));
+        assert!(!glob_match("*/zip", "application/zip2"));
+        assert!(glob_match("image/*", "Image/PNG"));
+
+        assert_eq!(with_essence("Application/JSON; charset=UTF-8", str::to_string), "application/json");
+        assert_eq!(with_essence(" text/plain ", str::to_string), "text/plain");
+
+        assert!(is_top_level_wildcard("image/*"));
+        assert!(!is_top_level_wildcard("*/zip"));
+        assert!(!is_top_level_wildcard("application/vnd.*"));
+
+        let patterns = [("application/*", 1), ("application/vnd.*", 2), ("*/*", 3)];
+        assert_eq!(best_match(patterns.into_iter(), "application/vnd.x").map(|(_, v)| v), Some(2));
+        assert_eq!(best_match(patterns.into_iter(), "text/plain").map(|(_, v)| v), Some(3));
+    }
+}
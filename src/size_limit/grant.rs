@@ -0,0 +1,242 @@
+//! Signed, expiring upload grants.
+//!
+//! An upload grant is a token, minted by your application, that raises the
+//! size limit for one route until it expires. The size limiter verifies the
+//! HMAC-SHA256 signature and applies the elevated limit, so a single large
+//! upload can be allowed without loosening the global configuration.
+//!
+//! Tokens have the form `<payload>.<signature>`, both base64url-encoded,
+//! where the payload is `<limit>:<expires_at>:<route>`. They are signed, not
+//! encrypted: the limit and route are readable by the client.
+//!
+//! A trusted frontend can also sign a one-off limit for a single upload:
+//! `X-Upload-Limit` carries the limit in bytes and `X-Upload-Limit-Signature`
+//! `<expires_at>.<signature>`, signed over the same claims with the request
+//! path as route (see [`UploadGrantKey::sign_limit`]).
+//!
+//! Each kind signs its claims behind its own prefix (`grant:` or `limit:`),
+//! so the signature of one is never valid as the other. Neither is recorded
+//! once used: like a grant, a signed limit can be replayed for its path until
+//! it expires, so keep its lifetime short.
+
+use base64::Engine;
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// Request header carrying an upload grant token.
+pub const UPLOAD_GRANT_HEADER: &str = "x-upload-grant";
+
+/// Request header carrying a signed one-off limit, in bytes.
+pub const UPLOAD_LIMIT_HEADER: &str = "x-upload-limit";
+
+/// Request header carrying the signature of `X-Upload-Limit`.
+pub const UPLOAD_LIMIT_SIGNATURE_HEADER: &str = "x-upload-limit-signature";
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Signed ahead of the claims of upload grant tokens.
+const GRANT_PREFIX: &[u8] = b"grant:";
+
+/// Signed ahead of the claims of signed one-off limits.
+const LIMIT_PREFIX: &[u8] = b"limit:";
+
+/// The claims of an upload grant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UploadGrant {
+    /// Elevated size limit in bytes.
+    pub limit: usize,
+    /// Route pattern the grant is valid for (see [`path_pattern::matches`](crate::path_pattern::matches)).
+    pub route: String,
+    /// Expiry as seconds since the Unix epoch.
+    pub expires_at: u64,
+}
+
+impl UploadGrant {
+    /// Creates a grant valid for `ttl` from now.
+    ///
+    /// # Arguments
+    /// * `route` - Route pattern the grant applies to (e.g., `"/videos/upload"`)
+    /// * `limit` - Elevated size limit
+    /// * `ttl` - How long the grant stays valid
+    pub fn new(route: &str, limit: impl Into<crate::size::SizeLimit>, ttl: Duration) -> Self {
+        Self {
+            limit: limit.into().0,
+            route: route.to_string(),
+            expires_at: now_secs().saturating_add(ttl.as_secs()),
+        }
+    }
+
+    /// Returns `true` if the grant applies to `path` and has not expired.
+    pub fn allows(&self, path: &str) -> bool {
+        self.expires_at > now_secs() && crate::path_pattern::matches(&self.route, path)
+    }
+}
+
+/// Secret key used to mint and verify upload grants.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::size_limit::{SizeLimit, UploadGrant, UploadGrantKey};
+/// use std::time::Duration;
+///
+/// let key = UploadGrantKey::new(b"a long random secret from your vault".to_vec());
+/// let grant = UploadGrant::new("/videos/upload", SizeLimit::gib(2.0), Duration::from_secs(600));
+///
+/// let token = key.mint(&grant);
+/// assert_eq!(key.verify(&token), Ok(grant));
+///
+/// let other = UploadGrantKey::new(b"another secret".to_vec());
+/// assert!(other.verify(&token).is_err());
+/// ```
+#[derive(Clone)]
+pub struct UploadGrantKey {
+    secret: Vec<u8>,
+}
+
+impl UploadGrantKey {
+    /// Creates a key from a secret; use at least 32 random bytes.
+    pub fn new(secret: impl Into<Vec<u8>>) -> Self {
+        Self {
+            secret: secret.into(),
+        }
+    }
+
+    /// Mints a signed token for `grant`.
+    pub fn mint(&self, grant: &UploadGrant) -> String {
+        let payload = format!("{}:{}:{}", grant.limit, grant.expires_at, grant.route);
+        let signature = self.mac(GRANT_PREFIX, payload.as_bytes()).finalize().into_bytes();
+        format!(
+            "{}.{}",
+            URL_SAFE_NO_PAD.encode(payload),
+            URL_SAFE_NO_PAD.encode(signature)
+        )
+    }
+
+    /// Verifies a token's signature and decodes its grant.
+    ///
+    /// Expiry and route are not checked here; see [`UploadGrant::allows`].
+    ///
+    /// # Returns
+    /// * `Ok(UploadGrant)` - The signed claims
+    /// * `Err(String)` - If the token is malformed or the signature is invalid
+    pub fn verify(&self, token: &str) -> Result<UploadGrant, String> {
+        let (payload, signature) = token
+            .trim()
+            .split_once('.')
+            .ok_or_else(|| "Malformed upload grant".to_string())?;
+        let payload = URL_SAFE_NO_PAD
+            .decode(payload)
+            .map_err(|_| "Malformed upload grant payload".to_string())?;
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| "Malformed upload grant signature".to_string())?;
+
+        // Constant-time comparison
+        self.mac(GRANT_PREFIX, &payload)
+            .verify_slice(&signature)
+            .map_err(|_| "Invalid upload grant signature".to_string())?;
+
+        let payload = String::from_utf8(payload).map_err(|_| "Malformed upload grant payload".to_string())?;
+        let mut fields = payload.splitn(3, ':');
+        let (Some(limit), Some(expires_at), Some(route)) = (fields.next(), fields.next(), fields.next()) else {
+            return Err("Malformed upload grant payload".to_string());
+        };
+        Ok(UploadGrant {
+            limit: limit.parse().map_err(|_| "Invalid upload grant limit".to_string())?,
+            expires_at: expires_at.parse().map_err(|_| "Invalid upload grant expiry".to_string())?,
+            route: route.to_string(),
+        })
+    }
+
+    /// Signs a one-off limit for one request path, as a frontend does
+    /// before handing an upload over.
+    ///
+    /// Send `grant.limit` in the `x-upload-limit` header and the returned
+    /// value in `x-upload-limit-signature`. The route of the grant must be
+    /// the exact path of the upload.
+    ///
+    /// The signature is not consumed by the upload: it stays valid for the
+    /// path until the grant expires, so give it a lifetime of minutes.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size_limit::{SizeLimit, UploadGrant, UploadGrantKey};
+    /// use std::time::Duration;
+    ///
+    /// let key = UploadGrantKey::new(b"secret shared with the frontend".to_vec());
+    /// let grant = UploadGrant::new("/videos/42/upload", SizeLimit::gib(2.0), Duration::from_secs(300));
+    ///
+    /// let signature = key.sign_limit(&grant);
+    /// let limit = grant.limit.to_string();
+    /// assert_eq!(key.verify_limit(&limit, &signature, "/videos/42/upload"), Ok(grant));
+    /// assert!(key.verify_limit(&limit, &signature, "/videos/43/upload").is_err());
+    /// ```
+    pub fn sign_limit(&self, grant: &UploadGrant) -> String {
+        let payload = format!("{}:{}:{}", grant.limit, grant.expires_at, grant.route);
+        let signature = self.mac(LIMIT_PREFIX, payload.as_bytes()).finalize().into_bytes();
+        format!("{}.{}", grant.expires_at, URL_SAFE_NO_PAD.encode(signature))
+    }
+
+    /// Verifies a signed one-off limit for the request path `path`.
+    ///
+    /// Expiry is not checked here; see [`UploadGrant::allows`].
+    ///
+    /// # Arguments
+    /// * `limit` - Value of the `x-upload-limit` header
+    /// * `signature` - Value of the `x-upload-limit-signature` header
+    /// * `path` - Path of the request
+    ///
+    /// # Returns
+    /// * `Ok(UploadGrant)` - The signed limit, with `path` as route
+    /// * `Err(String)` - If a header is malformed or the signature is invalid
+    pub fn verify_limit(&self, limit: &str, signature: &str, path: &str) -> Result<UploadGrant, String> {
+        let limit: usize = limit.trim().parse().map_err(|_| "Invalid upload limit".to_string())?;
+        let (expires_at, signature) = signature
+            .trim()
+            .split_once('.')
+            .ok_or_else(|| "Malformed upload limit signature".to_string())?;
+        let expires_at: u64 = expires_at
+            .parse()
+            .map_err(|_| "Invalid upload limit expiry".to_string())?;
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| "Malformed upload limit signature".to_string())?;
+
+        // Constant-time comparison
+        let payload = format!("{}:{}:{}", limit, expires_at, path);
+        self.mac(LIMIT_PREFIX, payload.as_bytes())
+            .verify_slice(&signature)
+            .map_err(|_| "Invalid upload limit signature".to_string())?;
+        Ok(UploadGrant {
+            limit,
+            route: path.to_string(),
+            expires_at,
+        })
+    }
+
+    /// HMAC of `payload` behind the prefix of its kind.
+    fn mac(&self, prefix: &[u8], payload: &[u8]) -> HmacSha256 {
+        // HMAC accepts keys of any length, so this cannot fail
+        let mut mac = <HmacSha256 as Mac>::new_from_slice(&self.secret)
+            .unwrap_or_else(|_| unreachable!("HMAC accepts any key length"));
+        mac.update(prefix);
+        mac.update(payload);
+        mac
+    }
+}
+
+impl fmt::Debug for UploadGrantKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("UploadGrantKey([redacted])")
+    }
+}
+
+fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or(0)
+}
@@ -0,0 +1,237 @@
+//! Runtime-adjustable size limits (feature `runtime-limits`).
+//!
+//! [`with_size_limit_handle`] returns a [`SizeLimitHandle`] next to the
+//! router. Application code - an admin API, a feature flag listener - can
+//! change limits through the handle while the server runs; each request reads
+//! the current limits through an [`ArcSwap`], so updates never block or
+//! interrupt requests in flight.
+
+use arc_swap::ArcSwap;
+use axum::Router;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::{Arc, Mutex};
+
+use crate::size_limit::{
+    BufferStrategy, ContentTypeMatcher, Decision, LimitPolicy, RequestContext, SizeLimit, SizeLimitConfig,
+    SizeLimitError, SizeLimitMiddlewareConfig, with_size_limit_policy,
+};
+
+/// Distinct content types counted in [`RejectionStats`]; rejections of
+/// further types are counted under [`OTHER_CONTENT_TYPES`], so clients
+/// inventing content types cannot grow the table without bound.
+pub const MAX_TRACKED_CONTENT_TYPES: usize = 256;
+
+/// Key counting rejections beyond [`MAX_TRACKED_CONTENT_TYPES`].
+pub const OTHER_CONTENT_TYPES: &str = "other";
+
+/// Requests rejected by the size limiter since startup or the last reset.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize)]
+pub struct RejectionStats {
+    /// All rejections.
+    pub total: u64,
+    /// Rejections by content type, without parameters and lowercased.
+    pub by_content_type: BTreeMap<String, u64>,
+}
+
+impl RejectionStats {
+    fn record(&mut self, content_type: &str) {
+        self.total += 1;
+        let essence = content_type.split(';').next().unwrap_or("").trim().to_ascii_lowercase();
+        if let Some(count) = self.by_content_type.get_mut(&essence) {
+            *count += 1;
+        } else if self.by_content_type.len() < MAX_TRACKED_CONTENT_TYPES {
+            self.by_content_type.insert(essence, 1);
+        } else {
+            *self.by_content_type.entry(OTHER_CONTENT_TYPES.to_string()).or_insert(0) += 1;
+        }
+    }
+}
+
+/// Limits in effect, with their precompiled lookup table.
+pub(crate) struct LiveLimits {
+    config: SizeLimitConfig,
+    strategy: BufferStrategy,
+    matcher: ContentTypeMatcher,
+}
+
+impl LiveLimits {
+    pub(crate) fn new(config: SizeLimitConfig, strategy: BufferStrategy) -> Self {
+        let matcher = ContentTypeMatcher::compile(&config, &strategy);
+        Self { config, strategy, matcher }
+    }
+}
+
+/// Cloneable handle to change size limits at runtime.
+///
+/// All clones share the same limits. Reads are lock-free; updates recompile
+/// the content-type lookup table and swap it in atomically.
+///
+/// # Examples
+/// ```
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::size_limit::{SizeLimitConfig, SizeLimitMiddlewareConfig, with_size_limit_handle};
+///
+/// let router: Router = Router::new().route("/upload", post(|| async { "ok" }));
+/// let (router, limits) = with_size_limit_handle(router, SizeLimitMiddlewareConfig::default());
+///
+/// // Later, e.g. from an admin endpoint
+/// limits.set_default_limit("5mb");
+/// limits.set_specific_limit("application/json", "256kb");
+/// assert_eq!(limits.config().get_limit_for_content_type("application/json"), 256_000);
+/// ```
+#[derive(Clone)]
+pub struct SizeLimitHandle {
+    live: Arc<ArcSwap<LiveLimits>>,
+    stats: Arc<Mutex<RejectionStats>>,
+}
+
+impl SizeLimitHandle {
+    /// Creates a handle starting from the given limits.
+    ///
+    /// # Arguments
+    /// * `config` - Initial size limits
+    /// * `strategy` - Initial buffering decision by content type
+    pub fn new(config: SizeLimitConfig, strategy: BufferStrategy) -> Self {
+        Self {
+            live: Arc::new(ArcSwap::from_pointee(LiveLimits::new(config, strategy))),
+            stats: Arc::new(Mutex::new(RejectionStats::default())),
+        }
+    }
+
+    /// Returns a copy of the limits currently in effect.
+    pub fn config(&self) -> SizeLimitConfig {
+        self.live.load().config.clone()
+    }
+
+    /// Returns a copy of the buffer strategy currently in effect.
+    pub fn buffer_strategy(&self) -> BufferStrategy {
+        self.live.load().strategy.clone()
+    }
+
+    /// Replaces the limits.
+    pub fn set_config(&self, config: SizeLimitConfig) {
+        self.update(|_| config.clone());
+    }
+
+    /// Replaces the buffer strategy.
+    pub fn set_buffer_strategy(&self, strategy: BufferStrategy) {
+        self.live.rcu(|live| LiveLimits::new(live.config.clone(), strategy.clone()));
+    }
+
+    /// Applies a change to the current limits.
+    ///
+    /// Concurrent updates are not lost: if another update lands first, `f`
+    /// runs again on the newer limits, so it may be called more than once.
+    ///
+    /// # Arguments
+    /// * `f` - Produces the new limits from the current ones
+    ///
+    /// # Examples
+    /// ```
+    /// use axum::http::Method;
+    /// use axum_jetpack::size_limit::{BufferStrategy, SizeLimitConfig, SizeLimitHandle};
+    ///
+    /// let limits = SizeLimitHandle::new(SizeLimitConfig::default(), BufferStrategy::with_defaults());
+    /// limits.update(|config| config.clone().with_method_limit(Method::PATCH, "64kb"));
+    /// assert_eq!(limits.config().method_limits[&Method::PATCH], 64_000);
+    /// ```
+    pub fn update(&self, f: impl Fn(&SizeLimitConfig) -> SizeLimitConfig) {
+        self.live.rcu(|live| LiveLimits::new(f(&live.config), live.strategy.clone()));
+    }
+
+    /// Sets the default limit.
+    pub fn set_default_limit(&self, limit: impl Into<SizeLimit>) {
+        let limit = limit.into();
+        self.update(|config| config.clone().with_default_limit(limit));
+    }
+
+    /// Sets the limit for an exact content type or a wildcard pattern.
+    ///
+    /// # Arguments
+    /// * `content_type` - Content type (`"application/json"`) or pattern (`"image/*"`)
+    /// * `limit` - Maximum body size
+    pub fn set_specific_limit(&self, content_type: &str, limit: impl Into<SizeLimit>) {
+        let limit = limit.into();
+        self.update(|config| {
+            if content_type.contains('*') {
+                config.clone().with_wildcard_limit(content_type, limit)
+            } else {
+                config.clone().with_specific_limit(content_type, limit)
+            }
+        });
+    }
+
+    /// Removes the limit for an exact content type or wildcard pattern, so
+    /// it falls back to broader matches or the default.
+    pub fn remove_specific_limit(&self, content_type: &str) {
+        let key = content_type.to_ascii_lowercase();
+        self.update(|config| {
+            let mut config = config.clone();
+            config.specific_limits.remove(&key);
+            config.wildcard_limits.remove(&key);
+            config
+        });
+    }
+
+    /// Returns the rejections counted so far.
+    pub fn stats(&self) -> RejectionStats {
+        self.stats.lock().map(|stats| stats.clone()).unwrap_or_default()
+    }
+
+    /// Resets the rejection counters.
+    pub fn reset_stats(&self) {
+        if let Ok(mut stats) = self.stats.lock() {
+            *stats = RejectionStats::default();
+        }
+    }
+
+    /// Weak reference for background tasks that should stop with the router.
+    #[cfg(feature = "hot-reload")]
+    pub(crate) fn downgrade(&self) -> std::sync::Weak<ArcSwap<LiveLimits>> {
+        Arc::downgrade(&self.live)
+    }
+}
+
+impl fmt::Debug for SizeLimitHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let live = self.live.load();
+        f.debug_struct("SizeLimitHandle")
+            .field("config", &live.config)
+            .field("buffer_strategy", &live.strategy)
+            .finish()
+    }
+}
+
+impl LimitPolicy for SizeLimitHandle {
+    async fn decide(&self, ctx: RequestContext<'_>) -> Decision {
+        let live = self.live.load_full();
+        live.matcher.decide(ctx).await
+    }
+
+    fn rejected(&self, content_type: &str, _error: SizeLimitError) {
+        if let Ok(mut stats) = self.stats.lock() {
+            stats.record(content_type);
+        }
+    }
+}
+
+/// Applies size limiting whose limits can be changed at runtime.
+///
+/// Works like [`with_size_limit`](crate::size_limit::with_size_limit), but
+/// `config.size_limits` and `config.buffer_strategy` only seed the returned
+/// [`SizeLimitHandle`]; changes made through the handle apply to the next
+/// request.
+///
+/// # Arguments
+/// * `router` - The Axum router to wrap with middleware
+/// * `config` - Initial limits, method rules, hooks and error format
+///
+/// # Returns
+/// The router with size limiting applied, and the handle controlling it.
+pub fn with_size_limit_handle(router: Router, config: SizeLimitMiddlewareConfig) -> (Router, SizeLimitHandle) {
+    let handle = SizeLimitHandle::new(config.size_limits.clone(), config.buffer_strategy.clone());
+    let router = with_size_limit_policy(router, config, handle.clone());
+    (router, handle)
+}
@@ -0,0 +1,119 @@
+//! Per-host (virtual host) limit sets.
+//!
+//! [`HostLimits`] is a [`LimitPolicy`] that picks one of several limit
+//! configurations by the request's `Host`, so a multi-tenant server can enforce
+//! different upload policies for `api.example.com` and `cdn.example.com` from a
+//! single router and a single size limiter.
+
+use axum::http::{header, request::Parts};
+use std::collections::HashMap;
+
+use crate::size_limit::glob;
+use crate::size_limit::{BufferStrategy, ContentTypeMatcher, Decision, LimitPolicy, RequestContext, SizeLimitConfig};
+
+/// Limit configurations selected by the `Host` header.
+///
+/// Hosts are matched case-insensitively and without the port. Exact host
+/// names are checked first, then patterns containing `*` (e.g.,
+/// `"*.example.com"`) in the order they were added; requests for any other
+/// host use the default configuration.
+///
+/// Install it with [`with_size_limit_policy`](crate::size_limit::with_size_limit_policy).
+///
+/// # Examples
+/// ```
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::size_limit::{
+///     BufferStrategy, HostLimits, SizeLimitConfig, SizeLimitMiddlewareConfig, with_size_limit_policy,
+/// };
+///
+/// let hosts = HostLimits::new(SizeLimitConfig::default(), BufferStrategy::with_defaults())
+///     .with_host("api.example.com", SizeLimitConfig::default().with_default_limit("256kb"))
+///     .with_host("cdn.example.com", SizeLimitConfig::default().with_default_limit("500mb"))
+///     .with_host("*.uploads.example.com", SizeLimitConfig::default().with_default_limit("2gb"));
+///
+/// let router: Router = Router::new().route("/upload", post(|| async { "ok" }));
+/// let router = with_size_limit_policy(router, SizeLimitMiddlewareConfig::default(), hosts);
+/// ```
+#[derive(Clone, Debug)]
+pub struct HostLimits {
+    /// Buffering strategy shared by every host.
+    strategy: BufferStrategy,
+    /// Limits for exact host names.
+    exact: HashMap<String, ContentTypeMatcher>,
+    /// Limits for host patterns, checked in order.
+    patterns: Vec<(String, ContentTypeMatcher)>,
+    /// Limits for hosts matching nothing.
+    default: ContentTypeMatcher,
+}
+
+impl HostLimits {
+    /// Creates a policy that applies `default` to every host.
+    ///
+    /// # Arguments
+    /// * `default` - Limits for hosts without their own configuration
+    /// * `strategy` - Buffering decision by content type, shared by all hosts
+    pub fn new(default: SizeLimitConfig, strategy: BufferStrategy) -> Self {
+        Self {
+            default: ContentTypeMatcher::compile(&default, &strategy),
+            strategy,
+            exact: HashMap::new(),
+            patterns: Vec::new(),
+        }
+    }
+
+    /// Builder method to set the limits for a host or host pattern.
+    ///
+    /// # Arguments
+    /// * `host` - Host name (`"api.example.com"`) or pattern (`"*.example.com"`)
+    /// * `limits` - Limits for requests to that host
+    pub fn with_host(mut self, host: &str, limits: SizeLimitConfig) -> Self {
+        let host = host.to_ascii_lowercase();
+        let matcher = ContentTypeMatcher::compile(&limits, &self.strategy);
+        if glob::is_glob(&host) {
+            self.patterns.retain(|(pattern, _)| *pattern != host);
+            self.patterns.push((host, matcher));
+        } else {
+            self.exact.insert(host, matcher);
+        }
+        self
+    }
+
+    /// Returns the limits that apply to a request head.
+    pub fn matcher_for(&self, parts: &Parts) -> &ContentTypeMatcher {
+        let Some(host) = request_host(parts) else {
+            return &self.default;
+        };
+        let host = host.to_ascii_lowercase();
+        if let Some(matcher) = self.exact.get(&host) {
+            return matcher;
+        }
+        self.patterns
+            .iter()
+            .find(|(pattern, _)| glob::glob_match(pattern, &host))
+            .map(|(_, matcher)| matcher)
+            .unwrap_or(&self.default)
+    }
+}
+
+impl LimitPolicy for HostLimits {
+    async fn decide(&self, ctx: RequestContext<'_>) -> Decision {
+        self.matcher_for(ctx.parts).decide(ctx).await
+    }
+}
+
+/// Host of a request without the port: the `Host` header, or the URI
+/// authority for HTTP/2 requests that carry none.
+fn request_host(parts: &Parts) -> Option<&str> {
+    let host = parts
+        .headers
+        .get(header::HOST)
+        .and_then(|h| h.to_str().ok())
+        .or_else(|| parts.uri.authority().map(|authority| authority.as_str()))?;
+    // IPv6 literals keep their brackets: "[::1]:8080" → "[::1]"
+    let host = match host.rfind(']') {
+        Some(end) => &host[..=end],
+        None => host.split(':').next().unwrap_or(host),
+    };
+    Some(host.trim_end_matches('.'))
+}
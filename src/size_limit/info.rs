@@ -0,0 +1,76 @@
+//! The limit applied to a request, for handlers.
+
+use axum::{
+    extract::{FromRequestParts, OptionalFromRequestParts},
+    http::{StatusCode, request::Parts},
+    response::{IntoResponse, Response},
+};
+use std::convert::Infallible;
+
+use crate::size_limit::SizeLimitConfig;
+use crate::size_limit::glob;
+
+/// The limit the size limiter applied to a request.
+///
+/// Stored in the request extensions of every request the limiter accepted
+/// with a limit, and available as an extractor, so handlers (e.g. of
+/// resumable uploads) can report the limit to clients or act on it. Extract
+/// `Option<SizeLimitInfo>` where the limiter may not run.
+///
+/// # Examples
+/// ```
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::size_limit::{RouterExt, SizeLimitInfo};
+///
+/// async fn upload(info: SizeLimitInfo) -> String {
+///     format!("you may send up to {} bytes", info.limit)
+/// }
+///
+/// let router: Router = Router::new().route("/upload", post(upload)).size_limit("10mb");
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SizeLimitInfo {
+    /// The enforced limit in bytes, after overrides and upload grants.
+    pub limit: usize,
+    /// Entry of the content-type table matching the request (`"image/*"`),
+    /// or `None` if the default limit applied to its content type.
+    pub content_type_rule: Option<String>,
+}
+
+impl SizeLimitInfo {
+    /// Describes the limit of a request with the given content type.
+    pub(crate) fn new(limit: usize, limits: &SizeLimitConfig, content_type: &str) -> Self {
+        let content_type_rule =
+            glob::with_essence(content_type, |essence| limits.resolve_content_type(essence).2.map(str::to_string));
+        Self {
+            limit,
+            content_type_rule,
+        }
+    }
+}
+
+impl<S> FromRequestParts<S> for SizeLimitInfo
+where
+    S: Send + Sync,
+{
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<SizeLimitInfo>()
+            .cloned()
+            .ok_or_else(|| (StatusCode::INTERNAL_SERVER_ERROR, "Size limit unavailable").into_response())
+    }
+}
+
+impl<S> OptionalFromRequestParts<S> for SizeLimitInfo
+where
+    S: Send + Sync,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Option<Self>, Self::Rejection> {
+        Ok(parts.extensions.get::<SizeLimitInfo>().cloned())
+    }
+}
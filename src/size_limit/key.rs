@@ -0,0 +1,128 @@
+//! Keys identifying who sent a request.
+//!
+//! Quotas and per-key caps account requests by a key: the client IP, an API
+//! key, a tenant id... A [`KeyExtractor`] picks it from the request head.
+
+use axum::http::{HeaderName, request::Parts};
+use std::marker::PhantomData;
+
+use crate::peer::peer_ip;
+
+/// Picks the accounting key of a request.
+///
+/// Implemented for closures taking the request head, so any key can be
+/// used. Requests without a key are not accounted.
+///
+/// # Examples
+/// ```
+/// use axum::http::{Request, request::Parts};
+/// use axum_jetpack::size_limit::{HeaderKey, KeyExtractor};
+///
+/// let req = Request::builder().uri("/acme/upload").header("x-api-key", "k-123").body(()).unwrap();
+/// let (parts, _) = req.into_parts();
+/// assert_eq!(HeaderKey::new("x-api-key").key(&parts).as_deref(), Some("k-123"));
+///
+/// let tenant = |parts: &Parts| parts.uri.path().split('/').nth(1).map(str::to_string);
+/// assert_eq!(tenant.key(&parts).as_deref(), Some("acme"));
+/// ```
+pub trait KeyExtractor: Send + Sync + 'static {
+    /// The key of the request, or `None` to leave it unaccounted.
+    fn key(&self, parts: &Parts) -> Option<String>;
+}
+
+impl<F> KeyExtractor for F
+where
+    F: Fn(&Parts) -> Option<String> + Send + Sync + 'static,
+{
+    fn key(&self, parts: &Parts) -> Option<String> {
+        self(parts)
+    }
+}
+
+/// Keys requests on the value of a header, e.g. `x-api-key` or a tenant id.
+#[derive(Clone, Debug)]
+pub struct HeaderKey(pub HeaderName);
+
+impl HeaderKey {
+    /// Keys requests on the header `name`.
+    ///
+    /// # Panics
+    /// If `name` is not a valid header name.
+    pub fn new(name: &'static str) -> Self {
+        Self(HeaderName::from_static(name))
+    }
+}
+
+impl KeyExtractor for HeaderKey {
+    fn key(&self, parts: &Parts) -> Option<String> {
+        parts
+            .headers
+            .get(&self.0)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string)
+    }
+}
+
+/// Keys requests on the client IP.
+///
+/// Uses the address resolved by the `client_ip` module when the `client-ip`
+/// feature is enabled and its layer ran, otherwise the peer address of
+/// `ConnectInfo<SocketAddr>`.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct PeerIp;
+
+impl KeyExtractor for PeerIp {
+    fn key(&self, parts: &Parts) -> Option<String> {
+        peer_ip(parts).map(|ip| ip.to_string())
+    }
+}
+
+/// Keys requests on an extension inserted by an earlier layer, e.g. the
+/// claims of an authentication layer.
+///
+/// Requests without the extension are not keyed.
+///
+/// # Examples
+/// ```
+/// use axum::http::Request;
+/// use axum_jetpack::size_limit::{ExtensionKey, KeyExtractor};
+///
+/// #[derive(Clone)]
+/// struct Claims {
+///     sub: String,
+/// }
+///
+/// let user = ExtensionKey::new(|claims: &Claims| Some(claims.sub.clone()));
+/// let mut req = Request::new(());
+/// req.extensions_mut().insert(Claims { sub: "user-42".to_string() });
+/// let (parts, _) = req.into_parts();
+/// assert_eq!(user.key(&parts).as_deref(), Some("user-42"));
+/// ```
+pub struct ExtensionKey<T, F> {
+    key: F,
+    _extension: PhantomData<fn(&T)>,
+}
+
+impl<T, F> ExtensionKey<T, F>
+where
+    T: Send + Sync + 'static,
+    F: Fn(&T) -> Option<String> + Send + Sync + 'static,
+{
+    /// Keys requests on what `key` picks from their `T` extension.
+    pub fn new(key: F) -> Self {
+        Self {
+            key,
+            _extension: PhantomData,
+        }
+    }
+}
+
+impl<T, F> KeyExtractor for ExtensionKey<T, F>
+where
+    T: Send + Sync + 'static,
+    F: Fn(&T) -> Option<String> + Send + Sync + 'static,
+{
+    fn key(&self, parts: &Parts) -> Option<String> {
+        parts.extensions.get::<T>().and_then(&self.key)
+    }
+}
@@ -0,0 +1,57 @@
+//! The [`size_limits!`](crate::size_limits) macro.
+
+/// Builds a [`SizeLimitConfig`](crate::size_limit::SizeLimitConfig) from a
+/// table of content types and sizes.
+///
+/// `default` sets the default limit; every other key is a content type, or a
+/// wildcard limit if it contains `*`. Sizes are human-readable strings parsed
+/// at compile time with [`SizeLimit::parse_const`](crate::size_limit::SizeLimit::parse_const),
+/// so a typo fails the build instead of panicking at startup.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::size_limits;
+///
+/// let config = size_limits! {
+///     default: "1mb",
+///     "application/json": "256kb",
+///     "image/*": "10mb",
+///     "video/*": "unlimited",
+/// };
+///
+/// assert_eq!(config.default_limit, 1_000_000);
+/// assert_eq!(config.get_limit_for_content_type("application/json"), 256_000);
+/// assert_eq!(config.get_limit_for_content_type("image/png"), 10_000_000);
+/// ```
+///
+/// Invalid sizes are compile errors:
+/// ```compile_fail
+/// use axum_jetpack::size_limits;
+///
+/// let config = size_limits! { "application/json": "256 kilo" };
+/// ```
+#[macro_export]
+macro_rules! size_limits {
+    (@entry $config:ident, default, $limit:expr) => {
+        $config.with_default_limit($limit)
+    };
+    (@entry $config:ident, $pattern:literal, $limit:expr) => {{
+        let pattern: &str = $pattern;
+        if pattern.contains('*') {
+            $config.with_wildcard_limit(pattern, $limit)
+        } else {
+            $config.with_specific_limit(pattern, $limit)
+        }
+    }};
+    ($($key:tt : $size:literal),* $(,)?) => {{
+        let config = $crate::size_limit::SizeLimitConfig::default();
+        $(
+            let config = $crate::size_limits!(
+                @entry config,
+                $key,
+                const { $crate::size_limit::SizeLimit::parse_const($size) }
+            );
+        )*
+        config
+    }};
+}
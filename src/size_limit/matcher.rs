@@ -0,0 +1,396 @@
+//! Precompiled content-type lookups for the request hot path.
+//!
+//! [`SizeLimitConfig::get_limit_for_content_type`] and
+//! [`BufferStrategy::should_buffer`] lowercase the header into a new string and
+//! scan their tables on every call. [`ContentTypeMatcher`] resolves both
+//! answers once, at layer construction, into two hash maps keyed by the
+//! normalized type and by its top-level type, so a lookup is at most two
+//! hash probes and does not allocate. Globs other than `type/*` (such as
+//! `application/vnd.*`) cannot be keyed this way; when present, types without
+//! an exact entry are resolved through the configuration instead.
+
+use axum::http::{HeaderMap, Method, header, request::Parts};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex};
+
+use crate::size_limit::{BufferStrategy, LimitRule, MissingContentType, SizeLimitConfig, SizeLimitError};
+use crate::size_limit::glob;
+
+/// The resolved handling of a content type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ContentTypeRule {
+    /// Size limit in bytes.
+    pub limit: usize,
+    /// Whether the body is buffered (`true`) or streamed (`false`).
+    pub buffer: bool,
+}
+
+/// A compiled rule and whether the type has an explicit limit (for
+/// allowlist mode).
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    rule: ContentTypeRule,
+    listed: bool,
+}
+
+/// Allocation-free lookup table compiled from a [`SizeLimitConfig`] and a
+/// [`BufferStrategy`].
+///
+/// Answers are identical to calling `get_limit_for_content_type` (or
+/// `get_limit_for_request`) and `should_buffer` directly;
+/// the configuration is not consulted again after compilation, so recompile
+/// after changing it.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::size_limit::{BufferStrategy, ContentTypeMatcher, SizeLimitConfig};
+///
+/// let limits = SizeLimitConfig::default()
+///     .with_default_limit("2mb")
+///     .with_specific_limit("application/json", "100kb")
+///     .with_wildcard_limit("image/*", "5mb");
+/// let matcher = ContentTypeMatcher::compile(&limits, &BufferStrategy::with_defaults());
+///
+/// let rule = matcher.lookup("Application/JSON; charset=utf-8");
+/// assert_eq!(rule.limit, 100_000);
+/// assert!(rule.buffer);
+///
+/// let rule = matcher.lookup("image/png");
+/// assert_eq!(rule.limit, 5_000_000);
+/// assert!(!rule.buffer);
+/// ```
+#[derive(Clone, Debug)]
+pub struct ContentTypeMatcher {
+    /// Rules for every exactly configured type.
+    exact: HashMap<Box<str>, Entry>,
+    /// Rules for other types, keyed by top-level type (`"image"`).
+    by_top_level: HashMap<Box<str>, Entry>,
+    /// Rule for types matching nothing.
+    fallback: ContentTypeRule,
+    /// Per-method caps on the limit.
+    method_limits: HashMap<Method, usize>,
+    /// Ordered rules, checked before the tables.
+    rules: Vec<LimitRule>,
+    /// Whether unlisted content types are rejected.
+    allowlist_mode: bool,
+    /// Handling of requests without a `Content-Type`.
+    missing_content_type: MissingContentType,
+    /// Set when the configuration uses globs other than `type/*`; types
+    /// without an exact entry are then resolved by the configuration itself.
+    glob_fallback: Option<Box<(SizeLimitConfig, BufferStrategy)>>,
+    /// Regex limits, checked between exact and top-level matches.
+    #[cfg(feature = "content-type-regex")]
+    regex_limits: Vec<(regex::Regex, usize)>,
+    /// Recent lookups by raw header value, shared by clones.
+    cache: Option<Arc<LookupCache>>,
+}
+
+impl ContentTypeMatcher {
+    /// Compiles the lookup table.
+    ///
+    /// # Arguments
+    /// * `limits` - Size limits by content type
+    /// * `strategy` - Buffering decision by content type
+    pub fn compile(limits: &SizeLimitConfig, strategy: &BufferStrategy) -> Self {
+        let entry = |content_type: &str| Entry {
+            rule: ContentTypeRule {
+                limit: limits.get_limit_for_content_type(content_type),
+                buffer: strategy.should_buffer(content_type),
+            },
+            listed: limits.is_content_type_listed(content_type),
+        };
+
+        let exact_types = limits
+            .specific_limits
+            .keys()
+            .chain(&strategy.buffered_types)
+            .chain(&strategy.streamed_types)
+            .filter(|t| !glob::is_glob(t));
+        let exact = exact_types
+            .map(|t| (t.to_ascii_lowercase().into(), entry(t)))
+            .collect();
+
+        let wildcard_types = limits
+            .wildcard_limits
+            .keys()
+            .chain(&strategy.buffered_types)
+            .chain(&strategy.streamed_types)
+            .filter(|t| glob::is_top_level_wildcard(t))
+            .filter_map(|t| t.strip_suffix("/*"));
+        let by_top_level = wildcard_types
+            .map(|top| {
+                let limit = limits.wildcard_limits.get(&format!("{}/*", top)).copied();
+                let wildcard = Entry {
+                    rule: ContentTypeRule {
+                        limit: limit.unwrap_or(limits.default_limit),
+                        buffer: wildcard_buffer(strategy, top),
+                    },
+                    listed: limit.is_some(),
+                };
+                (top.to_ascii_lowercase().into(), wildcard)
+            })
+            .collect();
+
+        let has_general_globs = limits
+            .wildcard_limits
+            .keys()
+            .chain(&strategy.buffered_types)
+            .chain(&strategy.streamed_types)
+            .any(|t| glob::is_glob(t) && !glob::is_top_level_wildcard(t));
+        let glob_fallback = has_general_globs.then(|| Box::new((limits.clone(), strategy.clone())));
+
+        Self {
+            exact,
+            by_top_level,
+            glob_fallback,
+            fallback: ContentTypeRule {
+                limit: limits.default_limit,
+                buffer: strategy.default_is_buffered,
+            },
+            method_limits: limits.method_limits.clone(),
+            rules: limits.rules.clone(),
+            allowlist_mode: limits.allowlist_mode,
+            missing_content_type: limits.missing_content_type,
+            #[cfg(feature = "content-type-regex")]
+            regex_limits: limits.regex_limits.clone(),
+            cache: None,
+        }
+    }
+
+    /// Builder method to remember the rules of recently seen `Content-Type`
+    /// values.
+    ///
+    /// Most servers see a handful of distinct content types, so a small cache
+    /// keyed by the raw header value answers most lookups with one
+    /// comparison. This pays off when the configuration has regex limits or
+    /// globs other than `type/*`, which are otherwise evaluated per request.
+    /// The least recently used value is evicted when the cache is full.
+    ///
+    /// # Arguments
+    /// * `capacity` - Number of header values to remember; `0` disables the cache
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size_limit::{BufferStrategy, ContentTypeMatcher, SizeLimitConfig};
+    ///
+    /// let limits = SizeLimitConfig::default().with_wildcard_limit("application/vnd.*", "5mb");
+    /// let matcher = ContentTypeMatcher::compile(&limits, &BufferStrategy::with_defaults()).with_cache(16);
+    ///
+    /// assert_eq!(matcher.lookup("application/vnd.api+json").limit, 5_000_000);
+    /// // Answered from the cache
+    /// assert_eq!(matcher.lookup("application/vnd.api+json").limit, 5_000_000);
+    /// ```
+    pub fn with_cache(mut self, capacity: usize) -> Self {
+        self.cache = (capacity > 0).then(|| {
+            Arc::new(LookupCache {
+                capacity,
+                entries: Mutex::new(VecDeque::with_capacity(capacity)),
+            })
+        });
+        self
+    }
+
+    /// Resolves the rule for a `Content-Type` header value.
+    ///
+    /// Parameters (`; charset=...`) are ignored and matching is
+    /// case-insensitive.
+    pub fn lookup(&self, content_type: &str) -> ContentTypeRule {
+        self.resolve(content_type).rule
+    }
+
+    /// Returns `false` if allowlist mode is on and the content type has no
+    /// explicit limit (see [`SizeLimitConfig::allowlist_mode`]).
+    pub fn is_allowed(&self, content_type: &str) -> bool {
+        !self.allowlist_mode || self.resolve(content_type).listed
+    }
+
+    /// Checks whether a request may be admitted at all.
+    ///
+    /// # Arguments
+    /// * `parts` - The request head
+    /// * `content_type` - The `Content-Type` header value (or a default)
+    ///
+    /// # Returns
+    /// * `Ok(())` - If the request may proceed to the size checks
+    /// * `Err(SizeLimitError::UnsupportedMediaType)` - If its content type is
+    ///   missing and those are rejected, or not allowed in allowlist mode
+    pub fn check_request(&self, parts: &Parts, content_type: &str) -> Result<(), SizeLimitError> {
+        let allowed = match (parts.headers.contains_key(header::CONTENT_TYPE), self.missing_content_type) {
+            (false, MissingContentType::Reject) => false,
+            (false, MissingContentType::Limit(_)) => true,
+            _ => self.is_allowed(content_type),
+        };
+        if allowed {
+            Ok(())
+        } else {
+            Err(SizeLimitError::UnsupportedMediaType)
+        }
+    }
+
+    fn resolve(&self, content_type: &str) -> Entry {
+        let Some(cache) = &self.cache else {
+            return glob::with_essence(content_type, |essence| self.lookup_normalized(essence));
+        };
+        if let Some(entry) = cache.get(content_type) {
+            return entry;
+        }
+        let entry = glob::with_essence(content_type, |essence| self.lookup_normalized(essence));
+        cache.insert(content_type, entry);
+        entry
+    }
+
+    /// Resolves the rule for a request method and `Content-Type` header value.
+    ///
+    /// Like [`lookup`](Self::lookup), with the limit capped by the method's
+    /// limit if one is configured.
+    pub fn lookup_for_method(&self, method: &Method, content_type: &str) -> ContentTypeRule {
+        let mut rule = self.lookup(content_type);
+        if let Some(cap) = self.method_limits.get(method) {
+            rule.limit = rule.limit.min(*cap);
+        }
+        rule
+    }
+
+    /// Resolves the rule for a request head.
+    ///
+    /// The first matching [`LimitRule`] sets the limit; otherwise this is
+    /// [`lookup_for_method`](Self::lookup_for_method), with the limit for
+    /// requests without a `Content-Type` if one is configured. Buffering
+    /// always follows the content type.
+    ///
+    /// # Arguments
+    /// * `parts` - The request head
+    /// * `content_type` - The `Content-Type` header value (or a default)
+    pub fn lookup_request(&self, parts: &Parts, content_type: &str) -> ContentTypeRule {
+        let rule = self.rules.iter().find(|rule| rule.matches_parts(parts));
+        let mut resolved = self.apply_rule(rule, &parts.method, content_type);
+
+        // Header-less bodies may have their own limit
+        if let (None, MissingContentType::Limit(limit)) = (rule, self.missing_content_type)
+            && !parts.headers.contains_key(header::CONTENT_TYPE)
+        {
+            resolved.limit = self
+                .method_limits
+                .get(&parts.method)
+                .map_or(limit, |cap| limit.min(*cap));
+        }
+        resolved
+    }
+
+    /// Resolves the rule from individual request attributes.
+    ///
+    /// Like [`lookup_request`](Self::lookup_request), for callers without a
+    /// request head (e.g., replaying captured traffic).
+    ///
+    /// # Arguments
+    /// * `method` - Request method
+    /// * `paths` - Route template and/or request path
+    /// * `content_type` - The `Content-Type` header value
+    /// * `headers` - Request headers
+    pub fn lookup_with(&self, method: &Method, paths: &[&str], content_type: &str, headers: &HeaderMap) -> ContentTypeRule {
+        let rule = self
+            .rules
+            .iter()
+            .find(|rule| rule.matches(method, paths, content_type, headers));
+        self.apply_rule(rule, method, content_type)
+    }
+
+    fn apply_rule(&self, rule: Option<&LimitRule>, method: &Method, content_type: &str) -> ContentTypeRule {
+        match rule {
+            Some(rule) => ContentTypeRule {
+                limit: rule.limit,
+                buffer: self.lookup(content_type).buffer,
+            },
+            None => self.lookup_for_method(method, content_type),
+        }
+    }
+
+    /// Size limit for a `Content-Type` header value.
+    pub fn limit_for(&self, content_type: &str) -> usize {
+        self.lookup(content_type).limit
+    }
+
+    /// Whether a `Content-Type` header value is buffered.
+    pub fn should_buffer(&self, content_type: &str) -> bool {
+        self.lookup(content_type).buffer
+    }
+
+    fn lookup_normalized(&self, essence: &str) -> Entry {
+        if let Some(entry) = self.exact.get(essence) {
+            return *entry;
+        }
+        if let Some(fallback) = &self.glob_fallback {
+            let (limits, strategy) = fallback.as_ref();
+            return Entry {
+                rule: ContentTypeRule {
+                    limit: limits.get_limit_for_content_type(essence),
+                    buffer: strategy.should_buffer(essence),
+                },
+                listed: limits.is_content_type_listed(essence),
+            };
+        }
+        let entry = essence
+            .split_once('/')
+            .and_then(|(top, _)| self.by_top_level.get(top))
+            .copied()
+            .unwrap_or(Entry {
+                rule: self.fallback,
+                listed: false,
+            });
+
+        // Regex limits override the top-level limit; buffering is unaffected
+        #[cfg(feature = "content-type-regex")]
+        if let Some((_, limit)) = self.regex_limits.iter().find(|(pattern, _)| pattern.is_match(essence)) {
+            return Entry {
+                rule: ContentTypeRule { limit: *limit, ..entry.rule },
+                listed: true,
+            };
+        }
+        entry
+    }
+}
+
+/// Longest header value kept in a [`LookupCache`], so clients sending huge
+/// content types cannot make the cache hold much memory.
+const MAX_CACHED_CONTENT_TYPE_LEN: usize = 128;
+
+/// Least recently used cache of lookups by raw `Content-Type` value.
+#[derive(Debug)]
+struct LookupCache {
+    capacity: usize,
+    /// Most recently used first.
+    entries: Mutex<VecDeque<(Box<str>, Entry)>>,
+}
+
+impl LookupCache {
+    fn get(&self, content_type: &str) -> Option<Entry> {
+        let mut entries = self.entries.lock().ok()?;
+        let position = entries.iter().position(|(key, _)| **key == *content_type)?;
+        let hit = entries.remove(position)?;
+        let entry = hit.1;
+        entries.push_front(hit);
+        Some(entry)
+    }
+
+    fn insert(&self, content_type: &str, entry: Entry) {
+        if content_type.len() > MAX_CACHED_CONTENT_TYPE_LEN {
+            return;
+        }
+        if let Ok(mut entries) = self.entries.lock() {
+            entries.truncate(self.capacity - 1);
+            entries.push_front((content_type.into(), entry));
+        }
+    }
+}
+
+/// Buffering decision for a type that only matches `top/*` patterns.
+fn wildcard_buffer(strategy: &BufferStrategy, top: &str) -> bool {
+    let wildcard = format!("{}/*", top);
+    if strategy.buffered_types.contains(&wildcard) {
+        true
+    } else if strategy.streamed_types.contains(&wildcard) {
+        false
+    } else {
+        strategy.default_is_buffered
+    }
+}
@@ -4,20 +4,42 @@
 //! with configurable strategies for handling different content types.
 //! It supports both buffered and streamed processing based on content type patterns.
 
-use axum::body::to_bytes;
 use axum::{
-    Router,
-    body::{Body, Bytes},
-    extract::{Request, State},
-    http::StatusCode,
-    middleware::{self, Next},
-    response::{IntoResponse, Response},
+    BoxError, Router,
+    body::{Body, Bytes, HttpBody},
+    extract::Request,
+    http::{HeaderMap, HeaderName, HeaderValue, Method, StatusCode, Version, header, request::Parts},
+    response::Response,
 };
-use futures::StreamExt;
-use std::sync::Arc;
-use tokio_stream::wrappers::ReceiverStream;
+use bytes::BytesMut;
+use futures::TryFutureExt;
+use futures::future::{BoxFuture, MapOk};
+use http_body::{Frame, SizeHint};
+use std::convert::Infallible;
+use std::future::{Future, poll_fn};
+use std::ops::ControlFlow;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Poll, ready};
+use std::time::Duration;
+use tokio::time::{Instant, Sleep};
+use tower::{Layer, Service};
 
-use crate::size_limit::SizeLimitConfig;
+use crate::error::ErrorFormat;
+use crate::size_limit::{
+    BodyHasher, BufferBudget, Buffering, Bundle, ByteQuota, ContentTypeMatcher, ContentTypePattern, Decision, DisconnectHook, DisconnectInfo, Enforcement, HasherFactory, LimitPolicy,
+    LimitSource, MAX_UPLOAD_SIZE, MultipartLimits, ObservedBodySize, ProgressHook, RejectionHeaders, ReportHook, RequestContext, SizeLimit, SizeLimitConfig, SizeLimitError, SizeLimitInfo, SizeLimitOverride, SoftLimit, SoftLimitExceeded,
+    SoftLimitHook, UploadProgress, WouldReject, X_MAX_BODY_SIZE,
+};
+use crate::size_limit::digest::DigestMeter;
+use crate::size_limit::disconnect::watch_disconnect;
+use crate::size_limit::glob;
+use crate::size_limit::pool::{self, Pooled};
+use crate::size_limit::multipart::{MultipartMeter, OversizedPart};
+use crate::size_limit::overrides::EnforcedLimit;
+use crate::size_limit::progress::ProgressMeter;
+use crate::size_limit::report::Reporter;
+use crate::size_limit::soft::SoftMeter;
 
 /// Defines strategy for whether to buffer or stream requests based on content type.
 ///
@@ -27,8 +49,14 @@ use crate::size_limit::SizeLimitConfig;
 /// - **Streamed**: Request body is processed in chunks as it arrives
 ///   (better for large files like videos or images)
 ///
-/// Content types can be specified with exact matches or wildcards (e.g., "image/*").
+/// Content types can be specified with exact matches or glob patterns (e.g., "image/*",
+/// "application/vnd.*", "*/zip"); the most specific matching pattern wins.
+///
+/// With the `serde` feature, missing fields deserialize to their
+/// [`with_defaults`](Self::with_defaults) values.
 #[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct BufferStrategy {
     /// Content types that should be fully buffered into memory before processing.
     /// Examples: ["application/json", "text/*", "multipart/form-data"]
@@ -140,7 +168,8 @@ impl BufferStrategy {
     /// Builder method to add content types that should be buffered.
     ///
     /// # Arguments
-    /// * `types` - Slice of content type patterns (supports wildcards like "text/*")
+    /// * `types` - Slice of content type patterns (supports wildcards like "text/*"), as
+    ///   strings or, with the `mime` feature, `mime::Mime`/`mime::Name` values
     ///
     /// # Example
     /// ```rust
@@ -149,16 +178,17 @@ impl BufferStrategy {
     /// let strategy = BufferStrategy::new()
     ///     .with_buffered_types(&["application/json", "text/*"]);
     /// ```
-    pub fn with_buffered_types(mut self, types: &[&str]) -> Self {
+    pub fn with_buffered_types<T: ContentTypePattern>(mut self, types: &[T]) -> Self {
         self.buffered_types
-            .extend(types.iter().map(|s| s.to_string()));
+            .extend(types.iter().map(|t| t.to_content_type_pattern()));
         self
     }
 
     /// Builder method to add content types that should be streamed.
     ///
     /// # Arguments
-    /// * `types` - Slice of content type patterns (supports wildcards like "video/*")
+    /// * `types` - Slice of content type patterns (supports wildcards like "video/*"), as
+    ///   strings or, with the `mime` feature, `mime::Mime`/`mime::Name` values
     ///
     /// # Example
     /// ```rust
@@ -167,12 +197,40 @@ impl BufferStrategy {
     /// let strategy = BufferStrategy::new()
     ///     .with_streamed_types(&["video/*", "image/png"]);
     /// ```
-    pub fn with_streamed_types(mut self, types: &[&str]) -> Self {
+    pub fn with_streamed_types<T: ContentTypePattern>(mut self, types: &[T]) -> Self {
         self.streamed_types
-            .extend(types.iter().map(|s| s.to_string()));
+            .extend(types.iter().map(|t| t.to_content_type_pattern()));
         self
     }
 
+    /// Builder method to buffer every content type in a bundle.
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum_jetpack::size_limit::{Bundle, middleware::BufferStrategy};
+    ///
+    /// let strategy = BufferStrategy::new().with_buffered_bundle(Bundle::Documents);
+    /// assert!(strategy.should_buffer("application/pdf"));
+    /// ```
+    pub fn with_buffered_bundle(self, bundle: Bundle) -> Self {
+        self.with_buffered_types(bundle.content_types())
+    }
+
+    /// Builder method to stream every content type in a bundle.
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum_jetpack::size_limit::{Bundle, middleware::BufferStrategy};
+    ///
+    /// let strategy = BufferStrategy::new()
+    ///     .with_default_buffered(true)
+    ///     .with_streamed_bundle(Bundle::Archives);
+    /// assert!(!strategy.should_buffer("application/zip"));
+    /// ```
+    pub fn with_streamed_bundle(self, bundle: Bundle) -> Self {
+        self.with_streamed_types(bundle.content_types())
+    }
+
     /// Builder method to set the default behavior for unlisted content types.
     ///
     /// # Arguments
@@ -255,45 +313,28 @@ impl BufferStrategy {
     /// assert!(!strategy.should_buffer("video/mp4"));
     /// ```
     pub fn should_buffer(&self, content_type: &str) -> bool {
-        // Normalize the content type: lowercase and remove charset/semantic
-        let ct_lower = content_type.to_lowercase();
-        let ct_trimmed = ct_lower.split(';').next().unwrap_or(&ct_lower).trim();
-
-        // Check for exact matches first (highest priority)
-        if self.buffered_types.iter().any(|t| t == ct_trimmed) {
-            return true;
-        }
-        if self.streamed_types.iter().any(|t| t == ct_trimmed) {
-            return false;
-        }
-
-        // Check for wildcard matches (e.g., "image/*" matches "image/png")
-        if let Some(slash_pos) = ct_trimmed.find('/') {
-            let wildcard = format!("{}/*", &ct_trimmed[..slash_pos]);
-
-            if self.buffered_types.iter().any(|t| t == &wildcard) {
+        // Normalize the content type: lowercase and remove charset/semantic (without allocating)
+        glob::with_essence(content_type, |essence| {
+            // Check for exact matches first (highest priority)
+            if self.buffered_types.iter().any(|t| t.eq_ignore_ascii_case(essence)) {
                 return true;
             }
-            if self.streamed_types.iter().any(|t| t == &wildcard) {
+            if self.streamed_types.iter().any(|t| t.eq_ignore_ascii_case(essence)) {
                 return false;
             }
-        }
-
-        // Check for partial wildcard matches (handles already defined wildcards)
-        for buffered in &self.buffered_types {
-            if buffered.ends_with("/*") && ct_trimmed.starts_with(&buffered[..buffered.len() - 1]) {
-                return true;
-            }
-        }
 
-        for streamed in &self.streamed_types {
-            if streamed.ends_with("/*") && ct_trimmed.starts_with(&streamed[..streamed.len() - 1]) {
-                return false;
+            // Check for wildcard matches (e.g., "image/*" matches "image/png");
+            // the most specific pattern wins, buffered on a tie
+            let buffered = glob::best_match(self.buffered_types.iter().map(|t| (t.as_str(), ())), essence);
+            let streamed = glob::best_match(self.streamed_types.iter().map(|t| (t.as_str(), ())), essence);
+            match (buffered, streamed) {
+                (Some((buffered, _)), Some((streamed, _))) => glob::specificity(buffered) >= glob::specificity(streamed),
+                (Some(_), None) => true,
+                (None, Some(_)) => false,
+                // Fall back to default behavior
+                (None, None) => self.default_is_buffered,
             }
-        }
-
-        // Fall back to default behavior
-        self.default_is_buffered
+        })
     }
 }
 
@@ -304,6 +345,37 @@ impl Default for BufferStrategy {
     }
 }
 
+/// What becomes of the unread rest of a rejected request body, or of a
+/// streamed one the handler answered without reading to the end.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RejectedBody {
+    /// Stop reading at once. HTTP/1.x rejections carry `Connection: close`,
+    /// so the client stops sending and the connection is not reused; on
+    /// HTTP/2 the server resets the request stream once the body is dropped.
+    #[default]
+    Abort,
+    /// Read and discard the rest of the body before responding, keeping
+    /// HTTP/1.1 connections reusable at the cost of receiving the whole upload.
+    Drain,
+    /// Drain up to this many more bytes, then give up and abort. Slightly
+    /// oversized requests keep their connection, large ones do not.
+    DrainUpTo(usize),
+}
+
+impl RejectedBody {
+    /// Drains up to `limit` more bytes (e.g. `"256kb"`), see [`RejectedBody::DrainUpTo`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum_jetpack::size_limit::middleware::{RejectedBody, SizeLimitMiddlewareConfig};
+    ///
+    /// let config = SizeLimitMiddlewareConfig::default().with_rejected_body(RejectedBody::drain_up_to("256kb"));
+    /// ```
+    pub fn drain_up_to(limit: impl Into<SizeLimit>) -> Self {
+        RejectedBody::DrainUpTo(limit.into().0)
+    }
+}
+
 /// Configuration for the size limit middleware.
 ///
 /// Combines size limits with buffering strategy to provide comprehensive
@@ -315,6 +387,136 @@ pub struct SizeLimitMiddlewareConfig {
 
     /// Strategy for deciding which content types to buffer vs. stream.
     pub buffer_strategy: BufferStrategy,
+
+    /// Methods passed straight through when the request has no body.
+    ///
+    /// Defaults to `GET`, `HEAD` and `OPTIONS`, so ordinary reads, CORS
+    /// preflights and `HEAD` probes skip content-type inspection and body
+    /// wrapping. Requests on these methods that do carry a body are still
+    /// limited.
+    pub fast_path_methods: Vec<Method>,
+
+    /// Methods passed straight through, body or not.
+    ///
+    /// Unlike [`fast_path_methods`](Self::fast_path_methods), requests on
+    /// these methods are never inspected, limited or rejected, e.g. for
+    /// `OPTIONS` and `CONNECT` handled by other layers. Empty by default.
+    pub exempt_methods: Vec<Method>,
+
+    /// Request paths passed straight through, body or not.
+    ///
+    /// Patterns follow [`path_pattern::matches`](crate::path_pattern::matches):
+    /// an exact path (`/health`) or a prefix (`/internal/*`). Meant for
+    /// infrastructure endpoints that need no limiting. Empty by default.
+    pub exempt_paths: Vec<String>,
+
+    /// Methods that must not carry a request body.
+    ///
+    /// Requests on these methods with a non-empty body (or a body announced by
+    /// `Content-Length`/`Transfer-Encoding`) are rejected with 400. Empty by
+    /// default; security baselines commonly require `GET` and `DELETE`.
+    pub bodyless_methods: Vec<Method>,
+
+    /// Verifies that bodies match their declared `Content-Length`.
+    ///
+    /// When enabled, bodies longer or shorter than their `Content-Length`,
+    /// requests declaring both `Content-Length` and `Transfer-Encoding`, and
+    /// unparseable `Content-Length` values are rejected with 400, catching
+    /// broken clients and request smuggling attempts. `false` by default.
+    pub strict_content_length: bool,
+
+    /// Rejects bodies without a `Content-Length` (chunked or of unknown
+    /// length) with 411, so every body's size is known before any of it is
+    /// read. `false` by default.
+    pub require_content_length: bool,
+
+    /// Longest wait for the next chunk of a body before it is rejected with
+    /// 408. `None` (no deadline) by default.
+    pub chunk_timeout: Option<Duration>,
+
+    /// Longest time to read a whole body, from the moment the limiter takes
+    /// the request, before it is rejected with 408. `None` (no deadline) by
+    /// default.
+    pub body_timeout: Option<Duration>,
+
+    /// Byte quota per key over a window; keys that used it up get 429.
+    /// `None` by default.
+    pub quota: Option<ByteQuota>,
+
+    /// Limits on the parts of `multipart/form-data` bodies (see
+    /// [`with_multipart_limits`](Self::with_multipart_limits)). `None` by
+    /// default.
+    pub multipart_limits: Option<Arc<MultipartLimits>>,
+
+    /// How rejections (413, 400) are rendered. Defaults to plain text.
+    pub error_format: ErrorFormat,
+
+    /// Callback fired when a client disconnects mid-body. `None` by default.
+    pub on_disconnect: Option<DisconnectHook>,
+
+    /// Whether rejections are enforced or only reported.
+    /// [`Enforcement::Enforce`] by default.
+    pub enforcement: Enforcement,
+
+    /// Header whose value picks the requests enforced by
+    /// [`Enforcement::Canary`], e.g. `x-request-id`. Requests without it, or
+    /// all requests when `None` (the default), are picked at random.
+    pub canary_key: Option<HeaderName>,
+
+    /// Callback fired for requests that would have been rejected, in
+    /// report-only mode or outside the canary share. `None` by default.
+    pub on_would_reject: Option<ReportHook>,
+
+    /// Threshold below the limit above which accepted requests are warned
+    /// about. `None` (no soft limit) by default.
+    pub soft_limit: Option<SoftLimit>,
+
+    /// Response header warning about bodies above the soft limit.
+    /// `Warning` by default.
+    pub soft_limit_header: HeaderName,
+
+    /// Callback fired for accepted requests above the soft limit. `None` by
+    /// default.
+    pub on_soft_limit: Option<SoftLimitHook>,
+
+    /// Callback fired as bodies are read (see
+    /// [`on_progress`](Self::on_progress)). `None` by default.
+    pub on_progress: Option<ProgressHook>,
+
+    /// Bytes between two calls of `on_progress`; `0` calls it for every
+    /// chunk.
+    pub progress_interval: usize,
+
+    /// Creates the hash function computing
+    /// [`BodyDigest`](crate::size_limit::BodyDigest)s (see
+    /// [`with_body_digest`](Self::with_body_digest)). `None` by default.
+    pub body_digest: Option<HasherFactory>,
+
+    /// Key verifying upload grants that raise the limit. `None` by default.
+    #[cfg(feature = "upload-grants")]
+    pub upload_grants: Option<crate::size_limit::UploadGrantKey>,
+
+    /// Number of `Content-Type` values whose rules are cached (see
+    /// [`ContentTypeMatcher::with_cache`]). `0` (disabled) by default.
+    pub lookup_cache: usize,
+
+    /// What becomes of the rest of a body rejected before it was fully read.
+    /// [`RejectedBody::Abort`] by default.
+    pub rejected_body: RejectedBody,
+
+    /// Attaches the limits to responses of `OPTIONS` requests (see
+    /// [`with_advertised_limits`](Self::with_advertised_limits)). `false` by
+    /// default.
+    pub advertise_limits: bool,
+
+    /// Memory shared by the buffered bodies of concurrent requests. `None`
+    /// (no cap beyond the per-request limits) by default.
+    pub buffer_budget: Option<BufferBudget>,
+
+    /// Response header carrying the body bytes read from the client (see
+    /// [`with_body_size_header`](Self::with_body_size_header)). `None` by
+    /// default.
+    pub body_size_header: Option<HeaderName>,
 }
 
 impl SizeLimitMiddlewareConfig {
@@ -333,6 +535,7 @@ impl SizeLimitMiddlewareConfig {
         Self {
             size_limits,
             buffer_strategy: BufferStrategy::new(),
+            ..Self::default()
         }
     }
 
@@ -352,7 +555,7 @@ impl SizeLimitMiddlewareConfig {
     pub fn with_default_buffer_strategy(size_limits: SizeLimitConfig) -> Self {
         Self {
             size_limits,
-            buffer_strategy: BufferStrategy::with_defaults(),
+            ..Self::default()
         }
     }
 
@@ -386,7 +589,7 @@ impl SizeLimitMiddlewareConfig {
     /// let config = SizeLimitMiddlewareConfig::default()
     ///     .with_buffered_types(&["application/custom+json"]);
     /// ```
-    pub fn with_buffered_types(mut self, types: &[&str]) -> Self {
+    pub fn with_buffered_types<T: ContentTypePattern>(mut self, types: &[T]) -> Self {
         self.buffer_strategy = self.buffer_strategy.with_buffered_types(types);
         self
     }
@@ -403,7 +606,7 @@ impl SizeLimitMiddlewareConfig {
     /// let config = SizeLimitMiddlewareConfig::default()
     ///     .with_streamed_types(&["model/gltf-binary"]);
     /// ```
-    pub fn with_streamed_types(mut self, types: &[&str]) -> Self {
+    pub fn with_streamed_types<T: ContentTypePattern>(mut self, types: &[T]) -> Self {
         self.buffer_strategy = self.buffer_strategy.with_streamed_types(types);
         self
     }
@@ -425,261 +628,1866 @@ impl SizeLimitMiddlewareConfig {
         self.buffer_strategy = self.buffer_strategy.with_default_buffered(is_buffered);
         self
     }
-}
 
-impl Default for SizeLimitMiddlewareConfig {
-    /// Returns a default middleware configuration with default size limits
-    /// and default buffer strategy.
-    fn default() -> Self {
-        Self {
-            size_limits: SizeLimitConfig::default(),
-            buffer_strategy: BufferStrategy::with_defaults(),
-        }
+    /// Builder method to set the methods that bypass the limiter when body-less.
+    ///
+    /// # Arguments
+    /// * `methods` - Methods to fast-path; an empty slice disables the fast path
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum::http::Method;
+    /// use axum_jetpack::size_limit::middleware::SizeLimitMiddlewareConfig;
+    ///
+    /// // Only skip body-less preflights
+    /// let config = SizeLimitMiddlewareConfig::default()
+    ///     .with_fast_path_methods(&[Method::OPTIONS]);
+    /// ```
+    pub fn with_fast_path_methods(mut self, methods: &[Method]) -> Self {
+        self.fast_path_methods = methods.to_vec();
+        self
     }
-}
-
-/// Applies size limiting middleware to an Axum router.
-///
-/// This middleware:
-/// 1. Inspects the Content-Type header of incoming requests
-/// 2. Checks Content-Length header for quick early rejection of obviously oversized requests
-/// 3. Uses the buffer strategy to decide whether to buffer or stream the request
-/// 4. Enforces size limits during processing
-/// 5. Returns 413 (Payload Too Large) if limits are exceeded
-///
-/// # Arguments
-/// * `router` - The Axum router to wrap with middleware
-/// * `config` - Configuration for size limits and buffering strategy
-///
-/// # Returns
-/// A new router with size limiting middleware applied.
-///
-/// # Example
-/// ```rust
-/// use axum::{Router, routing::post};
-/// use axum_jetpack::size_limit::{SizeLimitConfig, middleware::SizeLimitMiddlewareConfig, middleware::with_size_limit};
-///
-/// async fn upload_handler() -> &'static str {
-///     "Upload received"
-/// }
-///
-/// let router = Router::new()
-///     .route("/upload", post(upload_handler));
-///
-/// let config = SizeLimitMiddlewareConfig::default();
-/// let router = with_size_limit(router, config);
-/// ```
-pub fn with_size_limit(router: Router, config: SizeLimitMiddlewareConfig) -> Router {
-    let config = Arc::new(config);
-
-    router.layer(middleware::from_fn_with_state(
-        config,
-        |State(config): State<Arc<SizeLimitMiddlewareConfig>>, req: Request<Body>, next: Next| async move {
-            // Extract and normalize Content-Type header
-            let content_type = req.headers()
-                .get(axum::http::header::CONTENT_TYPE)
-                .and_then(|h| h.to_str().ok())
-                .unwrap_or("application/octet-stream"); // Default for unknown types
-
-            // Get size limit for this content type
-            let limit = config.size_limits.get_limit_for_content_type(content_type);
-
-            // Early rejection based on Content-Length header (if present)
-            if let Some(content_length) = req.headers().get(axum::http::header::CONTENT_LENGTH)
-                && let Ok(length_str) = content_length.to_str()
-                    && let Ok(content_length_value) = length_str.parse::<usize>()
-                        && content_length_value > limit {
-                            // Request is already too large based on Content-Length header
-                            return Ok((StatusCode::PAYLOAD_TOO_LARGE, "Payload too large").into_response());
-                        }
-
-            // Choose processing strategy based on content type
-            if config.buffer_strategy.should_buffer(content_type) {
-                buffer_with_limit(req, next, limit).await
-            } else {
-                stream_with_limit(req, next, limit).await
-            }
-        }
-    ))
-}
-
-/// Applies size limiting middleware with a simplified configuration.
-///
-/// This is a convenience wrapper that creates a default buffer strategy
-/// using the provided size limits.
-///
-/// # Arguments
-/// * `router` - The Axum router to wrap with middleware
-/// * `size_limits` - Size limits configuration
-///
-/// # Returns
-/// A new router with size limiting middleware applied.
-///
-/// # Example
-/// ```rust
-/// use axum::{Router, routing::post};
-/// use axum_jetpack::size_limit::{SizeLimitConfig, middleware::with_size_limit_simple};
-///
-/// async fn api_handler() -> &'static str {
-///     "API endpoint"
-/// }
-///
-/// let router = Router::new()
-///     .route("/api", post(api_handler));
-///
-/// let limits = SizeLimitConfig::default()
-///     .with_default_limit(1024 * 1024); // 1MB
-///
-/// let router = with_size_limit_simple(router, limits);
-/// ```
-pub fn with_size_limit_simple(router: Router, size_limits: SizeLimitConfig) -> Router {
-    let config = SizeLimitMiddlewareConfig::new(size_limits);
-    with_size_limit(router, config)
-}
 
-/// Processes a request with buffering strategy.
-///
-/// This function:
-/// 1. Reads the entire request body into memory
-/// 2. Checks if it exceeds the size limit
-/// 3. If within limits, continues processing
-/// 4. If exceeds limits, returns 413 (Payload Too Large)
-///
-/// # Arguments
-/// * `req` - The HTTP request
-/// * `next` - The next middleware/handler in the chain
-/// * `max_size` - Maximum allowed size in bytes
-///
-/// # Returns
-/// HTTP response or 413 error if size limit is exceeded.
-async fn buffer_with_limit(
-    mut req: Request<Body>,
-    next: Next,
-    max_size: usize,
-) -> Result<Response, StatusCode> {
-    use axum::response::IntoResponse;
+    /// Builder method to set the methods the limiter leaves alone entirely
+    /// (see [`exempt_methods`](Self::exempt_methods)).
+    ///
+    /// # Arguments
+    /// * `methods` - Methods passed through without inspecting or limiting the body
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum::http::Method;
+    /// use axum_jetpack::size_limit::middleware::SizeLimitMiddlewareConfig;
+    ///
+    /// let config = SizeLimitMiddlewareConfig::default()
+    ///     .with_exempt_methods(&[Method::OPTIONS, Method::CONNECT]);
+    /// ```
+    pub fn with_exempt_methods(mut self, methods: &[Method]) -> Self {
+        self.exempt_methods = methods.to_vec();
+        self
+    }
 
-    // Take ownership of the request body
-    let body = std::mem::take(req.body_mut());
-
-    // Read entire body into memory with size limit
-    match to_bytes(body, max_size).await {
-        Ok(bytes) => {
-            // Double-check size (to_bytes may read exactly max_size without error)
-            if bytes.len() > max_size {
-                return Ok((StatusCode::PAYLOAD_TOO_LARGE, "Payload too large").into_response());
-            }
+    /// Builder method to set the paths the limiter leaves alone entirely
+    /// (see [`exempt_paths`](Self::exempt_paths)).
+    ///
+    /// # Arguments
+    /// * `patterns` - Exact paths or prefixes ending in `/*`
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum_jetpack::size_limit::middleware::SizeLimitMiddlewareConfig;
+    ///
+    /// let config = SizeLimitMiddlewareConfig::default()
+    ///     .with_exempt_paths(["/health", "/metrics", "/internal/*"]);
+    /// ```
+    pub fn with_exempt_paths<I>(mut self, patterns: I) -> Self
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        self.exempt_paths = patterns.into_iter().map(Into::into).collect();
+        self
+    }
 
-            // Replace request body with buffered bytes
-            *req.body_mut() = Body::from(bytes);
+    /// Builder method to reject request bodies on the given methods.
+    ///
+    /// # Arguments
+    /// * `methods` - Methods on which any request body is refused with 400
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum::http::Method;
+    /// use axum_jetpack::size_limit::middleware::SizeLimitMiddlewareConfig;
+    ///
+    /// let config = SizeLimitMiddlewareConfig::default()
+    ///     .with_bodyless_methods(&[Method::GET, Method::DELETE]);
+    /// ```
+    pub fn with_bodyless_methods(mut self, methods: &[Method]) -> Self {
+        self.bodyless_methods = methods.to_vec();
+        self
+    }
 
-            // Continue to next middleware/handler
-            Ok(next.run(req).await)
-        }
-        Err(_) => {
-            // Body exceeded limit or other read error
-            Ok((StatusCode::PAYLOAD_TOO_LARGE, "Payload too large").into_response())
-        }
+    /// Builder method to verify bodies against their `Content-Length`
+    /// (see [`strict_content_length`](Self::strict_content_length)).
+    ///
+    /// Mismatches are rejected with [`SizeLimitError::ContentLengthMismatch`],
+    /// which a [`LimitPolicy`] observes through
+    /// [`rejected`](LimitPolicy::rejected), e.g. to log them.
+    ///
+    /// # Arguments
+    /// * `strict` - Whether to reject mismatching bodies
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum_jetpack::size_limit::middleware::SizeLimitMiddlewareConfig;
+    ///
+    /// let config = SizeLimitMiddlewareConfig::default().with_strict_content_length(true);
+    /// ```
+    pub fn with_strict_content_length(mut self, strict: bool) -> Self {
+        self.strict_content_length = strict;
+        self
     }
-}
 
-/// Processes a request with streaming strategy.
-///
-/// This function:
-/// 1. Sets up a streaming pipeline with size monitoring
-/// 2. Processes chunks as they arrive
-/// 3. Tracks total size and stops processing if limit is exceeded
-/// 4. Returns 413 if limit is exceeded during streaming
-///
-/// # Arguments
-/// * `req` - The HTTP request
-/// * `next` - The next middleware/handler in the chain
-/// * `max_size` - Maximum allowed size in bytes
-///
-/// # Returns
-/// HTTP response or 413 error if size limit is exceeded during streaming.
-async fn stream_with_limit(
-    req: Request<Body>,
-    next: Next,
-    max_size: usize,
-) -> Result<Response, StatusCode> {
-    use axum::response::IntoResponse;
+    /// Builder method to require a `Content-Length` on every request body
+    /// (see [`require_content_length`](Self::require_content_length)).
+    ///
+    /// # Arguments
+    /// * `required` - Whether to reject bodies of unknown length with 411
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum_jetpack::size_limit::middleware::SizeLimitMiddlewareConfig;
+    ///
+    /// let config = SizeLimitMiddlewareConfig::default().with_required_content_length(true);
+    /// ```
+    pub fn with_required_content_length(mut self, required: bool) -> Self {
+        self.require_content_length = required;
+        self
+    }
 
-    // Create a channel for streaming the body with backpressure
-    let (tx, rx) = tokio::sync::mpsc::channel::<Result<Bytes, axum::Error>>(32);
-    let (parts, body) = req.into_parts();
+    /// Builder method to reject bodies whose next chunk takes longer than
+    /// `timeout` to arrive (see [`chunk_timeout`](Self::chunk_timeout)).
+    ///
+    /// A client trickling a byte a minute gets 408 (Request Timeout) instead
+    /// of holding a connection and a handler indefinitely. The clock runs
+    /// while the body is being read: for streamed bodies, while the handler
+    /// waits for it.
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum_jetpack::size_limit::middleware::SizeLimitMiddlewareConfig;
+    /// use std::time::Duration;
+    ///
+    /// let config = SizeLimitMiddlewareConfig::default()
+    ///     .with_chunk_timeout(Duration::from_secs(10))
+    ///     .with_body_timeout(Duration::from_secs(120));
+    /// ```
+    pub fn with_chunk_timeout(mut self, timeout: Duration) -> Self {
+        self.chunk_timeout = Some(timeout);
+        self
+    }
 
-    // Shared flag to indicate if size limit was exceeded
-    let limit_exceeded = Arc::new(std::sync::atomic::AtomicBool::new(false));
-    let limit_exceeded_clone = limit_exceeded.clone();
-
-    // Channel to communicate if we should call the next handler
-    let (handler_tx, handler_rx) = tokio::sync::oneshot::channel::<bool>();
-
-    // Spawn a task to read and forward the stream with size checking
-    tokio::spawn(async move {
-        let mut stream = body.into_data_stream();
-        let mut total_size = 0usize;
-        let mut should_call_handler = true;
-
-        // Process stream chunks
-        while let Some(chunk_result) = stream.next().await {
-            match chunk_result {
-                Ok(chunk) => {
-                    total_size += chunk.len();
-
-                    // Check if we've exceeded the limit
-                    if total_size > max_size {
-                        limit_exceeded_clone.store(true, std::sync::atomic::Ordering::SeqCst);
-                        should_call_handler = false;
-                        break;
-                    }
+    /// Builder method to reject bodies not read in full within `timeout`
+    /// with 408 (Request Timeout) (see [`body_timeout`](Self::body_timeout)).
+    pub fn with_body_timeout(mut self, timeout: Duration) -> Self {
+        self.body_timeout = Some(timeout);
+        self
+    }
 
-                    // Forward chunk to the receiver
-                    if tx.send(Ok(chunk)).await.is_err() {
-                        // Receiver dropped, stop processing
-                        should_call_handler = false;
-                        break;
-                    }
-                }
-                Err(e) => {
-                    // Forward error to receiver
-                    let _ = tx.send(Err(e)).await;
-                    should_call_handler = false;
-                    break;
+    /// Builder method to account request bodies against a byte quota per key
+    /// (see [`ByteQuota`]).
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum_jetpack::size_limit::{ByteQuota, middleware::SizeLimitMiddlewareConfig};
+    /// use std::time::Duration;
+    ///
+    /// // 100 MB per API key per hour
+    /// let config = SizeLimitMiddlewareConfig::default()
+    ///     .with_quota(ByteQuota::new("100mb", Duration::from_secs(3600)).with_header_key("x-api-key"));
+    /// ```
+    pub fn with_quota(mut self, quota: ByteQuota) -> Self {
+        self.quota = Some(quota);
+        self
+    }
+
+    /// Builder method to enforce limits on the parts of
+    /// `multipart/form-data` bodies, by kind (text field or file) and by
+    /// field name, as the body streams through.
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum_jetpack::size_limit::{MultipartLimits, middleware::SizeLimitMiddlewareConfig};
+    ///
+    /// let config = SizeLimitMiddlewareConfig::default()
+    ///     .with_multipart_limits(MultipartLimits::new().with_field_limit("16kb").with_file_limit("50mb"));
+    /// ```
+    pub fn with_multipart_limits(mut self, limits: MultipartLimits) -> Self {
+        self.multipart_limits = Some(Arc::new(limits));
+        self
+    }
+
+    /// Builder method to set how rejections are rendered.
+    ///
+    /// # Arguments
+    /// * `format` - The error format for 413/400 responses
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum_jetpack::error::ErrorFormat;
+    /// use axum_jetpack::size_limit::middleware::SizeLimitMiddlewareConfig;
+    ///
+    /// let config = SizeLimitMiddlewareConfig::default()
+    ///     .with_error_format(ErrorFormat::ProblemDetails);
+    /// ```
+    pub fn with_error_format(mut self, format: ErrorFormat) -> Self {
+        self.error_format = format;
+        self
+    }
+
+    /// Builder method to set a callback fired when a client disconnects mid-body.
+    ///
+    /// The callback runs when the body fails with a transport error or ends
+    /// before its declared `Content-Length`, with the route and the number of
+    /// bytes received, so partial state (temp files, multipart uploads) can be
+    /// cleaned up without waiting for timeouts. It runs on the request task;
+    /// keep it short or hand off to a background task.
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum_jetpack::size_limit::middleware::SizeLimitMiddlewareConfig;
+    ///
+    /// let config = SizeLimitMiddlewareConfig::default().on_disconnect(|info| {
+    ///     eprintln!("{} {} aborted after {} bytes", info.method, info.route, info.bytes_received);
+    /// });
+    /// ```
+    pub fn on_disconnect<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&DisconnectInfo) + Send + Sync + 'static,
+    {
+        self.on_disconnect = Some(Arc::new(hook));
+        self
+    }
+
+    /// Builder method to enforce limits or only report would-be rejections
+    /// (see [`Enforcement`]).
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum_jetpack::size_limit::{Enforcement, middleware::SizeLimitMiddlewareConfig};
+    ///
+    /// // Observe a tighter limit before turning it on
+    /// let config = SizeLimitMiddlewareConfig::default()
+    ///     .with_enforcement(Enforcement::ReportOnly)
+    ///     .on_would_reject(|report| {
+    ///         eprintln!("{} {} would get {}", report.method, report.route, report.error.status());
+    ///     });
+    /// ```
+    pub fn with_enforcement(mut self, enforcement: Enforcement) -> Self {
+        self.enforcement = enforcement;
+        self
+    }
+
+    /// Builder method to enforce limits for `percent` of the requests only,
+    /// reporting the rest (see [`Enforcement::Canary`]).
+    ///
+    /// # Arguments
+    /// * `percent` - Share of requests enforced, 0-100
+    /// * `key` - Header picking the requests deterministically, e.g.
+    ///   `x-request-id`; `None` picks them at random
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum::http::HeaderName;
+    /// use axum_jetpack::size_limit::middleware::SizeLimitMiddlewareConfig;
+    ///
+    /// // Tighten limits for 5% of the traffic first
+    /// let config = SizeLimitMiddlewareConfig::default()
+    ///     .with_canary(5, Some(HeaderName::from_static("x-request-id")))
+    ///     .on_would_reject(|report| eprintln!("would reject {} {}", report.method, report.route));
+    /// ```
+    pub fn with_canary(mut self, percent: u8, key: Option<HeaderName>) -> Self {
+        self.enforcement = Enforcement::Canary(percent.min(100));
+        self.canary_key = key;
+        self
+    }
+
+    /// Builder method to set a callback fired for every request that would
+    /// have been rejected in report-only mode, or outside the canary share.
+    ///
+    /// The callback runs at most once per request, on the request task, when
+    /// the violation is detected: from the headers or while the body is read.
+    pub fn on_would_reject<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&WouldReject) + Send + Sync + 'static,
+    {
+        self.on_would_reject = Some(Arc::new(hook));
+        self
+    }
+
+    /// Builder method to warn about bodies above a soft limit.
+    ///
+    /// Requests between the soft and the enforced limit are accepted, but
+    /// their response gets a `Warning: 299` header (see
+    /// [`with_soft_limit_header`](Self::with_soft_limit_header)) and
+    /// [`on_soft_limit`](Self::on_soft_limit) fires, giving clients advance
+    /// notice before a limit is tightened.
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum_jetpack::size_limit::{SoftLimit, middleware::SizeLimitMiddlewareConfig};
+    ///
+    /// // Warn from 80% of each request's limit
+    /// let config = SizeLimitMiddlewareConfig::default()
+    ///     .with_soft_limit(SoftLimit::Percent(80))
+    ///     .on_soft_limit(|info| eprintln!("{} sent {} of {} bytes", info.route, info.body_size, info.limit));
+    /// ```
+    pub fn with_soft_limit(mut self, soft_limit: SoftLimit) -> Self {
+        self.soft_limit = Some(soft_limit);
+        self
+    }
+
+    /// Builder method to send the soft limit warning in a custom header
+    /// instead of `Warning`.
+    pub fn with_soft_limit_header(mut self, header: HeaderName) -> Self {
+        self.soft_limit_header = header;
+        self
+    }
+
+    /// Builder method to set a callback fired for accepted requests above
+    /// the soft limit, once the response is ready.
+    pub fn on_soft_limit<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&SoftLimitExceeded) + Send + Sync + 'static,
+    {
+        self.on_soft_limit = Some(Arc::new(hook));
+        self
+    }
+
+    /// Builder method to set a callback fired as bodies are read.
+    ///
+    /// The callback gets the bytes read so far, the limit and the content
+    /// type, on the first chunk and then each time the body crosses a
+    /// multiple of `every` bytes (every chunk when `every` is `0`). It runs
+    /// on the task reading the body, so it must be quick. Returning
+    /// [`ControlFlow::Break`] aborts the upload
+    /// with 413 (Payload Too Large).
+    ///
+    /// # Arguments
+    /// * `every` - Bytes between two calls
+    /// * `hook` - The callback
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum_jetpack::size_limit::middleware::SizeLimitMiddlewareConfig;
+    /// use std::ops::ControlFlow;
+    /// use tokio::sync::watch;
+    ///
+    /// // Publish the progress to whoever watches it, e.g. a progress API
+    /// let (progress, _watch) = watch::channel(0u64);
+    /// let config = SizeLimitMiddlewareConfig::default().on_progress("1mb", move |upload| {
+    ///     progress.send_replace(upload.consumed);
+    ///     ControlFlow::Continue(())
+    /// });
+    /// ```
+    pub fn on_progress<F>(mut self, every: impl Into<SizeLimit>, hook: F) -> Self
+    where
+        F: Fn(&UploadProgress<'_>) -> ControlFlow<()> + Send + Sync + 'static,
+    {
+        self.progress_interval = every.into().0;
+        self.on_progress = Some(Arc::new(hook));
+        self
+    }
+
+    /// Builder method to hash bodies as their size is checked.
+    ///
+    /// Every request the limiter reads gets a
+    /// [`BodyDigest`](crate::size_limit::BodyDigest) in its request
+    /// and response extensions, set once the body has been read to its end
+    /// within the limit. Any [`BodyHasher`] works; with the `body-digest`
+    /// feature every [`sha2::Digest`](https://docs.rs/sha2) hash is one.
+    ///
+    /// # Example
+    /// ```rust
+    /// # #[cfg(feature = "body-digest")]
+    /// # {
+    /// use axum_jetpack::size_limit::{Sha256, middleware::SizeLimitMiddlewareConfig};
+    ///
+    /// let config = SizeLimitMiddlewareConfig::default().with_body_digest::<Sha256>();
+    /// # }
+    /// ```
+    pub fn with_body_digest<H: BodyHasher + Default>(mut self) -> Self {
+        self.body_digest = Some(Arc::new(|| Box::new(H::default())));
+        self
+    }
+
+    /// Builder method to advertise the limits on `OPTIONS` responses.
+    ///
+    /// Responses to `OPTIONS` requests passing through the layer, such as
+    /// CORS preflights, get two headers so browsers and SDKs can check files
+    /// before uploading them:
+    /// - `X-Max-Body-Size`: the limit the request would get with the method
+    ///   of `Access-Control-Request-Method` (if sent) and its `Content-Type`
+    /// - `Max-Upload-Size`: the content-type table of
+    ///   [`size_limits`](Self::size_limits), e.g.
+    ///   `application/json=100000, image/*=5000000, */*=1000000`
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum_jetpack::size_limit::middleware::SizeLimitMiddlewareConfig;
+    ///
+    /// let config = SizeLimitMiddlewareConfig::default().with_advertised_limits(true);
+    /// ```
+    pub fn with_advertised_limits(mut self, advertise: bool) -> Self {
+        self.advertise_limits = advertise;
+        self
+    }
+
+    /// Builder method to report the body bytes read from the client in a
+    /// response header.
+    ///
+    /// The count is final: it covers bytes drained after the handler
+    /// stopped reading or after a rejection. It is always available in the
+    /// response extensions as an [`ObservedBodySize`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum::http::HeaderName;
+    /// use axum_jetpack::size_limit::middleware::SizeLimitMiddlewareConfig;
+    ///
+    /// let config = SizeLimitMiddlewareConfig::default()
+    ///     .with_body_size_header(HeaderName::from_static("x-request-body-size"));
+    /// ```
+    pub fn with_body_size_header(mut self, header: HeaderName) -> Self {
+        self.body_size_header = Some(header);
+        self
+    }
+
+    /// Builder method to cap the request bodies buffered at once.
+    ///
+    /// Requests that would buffer their body take their share of `budget`
+    /// first, and are refused with 503 or 429, or wait, when it has no room
+    /// left (see [`OnExhausted`](crate::size_limit::OnExhausted)). Share one
+    /// budget between several layers to cap them together.
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum_jetpack::size_limit::{BufferBudget, middleware::SizeLimitMiddlewareConfig};
+    ///
+    /// let config = SizeLimitMiddlewareConfig::default().with_buffer_budget(BufferBudget::new("512mb"));
+    /// ```
+    pub fn with_buffer_budget(mut self, budget: BufferBudget) -> Self {
+        self.buffer_budget = Some(budget);
+        self
+    }
+
+    /// Builder method to accept signed upload grants.
+    ///
+    /// A request presenting a valid, unexpired grant for its route in the
+    /// `x-upload-grant` header, or a one-off limit signed for its path in
+    /// `x-upload-limit` and `x-upload-limit-signature`, gets that limit if it
+    /// is higher than the configured one. Invalid, expired or foreign-route
+    /// grants are ignored and the configured limit applies.
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum_jetpack::size_limit::{UploadGrantKey, middleware::SizeLimitMiddlewareConfig};
+    ///
+    /// let config = SizeLimitMiddlewareConfig::default()
+    ///     .with_upload_grants(UploadGrantKey::new(b"a long random secret from your vault".to_vec()));
+    /// ```
+    #[cfg(feature = "upload-grants")]
+    pub fn with_upload_grants(mut self, key: crate::size_limit::UploadGrantKey) -> Self {
+        self.upload_grants = Some(key);
+        self
+    }
+
+    /// Builder method to cache the rules of recently seen `Content-Type`
+    /// values (see [`ContentTypeMatcher::with_cache`]).
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum_jetpack::size_limit::middleware::SizeLimitMiddlewareConfig;
+    ///
+    /// let config = SizeLimitMiddlewareConfig::default().with_lookup_cache(16);
+    /// ```
+    pub fn with_lookup_cache(mut self, capacity: usize) -> Self {
+        self.lookup_cache = capacity;
+        self
+    }
+
+    /// Builder method to choose what becomes of the rest of a rejected body.
+    ///
+    /// # Arguments
+    /// * `handling` - Abort the upload or drain it for connection reuse
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum_jetpack::size_limit::middleware::{RejectedBody, SizeLimitMiddlewareConfig};
+    ///
+    /// let config = SizeLimitMiddlewareConfig::default().with_rejected_body(RejectedBody::Drain);
+    /// ```
+    pub fn with_rejected_body(mut self, handling: RejectedBody) -> Self {
+        self.rejected_body = handling;
+        self
+    }
+}
+
+/// Compiles the content-type lookup table of a configuration.
+pub(crate) fn compile_matcher(config: &SizeLimitMiddlewareConfig) -> ContentTypeMatcher {
+    ContentTypeMatcher::compile(&config.size_limits, &config.buffer_strategy).with_cache(config.lookup_cache)
+}
+
+fn default_fast_path_methods() -> Vec<Method> {
+    vec![Method::GET, Method::HEAD, Method::OPTIONS]
+}
+
+impl Default for SizeLimitMiddlewareConfig {
+    /// Returns a default middleware configuration with default size limits
+    /// and default buffer strategy.
+    fn default() -> Self {
+        Self {
+            size_limits: SizeLimitConfig::default(),
+            buffer_strategy: BufferStrategy::with_defaults(),
+            fast_path_methods: default_fast_path_methods(),
+            exempt_methods: Vec::new(),
+            exempt_paths: Vec::new(),
+            bodyless_methods: Vec::new(),
+            strict_content_length: false,
+            require_content_length: false,
+            chunk_timeout: None,
+            body_timeout: None,
+            quota: None,
+            multipart_limits: None,
+            error_format: ErrorFormat::default(),
+            on_disconnect: None,
+            enforcement: Enforcement::Enforce,
+            canary_key: None,
+            on_would_reject: None,
+            soft_limit: None,
+            soft_limit_header: header::WARNING,
+            on_soft_limit: None,
+            on_progress: None,
+            progress_interval: 0,
+            body_digest: None,
+            #[cfg(feature = "upload-grants")]
+            upload_grants: None,
+            lookup_cache: 0,
+            rejected_body: RejectedBody::Abort,
+            advertise_limits: false,
+            body_size_header: None,
+            buffer_budget: None,
+        }
+    }
+}
+
+/// Applies size limiting middleware to an Axum router.
+///
+/// Convenience wrapper adding a [`SizeLimitLayer`] to the router. This middleware:
+/// 1. Passes requests on exempt methods and paths (none by default) straight through
+/// 2. Rejects bodies on methods configured as body-less (400, opt-in)
+/// 3. Passes body-less requests on fast-path methods (`GET`, `HEAD`, `OPTIONS` by default) straight through
+/// 4. Inspects the Content-Type header of incoming requests
+/// 5. Checks Content-Length header for quick early rejection of obviously oversized requests
+/// 6. Uses the buffer strategy to decide whether to buffer or stream the request
+/// 7. Enforces size limits during processing
+/// 8. Returns 413 (Payload Too Large) if limits are exceeded, rendered with the configured `error_format`
+///
+/// # Arguments
+/// * `router` - The Axum router to wrap with middleware
+/// * `config` - Configuration for size limits and buffering strategy
+///
+/// # Returns
+/// A new router with size limiting middleware applied.
+///
+/// # Example
+/// ```rust
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::size_limit::{SizeLimitConfig, middleware::SizeLimitMiddlewareConfig, middleware::with_size_limit};
+///
+/// async fn upload_handler() -> &'static str {
+///     "Upload received"
+/// }
+///
+/// let router = Router::new()
+///     .route("/upload", post(upload_handler));
+///
+/// let config = SizeLimitMiddlewareConfig::default();
+/// let router = with_size_limit(router, config);
+/// ```
+pub fn with_size_limit(router: Router, config: SizeLimitMiddlewareConfig) -> Router {
+    router.layer(SizeLimitLayer::new(config))
+}
+
+/// Applies size limiting middleware with a custom [`LimitPolicy`].
+///
+/// Works like [`with_size_limit`], except that the limit and buffering mode
+/// of each request with a body come from `policy` instead of
+/// `config.size_limits`/`config.buffer_strategy`. Body-less methods, the fast
+/// path, the disconnect hook, upload grants and the error format still come
+/// from `config`.
+///
+/// # Arguments
+/// * `router` - The Axum router to wrap with middleware
+/// * `config` - Method rules, hooks and error format
+/// * `policy` - The admission policy
+///
+/// # Returns
+/// A new router with size limiting middleware applied.
+///
+/// # Example
+/// ```rust
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::size_limit::{
+///     Buffering, Decision, LimitPolicy, RequestContext, SizeLimitMiddlewareConfig, with_size_limit_policy,
+/// };
+///
+/// struct Flat(usize);
+///
+/// impl LimitPolicy for Flat {
+///     async fn decide(&self, _ctx: RequestContext<'_>) -> Decision {
+///         Decision::Accept { limit: self.0, buffering: Buffering::Stream }
+///     }
+/// }
+///
+/// let router: Router = Router::new().route("/upload", post(|| async { "ok" }));
+/// let router = with_size_limit_policy(router, SizeLimitMiddlewareConfig::default(), Flat(1 << 20));
+/// ```
+pub fn with_size_limit_policy<P: LimitPolicy>(
+    router: Router,
+    config: SizeLimitMiddlewareConfig,
+    policy: P,
+) -> Router {
+    router.layer(SizeLimitLayer::with_policy(config, policy))
+}
+
+/// Tower layer applying the size limiting middleware.
+///
+/// This is the middleware itself: [`with_size_limit`] and
+/// [`with_size_limit_policy`] only add it to a router. Use it directly to
+/// stack it with other tower layers, or to wrap a service that is not a
+/// `Router`. Like the function-based middleware it buffers or streams each
+/// body according to the configured [`BufferStrategy`] (or the policy's
+/// decision).
+///
+/// Outside a router there is no matched route, so route conditions of rules
+/// and policies see `None`.
+///
+/// # Example
+/// ```rust
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::size_limit::{SizeLimitConfig, SizeLimitLayer, SizeLimitMiddlewareConfig};
+/// use tower::ServiceBuilder;
+///
+/// let config = SizeLimitMiddlewareConfig::new(SizeLimitConfig::default().with_default_limit("2mb"));
+///
+/// let router: Router = Router::new()
+///     .route("/upload", post(|| async { "ok" }))
+///     .layer(ServiceBuilder::new().layer(SizeLimitLayer::new(config)));
+/// ```
+pub struct SizeLimitLayer<P = ContentTypeMatcher> {
+    state: Arc<(SizeLimitMiddlewareConfig, P)>,
+}
+
+impl SizeLimitLayer {
+    /// Creates a size limiting layer using the limits and buffer strategy of
+    /// `config`.
+    ///
+    /// # Arguments
+    /// * `config` - Configuration for size limits and buffering strategy
+    pub fn new(config: SizeLimitMiddlewareConfig) -> Self {
+        // Resolve content-type rules once instead of on every request
+        let matcher = compile_matcher(&config);
+        Self::with_policy(config, matcher)
+    }
+}
+
+impl<P: LimitPolicy> SizeLimitLayer<P> {
+    /// Creates a size limiting layer with a custom [`LimitPolicy`].
+    ///
+    /// # Arguments
+    /// * `config` - Method rules, hooks and error format
+    /// * `policy` - The admission policy
+    pub fn with_policy(config: SizeLimitMiddlewareConfig, policy: P) -> Self {
+        Self {
+            state: Arc::new((config, policy)),
+        }
+    }
+}
+
+impl<P> Clone for SizeLimitLayer<P> {
+    fn clone(&self) -> Self {
+        Self {
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<S, P> Layer<S> for SizeLimitLayer<P> {
+    type Service = SizeLimitService<S, P>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SizeLimitService {
+            inner,
+            state: self.state.clone(),
+        }
+    }
+}
+
+/// Service created by [`SizeLimitLayer`].
+///
+/// For every request it:
+/// 1. Rejects bodies on methods configured as body-less (400, opt-in)
+/// 2. Passes body-less requests on fast-path methods (`GET`, `HEAD`, `OPTIONS` by default) straight through
+/// 3. Asks the policy for the limit and buffering mode
+/// 4. Checks Content-Length header for quick early rejection of obviously oversized requests
+/// 5. Buffers or streams the body while enforcing the limit
+/// 6. Returns 413 (Payload Too Large) if limits are exceeded, rendered with the configured `error_format`
+///
+/// Readiness is the inner service's: `poll_ready` returns its errors as they
+/// are, and so does `call`, so failures are never turned into rejections or
+/// a readiness that never resolves.
+pub struct SizeLimitService<S, P = ContentTypeMatcher> {
+    inner: S,
+    state: Arc<(SizeLimitMiddlewareConfig, P)>,
+}
+
+impl<S: Clone, P> Clone for SizeLimitService<S, P> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            state: self.state.clone(),
+        }
+    }
+}
+
+/// Accepts inner services with any response body, such as tonic services or
+/// tower-http wrappers; responses are returned with an axum [`Body`].
+impl<S, P, B> Service<Request<Body>> for SizeLimitService<S, P>
+where
+    S: Service<Request<Body>, Response = http::Response<B>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    S::Error: Send + 'static,
+    B: HttpBody<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+    P: LimitPolicy,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = BoxFuture<'static, Result<Response, S::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        // Call the service that was made ready, leave a fresh clone in its place
+        let clone = self.inner.clone();
+        let inner = std::mem::replace(&mut self.inner, clone);
+        Box::pin(limit_request(self.state.clone(), req, AxumResponse(inner)))
+    }
+}
+
+/// Adapts the response body of a service to [`Body`].
+struct AxumResponse<S>(S);
+
+impl<S, B> Service<Request<Body>> for AxumResponse<S>
+where
+    S: Service<Request<Body>, Response = http::Response<B>>,
+    B: HttpBody<Data = Bytes> + Send + 'static,
+    B::Error: Into<BoxError>,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = MapOk<S::Future, fn(http::Response<B>) -> Response>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.0.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        self.0.call(req).map_ok(|response| response.map(Body::new))
+    }
+}
+
+/// Applies the size limiter to one request.
+async fn limit_request<S, P>(
+    state: Arc<(SizeLimitMiddlewareConfig, P)>,
+    req: Request<Body>,
+    mut inner: S,
+) -> Result<Response, S::Error>
+where
+    S: Service<Request<Body>, Response = Response>,
+    P: LimitPolicy,
+{
+    let (config, policy) = &*state;
+
+    // Exempt methods and paths bypass the limiter altogether
+    if config.exempt_methods.contains(req.method())
+        || config
+            .exempt_paths
+            .iter()
+            .any(|pattern| crate::path_pattern::matches(pattern, req.uri().path()))
+    {
+        return inner.call(req).await;
+    }
+
+    // Kept for reporting rejections once the request has been consumed
+    let content_type = req.headers().get(axum::http::header::CONTENT_TYPE).cloned();
+
+    // Limits advertised on the response of an `OPTIONS` request
+    let advertised = match config.advertise_limits && req.method() == Method::OPTIONS {
+        true => Some(advertised_limits(config, policy, upload_parts(&req)).await),
+        false => None,
+    };
+
+    // Count the bytes read from the client, whatever becomes of the request
+    let (req, body_size) = ObservedBodySize::attach(req);
+
+    // Set once a limit is chosen, if soft limits are configured
+    let mut soft_meter = None;
+
+    // Set once a limit is chosen, if body digests are configured
+    let mut body_digest = None;
+
+    // Key and usage of the request, if it is accounted against a quota
+    let mut quota_usage = None;
+
+    let result: Result<Response, S::Error> = async {
+        // Per-route format from `with_error_formats`, then the policy's, else the configured one
+        let route = req.extensions().get::<axum::extract::MatchedPath>().map(|matched| matched.as_str());
+        let fallback = policy.error_format(route).unwrap_or(config.error_format);
+        let error_format = ErrorFormat::for_request(req.extensions(), fallback);
+        let mut rejection = RejectionContext {
+            error_format,
+            rejected_body: config.rejected_body,
+            version: req.version(),
+            limit: None,
+            headers: config.size_limits.rejection_headers,
+            part: None,
+        };
+
+        // Report-only mode (or outside the canary share): would-be rejections are reported and the request goes on
+        let enforced = config.enforcement.enforces(req.headers(), config.canary_key.as_ref());
+        let mut reporter =
+            (!enforced).then(|| Reporter::new(config.on_would_reject.clone(), would_reject(&req, route)));
+        macro_rules! refuse {
+            ($error:expr, $req:ident) => {
+                match &reporter {
+                    Some(reporter) => reporter.report($error, 0),
+                    None => return Ok(rejection.reject($error, $req.into_body()).await),
                 }
+            };
+        }
+
+        // Strict mode: refuse any body on methods configured as body-less
+        if config.bodyless_methods.contains(req.method()) && has_body(&req) {
+            refuse!(SizeLimitError::UnexpectedBody, req);
+        }
+
+        // Strict mode: the body must be framed by exactly one valid length or encoding
+        if config.strict_content_length && has_ambiguous_framing(&req) {
+            refuse!(SizeLimitError::ContentLengthMismatch, req);
+        }
+
+        // Fast path: body-less GET/HEAD/OPTIONS (e.g. CORS preflights) need no limiting
+        if config.fast_path_methods.contains(req.method()) && is_bodyless(&req, config.strict_content_length) {
+            return inner.call(req).await;
+        }
+
+        // Watch for clients disconnecting mid-body
+        let req = match &config.on_disconnect {
+            Some(hook) => watch_disconnect(req, hook.clone()),
+            None => req,
+        };
+
+        // Ask the policy for the limit and buffering mode
+        let (parts, body) = req.into_parts();
+        let content_length = parts
+            .headers
+            .get(axum::http::header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse::<u64>().ok());
+        let decision = policy
+            .decide(RequestContext {
+                parts: &parts,
+                route: parts
+                    .extensions
+                    .get::<axum::extract::MatchedPath>()
+                    .map(|matched| matched.as_str()),
+                content_type: parts
+                    .headers
+                    .get(axum::http::header::CONTENT_TYPE)
+                    .and_then(|h| h.to_str().ok())
+                    .unwrap_or("application/octet-stream"), // Default for unknown types
+                content_length,
+            })
+            .await;
+        let mut req = Request::from_parts(parts, body);
+
+        let (limit, buffering) = match decision {
+            Decision::Accept { limit, buffering } => (limit, buffering),
+            Decision::Reject(error) => {
+                refuse!(error, req);
+                return inner.call(req).await;
+            }
+            Decision::Bypass => return inner.call(req).await,
+        };
+
+        // A `SizeLimitOverride` layer that ran first replaces the limit
+        let limit = req
+            .extensions()
+            .get::<SizeLimitOverride>()
+            .map_or(limit, SizeLimitOverride::limit);
+
+        // A valid upload grant for this route may raise the limit
+        #[cfg(feature = "upload-grants")]
+        let limit = granted_limit(config, &req).map_or(limit, |granted| limit.max(granted));
+
+        // Lets overrides on the route tighten the limit further
+        req.extensions_mut().insert(EnforcedLimit { limit, error_format });
+
+        // Tells handlers which limit applies
+        let info = SizeLimitInfo::new(
+            limit,
+            &config.size_limits,
+            req.headers()
+                .get(header::CONTENT_TYPE)
+                .and_then(|h| h.to_str().ok())
+                .unwrap_or("application/octet-stream"),
+        );
+        req.extensions_mut().insert(info);
+        if let Some(reporter) = reporter.as_mut() {
+            reporter.set_limit(limit);
+        }
+        rejection.limit = Some(limit);
+
+        // Keys that used up their quota, or would with this body, are refused before it is read
+        if let Some(quota) = &config.quota {
+            let (parts, body) = req.into_parts();
+            quota_usage = quota.check(&parts).await;
+            req = Request::from_parts(parts, body);
+            if let Some((_, usage)) = &quota_usage
+                && quota.exceeded(*usage, content_length.max(Some(req.body().size_hint().lower())))
+            {
+                refuse!(SizeLimitError::QuotaExceeded, req);
+            }
+        }
+
+        // Hash the body as its size is checked
+        let digest = config.body_digest.as_ref().map(|hasher| {
+            let (meter, digest) = DigestMeter::new(hasher());
+            req.extensions_mut().insert(digest.clone());
+            body_digest = Some(digest);
+            meter
+        });
+
+        // Nothing to read: an empty body needs no wrapping
+        if is_bodyless(&req, config.strict_content_length) {
+            if let Some(digest) = &digest {
+                digest.finish();
+            }
+            return inner.call(req).await;
+        }
+
+        // Compare the counted body with the soft limit once answered
+        if let Some(soft_limit) = config.soft_limit {
+            let route = route_or_path(&req);
+            soft_meter = Some(SoftMeter::new(&req, route, body_size.clone(), soft_limit.threshold(limit), limit));
+        }
+
+        // Bodies of unknown length are refused before any of them is read
+        if config.require_content_length && content_length.is_none() {
+            refuse!(SizeLimitError::LengthRequired, req);
+        }
+
+        let content_type = req
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .and_then(|h| h.to_str().ok())
+            .unwrap_or("application/octet-stream");
+        let progress = config
+            .on_progress
+            .clone()
+            .map(|hook| ProgressMeter::new(hook, config.progress_interval, limit, content_type));
+        let multipart = config
+            .multipart_limits
+            .clone()
+            .and_then(|limits| MultipartMeter::new(limits, content_type));
+
+        // Strict mode: the body must match its declared length
+        let budget = BodyBudget::new(limit, content_length.filter(|_| config.strict_content_length))
+            .with_max_chunk(config.size_limits.max_chunk_size)
+            .with_progress(progress)
+            .with_digest(digest)
+            .with_multipart(multipart)
+            .with_timeouts(config.chunk_timeout, config.body_timeout);
+
+        // Nothing to enforce: skip counting the body altogether
+        if SizeLimit(limit).is_unlimited() && budget.expected.is_none() && budget.progress.is_none()
+            && budget.digest.is_none()
+            && budget.multipart.is_none()
+            && budget.deadline.is_none()
+            && budget.chunk_timeout.is_none()
+        {
+            return inner.call(req).await;
+        }
+
+        // A zero limit forbids bodies outright
+        if limit == 0 {
+            refuse!(SizeLimitError::BodyNotAllowed, req);
+        }
+
+        // Early rejection based on Content-Length header (if present) or a body of known size
+        if content_length.is_some_and(|length| length > limit as u64) || req.body().size_hint().lower() > limit as u64 {
+            refuse!(SizeLimitError::PayloadTooLarge, req);
+        }
+
+        // Report-only mode: measure the body as it passes through
+        if let Some(reporter) = reporter {
+            let (parts, body) = req.into_parts();
+            return inner.call(Request::from_parts(parts, reporter.measure(body, budget))).await;
+        }
+
+        // Choose processing strategy
+        match buffering {
+            Buffering::Buffer => {
+                // Take room for the body in the shared budget until the response is ready
+                let _reserved = match &config.buffer_budget {
+                    Some(shared) => {
+                        let expected = content_length.map_or(limit, |length| (length as usize).min(limit));
+                        match shared.reserve(expected).await {
+                            Ok(permit) => Some(permit),
+                            Err(error) => return Ok(rejection.reject(error, req.into_body()).await),
+                        }
+                    }
+                    None => None,
+                };
+                buffer_with_limit(req, inner, budget, rejection).await
             }
+            Buffering::Stream => stream_with_limit(req, inner, budget, rejection).await,
+        }
+    }
+    .await;
+
+    // Advertise the limits on OPTIONS responses
+    let mut result = result;
+    if let (Ok(response), Some(advertised)) = (&mut result, advertised) {
+        response.headers_mut().extend(advertised);
+    }
+    // Report the bytes actually read, and their digest
+    if let Ok(response) = &mut result {
+        if let Some(header) = &config.body_size_header {
+            response.headers_mut().insert(header.clone(), HeaderValue::from(body_size.bytes()));
+        }
+        response.extensions_mut().insert(body_size.clone());
+        if let Some(digest) = body_digest {
+            response.extensions_mut().insert(digest);
+        }
+    }
+    // Count the bytes read against the quota and tell the client what is left
+    if let (Ok(response), Some(quota), Some((key, usage))) = (&mut result, &config.quota, quota_usage) {
+        let exceeded = response.extensions().get::<SizeLimitError>() == Some(&SizeLimitError::QuotaExceeded);
+        let usage = match body_size.bytes() {
+            0 => usage,
+            _ if exceeded => usage,
+            bytes => quota.store.record(&key, bytes, quota.window).await.unwrap_or(usage),
+        };
+        quota.apply(response.headers_mut(), usage, exceeded);
+    }
+    // Warn about accepted bodies above the soft limit
+    if let (Ok(response), Some(meter)) = (&mut result, soft_meter)
+        && response.extensions().get::<SizeLimitError>().is_none()
+    {
+        meter.check(response, &config.soft_limit_header, config.on_soft_limit.as_ref());
+    }
+
+    // Let the policy observe rejections, e.g. for statistics
+    if let Ok(response) = &result
+        && let Some(error) = response.extensions().get::<SizeLimitError>()
+    {
+        let content_type = content_type
+            .as_ref()
+            .and_then(|h| h.to_str().ok())
+            .unwrap_or("application/octet-stream");
+        policy.rejected(content_type, *error);
+    }
+    result
+}
+
+/// Head of the upload a CORS preflight announces: the preflight with the
+/// method of `Access-Control-Request-Method`, if sent.
+fn upload_parts(req: &Request<Body>) -> Parts {
+    let method = req
+        .headers()
+        .get(header::ACCESS_CONTROL_REQUEST_METHOD)
+        .and_then(|value| Method::from_bytes(value.as_bytes()).ok())
+        .unwrap_or_else(|| req.method().clone());
+    let mut upload = Request::builder().method(method).uri(req.uri()).body(()).unwrap_or_default();
+    *upload.headers_mut() = req.headers().clone();
+    if let Some(matched) = req.extensions().get::<axum::extract::MatchedPath>() {
+        upload.extensions_mut().insert(matched.clone());
+    }
+    upload.into_parts().0
+}
+
+/// Headers describing the limits an upload would get.
+async fn advertised_limits<P: LimitPolicy>(config: &SizeLimitMiddlewareConfig, policy: &P, parts: Parts) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    let decision = policy
+        .decide(RequestContext {
+            parts: &parts,
+            route: parts
+                .extensions
+                .get::<axum::extract::MatchedPath>()
+                .map(|matched| matched.as_str()),
+            content_type: parts
+                .headers
+                .get(header::CONTENT_TYPE)
+                .and_then(|h| h.to_str().ok())
+                .unwrap_or("application/octet-stream"),
+            content_length: None,
+        })
+        .await;
+    if let Decision::Accept { limit, .. } = decision {
+        headers.insert(X_MAX_BODY_SIZE, limit.into());
+    }
+
+    // The content-type table; regexes and, in allowlist mode, the default are left out
+    let limits = &config.size_limits;
+    let table = limits
+        .rules()
+        .filter(|rule| match rule.source {
+            LimitSource::Specific | LimitSource::Wildcard => true,
+            LimitSource::Default => !limits.allowlist_mode,
+            LimitSource::Regex => false,
+        })
+        .map(|rule| format!("{}={}", rule.pattern, rule.limit))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if let Ok(value) = HeaderValue::from_str(&table) {
+        headers.insert(MAX_UPLOAD_SIZE, value);
+    }
+    headers
+}
+
+/// Route template of a request when known, otherwise its path.
+fn route_or_path(req: &Request<Body>) -> String {
+    req.extensions()
+        .get::<axum::extract::MatchedPath>()
+        .map_or_else(|| req.uri().path(), |matched| matched.as_str())
+        .to_string()
+}
+
+/// Describes a request for the report of its would-be rejection.
+fn would_reject(req: &Request<Body>, route: Option<&str>) -> WouldReject {
+    let headers = req.headers();
+    WouldReject {
+        method: req.method().clone(),
+        route: route.unwrap_or_else(|| req.uri().path()).to_string(),
+        content_type: headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|h| h.to_str().ok())
+            .unwrap_or("application/octet-stream")
+            .to_string(),
+        content_length: headers
+            .get(header::CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.trim().parse().ok()),
+        limit: None,
+        bytes_received: 0,
+        error: SizeLimitError::PayloadTooLarge,
+    }
+}
+
+/// Limit granted by a valid upload grant or signed limit presented for this
+/// route, if any.
+#[cfg(feature = "upload-grants")]
+fn granted_limit(config: &SizeLimitMiddlewareConfig, req: &Request<Body>) -> Option<usize> {
+    let key = config.upload_grants.as_ref()?;
+    let header = |name: &str| req.headers().get(name).and_then(|value| value.to_str().ok());
+
+    // A one-off limit signed for this very path
+    let path = req.uri().path();
+    let signed = match (
+        header(crate::size_limit::UPLOAD_LIMIT_HEADER),
+        header(crate::size_limit::UPLOAD_LIMIT_SIGNATURE_HEADER),
+    ) {
+        (Some(limit), Some(signature)) => key
+            .verify_limit(limit, signature, path)
+            .ok()
+            .filter(|grant| grant.allows(path))
+            .map(|grant| grant.limit),
+        _ => None,
+    };
+
+    let route = req
+        .extensions()
+        .get::<axum::extract::MatchedPath>()
+        .map(|matched| matched.as_str())
+        .unwrap_or(path);
+    let granted = header(crate::size_limit::UPLOAD_GRANT_HEADER)
+        .and_then(|token| key.verify(token).ok())
+        .filter(|grant| grant.allows(route))
+        .map(|grant| grant.limit);
+    signed.max(granted)
+}
+
+/// Returns `true` if the request declares both `Content-Length` and
+/// `Transfer-Encoding`, or a `Content-Length` that is not a single number.
+fn has_ambiguous_framing(req: &Request<Body>) -> bool {
+    let headers = req.headers();
+    let mut lengths = headers.get_all(axum::http::header::CONTENT_LENGTH).iter();
+    match lengths.next() {
+        None => false,
+        Some(_) if headers.contains_key(axum::http::header::TRANSFER_ENCODING) => true,
+        Some(length) => {
+            lengths.next().is_some() || length.to_str().ok().and_then(|l| l.trim().parse::<u64>().ok()).is_none()
         }
+    }
+}
+
+/// Returns `true` if the request has nothing to limit: no body is announced
+/// and, in strict mode, none is actually present either.
+fn is_bodyless(req: &Request<Body>, strict: bool) -> bool {
+    !has_body(req) && (!strict || req.body().is_end_stream())
+}
+
+/// Returns `true` if the request carries or announces a non-empty body.
+fn has_body(req: &Request<Body>) -> bool {
+    let headers = req.headers();
+    if headers.contains_key(axum::http::header::TRANSFER_ENCODING) {
+        return true;
+    }
+    if let Some(length) = headers.get(axum::http::header::CONTENT_LENGTH) {
+        return length.to_str().ok().and_then(|l| l.trim().parse::<u64>().ok()) != Some(0);
+    }
+    !req.body().is_end_stream()
+}
+
+/// Applies size limiting middleware with a simplified configuration.
+///
+/// This is a convenience wrapper that creates a default buffer strategy
+/// using the provided size limits.
+///
+/// # Arguments
+/// * `router` - The Axum router to wrap with middleware
+/// * `size_limits` - Size limits configuration
+///
+/// # Returns
+/// A new router with size limiting middleware applied.
+///
+/// # Example
+/// ```rust
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::size_limit::{SizeLimitConfig, middleware::with_size_limit_simple};
+///
+/// async fn api_handler() -> &'static str {
+///     "API endpoint"
+/// }
+///
+/// let router = Router::new()
+///     .route("/api", post(api_handler));
+///
+/// let limits = SizeLimitConfig::default()
+///     .with_default_limit(1024 * 1024); // 1MB
+///
+/// let router = with_size_limit_simple(router, limits);
+/// ```
+pub fn with_size_limit_simple(router: Router, size_limits: SizeLimitConfig) -> Router {
+    let config = SizeLimitMiddlewareConfig::new(size_limits);
+    with_size_limit(router, config)
+}
+
+/// Processes a request with buffering strategy.
+///
+/// This function:
+/// 1. Reads the entire request body, trailers included, into memory through a [`SizeLimitedBody`]
+/// 2. Stops reading as soon as it exceeds the size limit
+/// 3. If within limits, continues processing
+/// 4. If exceeds limits, returns 413 (Payload Too Large); if the body fails
+///    to arrive or, in strict mode, does not match its `Content-Length`,
+///    400 (Bad Request)
+///
+/// # Arguments
+/// * `req` - The HTTP request
+/// * `inner` - The next middleware/handler in the chain, ready to be called
+/// * `budget` - Maximum allowed size and, in strict mode, the declared size
+///
+/// # Returns
+/// HTTP response or the rejection if the body could not be read within the limit.
+async fn buffer_with_limit<S>(
+    mut req: Request<Body>,
+    mut inner: S,
+    budget: BodyBudget,
+    mut rejection: RejectionContext,
+) -> Result<Response, S::Error>
+where
+    S: Service<Request<Body>, Response = Response>,
+{
+    // Take ownership of the request body
+    let max_size = budget.max_size;
+    let (body, shared) = SizeLimitedBody::wrap(std::mem::take(req.body_mut()), budget);
+
+    // Read entire body into memory; the wrapper enforces the limit
+    #[cfg(feature = "server-timing")]
+    let body_read = req
+        .extensions()
+        .get::<crate::server_timing::ServerTiming>()
+        .map(|timing| timing.start(crate::server_timing::BODY_READ_METRIC));
+    match buffer_body(body, max_size).await {
+        Ok((buffered, pooled)) => {
+            #[cfg(feature = "server-timing")]
+            drop(body_read);
 
-        // Signal whether handler should be called
-        let _ = handler_tx.send(should_call_handler);
-    });
+            // Replace request body with buffered bytes (and trailers)
+            *req.body_mut() = buffered;
 
-    // Wait for signal from streaming task
-    let should_call_handler = match handler_rx.await {
-        Ok(should) => should,
+            // Continue to next middleware/handler, then offer the buffer back
+            let response = inner.call(req).await;
+            drop(pooled);
+            response
+        }
         Err(_) => {
-            // Streaming task was dropped unexpectedly
-            return Ok((StatusCode::INTERNAL_SERVER_ERROR, "Internal error").into_response());
+            // The wrapper recorded why: limit exceeded or a failed read
+            let (outcome, rest) = BodyState::take(&shared);
+            let error = outcome.rejection().unwrap_or(SizeLimitError::BodyReadFailed);
+            rejection.part = rest.as_ref().and_then(|(_, budget)| budget.oversized_part());
+            Ok(rejection.reject(error, rest.map_or_else(Body::empty, |(body, _)| body)).await)
+        }
+    }
+}
+
+/// Processes a request with streaming strategy.
+///
+/// This function:
+/// 1. Calls the next handler right away with the body wrapped in a [`SizeLimitedBody`]
+/// 2. Tracks total size as the handler reads chunks
+/// 3. Ends the body with an error as soon as the limit is exceeded
+/// 4. Returns the rejection in place of the handler's response if the limit was
+///    exceeded (413), the body failed to arrive or, in strict mode, did not
+///    match its `Content-Length` (400)
+/// 5. Keeps the handler's response if it dropped the body early, disposing of
+///    the rest as [`RejectedBody`] says
+///
+/// Handlers reading the body get the rejection as the body's error: its
+/// source chain holds the [`SizeLimitError`].
+///
+/// # Arguments
+/// * `req` - The HTTP request
+/// * `inner` - The next middleware/handler in the chain, ready to be called
+/// * `budget` - Maximum allowed size and, in strict mode, the declared size
+///
+/// # Returns
+/// HTTP response or the rejection if the body could not be read within the limit.
+async fn stream_with_limit<S>(
+    req: Request<Body>,
+    mut inner: S,
+    budget: BodyBudget,
+    mut rejection: RejectionContext,
+) -> Result<Response, S::Error>
+where
+    S: Service<Request<Body>, Response = Response>,
+{
+    let (parts, body) = req.into_parts();
+
+    #[cfg(feature = "server-timing")]
+    let body_read = parts
+        .extensions
+        .get::<crate::server_timing::ServerTiming>()
+        .map(|timing| timing.start(crate::server_timing::BODY_READ_METRIC));
+
+    // Call the next middleware/handler while the body is still arriving
+    let (body, shared) = SizeLimitedBody::wrap(body, budget);
+    let response = inner.call(Request::from_parts(parts, body)).await?;
+
+    let (error, rest) = match BodyState::take(&shared) {
+        (BodyOutcome::Rejected(error), rest) => {
+            rejection.part = rest.as_ref().and_then(|(_, budget)| budget.oversized_part());
+            (Some(error), rest.map(|(body, _)| body))
+        }
+        // The handler answered without reading the whole body: its response stands
+        (_, Some((body, budget))) => return Ok(rejection.dispose(response, body, budget).await),
+        // Read to the end, or still held by the handler
+        (_, None) => (None, None),
+    };
+
+    #[cfg(feature = "server-timing")]
+    drop(body_read);
+
+    // A body that failed or outgrew the limit voids the handler's response
+    match error {
+        Some(error) => Ok(rejection.reject(error, rest.unwrap_or_default()).await),
+        None => Ok(response),
+    }
+}
+
+/// Most bytes [`buffer_body`] allocates before they arrive.
+///
+/// A declared `Content-Length` is only a claim: a client announcing a large
+/// body and sending nothing must not cost more than this.
+const MAX_PREALLOCATION: usize = 8 * 1024 * 1024;
+
+/// Reads a whole body into memory.
+///
+/// A body arriving in one chunk is kept as is, without copying. Larger ones
+/// are assembled into a single buffer sized up front from the body's size
+/// hint (its `Content-Length`), capped at `max_size` and
+/// [`MAX_PREALLOCATION`], so the buffer rarely grows while the body arrives.
+/// That buffer is taken from a pool; keep the returned [`Pooled`] until the
+/// handler is done with the body, so the buffer can be reused afterwards.
+///
+/// The returned body replays the data in one frame, followed by the
+/// trailers of the original body, if it had any.
+pub(crate) async fn buffer_body(mut body: Body, max_size: usize) -> Result<(Body, Pooled), axum::Error> {
+    let expected = usize::try_from(body.size_hint().lower()).unwrap_or(usize::MAX);
+    let mut first: Option<Bytes> = None;
+    let mut assembled = BytesMut::new();
+    let mut trailers = None;
+    while let Some(frame) = poll_fn(|cx| Pin::new(&mut body).poll_frame(cx)).await {
+        match frame?.into_data() {
+            Ok(data) if data.is_empty() => {}
+            Ok(data) => match first.take() {
+                // Second chunk: switch to a single buffer sized for the whole body
+                Some(head) => {
+                    let capacity = expected.min(max_size).min(MAX_PREALLOCATION);
+                    assembled = pool::take(capacity.max(head.len() + data.len()));
+                    assembled.extend_from_slice(&head);
+                    assembled.extend_from_slice(&data);
+                }
+                None if assembled.is_empty() => first = Some(data),
+                None => assembled.extend_from_slice(&data),
+            },
+            Err(frame) => trailers = frame.into_trailers().ok().or(trailers),
+        }
+    }
+    let (data, pooled) = match first {
+        Some(data) => (data, Pooled::default()),
+        None => {
+            let data = assembled.freeze();
+            (data.clone(), Pooled::new(data))
         }
     };
+    let body = match trailers {
+        Some(trailers) => Body::new(BufferedBody {
+            data: Some(data),
+            trailers: Some(trailers),
+        }),
+        None => Body::from(data),
+    };
+    Ok((body, pooled))
+}
+
+/// A buffered body with trailers.
+struct BufferedBody {
+    data: Option<Bytes>,
+    trailers: Option<HeaderMap>,
+}
+
+impl HttpBody for BufferedBody {
+    type Data = Bytes;
+    type Error = Infallible;
+
+    fn poll_frame(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, Infallible>>> {
+        let this = self.get_mut();
+        if let Some(data) = this.data.take().filter(|data| !data.is_empty()) {
+            return Poll::Ready(Some(Ok(Frame::data(data))));
+        }
+        Poll::Ready(this.trailers.take().map(|trailers| Ok(Frame::trailers(trailers))))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.data.as_ref().is_none_or(Bytes::is_empty) && self.trailers.is_none()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        SizeHint::with_exact(self.data.as_ref().map_or(0, |data| data.len() as u64))
+    }
+}
+
+/// Reads what is left of a body, failing like [`SizeLimitedBody`] would.
+///
+/// On failure the unread rest is left in `body`.
+async fn drain_with_limit(body: &mut Body, mut budget: BodyBudget) -> Result<(), SizeLimitError> {
+    loop {
+        let next = poll_fn(|cx| Pin::new(&mut *body).poll_frame(cx));
+        let frame = match budget.next_deadline() {
+            Some(deadline) => tokio::time::timeout_at(deadline, next)
+                .await
+                .map_err(|_| SizeLimitError::BodyTimeout)?,
+            None => next.await,
+        };
+        let Some(frame) = frame else {
+            break;
+        };
+        let frame = frame.map_err(|_| SizeLimitError::BodyReadFailed)?;
+        budget.add_frame(&frame)?;
+    }
+    budget.finish()
+}
+
+/// How to render rejections and what to do with the rest of the body.
+#[derive(Clone, Debug)]
+struct RejectionContext {
+    error_format: ErrorFormat,
+    rejected_body: RejectedBody,
+    version: Version,
+    /// Limit that applied, once chosen.
+    limit: Option<usize>,
+    /// Headers describing the limit on 413 responses.
+    headers: RejectionHeaders,
+    /// The multipart part over its limit, once known.
+    part: Option<OversizedPart>,
+}
+
+impl RejectionContext {
+    /// Renders a rejection, disposing of the unread rest of the body.
+    async fn reject(self, error: SizeLimitError, mut rest: Body) -> Response {
+        let mut response = match self.part.clone() {
+            Some(part) => {
+                let mut response = error.render(self.error_format, &part.message(error));
+                response.extensions_mut().insert(part);
+                response
+            }
+            None => error.into_response(self.error_format),
+        };
+        if let Some(limit) = self.limit
+            && error.status() == StatusCode::PAYLOAD_TOO_LARGE
+        {
+            self.headers.apply(response.headers_mut(), limit);
+        }
+        if rest.is_end_stream() {
+            return response;
+        }
+        // A body too slow to read is too slow to drain
+        let drain_limit = if error == SizeLimitError::BodyTimeout { 0 } else { self.drain_limit() };
+        if drain_limit > 0 && discard(&mut rest, drain_limit).await {
+            return response;
+        }
+        self.close(&mut response);
+        response
+    }
+
+    /// Disposes of a body the handler left unread, keeping its response.
+    ///
+    /// The rest is drained within the body's limit and deadlines, up to what
+    /// [`RejectedBody`] allows; anything else closes the connection.
+    async fn dispose(self, mut response: Response, mut rest: Body, budget: BodyBudget) -> Response {
+        if rest.is_end_stream() {
+            return response;
+        }
+        let drain_limit = self.drain_limit();
+        if drain_limit > 0 && drain_with_limit(&mut rest, budget.for_drain(drain_limit)).await.is_ok() {
+            return response;
+        }
+        self.close(&mut response);
+        response
+    }
+
+    /// Most bytes to drain before giving up on the connection.
+    fn drain_limit(&self) -> usize {
+        match self.rejected_body {
+            RejectedBody::Abort => 0,
+            RejectedBody::Drain => usize::MAX,
+            RejectedBody::DrainUpTo(limit) => limit,
+        }
+    }
+
+    /// Dropping the body stops reading; tells HTTP/1.x clients to stop sending.
+    fn close(&self, response: &mut Response) {
+        if self.version <= Version::HTTP_11 {
+            response
+                .headers_mut()
+                .insert(header::CONNECTION, HeaderValue::from_static("close"));
+        }
+    }
+}
+
+/// Reads and discards up to `limit` bytes, returning `true` if the body ended
+/// within them.
+async fn discard(body: &mut Body, limit: usize) -> bool {
+    let mut discarded = 0usize;
+    while let Some(frame) = poll_fn(|cx| Pin::new(&mut *body).poll_frame(cx)).await {
+        let Ok(frame) = frame else {
+            return false;
+        };
+        discarded = discarded.saturating_add(frame.data_ref().map_or(0, Bytes::len));
+        if discarded > limit {
+            return false;
+        }
+    }
+    true
+}
+
+/// Byte accounting of a limited body.
+#[derive(Clone, Debug)]
+pub(crate) struct BodyBudget {
+    /// Bytes received so far.
+    received: usize,
+    /// Maximum allowed size in bytes.
+    max_size: usize,
+    /// Declared `Content-Length` the body must match, in strict mode.
+    expected: Option<u64>,
+    /// Largest allowed chunk in bytes, if capped.
+    max_chunk: Option<usize>,
+    /// Progress callback, if registered.
+    progress: Option<ProgressMeter>,
+    /// Hash of the body, if requested.
+    digest: Option<DigestMeter>,
+    /// Longest wait for a chunk, if limited.
+    chunk_timeout: Option<Duration>,
+    /// When the whole body must have arrived, if limited.
+    deadline: Option<Instant>,
+    /// Parts of a multipart body, if limited.
+    multipart: Option<MultipartMeter>,
+}
+
+impl BodyBudget {
+    pub(crate) fn new(max_size: usize, expected: Option<u64>) -> Self {
+        Self {
+            received: 0,
+            max_size,
+            expected,
+            max_chunk: None,
+            progress: None,
+            digest: None,
+            chunk_timeout: None,
+            deadline: None,
+            multipart: None,
+        }
+    }
+
+    /// Bytes counted so far.
+    pub(crate) fn received(&self) -> usize {
+        self.received
+    }
+
+    /// Caps the size of individual chunks.
+    pub(crate) fn with_max_chunk(mut self, max_chunk: Option<usize>) -> Self {
+        self.max_chunk = max_chunk;
+        self
+    }
+
+    /// Reports progress as chunks are counted.
+    pub(crate) fn with_progress(mut self, progress: Option<ProgressMeter>) -> Self {
+        self.progress = progress;
+        self
+    }
+
+    /// Hashes the body as chunks are counted.
+    pub(crate) fn with_digest(mut self, digest: Option<DigestMeter>) -> Self {
+        self.digest = digest;
+        self
+    }
 
-    // Don't call handler if limit was exceeded
-    if !should_call_handler {
-        return Ok((StatusCode::PAYLOAD_TOO_LARGE, "Payload too large").into_response());
+    /// The multipart part that outgrew its limit, if named.
+    fn oversized_part(&self) -> Option<OversizedPart> {
+        self.multipart.as_ref()?.oversized().cloned()
     }
 
-    // Create a new body from the receiver stream
-    let limited_body = Body::from_stream(ReceiverStream::new(rx));
-    let req = Request::from_parts(parts, limited_body);
+    /// Follows the parts of a multipart body as chunks are counted.
+    pub(crate) fn with_multipart(mut self, multipart: Option<MultipartMeter>) -> Self {
+        self.multipart = multipart;
+        self
+    }
+
+    /// Limits how long the body and each of its chunks may take.
+    pub(crate) fn with_timeouts(mut self, chunk: Option<Duration>, body: Option<Duration>) -> Self {
+        self.chunk_timeout = chunk;
+        self.deadline = body.map(|body| Instant::now() + body);
+        self
+    }
+
+    /// Budget for draining an abandoned body: at most `limit` more bytes,
+    /// within the same limit and deadlines, without reporting or hashing them.
+    fn for_drain(&self, limit: usize) -> Self {
+        Self {
+            max_size: self.max_size.min(self.received.saturating_add(limit)),
+            progress: None,
+            digest: None,
+            multipart: None,
+            ..self.clone()
+        }
+    }
+
+    /// When the next chunk must have arrived, if it has a deadline.
+    fn next_deadline(&self) -> Option<Instant> {
+        let chunk = self.chunk_timeout.map(|timeout| Instant::now() + timeout);
+        match (chunk, self.deadline) {
+            (Some(chunk), Some(body)) => Some(chunk.min(body)),
+            (chunk, body) => chunk.or(body),
+        }
+    }
+
+    /// Counts and hashes a frame, failing like [`add`](Self::add).
+    pub(crate) fn add_frame(&mut self, frame: &Frame<Bytes>) -> Result<(), SizeLimitError> {
+        let data = frame.data_ref();
+        self.add(data.map_or(0, Bytes::len))?;
+        if let (Some(multipart), Some(data)) = (&mut self.multipart, data) {
+            multipart.update(data)?;
+        }
+        if let (Some(digest), Some(data)) = (&self.digest, data) {
+            digest.update(data);
+        }
+        Ok(())
+    }
+
+    /// Counts a chunk, failing once the body outgrows its declared length or
+    /// the limit, the chunk itself is too large or the progress callback
+    /// aborts the upload.
+    pub(crate) fn add(&mut self, len: usize) -> Result<(), SizeLimitError> {
+        if self.max_chunk.is_some_and(|max_chunk| len > max_chunk) {
+            return Err(SizeLimitError::PayloadTooLarge);
+        }
+        self.received += len;
+        if self.expected.is_some_and(|expected| self.received as u64 > expected) {
+            return Err(SizeLimitError::ContentLengthMismatch);
+        }
+        if self.received > self.max_size {
+            return Err(SizeLimitError::PayloadTooLarge);
+        }
+        if let Some(progress) = self.progress.as_mut()
+            && len > 0
+            && progress.update(self.received as u64).is_break()
+        {
+            return Err(SizeLimitError::PayloadTooLarge);
+        }
+        Ok(())
+    }
+
+    /// Checks the body at its end, failing if it fell short of its declared length.
+    /// The digest, if requested, is set when the body passes.
+    pub(crate) fn finish(&self) -> Result<(), SizeLimitError> {
+        match self.expected {
+            Some(expected) if (self.received as u64) < expected => Err(SizeLimitError::ContentLengthMismatch),
+            _ => {
+                if let Some(digest) = &self.digest {
+                    digest.finish();
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// How a [`SizeLimitedBody`] ended.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+enum BodyOutcome {
+    /// Not read to the end yet.
+    #[default]
+    Reading,
+    /// Read to the end within the limit.
+    Complete,
+    /// Ended early with a rejection.
+    Rejected(SizeLimitError),
+}
+
+impl BodyOutcome {
+    /// The rejection, if the body ended with one.
+    fn rejection(self) -> Option<SizeLimitError> {
+        match self {
+            BodyOutcome::Rejected(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+/// What became of a [`SizeLimitedBody`], left for the middleware once the
+/// handler is done.
+#[derive(Default)]
+pub(crate) struct BodyState {
+    outcome: BodyOutcome,
+    /// The unread rest of the body and its accounting so far, if the body
+    /// was rejected or dropped before its end.
+    leftover: Option<(Body, BodyBudget)>,
+}
+
+impl BodyState {
+    /// Returns the outcome and takes the unread rest of the body.
+    fn take(shared: &Mutex<BodyState>) -> (BodyOutcome, Option<(Body, BodyBudget)>) {
+        let mut state = shared.lock().unwrap_or_else(PoisonError::into_inner);
+        (state.outcome, state.leftover.take())
+    }
+}
+
+/// Body enforcing a size limit as it is read.
+///
+/// Passes the frames of the wrapped body through, trailers and size hint
+/// included, and a [`SizeLimitError`] in place of the frame that crosses the
+/// limit, of a transport error or, in strict mode, where the body stops
+/// matching its `Content-Length`. How the body ended is recorded in its
+/// [`BodyState`], so the middleware never has to guess from error messages;
+/// dropped before the end, the body hands its unread rest back through it too.
+pub(crate) struct SizeLimitedBody {
+    inner: Option<Body>,
+    budget: BodyBudget,
+    shared: Arc<Mutex<BodyState>>,
+    /// Deadline of the chunk being waited for, if timed.
+    timer: Option<Pin<Box<Sleep>>>,
+}
+
+impl SizeLimitedBody {
+    /// Wraps a body, returning the limited body and its shared state.
+    pub(crate) fn wrap(body: Body, budget: BodyBudget) -> (Body, Arc<Mutex<BodyState>>) {
+        let shared = Arc::new(Mutex::new(BodyState::default()));
+        let limited = SizeLimitedBody {
+            inner: Some(body),
+            budget,
+            shared: shared.clone(),
+            timer: None,
+        };
+        (Body::new(limited), shared)
+    }
+
+    /// Records how the body ended and releases the wrapped body.
+    fn finish(&mut self, outcome: BodyOutcome) {
+        self.inner = None;
+        self.shared.lock().unwrap_or_else(PoisonError::into_inner).outcome = outcome;
+    }
+
+    /// Ends the body with a rejection, leaving the unread rest to the middleware.
+    fn reject(&mut self, error: SizeLimitError) -> Poll<Option<Result<Frame<Bytes>, SizeLimitError>>> {
+        let mut state = self.shared.lock().unwrap_or_else(PoisonError::into_inner);
+        state.outcome = BodyOutcome::Rejected(error);
+        state.leftover = self.inner.take().map(|rest| (rest, self.budget.clone()));
+        Poll::Ready(Some(Err(error)))
+    }
+}
+
+impl HttpBody for SizeLimitedBody {
+    type Data = Bytes;
+    type Error = SizeLimitError;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, SizeLimitError>>> {
+        let this = self.get_mut();
+        let Some(inner) = this.inner.as_mut() else {
+            return Poll::Ready(None);
+        };
+        let polled = Pin::new(inner).poll_frame(cx);
+        if polled.is_pending() {
+            // Time out bodies that stop arriving
+            if this.timer.is_none() {
+                this.timer = this.budget.next_deadline().map(|deadline| Box::pin(tokio::time::sleep_until(deadline)));
+            }
+            if let Some(timer) = this.timer.as_mut() {
+                ready!(timer.as_mut().poll(cx));
+                return this.reject(SizeLimitError::BodyTimeout);
+            }
+            return Poll::Pending;
+        }
+        this.timer = None;
+        match ready!(polled) {
+            Some(Ok(frame)) => {
+                // Check if we've exceeded the limit
+                match this.budget.add_frame(&frame) {
+                    Ok(()) => Poll::Ready(Some(Ok(frame))),
+                    Err(error) => this.reject(error),
+                }
+            }
+            Some(Err(_)) => this.reject(SizeLimitError::BodyReadFailed),
+            None => match this.budget.finish() {
+                Ok(()) => {
+                    this.finish(BodyOutcome::Complete);
+                    Poll::Ready(None)
+                }
+                Err(error) => this.reject(error),
+            },
+        }
+    }
 
-    // Call the next middleware/handler
-    let response = next.run(req).await;
+    fn is_end_stream(&self) -> bool {
+        self.inner.as_ref().is_none_or(HttpBody::is_end_stream)
+    }
 
-    // Double-check limit flag after handler completes
-    if limit_exceeded.load(std::sync::atomic::Ordering::SeqCst) {
-        return Ok((StatusCode::PAYLOAD_TOO_LARGE, "Payload too large").into_response());
+    fn size_hint(&self) -> SizeHint {
+        self.inner.as_ref().map_or_else(|| SizeHint::with_exact(0), HttpBody::size_hint)
     }
+}
 
-    Ok(response)
-}
\ No newline at end of file
+impl Drop for SizeLimitedBody {
+    fn drop(&mut self) {
+        if let Some(inner) = self.inner.take() {
+            self.shared.lock().unwrap_or_else(PoisonError::into_inner).leftover = Some((inner, self.budget.clone()));
+        }
+    }
+}
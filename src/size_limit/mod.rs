@@ -1,8 +1,97 @@
-pub mod size;
+#[cfg(feature = "size-limit-admin")]
+pub mod admin;
+pub mod body_size;
+pub mod buffer_budget;
+pub mod bundle;
+pub mod concurrency;
 pub mod config;
+pub mod connection;
+pub mod content_type;
+pub mod digest;
+pub mod disconnect;
+pub mod discovery;
+pub mod engine;
+pub mod env;
+pub mod error;
+pub mod expansion;
+pub mod explain;
+pub mod ext;
+#[cfg(feature = "config-file")]
+mod file;
+mod glob;
+#[cfg(feature = "upload-grants")]
+pub mod grant;
+#[cfg(feature = "runtime-limits")]
+pub mod handle;
+pub mod host;
+pub mod info;
+pub mod key;
+mod macros;
+pub mod matcher;
 pub mod middleware;
+pub mod multipart;
+#[cfg(feature = "openapi")]
+pub mod openapi;
+pub mod overrides;
+pub mod policy;
+mod pool;
+pub mod progress;
+pub mod quota;
+#[cfg(feature = "hot-reload")]
+pub mod reload;
+pub mod report;
+pub mod route;
+pub mod rule;
+#[cfg(feature = "serde")]
+mod serialization;
+pub mod soft;
+pub mod tenant;
+pub mod throttle;
+
+// Size types are shared crate-wide; re-exported here for compatibility
+pub use crate::size;
+pub use crate::size::*;
 
 // Public API re-exports
-pub use size::*;
+#[cfg(feature = "size-limit-admin")]
+pub use admin::*;
+pub use body_size::*;
+pub use buffer_budget::*;
+pub use bundle::*;
+pub use concurrency::*;
 pub use config::*;
-pub use middleware::*;
\ No newline at end of file
+pub use connection::*;
+pub use content_type::*;
+pub use digest::*;
+pub use disconnect::*;
+pub use discovery::*;
+pub use engine::*;
+pub use env::*;
+pub use error::*;
+pub use expansion::*;
+pub use explain::*;
+pub use ext::*;
+#[cfg(feature = "upload-grants")]
+pub use grant::*;
+#[cfg(feature = "runtime-limits")]
+pub use handle::*;
+pub use host::*;
+pub use info::*;
+pub use key::*;
+pub use matcher::*;
+pub use middleware::*;
+pub use multipart::*;
+#[cfg(feature = "openapi")]
+pub use openapi::*;
+pub use overrides::*;
+pub use policy::*;
+pub use progress::*;
+pub use quota::*;
+#[cfg(feature = "hot-reload")]
+pub use reload::*;
+pub use report::*;
+pub use route::*;
+pub use rule::*;
+pub use soft::*;
+pub use tenant::*;
+pub use throttle::*;
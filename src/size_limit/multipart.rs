@@ -0,0 +1,360 @@
+//! Per-field and per-file limits for `multipart/form-data` bodies.
+//!
+//! A single limit for a whole form is far too coarse: it lets a 5 GB file
+//! through as a "comment" text field, or forces the limit of the largest
+//! file on every field. With
+//! [`with_multipart_limits`](crate::size_limit::SizeLimitMiddlewareConfig::with_multipart_limits)
+//! the size limiter follows the part boundaries as the body streams through
+//! and enforces distinct [`MultipartLimits`] on text fields, file parts and
+//! named fields, without buffering the parts: the handler still parses the
+//! body itself, e.g. with axum's `Multipart`.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::size_limit::{SizeLimit, SizeLimitError};
+
+/// Largest header block of a part; longer ones are rejected as malformed.
+const MAX_PART_HEADERS: usize = 16 * 1024;
+
+/// Limits on the parts of `multipart/form-data` bodies.
+///
+/// Parts with a `filename` are files, the others text fields. A limit set
+/// for a field name takes precedence over the limit of its kind. Parts over
+/// their limit are rejected with 413, as
+/// [`SizeLimitError::FieldTooLarge`] or [`SizeLimitError::FileTooLarge`],
+/// naming the part when it has a name (see [`OversizedPart`]); the whole
+/// body stays under the limit of its content type.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::size_limit::{MultipartLimits, SizeLimitMiddlewareConfig};
+///
+/// let config = SizeLimitMiddlewareConfig::default().with_multipart_limits(
+///     MultipartLimits::new()
+///         .with_field_limit("64kb")
+///         .with_file_limit("20mb")
+///         .with_named_limit("avatar", "1mb"),
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MultipartLimits {
+    /// Largest text field in bytes, if capped.
+    pub field_limit: Option<usize>,
+    /// Largest file part in bytes, if capped.
+    pub file_limit: Option<usize>,
+    /// Limits of parts by field name.
+    pub named_limits: HashMap<String, usize>,
+}
+
+impl MultipartLimits {
+    /// Creates limits that cap nothing yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method to cap text fields (parts without a `filename`).
+    pub fn with_field_limit(mut self, limit: impl Into<SizeLimit>) -> Self {
+        self.field_limit = Some(limit.into().0);
+        self
+    }
+
+    /// Builder method to cap file parts (parts with a `filename`).
+    pub fn with_file_limit(mut self, limit: impl Into<SizeLimit>) -> Self {
+        self.file_limit = Some(limit.into().0);
+        self
+    }
+
+    /// Builder method to cap the parts of one field name.
+    ///
+    /// # Arguments
+    /// * `name` - The `name` of the part's `Content-Disposition`
+    /// * `limit` - Largest part of that name
+    pub fn with_named_limit(mut self, name: impl Into<String>, limit: impl Into<SizeLimit>) -> Self {
+        self.named_limits.insert(name.into(), limit.into().0);
+        self
+    }
+
+    /// Name, limit and rejection of a part, from its headers.
+    fn part_limit(&self, headers: &[u8]) -> (Option<String>, Option<usize>, SizeLimitError) {
+        let (name, is_file) = content_disposition(headers);
+        let error = match is_file {
+            true => SizeLimitError::FileTooLarge,
+            false => SizeLimitError::FieldTooLarge,
+        };
+        let limit = name
+            .as_ref()
+            .and_then(|name| self.named_limits.get(name).copied())
+            .or(if is_file { self.file_limit } else { self.field_limit });
+        (name, limit, error)
+    }
+}
+
+/// The part of a multipart body that was over its [`MultipartLimits`].
+///
+/// Stored in the response extensions of [`SizeLimitError::FieldTooLarge`]
+/// and [`SizeLimitError::FileTooLarge`] rejections when the part has a
+/// `name` in its `Content-Disposition`, which the message then quotes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OversizedPart {
+    /// The `name` of the part's `Content-Disposition`.
+    pub name: String,
+}
+
+impl OversizedPart {
+    /// Message of the rejection, naming the part.
+    pub(crate) fn message(&self, error: SizeLimitError) -> String {
+        match error {
+            SizeLimitError::FileTooLarge => format!("Uploaded file '{}' too large", self.name),
+            _ => format!("Form field '{}' too large", self.name),
+        }
+    }
+}
+
+/// Where a [`MultipartMeter`] is in the body.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    /// Before the first delimiter.
+    Preamble,
+    /// After a delimiter, before the end of its line.
+    Delimiter,
+    /// In the header block of a part.
+    Headers,
+    /// In the content of a part.
+    Content,
+    /// After the closing delimiter.
+    Epilogue,
+}
+
+/// Follows the parts of one multipart body as its chunks are counted.
+#[derive(Clone, Debug)]
+pub(crate) struct MultipartMeter {
+    limits: Arc<MultipartLimits>,
+    /// `CRLF--boundary`.
+    delimiter: Vec<u8>,
+    state: State,
+    /// Bytes not yet attributed, at most a delimiter long outside headers.
+    pending: Vec<u8>,
+    /// Content bytes of the current part.
+    part_size: usize,
+    part_name: Option<String>,
+    part_limit: Option<usize>,
+    part_error: SizeLimitError,
+    /// The part that outgrew its limit, once one has.
+    oversized: Option<OversizedPart>,
+}
+
+impl MultipartMeter {
+    /// Starts following a body of `content_type`, if it is
+    /// `multipart/form-data` with a boundary.
+    pub(crate) fn new(limits: Arc<MultipartLimits>, content_type: &str) -> Option<Self> {
+        let (essence, params) = content_type.split_once(';')?;
+        if !essence.trim().eq_ignore_ascii_case("multipart/form-data") {
+            return None;
+        }
+        let boundary = params.split(';').find_map(|param| {
+            let (key, value) = param.split_once('=')?;
+            key.trim()
+                .eq_ignore_ascii_case("boundary")
+                .then(|| value.trim().trim_matches('"'))
+        })?;
+        if boundary.is_empty() {
+            return None;
+        }
+        Some(Self {
+            limits,
+            delimiter: format!("\r\n--{}", boundary).into_bytes(),
+            state: State::Preamble,
+            // The first delimiter may open the body, without a line break before it
+            pending: b"\r\n".to_vec(),
+            part_size: 0,
+            part_name: None,
+            part_limit: None,
+            part_error: SizeLimitError::FieldTooLarge,
+            oversized: None,
+        })
+    }
+
+    /// The part that outgrew its limit, if it has a name.
+    pub(crate) fn oversized(&self) -> Option<&OversizedPart> {
+        self.oversized.as_ref()
+    }
+
+    /// Feeds a chunk of the body, failing once a part outgrows its limit.
+    pub(crate) fn update(&mut self, data: &[u8]) -> Result<(), SizeLimitError> {
+        if self.state == State::Epilogue {
+            return Ok(());
+        }
+        self.pending.extend_from_slice(data);
+        loop {
+            let consumed = match self.state {
+                State::Preamble => match find(&self.pending, &self.delimiter) {
+                    Some(at) => {
+                        self.state = State::Delimiter;
+                        at + self.delimiter.len()
+                    }
+                    None => self.pending.len().saturating_sub(self.delimiter.len() - 1),
+                },
+                State::Delimiter => {
+                    if self.pending.starts_with(b"--") {
+                        self.state = State::Epilogue;
+                        self.pending.clear();
+                        return Ok(());
+                    }
+                    match find(&self.pending, b"\r\n") {
+                        Some(at) => {
+                            self.state = State::Headers;
+                            at + 2
+                        }
+                        None if self.pending.len() > MAX_PART_HEADERS => {
+                            return Err(SizeLimitError::MalformedMultipart);
+                        }
+                        None => 0,
+                    }
+                }
+                State::Headers => {
+                    let end = match self.pending.starts_with(b"\r\n") {
+                        true => Some(0),
+                        false => find(&self.pending, b"\r\n\r\n").map(|at| at + 2),
+                    };
+                    match end {
+                        Some(end) => {
+                            (self.part_name, self.part_limit, self.part_error) =
+                                self.limits.part_limit(&self.pending[..end]);
+                            self.part_size = 0;
+                            self.state = State::Content;
+                            end + 2
+                        }
+                        None if self.pending.len() > MAX_PART_HEADERS => {
+                            return Err(SizeLimitError::MalformedMultipart);
+                        }
+                        None => 0,
+                    }
+                }
+                State::Content => {
+                    let (content, consumed) = match find(&self.pending, &self.delimiter) {
+                        Some(at) => {
+                            self.state = State::Delimiter;
+                            (at, at + self.delimiter.len())
+                        }
+                        // The tail may be the start of a delimiter
+                        None => {
+                            let content = self.pending.len().saturating_sub(self.delimiter.len() - 1);
+                            (content, content)
+                        }
+                    };
+                    self.part_size += content;
+                    if self.part_limit.is_some_and(|limit| self.part_size > limit) {
+                        self.oversized = self.part_name.clone().map(|name| OversizedPart { name });
+                        return Err(self.part_error);
+                    }
+                    consumed
+                }
+                State::Epilogue => self.pending.len(),
+            };
+            if consumed == 0 {
+                return Ok(());
+            }
+            self.pending.drain(..consumed);
+        }
+    }
+}
+
+/// Position of the first `needle` in `haystack`.
+fn find(haystack: &[u8], needle: &[u8]) -> Option<usize> {
+    let (first, rest) = needle.split_first()?;
+    let mut from = 0;
+    while let Some(offset) = haystack.get(from..)?.iter().position(|byte| byte == first) {
+        let at = from + offset;
+        match haystack.get(at + 1..at + needle.len()) {
+            Some(candidate) if candidate == rest => return Some(at),
+            // Too close to the end to hold the needle
+            None => return None,
+            Some(_) => from = at + 1,
+        }
+    }
+    None
+}
+
+/// Field name of a part and whether it is a file, from its headers.
+fn content_disposition(headers: &[u8]) -> (Option<String>, bool) {
+    let headers = String::from_utf8_lossy(headers);
+    let Some(disposition) = headers.split("\r\n").find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim().eq_ignore_ascii_case("content-disposition").then_some(value)
+    }) else {
+        return (None, false);
+    };
+    let mut name = None;
+    let mut is_file = false;
+    for param in disposition.split(';').skip(1) {
+        let Some((key, value)) = param.split_once('=') else {
+            continue;
+        };
+        match key.trim().to_ascii_lowercase().as_str() {
+            "name" => name = Some(value.trim().trim_matches('"').to_string()),
+            "filename" | "filename*" => is_file = true,
+            _ => {}
+        }
+    }
+    (name, is_file)
+}
+
+#[cfg(test)]
+#[allow(clippy::disallowed_methods)]
+mod tests {
+    use super::*;
+
+    /// A form with a text field of `field` bytes and a file of `file` bytes,
+    /// both holding near-delimiters.
+    fn form(field: usize, file: usize) -> Vec<u8> {
+        let content = |len: usize| b"\r\n--XyQ-".iter().copied().cycle().take(len).collect::<Vec<u8>>();
+        let mut body = b"preamble\r\n--XyZ\r\nContent-Disposition: form-data; name=\"comment\"\r\n\r\n".to_vec();
+        body.extend(content(field));
+        body.extend_from_slice(b"\r\n--XyZ\r\nContent-Disposition: form-data; name=\"doc\"; filename=\"f.bin\"\r\n\r\n");
+        body.extend(content(file));
+        body.extend_from_slice(b"\r\n--XyZ--\r\nepilogue");
+        body
+    }
+
+    /// Feeds `body` split at `at` to a meter with the given part limits.
+    fn feed(body: &[u8], at: &[usize], field_limit: usize, file_limit: usize) -> Result<(), SizeLimitError> {
+        let limits = MultipartLimits::new()
+            .with_field_limit(SizeLimit::bytes(field_limit))
+            .with_file_limit(SizeLimit::bytes(file_limit));
+        let mut meter = MultipartMeter::new(Arc::new(limits), "multipart/form-data; boundary=\"XyZ\"").unwrap();
+        let mut start = 0;
+        for &end in at.iter().chain([&body.len()]) {
+            meter.update(&body[start..end])?;
+            start = end;
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_boundaries_split_across_chunks() {
+        let body = form(20, 30);
+        for first in 0..body.len() {
+            // Parts are measured exactly wherever the chunks split
+            assert_eq!(feed(&body, &[first], 20, 30), Ok(()), "split at {}", first);
+            assert_eq!(feed(&body, &[first], 19, 30), Err(SizeLimitError::FieldTooLarge), "split at {}", first);
+            assert_eq!(feed(&body, &[first], 20, 29), Err(SizeLimitError::FileTooLarge), "split at {}", first);
+            for second in (first..body.len()).step_by(5) {
+                assert_eq!(feed(&body, &[first, second], 20, 30), Ok(()), "split at {} and {}", first, second);
+            }
+        }
+
+        // One byte at a time
+        let bytes = (1..body.len()).collect::<Vec<_>>();
+        assert_eq!(feed(&body, &bytes, 20, 30), Ok(()));
+        assert_eq!(feed(&body, &bytes, 20, 29), Err(SizeLimitError::FileTooLarge));
+    }
+
+    #[test]
+    fn test_non_multipart_bodies_are_not_followed() {
+        let limits = Arc::new(MultipartLimits::new());
+        assert!(MultipartMeter::new(limits.clone(), "application/json").is_none());
+        assert!(MultipartMeter::new(limits.clone(), "multipart/form-data").is_none());
+        assert!(MultipartMeter::new(limits, "multipart/form-data; boundary=").is_none());
+    }
+}
@@ -0,0 +1,249 @@
+//! OpenAPI documentation of the configured size limits (feature `openapi`).
+//!
+//! Annotates a [`utoipa`] document with the limits the middleware actually
+//! enforces, so API docs cannot drift from the running configuration, or
+//! goes the other way and derives the limits from the document.
+
+use axum::http::{HeaderMap, Method};
+use utoipa::openapi::{
+    Content, OpenApi, PathItem, RefOr, Response, Schema,
+    extensions::Extensions,
+    schema::{Object, Type},
+};
+
+use crate::size_limit::{LimitRule, SizeLimit, SizeLimitConfig};
+
+/// Name of the extension carrying the maximum body size in bytes.
+pub const MAX_BODY_SIZE_EXTENSION: &str = "x-max-body-size";
+
+/// Documents the size limits on every operation of an OpenAPI document.
+///
+/// For each operation with a request body:
+/// 1. Every request body content type gets an `x-max-body-size` extension with
+///    the limit (in bytes) that applies to that method, path and content type;
+///    rules with header conditions are skipped, as headers are not known here,
+///    and unlimited content types get no extension
+/// 2. A `413` response is added, unless the operation already documents one
+///
+/// # Arguments
+/// * `openapi` - The OpenAPI document to annotate
+/// * `config` - The size limit configuration used by the middleware
+///
+/// # Example
+/// ```rust
+/// use axum_jetpack::size_limit::{SizeLimitConfig, document_size_limits};
+/// use utoipa::openapi::{
+///     Content, HttpMethod, OpenApiBuilder, PathItem, Paths,
+///     path::OperationBuilder, request_body::RequestBodyBuilder,
+/// };
+///
+/// let operation = OperationBuilder::new()
+///     .request_body(Some(
+///         RequestBodyBuilder::new()
+///             .content("application/json", Content::default())
+///             .build(),
+///     ))
+///     .build();
+/// let mut openapi = OpenApiBuilder::new()
+///     .paths(Paths::builder().path("/items", PathItem::new(HttpMethod::Post, operation)))
+///     .build();
+///
+/// let config = SizeLimitConfig::default().with_specific_limit("application/json", "100kb");
+/// document_size_limits(&mut openapi, &config);
+///
+/// let json = openapi.to_json().unwrap();
+/// assert!(json.contains(r#""x-max-body-size":100000"#));
+/// ```
+pub fn document_size_limits(openapi: &mut OpenApi, config: &SizeLimitConfig) {
+    document_size_limits_matching(openapi, config, |_| true);
+}
+
+/// Documents the size limits on the operations of paths matching a predicate.
+///
+/// Use this when only part of the API is wrapped by the size limit middleware.
+///
+/// # Arguments
+/// * `openapi` - The OpenAPI document to annotate
+/// * `config` - The size limit configuration used by the middleware
+/// * `is_protected` - Returns `true` for paths (as written in the document) covered by the middleware
+pub fn document_size_limits_matching<F>(openapi: &mut OpenApi, config: &SizeLimitConfig, is_protected: F)
+where
+    F: Fn(&str) -> bool,
+{
+    for (path, item) in openapi.paths.paths.iter_mut() {
+        if !is_protected(path) {
+            continue;
+        }
+
+        for (method, operation) in operations_mut(item) {
+            let Some(request_body) = operation.request_body.as_mut() else {
+                continue;
+            };
+
+            for (content_type, content) in request_body.content.iter_mut() {
+                let limit = config
+                    .rules
+                    .iter()
+                    .find(|rule| rule.matches(&method, &[path.as_str()], content_type, &HeaderMap::new()))
+                    .map(|rule| rule.limit)
+                    .unwrap_or_else(|| config.get_limit_for_method_and_content_type(&method, content_type));
+                let Some(limit) = SizeLimit(limit).as_option() else {
+                    continue;
+                };
+                content
+                    .extensions
+                    .get_or_insert_with(Extensions::default)
+                    .insert(MAX_BODY_SIZE_EXTENSION.to_string(), limit.into());
+            }
+
+            operation
+                .responses
+                .responses
+                .entry("413".to_string())
+                .or_insert_with(|| RefOr::T(payload_too_large_response()));
+        }
+    }
+}
+
+impl SizeLimitConfig {
+    /// Derives size limits from the request bodies of an OpenAPI document.
+    ///
+    /// Starts from the default configuration and adds a limit for every
+    /// content type of an operation's request body that declares one; see
+    /// [`with_openapi_limits`](Self::with_openapi_limits).
+    ///
+    /// # Arguments
+    /// * `openapi` - The API contract
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum::http::Method;
+    /// use axum_jetpack::size_limit::SizeLimitConfig;
+    /// use utoipa::openapi::{
+    ///     Content, HttpMethod, OpenApiBuilder, PathItem, Paths, extensions::Extensions,
+    ///     path::OperationBuilder, request_body::RequestBodyBuilder,
+    /// };
+    ///
+    /// let mut json = Content::default();
+    /// let mut extensions = Extensions::default();
+    /// extensions.insert("x-max-body-size".to_string(), "100kb".into());
+    /// json.extensions = Some(extensions);
+    ///
+    /// let operation = OperationBuilder::new()
+    ///     .request_body(Some(RequestBodyBuilder::new().content("application/json", json).build()))
+    ///     .build();
+    /// let openapi = OpenApiBuilder::new()
+    ///     .paths(Paths::builder().path("/items/{id}", PathItem::new(HttpMethod::Put, operation)))
+    ///     .build();
+    ///
+    /// let config = SizeLimitConfig::from_openapi(&openapi);
+    /// assert_eq!(config.rules.len(), 1);
+    /// assert_eq!(config.rules[0].limit, 100_000);
+    /// assert_eq!(config.rules[0].path.as_deref(), Some("/items/{id}"));
+    /// assert_eq!(config.rules[0].methods, [Method::PUT]);
+    /// ```
+    pub fn from_openapi(openapi: &OpenApi) -> Self {
+        Self::default().with_openapi_limits(openapi)
+    }
+
+    /// Builder method to add the size limits declared in an OpenAPI document.
+    ///
+    /// For each operation, every request body content type with a limit
+    /// becomes a [`LimitRule`] for that path, method and content type. The
+    /// limit is read from:
+    /// 1. An `x-max-body-size` extension on the content, as bytes or a
+    ///    human-readable size (`"5mb"`)
+    /// 2. Otherwise `maxLength` of an inline content schema (e.g. a binary
+    ///    string upload); referenced schemas are not resolved
+    ///
+    /// Paths are used as route templates, which match axum's `/items/{id}`
+    /// syntax. Content types without a declared limit keep the rest of the
+    /// configuration.
+    ///
+    /// # Arguments
+    /// * `openapi` - The API contract
+    pub fn with_openapi_limits(mut self, openapi: &OpenApi) -> Self {
+        for (path, item) in &openapi.paths.paths {
+            for (method, operation) in operations(item) {
+                let Some(request_body) = &operation.request_body else {
+                    continue;
+                };
+                for (content_type, content) in &request_body.content {
+                    if let Some(limit) = declared_limit(content) {
+                        self = self.with_rule(
+                            LimitRule::new(limit)
+                                .for_path(path)
+                                .for_method(method.clone())
+                                .for_content_type(content_type),
+                        );
+                    }
+                }
+            }
+        }
+        self
+    }
+}
+
+/// The limit declared on a request body content type, if any.
+fn declared_limit(content: &Content) -> Option<SizeLimit> {
+    let extension = content
+        .extensions
+        .as_ref()
+        .and_then(|extensions| extensions.get(MAX_BODY_SIZE_EXTENSION));
+    if let Some(value) = extension {
+        if let Some(bytes) = value.as_u64() {
+            return Some(SizeLimit(usize::try_from(bytes).unwrap_or(usize::MAX)));
+        }
+        if let Some(limit) = value.as_str().and_then(|size| SizeLimit::parse(size).ok()) {
+            return Some(limit);
+        }
+    }
+    match &content.schema {
+        Some(RefOr::T(Schema::Object(object))) => object.max_length.map(SizeLimit),
+        _ => None,
+    }
+}
+
+/// All operations defined on a path item.
+fn operations(item: &PathItem) -> impl Iterator<Item = (Method, &utoipa::openapi::path::Operation)> {
+    [
+        (Method::GET, &item.get),
+        (Method::PUT, &item.put),
+        (Method::POST, &item.post),
+        (Method::DELETE, &item.delete),
+        (Method::OPTIONS, &item.options),
+        (Method::HEAD, &item.head),
+        (Method::PATCH, &item.patch),
+        (Method::TRACE, &item.trace),
+    ]
+    .into_iter()
+    .filter_map(|(method, operation)| operation.as_ref().map(|operation| (method, operation)))
+}
+
+/// All operations defined on a path item, mutably.
+fn operations_mut(
+    item: &mut PathItem,
+) -> impl Iterator<Item = (Method, &mut utoipa::openapi::path::Operation)> {
+    [
+        (Method::GET, &mut item.get),
+        (Method::PUT, &mut item.put),
+        (Method::POST, &mut item.post),
+        (Method::DELETE, &mut item.delete),
+        (Method::OPTIONS, &mut item.options),
+        (Method::HEAD, &mut item.head),
+        (Method::PATCH, &mut item.patch),
+        (Method::TRACE, &mut item.trace),
+    ]
+    .into_iter()
+    .filter_map(|(method, operation)| operation.as_mut().map(|operation| (method, operation)))
+}
+
+/// The 413 response as rendered by the middleware (plain text body).
+fn payload_too_large_response() -> Response {
+    let mut response = Response::new("Payload too large");
+    response.content.insert(
+        "text/plain".to_string(),
+        Content::new(Some(Object::with_type(Type::String))),
+    );
+    response
+}
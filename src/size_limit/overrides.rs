@@ -0,0 +1,137 @@
+//! Per-request overrides of the size limit.
+//!
+//! [`SizeLimitOverride`] is a tiny layer that stores a limit in the request
+//! extensions. The size limiter uses it instead of the limit its
+//! configuration or policy chose, so one global limiter can be relaxed or
+//! tightened for individual routes.
+//!
+//! Axum runs layers added with `Router::layer` *outside* the layers of a
+//! route, so where the override sits decides what it can do:
+//!
+//! * **Before the limiter**, e.g. added with `Router::layer` after the
+//!   limiter, the limiter sees the override and enforces it instead of its
+//!   own limit, in both directions.
+//! * **On a route, inside the limiter** the body has already passed the
+//!   global limit, so the override can only tighten it: it enforces its own
+//!   limit when that is lower, rejecting with the limiter's error format.
+
+use axum::{body::Body, extract::Request, response::Response};
+use futures::future::{BoxFuture, Either};
+use std::task::{Context, Poll};
+use tower::{Layer, Service};
+
+use crate::error::ErrorFormat;
+use crate::size_limit::middleware::{BodyBudget, SizeLimitedBody, buffer_body};
+use crate::size_limit::{SizeLimit, SizeLimitError, SizeLimitInfo};
+
+/// Layer overriding the size limit for the requests it sees.
+///
+/// # Examples
+/// ```
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::size_limit::{RouterExt, SizeLimitOverride};
+///
+/// let router: Router = Router::new()
+///     .route("/comments", post(|| async { "ok" }))
+///     // Tightens the global limit for this route only
+///     .route("/avatar", post(|| async { "ok" }).layer(SizeLimitOverride::new("100kb")))
+///     .size_limit("2mb");
+///
+/// // Runs before the limiter, so it may also relax it
+/// let router = router.layer(SizeLimitOverride::new("50mb"));
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct SizeLimitOverride {
+    limit: usize,
+}
+
+impl SizeLimitOverride {
+    /// Creates an override.
+    ///
+    /// # Arguments
+    /// * `limit` - Limit to enforce instead of the configured one
+    pub fn new(limit: impl Into<SizeLimit>) -> Self {
+        Self { limit: limit.into().0 }
+    }
+
+    /// The overriding limit in bytes.
+    pub fn limit(&self) -> usize {
+        self.limit
+    }
+}
+
+impl<S> Layer<S> for SizeLimitOverride {
+    type Service = SizeLimitOverrideService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SizeLimitOverrideService {
+            inner,
+            limit: self.limit,
+        }
+    }
+}
+
+/// Limit enforced by a size limiter further out, left in the request
+/// extensions for overrides on the route.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct EnforcedLimit {
+    pub(crate) limit: usize,
+    pub(crate) error_format: ErrorFormat,
+}
+
+/// Service created by [`SizeLimitOverride`].
+///
+/// For every request it:
+/// 1. Stores the override in the request extensions for a limiter further in
+/// 2. If a limiter further out already enforced a higher limit, enforces the
+///    override itself and returns 413 (Payload Too Large) for larger bodies
+#[derive(Clone, Debug)]
+pub struct SizeLimitOverrideService<S> {
+    inner: S,
+    limit: usize,
+}
+
+impl<S> Service<Request<Body>> for SizeLimitOverrideService<S>
+where
+    S: Service<Request<Body>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Either<S::Future, BoxFuture<'static, Result<Response, S::Error>>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<Body>) -> Self::Future {
+        let limit = self.limit;
+        req.extensions_mut().insert(SizeLimitOverride { limit });
+
+        let enforced = match req.extensions().get::<EnforcedLimit>() {
+            Some(enforced) if limit < enforced.limit => *enforced,
+            _ => return Either::Left(self.inner.call(req)),
+        };
+
+        // Call the service that was made ready, leave a fresh clone in its place
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        Either::Right(Box::pin(async move {
+            let error_format = ErrorFormat::for_request(req.extensions(), enforced.error_format);
+            let (mut parts, body) = req.into_parts();
+            let (body, _) = SizeLimitedBody::wrap(body, BodyBudget::new(limit, None));
+            match buffer_body(body, limit).await {
+                Ok((body, pooled)) => {
+                    parts.extensions.insert(EnforcedLimit { limit, error_format });
+                    if let Some(info) = parts.extensions.get_mut::<SizeLimitInfo>() {
+                        info.limit = limit;
+                    }
+                    let response = inner.call(Request::from_parts(parts, body)).await;
+                    drop(pooled);
+                    response
+                }
+                Err(_) => Ok(SizeLimitError::PayloadTooLarge.into_response(error_format)),
+            }
+        }))
+    }
+}
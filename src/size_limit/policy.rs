@@ -0,0 +1,125 @@
+//! Pluggable admission decisions for the size limiter.
+//!
+//! The size limiter asks a [`LimitPolicy`] what to do with each request that
+//! carries a body: accept it with a limit and a buffering mode, reject it, or
+//! bypass limiting altogether. The content-type configuration
+//! ([`ContentTypeMatcher`]) is the default policy; implement the trait to plug
+//! in other rule engines and install it with
+//! [`with_size_limit_policy`](crate::size_limit::with_size_limit_policy).
+
+use axum::http::request::Parts;
+use std::future::Future;
+
+use crate::error::ErrorFormat;
+use crate::size_limit::{ContentTypeMatcher, SizeLimitError};
+
+/// What a [`LimitPolicy`] knows about a request.
+#[derive(Clone, Copy, Debug)]
+pub struct RequestContext<'a> {
+    /// Method, URI, headers and extensions of the request.
+    pub parts: &'a Parts,
+    /// Route template, when the router matched one.
+    pub route: Option<&'a str>,
+    /// `Content-Type` header, `"application/octet-stream"` when missing.
+    pub content_type: &'a str,
+    /// Declared `Content-Length`, if any.
+    pub content_length: Option<u64>,
+}
+
+/// How an accepted body is read.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Buffering {
+    /// Read the whole body into memory before calling the handler.
+    Buffer,
+    /// Pass the body through in chunks while counting.
+    Stream,
+}
+
+/// Outcome of a [`LimitPolicy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Decision {
+    /// Enforce `limit` bytes, reading the body as `buffering` says.
+    Accept {
+        /// Size limit in bytes.
+        limit: usize,
+        /// How the body is read.
+        buffering: Buffering,
+    },
+    /// Reject the request with this error.
+    Reject(SizeLimitError),
+    /// Pass the request through without any limit.
+    Bypass,
+}
+
+/// Decides how the size limiter treats a request.
+///
+/// Implementations may be `async fn`s, e.g. to consult a remote policy
+/// engine; keep them fast, as they run on every request with a body.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::size_limit::{Buffering, Decision, LimitPolicy, RequestContext, SizeLimitError};
+///
+/// /// Admins may upload 1 GB, everyone else 10 MB; `/internal` is not limited.
+/// struct RolePolicy;
+///
+/// impl LimitPolicy for RolePolicy {
+///     async fn decide(&self, ctx: RequestContext<'_>) -> Decision {
+///         if ctx.route.is_some_and(|route| route.starts_with("/internal")) {
+///             return Decision::Bypass;
+///         }
+///         let admin = ctx.parts.headers.get("x-role").is_some_and(|role| role == "admin");
+///         let limit = if admin { 1_000_000_000 } else { 10_000_000 };
+///         match ctx.content_length {
+///             Some(length) if length > limit as u64 => Decision::Reject(SizeLimitError::PayloadTooLarge),
+///             _ => Decision::Accept { limit, buffering: Buffering::Stream },
+///         }
+///     }
+/// }
+/// ```
+pub trait LimitPolicy: Send + Sync + 'static {
+    /// Decides how to handle the request described by `ctx`.
+    fn decide(&self, ctx: RequestContext<'_>) -> impl Future<Output = Decision> + Send;
+
+    /// Called after the size limiter rejected a request, e.g. to keep
+    /// statistics. Does nothing by default.
+    ///
+    /// # Arguments
+    /// * `content_type` - `Content-Type` header, `"application/octet-stream"` when missing
+    /// * `error` - Why the request was rejected
+    fn rejected(&self, content_type: &str, error: SizeLimitError) {
+        let _ = (content_type, error);
+    }
+
+    /// Error format for rejections on a route, overriding the middleware's
+    /// `error_format`. Formats set with
+    /// [`with_error_formats`](crate::error::with_error_formats) still take
+    /// precedence. Returns `None` by default.
+    ///
+    /// # Arguments
+    /// * `route` - Route template, when the router matched one
+    fn error_format(&self, route: Option<&str>) -> Option<ErrorFormat> {
+        let _ = route;
+        None
+    }
+}
+
+/// The default policy: ordered rules, then limits and buffering by content
+/// type, capped per method. Unlisted content types in allowlist mode, and
+/// requests without a `Content-Type` if configured, are rejected with 415.
+impl LimitPolicy for ContentTypeMatcher {
+    async fn decide(&self, ctx: RequestContext<'_>) -> Decision {
+        if let Err(error) = self.check_request(ctx.parts, ctx.content_type) {
+            return Decision::Reject(error);
+        }
+        let rule = self.lookup_request(ctx.parts, ctx.content_type);
+        Decision::Accept {
+            limit: rule.limit,
+            buffering: if rule.buffer {
+                Buffering::Buffer
+            } else {
+                Buffering::Stream
+            },
+        }
+    }
+}
@@ -0,0 +1,86 @@
+//! Reusable buffers for assembling buffered bodies.
+//!
+//! A body arriving in several chunks is copied into one [`BytesMut`] taken
+//! from the pool and frozen into the [`Bytes`] handed to the handler. Once
+//! the handler is done, the buffer comes back if nothing else still holds
+//! it: a handler keeping the bytes (or a slice of them) keeps the buffer,
+//! which the pool then simply does without.
+
+use axum::body::Bytes;
+use bytes::BytesMut;
+use std::sync::{Mutex, PoisonError};
+
+/// Most buffers kept for reuse.
+const MAX_POOLED: usize = 64;
+
+/// Largest buffer kept for reuse; rare large bodies do not pin their memory.
+const MAX_POOLED_CAPACITY: usize = 1024 * 1024;
+
+/// Buffers shared by every buffered body of the process.
+static POOL: Mutex<Vec<BytesMut>> = Mutex::new(Vec::new());
+
+/// Takes an empty buffer with room for at least `capacity` bytes.
+pub(crate) fn take(capacity: usize) -> BytesMut {
+    let mut pool = POOL.lock().unwrap_or_else(PoisonError::into_inner);
+    match pool.iter().position(|buffer| buffer.capacity() >= capacity) {
+        Some(index) => pool.swap_remove(index),
+        None => BytesMut::with_capacity(capacity),
+    }
+}
+
+/// Holds the bytes of a pooled buffer, returning it to the pool on drop if
+/// they are no longer shared.
+#[derive(Debug, Default)]
+pub(crate) struct Pooled(Option<Bytes>);
+
+impl Pooled {
+    /// Tracks `bytes`, frozen from a buffer of [`take`].
+    pub(crate) fn new(bytes: Bytes) -> Self {
+        Self(Some(bytes))
+    }
+}
+
+impl Drop for Pooled {
+    fn drop(&mut self) {
+        let Some(Ok(mut buffer)) = self.0.take().map(Bytes::try_into_mut) else {
+            return;
+        };
+        if buffer.capacity() == 0 || buffer.capacity() > MAX_POOLED_CAPACITY {
+            return;
+        }
+        buffer.clear();
+        let mut pool = POOL.lock().unwrap_or_else(PoisonError::into_inner);
+        if pool.len() < MAX_POOLED {
+            pool.push(buffer);
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::disallowed_methods)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buffers_come_back_unless_shared() {
+        // A buffer nobody else holds is reused
+        let mut buffer = take(4096);
+        buffer.extend_from_slice(b"first body");
+        let address = buffer.as_ptr();
+        let bytes = buffer.freeze();
+        drop(Pooled::new(bytes.clone()));
+        drop(Pooled::new(bytes));
+        let reused = (0..MAX_POOLED).map(|_| take(4096)).collect::<Vec<_>>();
+        assert!(reused.iter().any(|buffer| buffer.as_ptr() == address && buffer.is_empty()));
+
+        // A buffer still held by the handler is left to it
+        let mut buffer = take(8192);
+        buffer.extend_from_slice(b"kept body");
+        let address = buffer.as_ptr();
+        let kept = buffer.freeze().slice(..4);
+        drop(Pooled::new(kept.clone()));
+        let taken = (0..MAX_POOLED).map(|_| take(8192)).collect::<Vec<_>>();
+        assert!(taken.iter().all(|buffer| buffer.as_ptr() != address));
+        assert_eq!(kept, "kept");
+    }
+}
@@ -0,0 +1,73 @@
+//! Upload progress callbacks.
+//!
+//! [`on_progress`](crate::size_limit::SizeLimitMiddlewareConfig::on_progress)
+//! registers a callback the size limiter calls as it reads a body, to log
+//! uploads, feed a progress API or abort uploads on custom grounds.
+
+use std::ops::ControlFlow;
+use std::sync::Arc;
+
+/// Callback fired as a body is read. Returning [`ControlFlow::Break`]
+/// aborts the upload.
+pub type ProgressHook = Arc<dyn Fn(&UploadProgress<'_>) -> ControlFlow<()> + Send + Sync>;
+
+/// Context passed to a [`ProgressHook`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UploadProgress<'a> {
+    /// Body bytes read so far.
+    pub consumed: u64,
+    /// The enforced limit in bytes.
+    pub limit: usize,
+    /// `Content-Type` header, `"application/octet-stream"` when missing.
+    pub content_type: &'a str,
+}
+
+/// Fires the progress callback of one body.
+#[derive(Clone)]
+pub(crate) struct ProgressMeter {
+    hook: ProgressHook,
+    interval: u64,
+    /// Bytes from which the callback fires next.
+    next: u64,
+    limit: usize,
+    content_type: Arc<str>,
+}
+
+impl ProgressMeter {
+    pub(crate) fn new(hook: ProgressHook, interval: usize, limit: usize, content_type: &str) -> Self {
+        Self {
+            hook,
+            interval: interval as u64,
+            next: 0,
+            limit,
+            content_type: content_type.into(),
+        }
+    }
+
+    /// Notes that `consumed` bytes were read, returning `Break` if the
+    /// callback aborts the upload.
+    pub(crate) fn update(&mut self, consumed: u64) -> ControlFlow<()> {
+        if consumed < self.next {
+            return ControlFlow::Continue(());
+        }
+        // Every chunk with no interval, otherwise the next multiple of it
+        self.next = match self.interval {
+            0 => consumed,
+            interval => (consumed / interval + 1) * interval,
+        };
+        (self.hook)(&UploadProgress {
+            consumed,
+            limit: self.limit,
+            content_type: &self.content_type,
+        })
+    }
+}
+
+impl std::fmt::Debug for ProgressMeter {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgressMeter")
+            .field("interval", &self.interval)
+            .field("next", &self.next)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,357 @@
+//! Byte quotas per key over a time window.
+//!
+//! Per-request limits bound each upload, not how much a client uploads in
+//! total. A [`ByteQuota`] accumulates the body bytes of each key (API key,
+//! tenant, client IP, see [`KeyExtractor`]) over a fixed window, e.g. 1 GB a
+//! day, in a [`QuotaStore`]: in memory for one instance, or in Redis (feature
+//! `quota-redis`) to share it between instances. Keys that used up their
+//! quota get 429 (Too Many Requests) until the window resets.
+
+use axum::http::{HeaderMap, HeaderName, HeaderValue, header, request::Parts};
+use futures::future::BoxFuture;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use crate::size_limit::{HeaderKey, KeyExtractor, PeerIp, SizeLimit};
+
+/// `X-Quota-Limit`: the byte quota of the key per window.
+pub const X_QUOTA_LIMIT: HeaderName = HeaderName::from_static("x-quota-limit");
+/// `X-Quota-Remaining`: bytes the key may still upload in this window.
+pub const X_QUOTA_REMAINING: HeaderName = HeaderName::from_static("x-quota-remaining");
+/// `X-Quota-Reset`: seconds until the window resets.
+pub const X_QUOTA_RESET: HeaderName = HeaderName::from_static("x-quota-reset");
+
+/// Usage of a key in its current window.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct QuotaUsage {
+    /// Bytes used in the window.
+    pub used: u64,
+    /// Time until the window resets.
+    pub resets_in: Duration,
+}
+
+/// Storage of the byte counts of quota windows.
+///
+/// Windows are fixed: a key's window starts with its first recorded bytes
+/// and its count resets once it has elapsed. Store errors let requests
+/// through: an unavailable store must not take uploads down.
+pub trait QuotaStore: Send + Sync + 'static {
+    /// Returns the usage of `key` in its current window.
+    fn usage<'a>(&'a self, key: &'a str, window: Duration) -> BoxFuture<'a, Result<QuotaUsage, String>>;
+
+    /// Adds `bytes` to `key`, starting a window if it has none, and returns
+    /// the new usage.
+    fn record<'a>(&'a self, key: &'a str, bytes: u64, window: Duration) -> BoxFuture<'a, Result<QuotaUsage, String>>;
+}
+
+/// [`QuotaStore`] keeping the windows in memory, for a single instance.
+///
+/// Clones share the counts.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::size_limit::{MemoryQuotaStore, QuotaStore};
+/// use std::time::Duration;
+///
+/// # tokio_test();
+/// # #[tokio::main(flavor = "current_thread")]
+/// # async fn tokio_test() {
+/// let store = MemoryQuotaStore::new();
+/// let hour = Duration::from_secs(3600);
+/// store.record("tenant-a", 1_500, hour).await.unwrap();
+/// assert_eq!(store.usage("tenant-a", hour).await.unwrap().used, 1_500);
+/// assert_eq!(store.usage("tenant-b", hour).await.unwrap().used, 0);
+/// # }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MemoryQuotaStore {
+    windows: Arc<Mutex<Windows>>,
+}
+
+/// Windows of a [`MemoryQuotaStore`].
+#[derive(Debug, Default)]
+struct Windows {
+    /// Start and byte count of each key's window.
+    counts: HashMap<String, (Instant, u64)>,
+    /// When expired windows were last swept out.
+    swept: Option<Instant>,
+}
+
+/// Keys kept before expired windows are swept out, at most once per window.
+const SWEEP_THRESHOLD: usize = 1024;
+
+impl MemoryQuotaStore {
+    /// Creates an empty store.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn usage_now(&self, key: &str, window: Duration) -> QuotaUsage {
+        let windows = self.windows.lock().unwrap_or_else(PoisonError::into_inner);
+        let elapsed = windows.counts.get(key).map(|(start, used)| (start.elapsed(), *used));
+        match elapsed {
+            Some((elapsed, used)) if elapsed < window => QuotaUsage {
+                used,
+                resets_in: window.saturating_sub(elapsed),
+            },
+            _ => QuotaUsage {
+                used: 0,
+                resets_in: window,
+            },
+        }
+    }
+
+    fn record_now(&self, key: &str, bytes: u64, window: Duration) -> QuotaUsage {
+        let mut windows = self.windows.lock().unwrap_or_else(PoisonError::into_inner);
+        let now = Instant::now();
+        // Nothing expires sooner than a window after the last sweep
+        if windows.counts.len() >= SWEEP_THRESHOLD
+            && !windows.counts.contains_key(key)
+            && windows.swept.is_none_or(|swept| now.duration_since(swept) >= window)
+        {
+            windows.counts.retain(|_, (start, _)| now.duration_since(*start) < window);
+            windows.swept = Some(now);
+        }
+        let (start, used) = windows.counts.entry(key.to_string()).or_insert((now, 0));
+        if now.duration_since(*start) >= window {
+            *start = now;
+            *used = 0;
+        }
+        *used = used.saturating_add(bytes);
+        QuotaUsage {
+            used: *used,
+            resets_in: window.saturating_sub(now.duration_since(*start)),
+        }
+    }
+}
+
+impl QuotaStore for MemoryQuotaStore {
+    fn usage<'a>(&'a self, key: &'a str, window: Duration) -> BoxFuture<'a, Result<QuotaUsage, String>> {
+        let usage = self.usage_now(key, window);
+        Box::pin(async move { Ok(usage) })
+    }
+
+    fn record<'a>(&'a self, key: &'a str, bytes: u64, window: Duration) -> BoxFuture<'a, Result<QuotaUsage, String>> {
+        let usage = self.record_now(key, bytes, window);
+        Box::pin(async move { Ok(usage) })
+    }
+}
+
+/// [`QuotaStore`] keeping the windows in Redis, shared by all instances.
+///
+/// Each key is a Redis counter expiring with its window.
+///
+/// # Examples
+/// ```rust,no_run
+/// use axum_jetpack::size_limit::{ByteQuota, RedisQuotaStore};
+/// use std::time::Duration;
+///
+/// # async fn run() -> Result<(), String> {
+/// let store = RedisQuotaStore::connect("redis://127.0.0.1/").await?.with_prefix("uploads:");
+/// let quota = ByteQuota::new("1gb", Duration::from_secs(86_400)).with_store(store);
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(feature = "quota-redis")]
+#[derive(Clone)]
+pub struct RedisQuotaStore {
+    connection: redis::aio::MultiplexedConnection,
+    prefix: String,
+}
+
+#[cfg(feature = "quota-redis")]
+impl RedisQuotaStore {
+    /// Creates a store on an open connection.
+    pub fn new(connection: redis::aio::MultiplexedConnection) -> Self {
+        Self {
+            connection,
+            prefix: "quota:".to_string(),
+        }
+    }
+
+    /// Connects to the Redis server at `url`, e.g. `redis://127.0.0.1/`.
+    pub async fn connect(url: &str) -> Result<Self, String> {
+        let client = redis::Client::open(url).map_err(|e| format!("Invalid Redis URL '{}': {}", url, e))?;
+        let connection = client
+            .get_multiplexed_async_connection()
+            .await
+            .map_err(|e| format!("Failed to connect to Redis at '{}': {}", url, e))?;
+        Ok(Self::new(connection))
+    }
+
+    /// Builder method to set the prefix of the Redis keys. `"quota:"` by
+    /// default.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Turns a count and its remaining time to live into a usage.
+    fn to_usage((used, ttl): (Option<u64>, i64), window: Duration) -> QuotaUsage {
+        match u64::try_from(ttl) {
+            Ok(ttl) => QuotaUsage {
+                used: used.unwrap_or(0),
+                resets_in: Duration::from_millis(ttl).min(window),
+            },
+            // No window running
+            Err(_) => QuotaUsage {
+                used: 0,
+                resets_in: window,
+            },
+        }
+    }
+}
+
+#[cfg(feature = "quota-redis")]
+impl fmt::Debug for RedisQuotaStore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RedisQuotaStore").field("prefix", &self.prefix).finish_non_exhaustive()
+    }
+}
+
+#[cfg(feature = "quota-redis")]
+impl QuotaStore for RedisQuotaStore {
+    fn usage<'a>(&'a self, key: &'a str, window: Duration) -> BoxFuture<'a, Result<QuotaUsage, String>> {
+        Box::pin(async move {
+            let key = format!("{}{}", self.prefix, key);
+            let mut connection = self.connection.clone();
+            let reply = redis::pipe()
+                .cmd("GET")
+                .arg(&key)
+                .cmd("PTTL")
+                .arg(&key)
+                .query_async(&mut connection)
+                .await
+                .map_err(|e| format!("Failed to read quota of '{}': {}", key, e))?;
+            Ok(Self::to_usage(reply, window))
+        })
+    }
+
+    fn record<'a>(&'a self, key: &'a str, bytes: u64, window: Duration) -> BoxFuture<'a, Result<QuotaUsage, String>> {
+        Box::pin(async move {
+            let key = format!("{}{}", self.prefix, key);
+            let mut connection = self.connection.clone();
+            let millis = u64::try_from(window.as_millis()).unwrap_or(u64::MAX).max(1);
+            // Starts the window if none is running, then counts
+            let (used, ttl): (u64, i64) = redis::pipe()
+                .atomic()
+                .cmd("SET")
+                .arg(&key)
+                .arg(0)
+                .arg("PX")
+                .arg(millis)
+                .arg("NX")
+                .ignore()
+                .cmd("INCRBY")
+                .arg(&key)
+                .arg(bytes)
+                .cmd("PTTL")
+                .arg(&key)
+                .query_async(&mut connection)
+                .await
+                .map_err(|e| format!("Failed to record quota of '{}': {}", key, e))?;
+            Ok(Self::to_usage((Some(used), ttl), window))
+        })
+    }
+}
+
+/// A quota on request-body bytes per key over a fixed window.
+///
+/// Checked before a body is read: keys that used up their quota, or whose
+/// body is known (e.g. by `Content-Length`) to exceed what is left, get 429
+/// with `Retry-After`. The bytes read are recorded once the response is
+/// ready, so a key may overshoot its quota by one request. Every response of
+/// an accounted key carries `X-Quota-Limit`, `X-Quota-Remaining` and
+/// `X-Quota-Reset` (seconds).
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::size_limit::{ByteQuota, SizeLimitMiddlewareConfig};
+/// use std::time::Duration;
+///
+/// // 1 GB per API key per day
+/// let quota = ByteQuota::new("1gb", Duration::from_secs(86_400)).with_header_key("x-api-key");
+/// assert_eq!(quota.max_bytes, 1_000_000_000);
+///
+/// let config = SizeLimitMiddlewareConfig::default().with_quota(quota);
+/// ```
+#[derive(Clone)]
+pub struct ByteQuota {
+    /// Body bytes allowed per key and window.
+    pub max_bytes: u64,
+    /// Length of a window.
+    pub window: Duration,
+    /// Picks the key of a request. The client IP ([`PeerIp`]) by default.
+    pub key: Arc<dyn KeyExtractor>,
+    /// Where the counts are kept. A fresh [`MemoryQuotaStore`] by default.
+    pub store: Arc<dyn QuotaStore>,
+}
+
+impl ByteQuota {
+    /// Creates a quota keyed on the client IP, counted in memory.
+    ///
+    /// # Arguments
+    /// * `max_bytes` - Body bytes allowed per key and window
+    /// * `window` - Length of a window
+    pub fn new(max_bytes: impl Into<SizeLimit>, window: Duration) -> Self {
+        Self {
+            max_bytes: max_bytes.into().0 as u64,
+            window,
+            key: Arc::new(PeerIp),
+            store: Arc::new(MemoryQuotaStore::new()),
+        }
+    }
+
+    /// Builder method to account requests by a custom key.
+    pub fn with_key(mut self, key: impl KeyExtractor) -> Self {
+        self.key = Arc::new(key);
+        self
+    }
+
+    /// Builder method to account requests by a header, e.g. `x-api-key`.
+    pub fn with_header_key(self, header: &'static str) -> Self {
+        self.with_key(HeaderKey::new(header))
+    }
+
+    /// Builder method to keep the counts in another store.
+    pub fn with_store(mut self, store: impl QuotaStore) -> Self {
+        self.store = Arc::new(store);
+        self
+    }
+
+    /// Looks up the usage of the key of a request, if it has one and the
+    /// store answers.
+    pub(crate) async fn check(&self, parts: &Parts) -> Option<(String, QuotaUsage)> {
+        let key = self.key.key(parts)?;
+        let usage = self.store.usage(&key, self.window).await.ok()?;
+        Some((key, usage))
+    }
+
+    /// Whether a body of `declared` bytes is refused for a key with `usage`.
+    pub(crate) fn exceeded(&self, usage: QuotaUsage, declared: Option<u64>) -> bool {
+        usage.used >= self.max_bytes || usage.used.saturating_add(declared.unwrap_or(0)) > self.max_bytes
+    }
+
+    /// Describes the quota of a key on a response.
+    pub(crate) fn apply(&self, headers: &mut HeaderMap, usage: QuotaUsage, exceeded: bool) {
+        // Round up so clients never retry before the window resets
+        let reset = usage.resets_in.as_secs() + u64::from(usage.resets_in.subsec_nanos() > 0);
+        headers.insert(X_QUOTA_LIMIT, HeaderValue::from(self.max_bytes));
+        headers.insert(X_QUOTA_REMAINING, HeaderValue::from(self.max_bytes.saturating_sub(usage.used)));
+        headers.insert(X_QUOTA_RESET, HeaderValue::from(reset));
+        if exceeded {
+            headers.insert(header::RETRY_AFTER, HeaderValue::from(reset));
+        }
+    }
+}
+
+impl fmt::Debug for ByteQuota {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ByteQuota")
+            .field("max_bytes", &self.max_bytes)
+            .field("window", &self.window)
+            .finish_non_exhaustive()
+    }
+}
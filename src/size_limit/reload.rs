@@ -0,0 +1,186 @@
+//! Size limits reloaded from a watched configuration file (feature `hot-reload`).
+//!
+//! Upload limits are operational knobs; [`with_size_limit_watching`] lets
+//! operators change them by editing a file instead of restarting the service.
+//! The file is polled, and a changed file is parsed and validated before the
+//! new limits replace the old ones, so a broken edit never takes effect.
+
+use arc_swap::ArcSwap;
+use axum::Router;
+use std::fmt;
+use std::path::PathBuf;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use crate::size_limit::file::parse_middleware_file;
+use crate::size_limit::handle::LiveLimits;
+use crate::size_limit::{SizeLimitHandle, with_size_limit_policy};
+
+/// How often a watched file is checked for changes by default.
+pub const DEFAULT_WATCH_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Callback fired after each reload attempt: `Ok(())` when new limits were
+/// applied, `Err(message)` when the file could not be read or parsed and the
+/// previous limits stay in effect.
+pub type ReloadHook = Arc<dyn Fn(&Result<(), String>) + Send + Sync>;
+
+/// A configuration file to load size limits from and watch for changes.
+///
+/// The file uses the [`SizeLimitMiddlewareConfig::from_toml_str`](crate::size_limit::SizeLimitMiddlewareConfig::from_toml_str)
+/// schema, in TOML or YAML by extension (see
+/// [`SizeLimitMiddlewareConfig::from_file`](crate::size_limit::SizeLimitMiddlewareConfig::from_file)).
+#[derive(Clone)]
+pub struct ConfigWatch {
+    /// Path of the watched file.
+    pub path: PathBuf,
+    /// Polling interval. Defaults to [`DEFAULT_WATCH_INTERVAL`].
+    pub interval: Duration,
+    /// Callback fired after each reload attempt. `None` by default.
+    pub on_reload: Option<ReloadHook>,
+}
+
+impl ConfigWatch {
+    /// Watches `path` at the default interval.
+    ///
+    /// # Arguments
+    /// * `path` - Path of the TOML or YAML configuration file
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            interval: DEFAULT_WATCH_INTERVAL,
+            on_reload: None,
+        }
+    }
+
+    /// Builder method to set the polling interval.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Builder method to set a callback fired after each reload attempt,
+    /// e.g. to log rejected edits.
+    ///
+    /// # Example
+    /// ```rust
+    /// use axum_jetpack::size_limit::ConfigWatch;
+    ///
+    /// let watch = ConfigWatch::new("limits.toml").on_reload(|result| {
+    ///     if let Err(error) = result {
+    ///         eprintln!("size limits not reloaded: {}", error);
+    ///     }
+    /// });
+    /// ```
+    pub fn on_reload(mut self, hook: impl Fn(&Result<(), String>) + Send + Sync + 'static) -> Self {
+        self.on_reload = Some(Arc::new(hook));
+        self
+    }
+}
+
+impl fmt::Debug for ConfigWatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConfigWatch")
+            .field("path", &self.path)
+            .field("interval", &self.interval)
+            .field("on_reload", &self.on_reload.as_ref().map(|_| "<hook>"))
+            .finish()
+    }
+}
+
+/// Applies size limiting configured by a file that is reloaded when it changes.
+///
+/// The file is loaded once up front; an invalid file is an error. A
+/// background task then polls it every `watch.interval` and, once changed
+/// contents have settled for one interval, parses the new version and swaps
+/// in its limits
+/// (`limits`) and buffer strategy (`buffering`) without interrupting
+/// in-flight requests. Edits that fail to parse are reported through
+/// `watch.on_reload` and otherwise ignored.
+///
+/// Settings read by the middleware itself - `error_format`,
+/// `fast_path_methods`, `exempt_methods`, `exempt_paths`,
+/// `bodyless_methods`, `strict_content_length` and `require_content_length` -
+/// are taken from the initial load only.
+///
+/// The task stops once the returned router is dropped. Must be called from
+/// within a Tokio runtime.
+///
+/// # Arguments
+/// * `router` - The Axum router to wrap with middleware
+/// * `watch` - The file to load and watch
+///
+/// # Returns
+/// The router with size limiting applied, or an error if the file could
+/// not be loaded or no runtime is running.
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::size_limit::{ConfigWatch, with_size_limit_watching};
+///
+/// # async fn run() -> Result<(), String> {
+/// let router: Router = Router::new().route("/upload", post(|| async { "ok" }));
+/// let router = with_size_limit_watching(router, ConfigWatch::new("/etc/app/limits.toml"))?;
+/// # Ok(())
+/// # }
+/// ```
+pub fn with_size_limit_watching(router: Router, watch: ConfigWatch) -> Result<Router, String> {
+    let runtime = tokio::runtime::Handle::try_current()
+        .map_err(|_| "with_size_limit_watching must be called within a Tokio runtime".to_string())?;
+    let contents = std::fs::read_to_string(&watch.path)
+        .map_err(|e| format!("Failed to read '{}': {}", watch.path.display(), e))?;
+    let config = parse_middleware_file(&watch.path, &contents)?;
+
+    let handle = SizeLimitHandle::new(config.size_limits.clone(), config.buffer_strategy.clone());
+    runtime.spawn(poll(watch, handle.downgrade(), Some(contents)));
+
+    Ok(with_size_limit_policy(router, config, handle))
+}
+
+/// Polls the watched file until the limits it feeds are dropped.
+///
+/// A change is applied once the file reads the same on two consecutive
+/// ticks, so a file caught halfway through being rewritten is never parsed.
+async fn poll(watch: ConfigWatch, current: Weak<ArcSwap<LiveLimits>>, mut last: Option<String>) {
+    let mut interval = tokio::time::interval(watch.interval);
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    interval.tick().await;
+    let mut pending: Option<String> = None;
+
+    loop {
+        interval.tick().await;
+        let Some(current) = current.upgrade() else {
+            return;
+        };
+
+        let result = match tokio::fs::read_to_string(&watch.path).await {
+            Ok(contents) if last.as_deref() == Some(contents.as_str()) => {
+                pending = None;
+                continue;
+            }
+            Ok(contents) if pending.as_deref() != Some(contents.as_str()) => {
+                pending = Some(contents);
+                continue;
+            }
+            Ok(contents) => {
+                pending = None;
+                let result = parse_middleware_file(&watch.path, &contents).map(|config| {
+                    current.store(Arc::new(LiveLimits::new(config.size_limits, config.buffer_strategy)));
+                });
+                last = Some(contents);
+                result
+            }
+            // Report a vanished file once, not on every tick
+            Err(_) if last.is_none() => continue,
+            Err(e) => {
+                last = None;
+                pending = None;
+                Err(format!("Failed to read '{}': {}", watch.path.display(), e))
+            }
+        };
+
+        if let Some(hook) = &watch.on_reload {
+            hook(&result);
+        }
+    }
+}
@@ -0,0 +1,189 @@
+//! Report-only (dry-run) and canary enforcement.
+//!
+//! With [`Enforcement::ReportOnly`] the size limiter measures bodies and
+//! reports every request it would have rejected through
+//! [`on_would_reject`](crate::size_limit::SizeLimitMiddlewareConfig::on_would_reject),
+//! but lets them all through, so new limits can be observed in production
+//! before they are enforced. [`Enforcement::Canary`] enforces them for a
+//! share of the requests only and reports the rest.
+
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    http::{HeaderMap, HeaderName, Method},
+};
+use http_body::{Frame, SizeHint};
+use std::hash::{BuildHasher, RandomState};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::task::{Context, Poll, ready};
+
+use crate::size_limit::SizeLimitError;
+use crate::size_limit::middleware::BodyBudget;
+
+/// Whether the size limiter rejects requests or only reports them.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Enforcement {
+    /// Reject requests that break a limit.
+    #[default]
+    Enforce,
+    /// Let every request through, reporting those that would have been
+    /// rejected. Bodies are passed through as they arrive, never buffered.
+    ReportOnly,
+    /// Enforce for this percentage of requests (0-100), report-only for the
+    /// rest. Requests are picked by the
+    /// [`canary_key`](crate::size_limit::SizeLimitMiddlewareConfig::canary_key)
+    /// header, so retries of a request are treated alike, or at random.
+    Canary(u8),
+}
+
+impl Enforcement {
+    /// Returns `true` if rejections are enforced for a request.
+    ///
+    /// # Arguments
+    /// * `headers` - The request headers
+    /// * `key` - Header identifying the request (e.g. `x-request-id`); requests
+    ///   without it are picked at random
+    ///
+    /// # Examples
+    /// ```
+    /// use axum::http::{HeaderMap, HeaderName, HeaderValue};
+    /// use axum_jetpack::size_limit::Enforcement;
+    ///
+    /// let key = HeaderName::from_static("x-request-id");
+    /// let mut headers = HeaderMap::new();
+    /// headers.insert(&key, HeaderValue::from_static("abc"));
+    ///
+    /// assert!(Enforcement::Canary(100).enforces(&headers, Some(&key)));
+    /// assert!(!Enforcement::Canary(0).enforces(&headers, Some(&key)));
+    /// // The same request id always gets the same answer
+    /// let canary = Enforcement::Canary(30);
+    /// assert_eq!(canary.enforces(&headers, Some(&key)), canary.enforces(&headers, Some(&key)));
+    /// ```
+    pub fn enforces(&self, headers: &HeaderMap, key: Option<&HeaderName>) -> bool {
+        match *self {
+            Enforcement::Enforce => true,
+            Enforcement::ReportOnly => false,
+            Enforcement::Canary(percent) => {
+                let hash = match key.and_then(|key| headers.get(key)) {
+                    Some(value) => fnv1a(value.as_bytes()),
+                    None => RandomState::new().hash_one(()),
+                };
+                hash % 100 < u64::from(percent)
+            }
+        }
+    }
+}
+
+/// FNV-1a, a hash that is the same in every process and build, so every
+/// instance treats a request id alike.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Callback fired for each request the size limiter would have rejected.
+pub type ReportHook = Arc<dyn Fn(&WouldReject) + Send + Sync>;
+
+/// Context passed to a [`ReportHook`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WouldReject {
+    /// Request method.
+    pub method: Method,
+    /// Route template when known, otherwise the request path.
+    pub route: String,
+    /// `Content-Type` header, `"application/octet-stream"` when missing.
+    pub content_type: String,
+    /// Declared `Content-Length`, if any.
+    pub content_length: Option<u64>,
+    /// Limit in bytes that applied, once the policy chose one.
+    pub limit: Option<usize>,
+    /// Body bytes received when the rejection would have happened.
+    pub bytes_received: u64,
+    /// The rejection that was skipped.
+    pub error: SizeLimitError,
+}
+
+/// Reports the would-be rejection of one request, at most once.
+pub(crate) struct Reporter {
+    hook: Option<ReportHook>,
+    info: WouldReject,
+    reported: AtomicBool,
+}
+
+impl Reporter {
+    pub(crate) fn new(hook: Option<ReportHook>, info: WouldReject) -> Self {
+        Self {
+            hook,
+            info,
+            reported: AtomicBool::new(false),
+        }
+    }
+
+    /// Records the limit chosen for the request.
+    pub(crate) fn set_limit(&mut self, limit: usize) {
+        self.info.limit = Some(limit);
+    }
+
+    /// Reports a rejection, unless one was reported already.
+    pub(crate) fn report(&self, error: SizeLimitError, bytes_received: u64) {
+        if self.reported.swap(true, Ordering::Relaxed) {
+            return;
+        }
+        if let Some(hook) = &self.hook {
+            hook(&WouldReject {
+                error,
+                bytes_received,
+                ..self.info.clone()
+            });
+        }
+    }
+
+    /// Wraps a body to report when it breaks its budget.
+    pub(crate) fn measure(self, body: Body, budget: BodyBudget) -> Body {
+        Body::new(ReportingBody {
+            inner: body,
+            budget: Some(budget),
+            reporter: Arc::new(self),
+        })
+    }
+}
+
+/// A body passed through unchanged while its size is checked.
+struct ReportingBody {
+    inner: Body,
+    /// `None` once the body broke its budget.
+    budget: Option<BodyBudget>,
+    reporter: Arc<Reporter>,
+}
+
+impl HttpBody for ReportingBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, axum::Error>>> {
+        let this = self.get_mut();
+        let frame = ready!(Pin::new(&mut this.inner).poll_frame(cx));
+        if let Some(budget) = this.budget.as_mut() {
+            let checked = match &frame {
+                Some(Ok(frame)) => budget.add_frame(frame),
+                Some(Err(_)) => Ok(()),
+                None => budget.finish(),
+            };
+            if let Err(error) = checked {
+                this.reporter.report(error, budget.received() as u64);
+                this.budget = None;
+            }
+        }
+        Poll::Ready(frame)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        self.inner.size_hint()
+    }
+}
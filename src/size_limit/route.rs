@@ -0,0 +1,197 @@
+//! Per-route size limit policies.
+//!
+//! A [`PolicyResolver`] maps the route template of a request (axum's
+//! [`MatchedPath`](axum::extract::MatchedPath)) to a [`RoutePolicy`]: limits,
+//! buffer strategy and error format for that route. [`RouteLimits`] is the
+//! table-based resolver, so the limits of every route can be kept in one
+//! place instead of layered onto individual routes.
+
+use axum::Router;
+
+use crate::error::ErrorFormat;
+use crate::size_limit::middleware::compile_matcher;
+use crate::size_limit::{
+    BufferStrategy, ContentTypeMatcher, Decision, LimitPolicy, RequestContext, SizeLimitConfig,
+    SizeLimitMiddlewareConfig, with_size_limit_policy,
+};
+
+/// Limits, buffering and error format of a route.
+#[derive(Clone, Debug)]
+pub struct RoutePolicy {
+    /// Compiled limits and buffer strategy.
+    matcher: ContentTypeMatcher,
+    /// Error format for rejections; `None` keeps the middleware's.
+    error_format: Option<ErrorFormat>,
+}
+
+impl RoutePolicy {
+    /// Creates a route policy.
+    ///
+    /// # Arguments
+    /// * `limits` - Size limits by content type
+    /// * `strategy` - Buffering decision by content type
+    pub fn new(limits: SizeLimitConfig, strategy: BufferStrategy) -> Self {
+        Self {
+            matcher: ContentTypeMatcher::compile(&limits, &strategy),
+            error_format: None,
+        }
+    }
+
+    /// Builder method to set the error format of rejections on the route.
+    pub fn with_error_format(mut self, format: ErrorFormat) -> Self {
+        self.error_format = Some(format);
+        self
+    }
+
+    /// Returns the compiled limits of the route.
+    pub fn matcher(&self) -> &ContentTypeMatcher {
+        &self.matcher
+    }
+
+    /// Returns the error format of the route, if it sets one.
+    pub fn error_format(&self) -> Option<ErrorFormat> {
+        self.error_format
+    }
+}
+
+/// Looks up the policy of a route.
+///
+/// Install a resolver with [`with_size_limit_resolver`]; routes it returns
+/// `None` for keep the middleware's own configuration.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::size_limit::{BufferStrategy, PolicyResolver, RoutePolicy, SizeLimitConfig};
+///
+/// /// Everything under `/media` streams up to 1 GB.
+/// struct MediaRoutes(RoutePolicy);
+///
+/// impl PolicyResolver for MediaRoutes {
+///     fn resolve(&self, route: Option<&str>) -> Option<&RoutePolicy> {
+///         route.filter(|route| route.starts_with("/media/")).map(|_| &self.0)
+///     }
+/// }
+///
+/// let media = MediaRoutes(RoutePolicy::new(
+///     SizeLimitConfig::default().with_default_limit("1gb"),
+///     BufferStrategy::all_streamed(),
+/// ));
+/// assert!(media.resolve(Some("/media/{id}")).is_some());
+/// assert!(media.resolve(None).is_none());
+/// ```
+pub trait PolicyResolver: Send + Sync + 'static {
+    /// Returns the policy of a route.
+    ///
+    /// # Arguments
+    /// * `route` - Route template (`"/users/{id}"`), when the router matched one
+    fn resolve(&self, route: Option<&str>) -> Option<&RoutePolicy>;
+}
+
+/// Route-pattern to policy table.
+///
+/// Patterns use [`path_pattern::matches`](crate::path_pattern::matches) and
+/// are checked against the route template in order; the first match wins.
+///
+/// # Examples
+/// ```
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::error::ErrorFormat;
+/// use axum_jetpack::size_limit::{
+///     BufferStrategy, RouteLimits, RoutePolicy, SizeLimitConfig, SizeLimitMiddlewareConfig,
+///     with_size_limit_resolver,
+/// };
+///
+/// let routes = RouteLimits::new()
+///     .with_route(
+///         "/videos/*",
+///         RoutePolicy::new(SizeLimitConfig::default().with_default_limit("2gb"), BufferStrategy::all_streamed()),
+///     )
+///     .with_route(
+///         "/api/*",
+///         RoutePolicy::new(SizeLimitConfig::default().with_default_limit("256kb"), BufferStrategy::all_buffered())
+///             .with_error_format(ErrorFormat::ProblemDetails),
+///     );
+///
+/// let router: Router = Router::new()
+///     .route("/videos/upload", post(|| async { "ok" }))
+///     .route("/api/users", post(|| async { "ok" }));
+/// let router = with_size_limit_resolver(router, SizeLimitMiddlewareConfig::default(), routes);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct RouteLimits {
+    /// Ordered `(pattern, policy)` pairs.
+    routes: Vec<(String, RoutePolicy)>,
+}
+
+impl RouteLimits {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method to map a route pattern to a policy.
+    ///
+    /// # Arguments
+    /// * `pattern` - Route template (`"/users/{id}"`) or prefix (`"/api/*"`)
+    /// * `policy` - Policy for matching routes
+    pub fn with_route(mut self, pattern: &str, policy: RoutePolicy) -> Self {
+        self.routes.push((pattern.to_string(), policy));
+        self
+    }
+}
+
+impl PolicyResolver for RouteLimits {
+    fn resolve(&self, route: Option<&str>) -> Option<&RoutePolicy> {
+        let route = route?;
+        self.routes
+            .iter()
+            .find(|(pattern, _)| crate::path_pattern::matches(pattern, route))
+            .map(|(_, policy)| policy)
+    }
+}
+
+/// A resolver with the middleware's own limits as fallback.
+struct Resolved<R> {
+    resolver: R,
+    fallback: ContentTypeMatcher,
+}
+
+impl<R: PolicyResolver> Resolved<R> {
+    fn matcher(&self, route: Option<&str>) -> &ContentTypeMatcher {
+        self.resolver
+            .resolve(route)
+            .map_or(&self.fallback, RoutePolicy::matcher)
+    }
+}
+
+impl<R: PolicyResolver> LimitPolicy for Resolved<R> {
+    async fn decide(&self, ctx: RequestContext<'_>) -> Decision {
+        self.matcher(ctx.route).decide(ctx).await
+    }
+
+    fn error_format(&self, route: Option<&str>) -> Option<ErrorFormat> {
+        self.resolver.resolve(route).and_then(RoutePolicy::error_format)
+    }
+}
+
+/// Applies size limiting with limits chosen per route.
+///
+/// Works like [`with_size_limit`](crate::size_limit::with_size_limit), but
+/// each request is handled by the policy `resolver` returns for its route;
+/// routes without one use `config.size_limits` and `config.buffer_strategy`.
+///
+/// # Arguments
+/// * `router` - The Axum router to wrap with middleware
+/// * `config` - Fallback limits, method rules, hooks and error format
+/// * `resolver` - Policy lookup by route template, e.g. [`RouteLimits`]
+///
+/// # Returns
+/// A new router with size limiting middleware applied.
+pub fn with_size_limit_resolver<R: PolicyResolver>(
+    router: Router,
+    config: SizeLimitMiddlewareConfig,
+    resolver: R,
+) -> Router {
+    let fallback = compile_matcher(&config);
+    with_size_limit_policy(router, config, Resolved { resolver, fallback })
+}
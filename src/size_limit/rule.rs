@@ -0,0 +1,192 @@
+//! Ordered limit rules matching on content type, method, path, headers and
+//! (with the `client-ip` feature) the client network.
+
+use axum::extract::MatchedPath;
+use axum::http::{HeaderMap, HeaderName, HeaderValue, Method, header, request::Parts};
+
+use crate::size_limit::SizeLimit;
+use crate::size_limit::glob;
+#[cfg(feature = "client-ip")]
+use crate::peer::peer_ip;
+
+/// A size limit applied to requests matching all of its conditions.
+///
+/// Rules live in [`SizeLimitConfig::rules`](crate::size_limit::SizeLimitConfig::rules)
+/// and are evaluated in order before the content-type maps; the first
+/// matching rule decides the limit. Conditions left unset match everything.
+///
+/// # Examples
+/// ```
+/// use axum::http::Method;
+/// use axum_jetpack::size_limit::{LimitRule, SizeLimitConfig};
+///
+/// // Images on /admin get 20MB, images elsewhere 2MB
+/// let config = SizeLimitConfig::default()
+///     .with_rule(LimitRule::new("20mb").for_content_type("image/*").for_path("/admin/*"))
+///     .with_rule(LimitRule::new("2mb").for_content_type("image/*"))
+///     .with_rule(LimitRule::new("64kb").for_method(Method::PATCH));
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LimitRule {
+    /// Size limit in bytes.
+    pub limit: usize,
+    /// Content type (`"application/json"`) or glob (`"image/*"`, `"*/zip"`), lowercase.
+    pub content_type: Option<String>,
+    /// Methods the rule applies to; empty means all.
+    pub methods: Vec<Method>,
+    /// Route pattern (see [`path_pattern::matches`](crate::path_pattern::matches)),
+    /// checked against the route template and the request path.
+    pub path: Option<String>,
+    /// Header conditions: the header must be present and, if a value is
+    /// given, equal to it.
+    pub headers: Vec<(HeaderName, Option<HeaderValue>)>,
+    /// Client networks (feature `client-ip`); empty means all clients.
+    ///
+    /// The client address is the [`ClientIp`](crate::client_ip::ClientIp)
+    /// extension set by [`with_client_ip`](crate::client_ip::with_client_ip),
+    /// which honours trusted proxies and forwarding headers, or else the peer
+    /// address, as [`PeerIp`](crate::size_limit::PeerIp) finds it. Rules with
+    /// networks never match requests whose client address is unknown.
+    #[cfg(feature = "client-ip")]
+    pub networks: Vec<ipnet::IpNet>,
+}
+
+impl LimitRule {
+    /// Creates a rule matching every request.
+    ///
+    /// # Arguments
+    /// * `limit` - The size limit for matching requests
+    pub fn new(limit: impl Into<SizeLimit>) -> Self {
+        Self {
+            limit: limit.into().0,
+            content_type: None,
+            methods: Vec::new(),
+            path: None,
+            headers: Vec::new(),
+            #[cfg(feature = "client-ip")]
+            networks: Vec::new(),
+        }
+    }
+
+    /// Builder method to match a content type or glob (`"image/*"`, `"application/vnd.*"`).
+    pub fn for_content_type(mut self, content_type: &str) -> Self {
+        self.content_type = Some(content_type.to_lowercase());
+        self
+    }
+
+    /// Builder method to match a method; call repeatedly for several.
+    pub fn for_method(mut self, method: Method) -> Self {
+        self.methods.push(method);
+        self
+    }
+
+    /// Builder method to match a route pattern (e.g., `"/admin/*"`).
+    pub fn for_path(mut self, pattern: &str) -> Self {
+        self.path = Some(pattern.to_string());
+        self
+    }
+
+    /// Builder method to require a header, optionally with an exact value.
+    pub fn with_header(mut self, name: HeaderName, value: Option<HeaderValue>) -> Self {
+        self.headers.push((name, value));
+        self
+    }
+
+    /// Builder method to match clients in a CIDR network (feature `client-ip`);
+    /// call repeatedly for several.
+    ///
+    /// # Arguments
+    /// * `cidr` - A network (`"10.0.0.0/8"`, `"fd00::/8"`) or single address
+    ///
+    /// # Returns
+    /// - `Ok(Self)` for method chaining
+    /// - `Err(String)` if the network cannot be parsed
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size_limit::{LimitRule, SizeLimitConfig};
+    ///
+    /// // Internal traffic may upload 1GB, everyone else 10MB
+    /// let config = SizeLimitConfig::default()
+    ///     .with_default_limit("10mb")
+    ///     .with_rule(LimitRule::new("1gb").for_client_network("10.0.0.0/8")?);
+    /// # Ok::<(), String>(())
+    /// ```
+    #[cfg(feature = "client-ip")]
+    pub fn for_client_network(mut self, cidr: &str) -> Result<Self, String> {
+        self.networks.push(crate::client_ip::parse_network(cidr)?);
+        Ok(self)
+    }
+
+    /// Returns `true` if the rule matches the given request attributes.
+    ///
+    /// The client address is not known here, so rules with client networks
+    /// do not match; use [`matches_parts`](Self::matches_parts) for those.
+    ///
+    /// # Arguments
+    /// * `method` - Request method
+    /// * `paths` - Route template and/or request path; any match counts
+    /// * `content_type` - `Content-Type` header value (parameters are ignored)
+    /// * `headers` - Request headers
+    pub fn matches(&self, method: &Method, paths: &[&str], content_type: &str, headers: &HeaderMap) -> bool {
+        #[cfg(feature = "client-ip")]
+        if !self.networks.is_empty() {
+            return false;
+        }
+        self.matches_request(method, paths, content_type, headers)
+    }
+
+    /// Checks every condition except the client network.
+    fn matches_request(&self, method: &Method, paths: &[&str], content_type: &str, headers: &HeaderMap) -> bool {
+        if !self.methods.is_empty() && !self.methods.contains(method) {
+            return false;
+        }
+        if let Some(pattern) = &self.path
+            && !paths.iter().any(|path| crate::path_pattern::matches(pattern, path))
+        {
+            return false;
+        }
+        if let Some(expected) = &self.content_type
+            && !content_type_matches(expected, content_type)
+        {
+            return false;
+        }
+        self.headers.iter().all(|(name, value)| match (headers.get(name), value) {
+            (Some(actual), Some(expected)) => actual == expected,
+            (Some(_), None) => true,
+            (None, _) => false,
+        })
+    }
+
+    /// Returns `true` if the rule matches a request head.
+    pub fn matches_parts(&self, parts: &Parts) -> bool {
+        #[cfg(feature = "client-ip")]
+        if !self.networks.is_empty() {
+            let client = peer_ip(parts);
+            if !client.is_some_and(|ip| self.networks.iter().any(|net| net.contains(&ip))) {
+                return false;
+            }
+        }
+
+        let content_type = parts
+            .headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|h| h.to_str().ok())
+            .unwrap_or("application/octet-stream");
+        let route = parts.extensions.get::<MatchedPath>().map(|matched| matched.as_str());
+        let path = parts.uri.path();
+        match route {
+            Some(route) => self.matches_request(&parts.method, &[route, path], content_type, &parts.headers),
+            None => self.matches_request(&parts.method, &[path], content_type, &parts.headers),
+        }
+    }
+}
+
+fn content_type_matches(expected: &str, content_type: &str) -> bool {
+    let essence = content_type.split(';').next().unwrap_or(content_type).trim();
+    if glob::is_glob(expected) {
+        glob::glob_match(expected, essence)
+    } else {
+        essence.eq_ignore_ascii_case(expected)
+    }
+}
@@ -0,0 +1,312 @@
+//! serde support for size limit configuration (feature `serde`).
+//!
+//! Sizes deserialize from byte counts (`1048576`) or human-readable strings
+//! (`"1mb"`, `"unlimited"`) and serialize as byte counts. Methods, header
+//! names and networks are written as strings. Regex limits are written as
+//! their pattern source.
+
+use axum::http::{HeaderName, HeaderValue, Method};
+use serde::de::{self, Deserializer, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+use std::collections::BTreeMap;
+use std::fmt;
+
+use crate::size_limit::{LimitRule, MissingContentType, RejectionHeaders, SizeLimit, SizeLimitConfig};
+
+impl Serialize for SizeLimit {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.as_option() {
+            Some(bytes) => serializer.serialize_u64(bytes as u64),
+            None => serializer.serialize_str("unlimited"),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for SizeLimit {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct SizeVisitor;
+
+        impl Visitor<'_> for SizeVisitor {
+            type Value = SizeLimit;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a byte count or a size such as \"5mb\"")
+            }
+
+            fn visit_u64<E: de::Error>(self, bytes: u64) -> Result<SizeLimit, E> {
+                usize::try_from(bytes)
+                    .map(SizeLimit)
+                    .map_err(|_| E::custom(format!("size {} does not fit in usize", bytes)))
+            }
+
+            fn visit_i64<E: de::Error>(self, bytes: i64) -> Result<SizeLimit, E> {
+                u64::try_from(bytes)
+                    .map_err(|_| E::custom(format!("size {} is negative", bytes)))
+                    .and_then(|bytes| self.visit_u64(bytes))
+            }
+
+            fn visit_str<E: de::Error>(self, s: &str) -> Result<SizeLimit, E> {
+                SizeLimit::parse(s).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(SizeVisitor)
+    }
+}
+
+/// Serialized form of [`MissingContentType`]: `"as_octet_stream"`,
+/// `"reject"` or `{ "limit": "16kb" }`.
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum MissingContentTypeRepr {
+    AsOctetStream,
+    Limit(SizeLimit),
+    Reject,
+}
+
+impl Serialize for MissingContentType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match *self {
+            MissingContentType::AsOctetStream => MissingContentTypeRepr::AsOctetStream,
+            MissingContentType::Limit(limit) => MissingContentTypeRepr::Limit(SizeLimit(limit)),
+            MissingContentType::Reject => MissingContentTypeRepr::Reject,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for MissingContentType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(match MissingContentTypeRepr::deserialize(deserializer)? {
+            MissingContentTypeRepr::AsOctetStream => MissingContentType::AsOctetStream,
+            MissingContentTypeRepr::Limit(limit) => MissingContentType::Limit(limit.0),
+            MissingContentTypeRepr::Reject => MissingContentType::Reject,
+        })
+    }
+}
+
+/// Serialized form of [`RejectionHeaders`]; every field is optional.
+#[derive(Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct RejectionHeadersRepr {
+    max_body_size: bool,
+    upload_limit: bool,
+    /// Seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    retry_after: Option<u64>,
+}
+
+impl Serialize for RejectionHeaders {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RejectionHeadersRepr {
+            max_body_size: self.max_body_size,
+            upload_limit: self.upload_limit,
+            retry_after: self.retry_after,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for RejectionHeaders {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = RejectionHeadersRepr::deserialize(deserializer)?;
+        Ok(RejectionHeaders {
+            max_body_size: repr.max_body_size,
+            upload_limit: repr.upload_limit,
+            retry_after: repr.retry_after,
+        })
+    }
+}
+
+/// Serialized form of a [`LimitRule`].
+#[derive(Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LimitRuleRepr {
+    limit: SizeLimit,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    content_type: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    methods: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    path: Option<String>,
+    /// Header name → required value, or `null` for presence only.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    headers: BTreeMap<String, Option<String>>,
+    #[cfg(feature = "client-ip")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    networks: Vec<String>,
+}
+
+impl Serialize for LimitRule {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        LimitRuleRepr {
+            limit: SizeLimit(self.limit),
+            content_type: self.content_type.clone(),
+            methods: self.methods.iter().map(|method| method.to_string()).collect(),
+            path: self.path.clone(),
+            headers: self
+                .headers
+                .iter()
+                .map(|(name, value)| {
+                    let value = value.as_ref().and_then(|v| v.to_str().ok()).map(str::to_string);
+                    (name.to_string(), value)
+                })
+                .collect(),
+            #[cfg(feature = "client-ip")]
+            networks: self.networks.iter().map(|net| net.to_string()).collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for LimitRule {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = LimitRuleRepr::deserialize(deserializer)?;
+        let mut rule = LimitRule::new(repr.limit);
+        if let Some(content_type) = &repr.content_type {
+            rule = rule.for_content_type(content_type);
+        }
+        for method in &repr.methods {
+            rule = rule.for_method(parse_method(method).map_err(de::Error::custom)?);
+        }
+        if let Some(path) = &repr.path {
+            rule = rule.for_path(path);
+        }
+        for (name, value) in &repr.headers {
+            let name = HeaderName::try_from(name.as_str())
+                .map_err(|_| de::Error::custom(format!("Invalid header name '{}'", name)))?;
+            let value = value
+                .as_deref()
+                .map(HeaderValue::try_from)
+                .transpose()
+                .map_err(|_| de::Error::custom(format!("Invalid value for header '{}'", name)))?;
+            rule = rule.with_header(name, value);
+        }
+        #[cfg(feature = "client-ip")]
+        for network in &repr.networks {
+            rule = rule.for_client_network(network).map_err(de::Error::custom)?;
+        }
+        Ok(rule)
+    }
+}
+
+/// Serialized form of a [`SizeLimitConfig`]; every field is optional.
+#[derive(Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct SizeLimitConfigRepr {
+    default_limit: SizeLimit,
+    specific_limits: BTreeMap<String, SizeLimit>,
+    wildcard_limits: BTreeMap<String, SizeLimit>,
+    /// `(pattern, limit)` pairs, in order.
+    #[cfg(feature = "content-type-regex")]
+    regex_limits: Vec<(String, SizeLimit)>,
+    method_limits: BTreeMap<String, SizeLimit>,
+    rules: Vec<LimitRule>,
+    allowlist_mode: bool,
+    missing_content_type: MissingContentType,
+    /// `null` disables the check.
+    max_chunk_size: Option<SizeLimit>,
+    rejection_headers: RejectionHeaders,
+}
+
+impl Default for SizeLimitConfigRepr {
+    fn default() -> Self {
+        let config = SizeLimitConfig::default();
+        Self {
+            default_limit: SizeLimit(config.default_limit),
+            specific_limits: BTreeMap::new(),
+            wildcard_limits: BTreeMap::new(),
+            #[cfg(feature = "content-type-regex")]
+            regex_limits: Vec::new(),
+            method_limits: BTreeMap::new(),
+            rules: Vec::new(),
+            allowlist_mode: config.allowlist_mode,
+            missing_content_type: config.missing_content_type,
+            max_chunk_size: config.max_chunk_size.map(SizeLimit),
+            rejection_headers: config.rejection_headers,
+        }
+    }
+}
+
+/// Serializes with sizes as byte counts and maps in sorted order.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::size_limit::SizeLimitConfig;
+///
+/// let config: SizeLimitConfig = serde_json::from_str(r#"{
+///     "default_limit": "2mb",
+///     "specific_limits": { "application/json": "100kb" },
+///     "wildcard_limits": { "image/*": 5000000 },
+///     "method_limits": { "PATCH": "64kb" },
+///     "rules": [{ "limit": "20mb", "content_type": "image/*", "path": "/admin/*" }]
+/// }"#).unwrap();
+///
+/// assert_eq!(config.default_limit, 2_000_000);
+/// assert_eq!(config.get_limit_for_content_type("image/png"), 5_000_000);
+/// ```
+impl Serialize for SizeLimitConfig {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let sizes = |map: &std::collections::HashMap<String, usize>| {
+            map.iter()
+                .map(|(key, limit)| (key.clone(), SizeLimit(*limit)))
+                .collect::<BTreeMap<_, _>>()
+        };
+        SizeLimitConfigRepr {
+            default_limit: SizeLimit(self.default_limit),
+            specific_limits: sizes(&self.specific_limits),
+            wildcard_limits: sizes(&self.wildcard_limits),
+            #[cfg(feature = "content-type-regex")]
+            regex_limits: self
+                .regex_limits
+                .iter()
+                .map(|(pattern, limit)| (pattern.as_str().to_string(), SizeLimit(*limit)))
+                .collect(),
+            method_limits: self
+                .method_limits
+                .iter()
+                .map(|(method, limit)| (method.to_string(), SizeLimit(*limit)))
+                .collect(),
+            rules: self.rules.clone(),
+            allowlist_mode: self.allowlist_mode,
+            missing_content_type: self.missing_content_type,
+            max_chunk_size: self.max_chunk_size.map(SizeLimit),
+            rejection_headers: self.rejection_headers,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for SizeLimitConfig {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let repr = SizeLimitConfigRepr::deserialize(deserializer)?;
+        let mut config = SizeLimitConfig::default()
+            .with_default_limit(repr.default_limit)
+            .with_allowlist_mode(repr.allowlist_mode);
+        config.missing_content_type = repr.missing_content_type;
+        config.max_chunk_size = repr.max_chunk_size.map(|limit| limit.0);
+        config.rejection_headers = repr.rejection_headers;
+        for (content_type, limit) in repr.specific_limits {
+            config = config.with_specific_limit(&content_type, limit);
+        }
+        for (pattern, limit) in repr.wildcard_limits {
+            config = config.with_wildcard_limit(&pattern, limit);
+        }
+        #[cfg(feature = "content-type-regex")]
+        for (pattern, limit) in repr.regex_limits {
+            config = config.with_regex_limit(&pattern, limit).map_err(de::Error::custom)?;
+        }
+        for (method, limit) in repr.method_limits {
+            config = config.with_method_limit(parse_method(&method).map_err(de::Error::custom)?, limit);
+        }
+        for rule in repr.rules {
+            config = config.with_rule(rule);
+        }
+        Ok(config)
+    }
+}
+
+pub(super) fn parse_method(method: &str) -> Result<Method, String> {
+    Method::from_bytes(method.trim().to_ascii_uppercase().as_bytes())
+        .map_err(|_| format!("Invalid method '{}'", method))
+}
@@ -0,0 +1,124 @@
+//! Soft limits warning clients before a hard cut.
+//!
+//! Bodies above a [`SoftLimit`] but within the enforced limit are accepted,
+//! but their response carries a `Warning` header and the
+//! [`on_soft_limit`](crate::size_limit::SizeLimitMiddlewareConfig::on_soft_limit)
+//! hook fires, so clients and operators notice before the limit is tightened.
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{HeaderName, HeaderValue, Method, header},
+    response::Response,
+};
+use std::sync::Arc;
+
+use crate::size_limit::{ObservedBodySize, SizeLimit};
+
+/// Threshold below the enforced limit from which requests are warned about.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::size_limit::SoftLimit;
+///
+/// assert_eq!(SoftLimit::Percent(80).threshold(1_000_000), 800_000);
+/// assert_eq!(SoftLimit::bytes("500kb").threshold(1_000_000), 500_000);
+/// // Never above the enforced limit
+/// assert_eq!(SoftLimit::bytes("5mb").threshold(1_000_000), 1_000_000);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SoftLimit {
+    /// A percentage (0-100) of the limit enforced for the request.
+    Percent(u8),
+    /// A fixed size in bytes, for every request.
+    Bytes(usize),
+}
+
+impl SoftLimit {
+    /// Creates a fixed soft limit.
+    ///
+    /// # Arguments
+    /// * `limit` - Size from which requests are warned about
+    pub fn bytes(limit: impl Into<SizeLimit>) -> Self {
+        SoftLimit::Bytes(limit.into().0)
+    }
+
+    /// Returns the soft limit in bytes for a request with the given limit.
+    pub fn threshold(&self, limit: usize) -> usize {
+        match *self {
+            SoftLimit::Percent(percent) => {
+                let percent = u128::from(percent.min(100));
+                (limit as u128 * percent / 100) as usize
+            }
+            SoftLimit::Bytes(bytes) => bytes.min(limit),
+        }
+    }
+}
+
+/// Callback fired for each accepted request above its soft limit.
+pub type SoftLimitHook = Arc<dyn Fn(&SoftLimitExceeded) + Send + Sync>;
+
+/// Context passed to a [`SoftLimitHook`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SoftLimitExceeded {
+    /// Request method.
+    pub method: Method,
+    /// Route template when known, otherwise the request path.
+    pub route: String,
+    /// `Content-Type` header, `"application/octet-stream"` when missing.
+    pub content_type: String,
+    /// Body size: bytes read, or the declared `Content-Length` if larger.
+    pub body_size: u64,
+    /// The soft limit in bytes.
+    pub soft_limit: usize,
+    /// The enforced limit in bytes.
+    pub limit: usize,
+}
+
+/// Measures the body of one request against its soft limit.
+pub(crate) struct SoftMeter {
+    info: SoftLimitExceeded,
+    size: ObservedBodySize,
+}
+
+impl SoftMeter {
+    /// Measures the body of `req`, counted by `size`.
+    pub(crate) fn new(req: &Request<Body>, route: String, size: ObservedBodySize, soft_limit: usize, limit: usize) -> Self {
+        let headers = req.headers();
+        let info = SoftLimitExceeded {
+            method: req.method().clone(),
+            route,
+            content_type: headers
+                .get(header::CONTENT_TYPE)
+                .and_then(|h| h.to_str().ok())
+                .unwrap_or("application/octet-stream")
+                .to_string(),
+            body_size: headers
+                .get(header::CONTENT_LENGTH)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse().ok())
+                .unwrap_or(0),
+            soft_limit,
+            limit,
+        };
+        Self { info, size }
+    }
+
+    /// Warns about a body above the soft limit on its response.
+    pub(crate) fn check(self, response: &mut Response, header: &HeaderName, hook: Option<&SoftLimitHook>) {
+        let body_size = self.info.body_size.max(self.size.bytes());
+        if body_size <= self.info.soft_limit as u64 {
+            return;
+        }
+        let warning = format!(
+            "299 - \"Request body of {} bytes exceeds the soft limit of {} bytes\"",
+            body_size, self.info.soft_limit
+        );
+        if let Ok(value) = HeaderValue::from_str(&warning) {
+            response.headers_mut().append(header.clone(), value);
+        }
+        if let Some(hook) = hook {
+            hook(&SoftLimitExceeded { body_size, ..self.info });
+        }
+    }
+}
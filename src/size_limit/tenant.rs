@@ -0,0 +1,265 @@
+//! Limits per API key or tenant.
+//!
+//! [`TenantLimits`] is a [`LimitPolicy`] that keys requests by a header (an
+//! API key, a tenant id, see [`KeyExtractor`]), asks a [`TenantLimitStore`]
+//! which named profile the tenant is on and enforces that profile's limits,
+//! so premium tenants get bigger upload caps from one size limiter.
+//!
+//! Behind an authentication layer the profile can come from the user instead:
+//! the layer inserts a [`UserPlan`] extension (or its own claims type, read
+//! with [`with_plan`](TenantLimits::with_plan)) and the user's plan or role
+//! picks the limits.
+
+use axum::http::request::Parts;
+use futures::future::BoxFuture;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::{Arc, PoisonError, RwLock};
+
+use crate::size_limit::{
+    BufferStrategy, ContentTypeMatcher, Decision, ExtensionKey, HeaderKey, KeyExtractor, LimitPolicy, RequestContext, SizeLimitConfig,
+};
+
+/// Extension naming the limits profile of the authenticated user.
+///
+/// This is the contract with authentication layers: insert it into the
+/// request extensions, before the size limiter runs, with the user's plan or
+/// role, and [`TenantLimits`] enforces the profile of that name. It takes
+/// precedence over the tenant key.
+///
+/// # Examples
+/// ```
+/// use axum::{extract::Request, middleware::Next, response::Response};
+/// use axum_jetpack::size_limit::UserPlan;
+///
+/// // In the authentication middleware, once the token is verified
+/// async fn authenticate(mut req: Request, next: Next) -> Response {
+///     let plan = "premium"; // e.g. the `plan` claim of the JWT
+///     req.extensions_mut().insert(UserPlan::new(plan));
+///     next.run(req).await
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct UserPlan(pub String);
+
+impl UserPlan {
+    /// Names the profile of the user.
+    pub fn new(profile: impl Into<String>) -> Self {
+        Self(profile.into())
+    }
+}
+
+/// Maps tenant keys to the name of their limits profile.
+///
+/// Implement it over a database or a billing service to look plans up;
+/// [`MemoryTenantStore`] keeps a fixed table. Unknown tenants, and lookups
+/// that fail, get the default limits.
+pub trait TenantLimitStore: Send + Sync + 'static {
+    /// Name of the profile of the tenant `key`, `None` for the default one.
+    fn profile<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<String>, String>>;
+}
+
+/// [`TenantLimitStore`] keeping the profile of each tenant in memory.
+///
+/// Clones share the table, so tenants can be moved between profiles while
+/// serving.
+///
+/// # Examples
+/// ```
+/// use axum_jetpack::size_limit::MemoryTenantStore;
+///
+/// let store = MemoryTenantStore::new().with_tenant("key-acme", "premium");
+/// store.set_tenant("key-initech", "premium");
+/// assert_eq!(store.tenant_profile("key-acme").as_deref(), Some("premium"));
+/// assert_eq!(store.tenant_profile("key-unknown"), None);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct MemoryTenantStore {
+    tenants: Arc<RwLock<HashMap<String, String>>>,
+}
+
+impl MemoryTenantStore {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builder method to put tenant `key` on `profile`.
+    pub fn with_tenant(self, key: impl Into<String>, profile: impl Into<String>) -> Self {
+        self.set_tenant(key, profile);
+        self
+    }
+
+    /// Puts tenant `key` on `profile`.
+    pub fn set_tenant(&self, key: impl Into<String>, profile: impl Into<String>) {
+        let mut tenants = self.tenants.write().unwrap_or_else(PoisonError::into_inner);
+        tenants.insert(key.into(), profile.into());
+    }
+
+    /// Moves tenant `key` back to the default limits.
+    pub fn remove_tenant(&self, key: &str) {
+        let mut tenants = self.tenants.write().unwrap_or_else(PoisonError::into_inner);
+        tenants.remove(key);
+    }
+
+    /// Name of the profile of tenant `key`, if it has one.
+    pub fn tenant_profile(&self, key: &str) -> Option<String> {
+        let tenants = self.tenants.read().unwrap_or_else(PoisonError::into_inner);
+        tenants.get(key).cloned()
+    }
+}
+
+impl TenantLimitStore for MemoryTenantStore {
+    fn profile<'a>(&'a self, key: &'a str) -> BoxFuture<'a, Result<Option<String>, String>> {
+        let profile = self.tenant_profile(key);
+        Box::pin(async move { Ok(profile) })
+    }
+}
+
+/// Limit configurations selected by the tenant of the request.
+///
+/// The profile is named by the [`UserPlan`] extension if an authentication
+/// layer inserted one, otherwise looked up for the tenant, keyed by the
+/// `x-api-key` header by default. Requests without either, of unknown
+/// tenants, or on a profile that was never added use the default
+/// configuration.
+///
+/// Install it with [`with_size_limit_policy`](crate::size_limit::with_size_limit_policy).
+///
+/// # Examples
+/// ```
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::size_limit::{
+///     BufferStrategy, MemoryTenantStore, SizeLimitConfig, SizeLimitMiddlewareConfig, TenantLimits,
+///     with_size_limit_policy,
+/// };
+///
+/// let store = MemoryTenantStore::new().with_tenant("acme", "premium");
+/// let tenants = TenantLimits::new(SizeLimitConfig::default().with_default_limit("10mb"), BufferStrategy::with_defaults())
+///     .with_profile("premium", SizeLimitConfig::default().with_default_limit("1gb"))
+///     .with_header_key("x-tenant-id")
+///     .with_store(store);
+///
+/// let router: Router = Router::new().route("/upload", post(|| async { "ok" }));
+/// let router = with_size_limit_policy(router, SizeLimitMiddlewareConfig::default(), tenants);
+/// ```
+#[derive(Clone)]
+pub struct TenantLimits {
+    /// Buffering strategy shared by every profile.
+    strategy: BufferStrategy,
+    /// Limits of the named profiles.
+    profiles: HashMap<String, ContentTypeMatcher>,
+    /// Limits for requests of no known profile.
+    default: ContentTypeMatcher,
+    /// Names the profile of the user, before the tenant is looked up.
+    plan: Arc<dyn KeyExtractor>,
+    key: Arc<dyn KeyExtractor>,
+    store: Arc<dyn TenantLimitStore>,
+}
+
+impl TenantLimits {
+    /// Creates a policy that applies `default` to every tenant.
+    ///
+    /// # Arguments
+    /// * `default` - Limits for requests without a known profile
+    /// * `strategy` - Buffering decision by content type, shared by all profiles
+    pub fn new(default: SizeLimitConfig, strategy: BufferStrategy) -> Self {
+        Self {
+            default: ContentTypeMatcher::compile(&default, &strategy),
+            strategy,
+            profiles: HashMap::new(),
+            plan: Arc::new(ExtensionKey::new(|plan: &UserPlan| Some(plan.0.clone()))),
+            key: Arc::new(HeaderKey::new("x-api-key")),
+            store: Arc::new(MemoryTenantStore::new()),
+        }
+    }
+
+    /// Builder method to add a named profile.
+    ///
+    /// # Arguments
+    /// * `name` - Profile name, as returned by the store (e.g. `"premium"`)
+    /// * `limits` - Limits for tenants on that profile
+    pub fn with_profile(mut self, name: impl Into<String>, limits: SizeLimitConfig) -> Self {
+        let matcher = ContentTypeMatcher::compile(&limits, &self.strategy);
+        self.profiles.insert(name.into(), matcher);
+        self
+    }
+
+    /// Builder method to read the profile from the claims an authentication
+    /// layer inserted, instead of the [`UserPlan`] extension.
+    ///
+    /// # Examples
+    /// ```
+    /// use axum_jetpack::size_limit::{BufferStrategy, SizeLimitConfig, TenantLimits};
+    ///
+    /// /// Inserted by the authentication layer.
+    /// #[derive(Clone)]
+    /// struct Claims {
+    ///     role: String,
+    /// }
+    ///
+    /// let limits = TenantLimits::new(SizeLimitConfig::default(), BufferStrategy::with_defaults())
+    ///     .with_profile("admin", SizeLimitConfig::default().with_default_limit("5gb"))
+    ///     .with_plan(|claims: &Claims| Some(claims.role.clone()));
+    /// ```
+    pub fn with_plan<T, F>(mut self, plan: F) -> Self
+    where
+        T: Send + Sync + 'static,
+        F: Fn(&T) -> Option<String> + Send + Sync + 'static,
+    {
+        self.plan = Arc::new(ExtensionKey::new(plan));
+        self
+    }
+
+    /// Builder method to identify tenants by a custom key.
+    pub fn with_key(mut self, key: impl KeyExtractor) -> Self {
+        self.key = Arc::new(key);
+        self
+    }
+
+    /// Builder method to identify tenants by a header, e.g. `x-tenant-id`.
+    pub fn with_header_key(self, header: &'static str) -> Self {
+        self.with_key(HeaderKey::new(header))
+    }
+
+    /// Builder method to look the profiles of tenants up in `store`.
+    pub fn with_store(mut self, store: impl TenantLimitStore) -> Self {
+        self.store = Arc::new(store);
+        self
+    }
+
+    /// Returns the limits of a named profile.
+    pub fn profile(&self, name: &str) -> Option<&ContentTypeMatcher> {
+        self.profiles.get(name)
+    }
+
+    /// Returns the limits that apply to a request head.
+    pub async fn matcher_for(&self, parts: &Parts) -> &ContentTypeMatcher {
+        if let Some(plan) = self.plan.key(parts) {
+            return self.profiles.get(&plan).unwrap_or(&self.default);
+        }
+        let Some(key) = self.key.key(parts) else {
+            return &self.default;
+        };
+        match self.store.profile(&key).await {
+            Ok(Some(name)) => self.profiles.get(&name).unwrap_or(&self.default),
+            _ => &self.default,
+        }
+    }
+}
+
+impl LimitPolicy for TenantLimits {
+    async fn decide(&self, ctx: RequestContext<'_>) -> Decision {
+        self.matcher_for(ctx.parts).await.decide(ctx).await
+    }
+}
+
+impl fmt::Debug for TenantLimits {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut profiles: Vec<_> = self.profiles.keys().collect();
+        profiles.sort();
+        f.debug_struct("TenantLimits")
+            .field("profiles", &profiles)
+            .finish_non_exhaustive()
+    }
+}
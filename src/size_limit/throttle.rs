@@ -0,0 +1,295 @@
+//! Ingress bandwidth throttling.
+//!
+//! [`ThrottleLayer`] caps how fast request bodies are read, per request or
+//! per client, so one upload cannot saturate the link. Rates are sizes per
+//! second and take the bit-based units of [`SizeLimit`], e.g. `"10 Mbit"`.
+//! The layer only slows bodies down; it stacks with
+//! [`SizeLimitLayer`](crate::size_limit::SizeLimitLayer), which still
+//! enforces their size.
+
+use axum::{
+    body::{Body, Bytes, HttpBody},
+    extract::Request,
+    http::request::Parts,
+};
+use http_body::{Frame, SizeHint};
+use std::collections::HashMap;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Poll, ready};
+use std::time::Duration;
+use tokio::time::{Instant, Sleep};
+use tower::{Layer, Service};
+
+use crate::size_limit::{HeaderKey, KeyExtractor, PeerIp, SizeLimit};
+
+/// Layer throttling the bodies of the requests it sees.
+///
+/// Each request, or each client with [`per_client`](Self::per_client), gets
+/// a token bucket refilled at `rate` bytes per second and holding up to
+/// `burst` bytes. A chunk that does not fit is held back until it does,
+/// without reading further from the client.
+///
+/// # Examples
+/// ```
+/// use axum::{Router, routing::post};
+/// use axum_jetpack::size_limit::{RouterExt, ThrottleLayer};
+///
+/// let router: Router = Router::new()
+///     .route("/upload", post(|| async { "ok" }))
+///     .size_limit("1gb")
+///     .layer(ThrottleLayer::new("10 Mbit").per_client());
+/// ```
+#[derive(Clone)]
+pub struct ThrottleLayer {
+    rate: usize,
+    burst: usize,
+    /// Key sharing a bucket between requests, if any.
+    key: Option<Arc<dyn KeyExtractor>>,
+    clients: Arc<Mutex<Clients>>,
+}
+
+/// Buckets of the clients with requests in flight.
+type Clients = HashMap<String, Arc<Bucket>>;
+
+impl ThrottleLayer {
+    /// Creates a layer throttling each request on its own.
+    ///
+    /// # Arguments
+    /// * `rate` - Bytes per second, e.g. `"10 Mbit"` or `"2mb"`; unlimited
+    ///   disables throttling
+    pub fn new(rate: impl Into<SizeLimit>) -> Self {
+        let rate = rate.into().0;
+        Self {
+            rate,
+            burst: rate,
+            key: None,
+            clients: Arc::default(),
+        }
+    }
+
+    /// Builder method to let the bucket hold more (or less) than one second
+    /// of traffic, so short bursts pass at full speed.
+    pub fn with_burst(mut self, burst: impl Into<SizeLimit>) -> Self {
+        self.burst = burst.into().0;
+        self
+    }
+
+    /// Builder method to share one bucket between the concurrent requests of
+    /// a client, identified by its IP ([`PeerIp`]). Requests without a known
+    /// client IP are throttled on their own.
+    pub fn per_client(self) -> Self {
+        self.with_key(PeerIp)
+    }
+
+    /// Builder method to share one bucket between the concurrent requests of
+    /// a custom key. Requests without a key are throttled on their own.
+    pub fn with_key(mut self, key: impl KeyExtractor) -> Self {
+        self.key = Some(Arc::new(key));
+        self
+    }
+
+    /// Builder method to share one bucket between the concurrent requests
+    /// with the same value of a header, e.g. `x-api-key`.
+    pub fn with_header_key(self, header: &'static str) -> Self {
+        self.with_key(HeaderKey::new(header))
+    }
+
+    /// The bucket for a request.
+    fn bucket(&self, parts: &Parts) -> HeldBucket {
+        let Some(key) = self.key.as_ref().and_then(|key| key.key(parts)) else {
+            return HeldBucket::own(self.rate, self.burst);
+        };
+        let bucket = self
+            .clients
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .entry(key.clone())
+            .or_insert_with(|| Arc::new(Bucket::new(self.rate, self.burst)))
+            .clone();
+        HeldBucket {
+            bucket,
+            client: Some((key, self.clients.clone())),
+        }
+    }
+}
+
+impl fmt::Debug for ThrottleLayer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ThrottleLayer")
+            .field("rate", &self.rate)
+            .field("burst", &self.burst)
+            .field("per_key", &self.key.is_some())
+            .finish()
+    }
+}
+
+impl<S> Layer<S> for ThrottleLayer {
+    type Service = ThrottleService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ThrottleService {
+            inner,
+            layer: self.clone(),
+        }
+    }
+}
+
+/// Service created by [`ThrottleLayer`].
+#[derive(Clone, Debug)]
+pub struct ThrottleService<S> {
+    inner: S,
+    layer: ThrottleLayer,
+}
+
+impl<S> Service<Request<Body>> for ThrottleService<S>
+where
+    S: Service<Request<Body>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        if SizeLimit(self.layer.rate).is_unlimited() || req.body().is_end_stream() {
+            return self.inner.call(req);
+        }
+        let (parts, body) = req.into_parts();
+        let bucket = self.layer.bucket(&parts);
+        let body = Body::new(ThrottledBody {
+            inner: body,
+            bucket,
+            pending: None,
+            sleep: None,
+        });
+        self.inner.call(Request::from_parts(parts, body))
+    }
+}
+
+/// Token bucket kept as the time its debt is paid off (GCRA).
+#[derive(Debug)]
+struct Bucket {
+    /// Time per byte.
+    per_byte: f64,
+    /// Debt the bucket may run ahead of now.
+    burst: Duration,
+    /// When the bytes let through so far are paid for.
+    paid_until: Mutex<Option<Instant>>,
+}
+
+impl Bucket {
+    fn new(rate: usize, burst: usize) -> Self {
+        let per_byte = 1.0 / rate.max(1) as f64;
+        Self {
+            per_byte,
+            burst: Duration::from_secs_f64(burst as f64 * per_byte),
+            paid_until: Mutex::new(None),
+        }
+    }
+
+    /// Takes `len` bytes, returning when they may be let through.
+    fn take(&self, len: usize) -> Instant {
+        let now = Instant::now();
+        let mut paid_until = self.paid_until.lock().unwrap_or_else(PoisonError::into_inner);
+        let start = paid_until.map_or(now, |paid_until| paid_until.max(now));
+        let paid = start + Duration::from_secs_f64(len as f64 * self.per_byte);
+        *paid_until = Some(paid);
+        paid.checked_sub(self.burst).map_or(now, |ready| ready.max(now))
+    }
+}
+
+/// A bucket held by a request, shared with the other requests of its client.
+struct HeldBucket {
+    bucket: Arc<Bucket>,
+    /// Key and map of the client, to forget it once its last request ends.
+    client: Option<(String, Arc<Mutex<Clients>>)>,
+}
+
+impl HeldBucket {
+    /// A bucket of the request's own.
+    fn own(rate: usize, burst: usize) -> Self {
+        Self {
+            bucket: Arc::new(Bucket::new(rate, burst)),
+            client: None,
+        }
+    }
+}
+
+impl Drop for HeldBucket {
+    fn drop(&mut self) {
+        let Some((key, clients)) = &self.client else {
+            return;
+        };
+        // Buckets are only handed out under the lock: held by the map and this
+        // request alone, it has no other request in flight
+        let mut clients = clients.lock().unwrap_or_else(PoisonError::into_inner);
+        if Arc::strong_count(&self.bucket) == 2 {
+            clients.remove(key);
+        }
+    }
+}
+
+/// A body whose chunks are held back to keep to its bucket's rate.
+struct ThrottledBody {
+    inner: Body,
+    bucket: HeldBucket,
+    /// A chunk waiting for `sleep`.
+    pending: Option<Frame<Bytes>>,
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl HttpBody for ThrottledBody {
+    type Data = Bytes;
+    type Error = axum::Error;
+
+    fn poll_frame(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Result<Frame<Bytes>, axum::Error>>> {
+        let this = self.get_mut();
+        if let Some(sleep) = this.sleep.as_mut() {
+            ready!(sleep.as_mut().poll(cx));
+            this.sleep = None;
+            return Poll::Ready(this.pending.take().map(Ok));
+        }
+        let frame = match ready!(Pin::new(&mut this.inner).poll_frame(cx)) {
+            Some(Ok(frame)) => frame,
+            other => return Poll::Ready(other),
+        };
+        let len = frame.data_ref().map_or(0, Bytes::len);
+        if len == 0 {
+            return Poll::Ready(Some(Ok(frame)));
+        }
+        let ready_at = this.bucket.bucket.take(len);
+        if ready_at <= Instant::now() {
+            return Poll::Ready(Some(Ok(frame)));
+        }
+        let mut sleep = Box::pin(tokio::time::sleep_until(ready_at));
+        match sleep.as_mut().poll(cx) {
+            Poll::Ready(()) => Poll::Ready(Some(Ok(frame))),
+            Poll::Pending => {
+                this.pending = Some(frame);
+                this.sleep = Some(sleep);
+                Poll::Pending
+            }
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.pending.is_none() && self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> SizeHint {
+        let pending = self.pending.as_ref().and_then(Frame::data_ref).map_or(0, Bytes::len) as u64;
+        let inner = self.inner.size_hint();
+        let mut hint = SizeHint::new();
+        hint.set_lower(inner.lower() + pending);
+        if let Some(upper) = inner.upper() {
+            hint.set_upper(upper + pending);
+        }
+        hint
+    }
+}
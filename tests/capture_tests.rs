@@ -0,0 +1,81 @@
+// tests/capture_tests.rs
+#![cfg(feature = "capture")]
+#![allow(clippy::disallowed_methods)]
+
+use axum::{
+    body::{Body, Bytes},
+    extract::Request,
+    http::StatusCode,
+    routing::post,
+    Router,
+};
+use std::path::PathBuf;
+use tower::ServiceExt;
+
+use axum_jetpack::capture::{load_envelopes, replay, with_capture, CaptureConfig, REDACTED};
+use axum_jetpack::size_limit::{SizeLimitConfig, SizeLimitMiddlewareConfig};
+
+fn capture_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("axum-jetpack-{}-{}.jsonl", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+async fn record(path: &PathBuf) {
+    let router = Router::new().route("/items/{id}", post(|body: Bytes| async move { body.len().to_string() }));
+    let app = with_capture(router, CaptureConfig::new(path).with_body_prefix(4)).unwrap();
+
+    for (id, body, content_type) in [(1, "small", "text/plain"), (2, "a much larger body", "application/json")] {
+        let req = Request::builder()
+            .method("POST")
+            .uri(format!("/items/{}?secret=1", id))
+            .header("authorization", "Bearer token")
+            .header("content-type", content_type)
+            .header("content-length", body.len())
+            .body(Body::from(body))
+            .unwrap();
+        let response = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
+
+#[tokio::test]
+async fn test_capture_writes_sanitized_envelopes() {
+    let path = capture_path("capture");
+    record(&path).await;
+
+    let envelopes = load_envelopes(&path).unwrap();
+    assert_eq!(envelopes.len(), 2);
+
+    let first = &envelopes[0];
+    assert_eq!(first.method, "POST");
+    assert_eq!(first.route, "/items/{id}");
+    assert_eq!(first.content_length, Some(5));
+    assert_eq!(first.body_bytes, 5);
+    assert_eq!(first.body_prefix.as_deref(), Some("smal"));
+    assert_eq!(first.status, 200);
+    assert!(first.headers.contains(&("authorization".to_string(), REDACTED.to_string())));
+
+    let _ = std::fs::remove_file(&path);
+}
+
+#[tokio::test]
+async fn test_replay_reports_candidate_rejections() {
+    let path = capture_path("replay");
+    record(&path).await;
+    let envelopes = load_envelopes(&path).unwrap();
+
+    let candidate = SizeLimitMiddlewareConfig::new(
+        SizeLimitConfig::default().with_specific_limit("application/json", "10b"),
+    );
+    let report = replay(&envelopes, &candidate);
+
+    assert_eq!(report.total, 2);
+    assert_eq!(report.rejected(), 1);
+    assert_eq!(report.newly_rejected, 1);
+    assert_eq!(report.rejections[0].status, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(report.rejected_by_route["/items/{id}"], 1);
+    assert!(report.to_string().starts_with("1 of 2 requests rejected"));
+
+    let _ = std::fs::remove_file(&path);
+}
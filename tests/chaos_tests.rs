@@ -0,0 +1,85 @@
+// tests/chaos_tests.rs
+#![cfg(feature = "chaos")]
+#![allow(clippy::disallowed_methods)]
+
+use axum::{
+    body::{Body, Bytes},
+    extract::Request,
+    http::StatusCode,
+    response::Response,
+    routing::{get, post},
+    Router,
+};
+use http_body_util::BodyExt;
+use std::time::{Duration, Instant};
+use tower::ServiceExt;
+
+use axum_jetpack::chaos::{with_chaos, ChaosConfig, Fault, CHAOS_HEADER};
+
+fn app(config: ChaosConfig) -> Router {
+    let router = Router::new()
+        .route("/api/echo", post(|body: Bytes| async move { body }))
+        .route("/api/data", get(|| async { "0123456789" }))
+        .route("/other", get(|| async { "untouched" }));
+    with_chaos(router, config)
+}
+
+async fn call(app: Router, method: &str, uri: &str, body: &'static str) -> Response {
+    let req = Request::builder()
+        .method(method)
+        .uri(uri)
+        .body(Body::from(body))
+        .unwrap();
+    app.oneshot(req).await.unwrap()
+}
+
+#[tokio::test]
+async fn test_status_and_latency_faults() {
+    let config = ChaosConfig::new()
+        .with_rule("/api/*", 1.0, Fault::Latency(Duration::from_millis(20)))
+        .with_rule("/api/*", 1.0, Fault::Status(StatusCode::SERVICE_UNAVAILABLE));
+    let app = app(config);
+
+    let started = Instant::now();
+    let response = call(app.clone(), "GET", "/api/data", "").await;
+    assert!(started.elapsed() >= Duration::from_millis(20));
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    assert_eq!(response.headers()[CHAOS_HEADER], "latency, status");
+
+    // Unmatched routes are untouched
+    let response = call(app, "GET", "/other", "").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(!response.headers().contains_key(CHAOS_HEADER));
+}
+
+#[tokio::test]
+async fn test_zero_probability_never_fires() {
+    let app = app(ChaosConfig::new().with_rule("*", 0.0, Fault::Status(StatusCode::IM_A_TEAPOT)));
+
+    for _ in 0..20 {
+        let response = call(app.clone(), "GET", "/api/data", "").await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
+
+#[tokio::test]
+async fn test_abort_request_body() {
+    let app = app(ChaosConfig::new().with_rule("/api/echo", 1.0, Fault::AbortRequestBody { after: 4 }));
+
+    let response = call(app, "POST", "/api/echo", "a long upload body").await;
+    // The handler's body extractor sees the failure
+    assert!(response.status().is_client_error());
+}
+
+#[tokio::test]
+async fn test_truncate_response() {
+    let app = app(ChaosConfig::new().with_rule("/api/data", 1.0, Fault::TruncateResponse { after: 4 }));
+
+    let response = call(app, "GET", "/api/data", "").await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let mut body = response.into_body();
+    let first = body.frame().await.unwrap().unwrap().into_data().unwrap();
+    assert_eq!(&first[..], b"0123");
+    assert!(body.frame().await.unwrap().is_err());
+}
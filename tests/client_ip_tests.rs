@@ -0,0 +1,136 @@
+// tests/client_ip_tests.rs
+#![cfg(feature = "client-ip")]
+#![allow(clippy::disallowed_methods)]
+
+use axum::{
+    body::Body,
+    extract::{ConnectInfo, Request},
+    http::{HeaderMap, StatusCode},
+    routing::get,
+    Router,
+};
+use http_body_util::BodyExt;
+use std::net::{IpAddr, SocketAddr};
+use tower::ServiceExt;
+
+use axum_jetpack::client_ip::{with_client_ip, ClientIp, ClientIpResolver, ForwardedHeader};
+
+fn ip(s: &str) -> IpAddr {
+    s.parse().unwrap()
+}
+
+fn headers(pairs: &[(&'static str, &str)]) -> HeaderMap {
+    let mut headers = HeaderMap::new();
+    for (name, value) in pairs {
+        headers.append(*name, value.parse().unwrap());
+    }
+    headers
+}
+
+#[test]
+fn test_untrusted_peer_ignores_headers() {
+    let resolver = ClientIpResolver::new();
+    let headers = headers(&[("x-forwarded-for", "1.2.3.4"), ("x-real-ip", "5.6.7.8")]);
+
+    assert_eq!(resolver.resolve(Some(ip("9.9.9.9")), &headers), Some(ip("9.9.9.9")));
+}
+
+#[test]
+fn test_x_forwarded_for_skips_trusted_hops() {
+    let resolver = ClientIpResolver::new()
+        .with_trusted_proxies(&["10.0.0.0/8", "192.168.1.1"])
+        .unwrap();
+    // Client spoofed the first entry; the right-most untrusted hop wins
+    let headers = headers(&[("x-forwarded-for", "6.6.6.6, 203.0.113.9, 10.1.1.1, 192.168.1.1")]);
+
+    assert_eq!(resolver.resolve(Some(ip("10.0.0.1")), &headers), Some(ip("203.0.113.9")));
+}
+
+#[test]
+fn test_forwarded_header_with_ipv6_and_ports() {
+    let resolver = ClientIpResolver::new().with_trusted_proxy("10.0.0.0/8").unwrap();
+    let headers = headers(&[(
+        "forwarded",
+        r#"for="[2001:db8:cafe::17]:4711";proto=https, for=10.0.0.5:8080"#,
+    )]);
+
+    assert_eq!(
+        resolver.resolve(Some(ip("10.0.0.1")), &headers),
+        Some(ip("2001:db8:cafe::17"))
+    );
+}
+
+#[test]
+fn test_header_preference_and_fallbacks() {
+    let resolver = ClientIpResolver::new()
+        .with_trusted_proxy("10.0.0.0/8")
+        .unwrap()
+        .with_headers(&[ForwardedHeader::XRealIp]);
+    let headers = headers(&[("x-forwarded-for", "1.1.1.1"), ("x-real-ip", "2.2.2.2")]);
+    assert_eq!(resolver.resolve(Some(ip("10.0.0.1")), &headers), Some(ip("2.2.2.2")));
+
+    // Obfuscated hop: the client cannot be determined from the chain, use the peer
+    let resolver = ClientIpResolver::new().with_trusted_proxy("10.0.0.0/8").unwrap();
+    let headers = self::headers(&[("forwarded", "for=_hidden, for=10.0.0.3")]);
+    assert_eq!(resolver.resolve(Some(ip("10.0.0.1")), &headers), Some(ip("10.0.0.1")));
+
+    // Unknown peer
+    assert_eq!(resolver.resolve(None, &HeaderMap::new()), None);
+}
+
+#[tokio::test]
+async fn test_middleware_and_extractor() {
+    let resolver = ClientIpResolver::new().with_trusted_proxy("127.0.0.1").unwrap();
+    let app = with_client_ip(
+        Router::new().route("/", get(|ClientIp(ip): ClientIp| async move { ip.to_string() })),
+        resolver,
+    );
+
+    let mut req = Request::builder()
+        .uri("/")
+        .header("x-forwarded-for", "198.51.100.20")
+        .body(Body::empty())
+        .unwrap();
+    req.extensions_mut()
+        .insert(ConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000))));
+
+    let response = app.oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.collect().await.unwrap().to_bytes();
+    assert_eq!(body, "198.51.100.20");
+}
+
+#[tokio::test]
+async fn test_extractor_without_peer_is_rejected() {
+    let app = Router::new().route("/", get(|ClientIp(ip): ClientIp| async move { ip.to_string() }));
+
+    let req = Request::builder().uri("/").body(Body::empty()).unwrap();
+    let response = app.oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+}
+
+#[tokio::test]
+async fn test_mock_connect_info_is_the_peer() {
+    use axum::extract::connect_info::MockConnectInfo;
+
+    let handler = get(|ClientIp(ip): ClientIp| async move { ip.to_string() });
+    let peer = MockConnectInfo(SocketAddr::from(([127, 0, 0, 1], 4000)));
+    let request = || {
+        Request::builder()
+            .uri("/")
+            .header("x-forwarded-for", "198.51.100.20")
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    // The resolver trusts the mocked peer as a proxy
+    let resolver = ClientIpResolver::new().with_trusted_proxy("127.0.0.1").unwrap();
+    let app = with_client_ip(Router::new().route("/", handler.clone()), resolver).layer(peer);
+    let body = app.oneshot(request()).await.unwrap().collect().await.unwrap().to_bytes();
+    assert_eq!(body, "198.51.100.20");
+
+    // Without the middleware, the extractor falls back to the mocked peer
+    let app = Router::new().route("/", handler).layer(peer);
+    let body = app.oneshot(request()).await.unwrap().collect().await.unwrap().to_bytes();
+    assert_eq!(body, "127.0.0.1");
+}
@@ -0,0 +1,121 @@
+// tests/cors_tests.rs
+#![cfg(feature = "cors")]
+#![allow(clippy::disallowed_methods)]
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::Method,
+    routing::post,
+    Router,
+};
+use tower::ServiceExt;
+
+use axum_jetpack::cors::{with_cors, CorsConfig};
+
+fn app(config: CorsConfig) -> Router {
+    with_cors(Router::new().route("/api", post(|| async { "ok" })), config)
+}
+
+fn preflight(origin: &str) -> Request {
+    Request::builder()
+        .method(Method::OPTIONS)
+        .uri("/api")
+        .header("origin", origin)
+        .header("access-control-request-method", "POST")
+        .header("access-control-request-headers", "content-type,x-token")
+        .body(Body::empty())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_public_api_allows_any_origin_without_credentials() {
+    let response = app(CorsConfig::public_api())
+        .oneshot(preflight("https://anywhere.example"))
+        .await
+        .unwrap();
+
+    let headers = response.headers();
+    assert_eq!(headers["access-control-allow-origin"], "*");
+    assert!(!headers.contains_key("access-control-allow-credentials"));
+}
+
+#[tokio::test]
+async fn test_credentialed_spa_never_uses_wildcards() {
+    let config = CorsConfig::credentialed_spa(&["https://app.example.com/"]).unwrap();
+    let response = app(config)
+        .oneshot(preflight("https://app.example.com"))
+        .await
+        .unwrap();
+
+    let headers = response.headers();
+    assert_eq!(headers["access-control-allow-origin"], "https://app.example.com");
+    assert_eq!(headers["access-control-allow-credentials"], "true");
+    assert_eq!(headers["access-control-allow-methods"], "POST");
+    assert_eq!(headers["access-control-allow-headers"], "content-type,x-token");
+}
+
+#[tokio::test]
+async fn test_credentialed_spa_rejects_unknown_origin() {
+    let config = CorsConfig::credentialed_spa(&["https://app.example.com"]).unwrap();
+    let response = app(config)
+        .oneshot(preflight("https://evil.example"))
+        .await
+        .unwrap();
+
+    assert!(!response.headers().contains_key("access-control-allow-origin"));
+}
+
+#[test]
+fn test_credentialed_spa_validation() {
+    assert!(CorsConfig::credentialed_spa(&[]).is_err());
+    assert!(CorsConfig::credentialed_spa(&["*"]).is_err());
+    assert!(CorsConfig::credentialed_spa(&["null"]).is_err());
+    assert!(CorsConfig::credentialed_spa(&["app.example.com"]).is_err());
+}
+
+#[tokio::test]
+async fn test_deny_all_sends_no_cors_headers() {
+    let response = app(CorsConfig::deny_all())
+        .oneshot(preflight("https://app.example.com"))
+        .await
+        .unwrap();
+
+    assert!(!response.headers().contains_key("access-control-allow-origin"));
+}
+
+#[cfg(feature = "size-limit")]
+#[tokio::test]
+async fn test_rejections_carry_cors_headers() {
+    use axum::http::StatusCode;
+    use axum_jetpack::size_limit::{with_size_limit, SizeLimitConfig, SizeLimitMiddlewareConfig};
+
+    let router = Router::new().route("/api", post(|body: String| async move { body }));
+    let router = with_size_limit(
+        router,
+        SizeLimitMiddlewareConfig {
+            size_limits: SizeLimitConfig::default().with_default_limit(4),
+            ..Default::default()
+        },
+    );
+    let app = with_cors(router, CorsConfig::public_api());
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method(Method::POST)
+                .uri("/api")
+                .header("origin", "https://anywhere.example")
+                .body(Body::from("way too large"))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(response.headers()["access-control-allow-origin"], "*");
+    assert!(response.headers()["access-control-expose-headers"]
+        .to_str()
+        .unwrap()
+        .contains("retry-after"));
+}
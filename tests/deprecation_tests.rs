@@ -0,0 +1,143 @@
+// tests/deprecation_tests.rs
+#![cfg(feature = "deprecation")]
+#![allow(clippy::disallowed_methods)]
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{Method, StatusCode},
+    routing::get,
+    Router,
+};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, UNIX_EPOCH};
+use tower::ServiceExt;
+
+use axum_jetpack::deprecation::{
+    with_deprecation, DeprecationConfig, DeprecationEvent, DeprecationRule, DeprecationUsage,
+};
+
+fn app(config: DeprecationConfig) -> Router {
+    with_deprecation(
+        Router::new()
+            .route("/v1/users/{id}", get(|| async { "v1" }).post(|| async { "v1 post" }))
+            .route("/v2/users/{id}", get(|| async { "v2" }))
+            .route(
+                "/custom",
+                get(|| async { ([("deprecation", "@1")], "custom") }),
+            ),
+        config,
+    )
+}
+
+fn request(method: Method, uri: &str, key: Option<&str>) -> Request {
+    let mut builder = Request::builder().method(method).uri(uri);
+    if let Some(key) = key {
+        builder = builder.header("x-api-key", key);
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn test_headers_on_deprecated_route() {
+    let config = DeprecationConfig::new().with_rule(
+        DeprecationRule::new("/v1/*")
+            .deprecated_at(UNIX_EPOCH + Duration::from_secs(1_735_689_600))
+            .sunset_at(UNIX_EPOCH + Duration::from_secs(1_767_225_600))
+            .with_link("https://example.com/migrate"),
+    );
+
+    let response = app(config)
+        .oneshot(request(Method::GET, "/v1/users/42", None))
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let headers = response.headers();
+    assert_eq!(headers["deprecation"], "@1735689600");
+    assert_eq!(headers["sunset"], "Thu, 01 Jan 2026 00:00:00 GMT");
+    assert_eq!(
+        headers["link"],
+        "<https://example.com/migrate>; rel=\"deprecation\""
+    );
+}
+
+#[tokio::test]
+async fn test_undeprecated_route_untouched() {
+    let config = DeprecationConfig::new().with_rule(DeprecationRule::new("/v1/*"));
+
+    let response = app(config)
+        .oneshot(request(Method::GET, "/v2/users/42", None))
+        .await
+        .unwrap();
+
+    assert!(!response.headers().contains_key("deprecation"));
+    assert!(!response.headers().contains_key("sunset"));
+    assert!(!response.headers().contains_key("link"));
+}
+
+#[tokio::test]
+async fn test_rule_matches_route_template_and_methods() {
+    let config = DeprecationConfig::new()
+        .with_rule(DeprecationRule::new("/v1/users/{id}").with_methods(&[Method::POST]));
+    let app = app(config);
+
+    let response = app
+        .clone()
+        .oneshot(request(Method::POST, "/v1/users/7", None))
+        .await
+        .unwrap();
+    assert_eq!(response.headers()["deprecation"], "true");
+
+    let response = app
+        .oneshot(request(Method::GET, "/v1/users/7", None))
+        .await
+        .unwrap();
+    assert!(!response.headers().contains_key("deprecation"));
+}
+
+#[tokio::test]
+async fn test_handler_headers_are_kept() {
+    let config = DeprecationConfig::new().with_rule(DeprecationRule::new("/custom"));
+
+    let response = app(config)
+        .oneshot(request(Method::GET, "/custom", None))
+        .await
+        .unwrap();
+
+    assert_eq!(response.headers()["deprecation"], "@1");
+}
+
+#[tokio::test]
+async fn test_usage_tracked_per_client() {
+    let usage = DeprecationUsage::new();
+    let events = Arc::new(Mutex::new(Vec::<DeprecationEvent>::new()));
+    let sink = events.clone();
+
+    let config = DeprecationConfig::new()
+        .with_rule(DeprecationRule::new("/v1/*"))
+        .with_header_key("x-api-key")
+        .with_usage(usage.clone())
+        .on_use(move |event| sink.lock().unwrap().push(event.clone()));
+    let app = app(config);
+
+    for key in [Some("alice"), Some("alice"), Some("bob"), None] {
+        app.clone()
+            .oneshot(request(Method::GET, "/v1/users/1", key))
+            .await
+            .unwrap();
+    }
+    app.oneshot(request(Method::GET, "/v2/users/1", Some("alice")))
+        .await
+        .unwrap();
+
+    assert_eq!(usage.count("/v1/*", Some("alice")), 2);
+    assert_eq!(usage.count("/v1/*", Some("bob")), 1);
+    assert_eq!(usage.count("/v1/*", None), 1);
+    assert_eq!(usage.total("/v1/*"), 4);
+
+    let events = events.lock().unwrap();
+    assert_eq!(events.len(), 4);
+    assert_eq!(events[0].path, "/v1/users/1");
+    assert_eq!(events[0].client.as_deref(), Some("alice"));
+}
@@ -0,0 +1,150 @@
+// tests/egress_tests.rs
+#![cfg(feature = "egress")]
+#![allow(clippy::disallowed_methods)]
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::StatusCode,
+    routing::get,
+    Router,
+};
+use http_body_util::BodyExt;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tower::ServiceExt;
+
+use axum_jetpack::egress::{
+    with_egress_accounting, EgressBytes, EgressConfig, EgressLedger, EgressQuota, EgressRecord,
+};
+
+fn app(config: EgressConfig) -> Router {
+    with_egress_accounting(
+        Router::new().route("/data", get(|| async { "x".repeat(100) })),
+        config,
+    )
+}
+
+fn request(key: Option<&str>) -> Request {
+    let mut builder = Request::builder().uri("/data");
+    if let Some(key) = key {
+        builder = builder.header("x-api-key", key);
+    }
+    builder.body(Body::empty()).unwrap()
+}
+
+#[tokio::test]
+async fn test_counts_response_bytes_per_key() {
+    let ledger = EgressLedger::new();
+    let records = Arc::new(Mutex::new(Vec::<EgressRecord>::new()));
+    let sink = records.clone();
+
+    let config = EgressConfig::new()
+        .with_header_key("x-api-key")
+        .with_ledger(ledger.clone())
+        .on_complete(move |record| sink.lock().unwrap().push(record.clone()));
+    let app = app(config);
+
+    let response = app.clone().oneshot(request(Some("alice"))).await.unwrap();
+    let counter = response.extensions().get::<EgressBytes>().cloned().unwrap();
+    let body = response.collect().await.unwrap().to_bytes();
+    assert_eq!(body.len(), 100);
+    assert_eq!(counter.get(), 100);
+
+    app.oneshot(request(Some("alice"))).await.unwrap()
+        .collect().await.unwrap();
+
+    assert_eq!(ledger.usage("alice").total_bytes, 200);
+    let records = records.lock().unwrap();
+    assert_eq!(records.len(), 2);
+    assert!(records.iter().all(|r| r.key == "alice" && r.bytes == 100 && r.completed));
+}
+
+#[tokio::test]
+async fn test_requests_without_key_are_not_counted() {
+    let ledger = EgressLedger::new();
+    let app = app(EgressConfig::new().with_header_key("x-api-key").with_ledger(ledger.clone()));
+
+    let response = app.oneshot(request(None)).await.unwrap();
+    assert!(response.extensions().get::<EgressBytes>().is_none());
+    response.collect().await.unwrap();
+
+    assert!(ledger.snapshot().is_empty());
+}
+
+#[tokio::test]
+async fn test_quota_rejects_after_exhaustion() {
+    let config = EgressConfig::new()
+        .with_header_key("x-api-key")
+        .with_quota(EgressQuota::new(150, Duration::from_secs(60)));
+    let app = app(config);
+
+    // First two responses fit (the second one overshoots, which is allowed)
+    for _ in 0..2 {
+        let response = app.clone().oneshot(request(Some("bob"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        response.collect().await.unwrap();
+    }
+
+    let response = app.clone().oneshot(request(Some("bob"))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    assert!(response.headers().contains_key("retry-after"));
+
+    // Other keys are unaffected
+    let response = app.oneshot(request(Some("carol"))).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_dropped_body_is_recorded_as_incomplete() {
+    let records = Arc::new(Mutex::new(Vec::<EgressRecord>::new()));
+    let sink = records.clone();
+    let app = app(
+        EgressConfig::new()
+            .with_header_key("x-api-key")
+            .on_complete(move |record| sink.lock().unwrap().push(record.clone())),
+    );
+
+    let response = app.oneshot(request(Some("dave"))).await.unwrap();
+    drop(response);
+
+    let records = records.lock().unwrap();
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].bytes, 0);
+    assert!(!records[0].completed);
+}
+
+#[tokio::test]
+async fn test_default_key_is_the_peer_ip() {
+    use axum::extract::connect_info::MockConnectInfo;
+    use std::net::SocketAddr;
+
+    // No client IP layer: the peer address keys the request
+    let ledger = EgressLedger::new();
+    let app = app(EgressConfig::new().with_ledger(ledger.clone()))
+        .layer(MockConnectInfo(SocketAddr::from(([192, 0, 2, 7], 4000))));
+
+    app.oneshot(request(None)).await.unwrap().collect().await.unwrap();
+
+    assert_eq!(ledger.usage("192.0.2.7").total_bytes, 100);
+}
+
+#[test]
+fn test_expired_windows_are_evicted() {
+    let ledger = EgressLedger::new();
+    let window = Some(Duration::from_millis(20));
+    for key in 0..1024 {
+        ledger.record(&key.to_string(), 10, window);
+    }
+    ledger.record("count-only", 10, None);
+
+    // Once the windows expire, the next new key sweeps them out
+    std::thread::sleep(Duration::from_millis(40));
+    ledger.record("new", 10, window);
+    let mut keys: Vec<String> = ledger.snapshot().into_keys().collect();
+    keys.sort();
+    assert_eq!(keys, ["count-only", "new"]);
+
+    assert_eq!(ledger.take().len(), 2);
+    assert!(ledger.snapshot().is_empty());
+}
@@ -0,0 +1,85 @@
+// tests/error_format_tests.rs
+#![cfg(feature = "size-limit")]
+#![allow(clippy::disallowed_methods)]
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::StatusCode,
+    response::Response,
+    routing::post,
+    Router,
+};
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+use axum_jetpack::error::{with_error_formats, ErrorFormat, ErrorFormats};
+use axum_jetpack::size_limit::{with_size_limit, SizeLimit, SizeLimitConfig, SizeLimitMiddlewareConfig};
+
+fn app() -> Router {
+    let router = Router::new()
+        .route("/api/upload", post(|| async { "ok" }))
+        .route("/web/upload", post(|| async { "ok" }))
+        .route("/other", post(|| async { "ok" }));
+    let router = with_size_limit(
+        router,
+        SizeLimitMiddlewareConfig::new(
+            SizeLimitConfig::default().with_default_limit(SizeLimit::bytes(4)),
+        )
+        .with_error_format(ErrorFormat::Json),
+    );
+    with_error_formats(
+        router,
+        ErrorFormats::new()
+            .with_route("/api/*", ErrorFormat::ProblemDetails)
+            .with_route("/web/*", ErrorFormat::Html),
+    )
+}
+
+async fn upload(uri: &str) -> Response {
+    let req = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .body(Body::from("way too large"))
+        .unwrap();
+    app().oneshot(req).await.unwrap()
+}
+
+async fn body_string(response: Response) -> String {
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_api_routes_get_problem_details() {
+    let response = upload("/api/upload").await;
+
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(response.headers()["content-type"], "application/problem+json");
+    assert!(body_string(response).await.contains("\"title\":\"Payload Too Large\""));
+}
+
+#[tokio::test]
+async fn test_web_routes_get_html() {
+    let response = upload("/web/upload").await;
+
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(response.headers()["content-type"], "text/html; charset=utf-8");
+    assert!(body_string(response).await.contains("<h1>413 Payload Too Large</h1>"));
+}
+
+#[tokio::test]
+async fn test_unmatched_routes_keep_configured_format() {
+    let response = upload("/other").await;
+
+    assert_eq!(response.headers()["content-type"], "application/json");
+}
+
+#[test]
+fn test_html_escapes_message() {
+    let response = ErrorFormat::Html.response(StatusCode::BAD_REQUEST, "<script>");
+    let body = futures::executor::block_on(body_string(response));
+
+    assert!(body.contains("&lt;script&gt;"));
+    assert!(!body.contains("<script>"));
+}
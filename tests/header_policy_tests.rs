@@ -0,0 +1,95 @@
+// tests/header_policy_tests.rs
+#![cfg(feature = "header-policy")]
+#![allow(clippy::disallowed_methods)]
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header, HeaderName, HeaderValue, StatusCode},
+    response::Response,
+    routing::get,
+    Router,
+};
+use tower::ServiceExt;
+
+use axum_jetpack::header_policy::{with_header_policy, HeaderPolicy};
+
+fn app(policy: HeaderPolicy) -> Router {
+    let leaky = || async {
+        (
+            [
+                ("server", "nginx/1.2.3"),
+                ("x-powered-by", "Express"),
+                ("x-debug-sql", "select *"),
+                ("x-request-id", "abc"),
+            ],
+            "ok",
+        )
+    };
+    with_header_policy(
+        Router::new()
+            .route("/leaky", get(leaky))
+            .route("/plain", get(|| async { "plain" }))
+            .route("/metrics", get(|| async { "metrics" })),
+        policy,
+    )
+}
+
+async fn call(app: Router, uri: &str) -> Response {
+    app.oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn test_default_strips_server_identifying_headers() {
+    let response = call(app(HeaderPolicy::new()), "/leaky").await;
+
+    let headers = response.headers();
+    assert!(!headers.contains_key("server"));
+    assert!(!headers.contains_key("x-powered-by"));
+    assert!(headers.contains_key("x-debug-sql"));
+    assert!(headers.contains_key("x-request-id"));
+}
+
+#[tokio::test]
+async fn test_prefix_strip_and_override() {
+    let policy = HeaderPolicy::new()
+        .with_stripped_prefix("X-Debug-")
+        .with_override(header::SERVER, HeaderValue::from_static("edge"));
+    let response = call(app(policy), "/leaky").await;
+
+    let headers = response.headers();
+    assert!(!headers.contains_key("x-debug-sql"));
+    assert_eq!(headers["server"], "edge");
+}
+
+#[tokio::test]
+async fn test_required_header_inserted() {
+    let policy = HeaderPolicy::new()
+        .require(header::X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
+    let response = call(app(policy), "/plain").await;
+
+    assert_eq!(response.headers()["x-content-type-options"], "nosniff");
+}
+
+#[tokio::test]
+async fn test_strict_requirement_and_exceptions() {
+    let policy = HeaderPolicy::new()
+        .require_strict(HeaderName::from_static("x-request-id"))
+        .require(header::X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"))
+        .with_exception("/metrics", &[HeaderName::from_static("x-request-id")]);
+    let app = app(policy);
+
+    let response = call(app.clone(), "/leaky").await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = call(app.clone(), "/plain").await;
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    // Non-strict requirements still apply to the error response
+    assert_eq!(response.headers()["x-content-type-options"], "nosniff");
+
+    let response = call(app, "/metrics").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-content-type-options"], "nosniff");
+}
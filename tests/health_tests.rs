@@ -0,0 +1,131 @@
+// tests/health_tests.rs
+#![cfg(feature = "health")]
+#![allow(clippy::disallowed_methods)]
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::StatusCode,
+    response::Response,
+    Router,
+};
+use http_body_util::BodyExt;
+use std::time::Duration;
+use tower::ServiceExt;
+
+use axum_jetpack::health::{health_routes, HealthCheck, HealthRegistry, HealthStatus};
+
+async fn call(app: Router, uri: &str) -> Response {
+    app.oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+async fn body_string(response: Response) -> String {
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+fn failing(name: &str) -> HealthCheck {
+    HealthCheck::new(name, || async { Err("connection refused".to_string()) })
+}
+
+#[tokio::test]
+async fn test_ready_when_all_checks_pass() {
+    let registry = HealthRegistry::new().with_check(HealthCheck::new("db", || async { Ok(()) }));
+    let response = call(health_routes(registry), "/readyz").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = body_string(response).await;
+    assert!(body.starts_with("{\"status\":\"healthy\",\"checks\":[{\"name\":\"db\",\"status\":\"healthy\""));
+    assert!(!body.contains("error"));
+}
+
+#[tokio::test]
+async fn test_critical_failure_is_503() {
+    let registry = HealthRegistry::new()
+        .with_check(HealthCheck::new("db", || async { Ok(()) }))
+        .with_check(failing("queue"));
+    let response = call(health_routes(registry), "/readyz").await;
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let body = body_string(response).await;
+    assert!(body.contains("\"status\":\"unhealthy\""));
+    assert!(body.contains("\"error\":\"connection refused\""));
+}
+
+#[tokio::test]
+async fn test_non_critical_failure_degrades() {
+    let registry = HealthRegistry::new().with_check(failing("cache").non_critical());
+    let response = call(health_routes(registry), "/readyz").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(body_string(response).await.contains("\"status\":\"degraded\""));
+}
+
+#[tokio::test]
+async fn test_timeout_counts_as_failure() {
+    let check = HealthCheck::new("slow", || async {
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        Ok(())
+    })
+    .with_timeout(Duration::from_millis(20));
+
+    let report = HealthRegistry::new().with_check(check).run().await;
+
+    assert_eq!(report.status, HealthStatus::Unhealthy);
+    assert_eq!(report.checks[0].error.as_deref(), Some("timed out after 20 ms"));
+}
+
+#[tokio::test]
+async fn test_liveness_ignores_checks() {
+    let registry = HealthRegistry::new().with_check(failing("db"));
+    let response = call(health_routes(registry), "/healthz").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(body_string(response).await, "{\"status\":\"healthy\",\"checks\":[]}");
+}
+
+#[cfg(feature = "size-limit")]
+#[tokio::test]
+async fn test_buffer_budget_check() {
+    use axum::{body::Bytes, routing::post};
+    use axum_jetpack::size_limit::{with_size_limit, BufferBudget, SizeLimitMiddlewareConfig};
+    use std::sync::Arc;
+    use tokio::sync::Semaphore;
+
+    let budget = BufferBudget::new(2048);
+    let registry = HealthRegistry::new().with_check(HealthCheck::buffer_budget(budget.clone(), 1024));
+    let report = registry.run().await;
+    assert_eq!(report.status, HealthStatus::Healthy);
+
+    // A buffered upload held by its handler takes the budget
+    let gate = Arc::new(Semaphore::new(0));
+    let handler_gate = gate.clone();
+    let app = with_size_limit(
+        Router::new().route(
+            "/upload",
+            post(move |_: Bytes| async move {
+                let _ = handler_gate.acquire().await;
+            }),
+        ),
+        SizeLimitMiddlewareConfig::default().with_buffer_budget(budget.clone()),
+    );
+    let req = Request::builder()
+        .method("POST")
+        .uri("/upload")
+        .header("content-type", "application/json")
+        .body(Body::from(vec![0u8; 1500]))
+        .unwrap();
+    let upload = tokio::spawn(app.oneshot(req));
+    tokio::time::sleep(Duration::from_millis(50)).await;
+
+    let report = registry.run().await;
+    assert_eq!(report.status, HealthStatus::Degraded);
+    assert_eq!(report.checks[0].name, "buffer_budget");
+    assert_eq!(report.checks[0].error.as_deref(), Some("0 B of 2 KiB available"));
+
+    gate.add_permits(1);
+    assert_eq!(upload.await.unwrap().unwrap().status(), StatusCode::OK);
+    assert_eq!(registry.run().await.status, HealthStatus::Healthy);
+}
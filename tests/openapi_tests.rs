@@ -0,0 +1,143 @@
+// tests/openapi_tests.rs
+#![cfg(feature = "openapi")]
+#![allow(clippy::disallowed_methods)]
+
+use utoipa::openapi::{
+    path::OperationBuilder, request_body::RequestBodyBuilder, Content, HttpMethod, OpenApi,
+    OpenApiBuilder, PathItem, Paths, RefOr, Response,
+};
+
+use axum_jetpack::size_limit::{
+    document_size_limits, document_size_limits_matching, SizeLimitConfig,
+    MAX_BODY_SIZE_EXTENSION,
+};
+
+fn upload_operation(content_types: &[&str]) -> utoipa::openapi::path::Operation {
+    let mut body = RequestBodyBuilder::new();
+    for content_type in content_types {
+        body = body.content(*content_type, Content::default());
+    }
+    OperationBuilder::new().request_body(Some(body.build())).build()
+}
+
+fn document() -> OpenApi {
+    let paths = Paths::builder()
+        .path(
+            "/api/upload",
+            PathItem::new(HttpMethod::Post, upload_operation(&["application/json", "image/png"])),
+        )
+        .path("/internal/import", PathItem::new(HttpMethod::Put, upload_operation(&["text/csv"])))
+        .path("/api/items", PathItem::new(HttpMethod::Get, OperationBuilder::new().build()))
+        .build();
+    OpenApiBuilder::new().paths(paths).build()
+}
+
+fn config() -> SizeLimitConfig {
+    SizeLimitConfig::default()
+        .with_default_limit("2mb")
+        .with_specific_limit("application/json", "100kb")
+        .with_wildcard_limit("image/*", "5mb")
+}
+
+fn limit_of(openapi: &OpenApi, path: &str, content_type: &str) -> Option<serde_json::Value> {
+    let item = openapi.paths.paths.get(path)?;
+    let operation = item.post.as_ref().or(item.put.as_ref())?;
+    let content = operation.request_body.as_ref()?.content.get(content_type)?;
+    content.extensions.as_ref()?.get(MAX_BODY_SIZE_EXTENSION).cloned()
+}
+
+#[test]
+fn test_limits_documented_per_content_type() {
+    let mut openapi = document();
+    document_size_limits(&mut openapi, &config());
+
+    assert_eq!(limit_of(&openapi, "/api/upload", "application/json"), Some(100_000.into()));
+    assert_eq!(limit_of(&openapi, "/api/upload", "image/png"), Some(5_000_000.into()));
+    assert_eq!(limit_of(&openapi, "/internal/import", "text/csv"), Some(2_000_000.into()));
+
+    let upload = openapi.paths.paths["/api/upload"].post.as_ref().unwrap();
+    assert!(upload.responses.responses.contains_key("413"));
+
+    // Operations without a request body are left alone
+    let items = openapi.paths.paths["/api/items"].get.as_ref().unwrap();
+    assert!(!items.responses.responses.contains_key("413"));
+}
+
+#[test]
+fn test_existing_413_response_is_kept() {
+    let mut openapi = document();
+    if let Some(operation) = openapi.paths.paths.get_mut("/api/upload").and_then(|i| i.post.as_mut()) {
+        operation
+            .responses
+            .responses
+            .insert("413".to_string(), RefOr::T(Response::new("Custom description")));
+    }
+
+    document_size_limits(&mut openapi, &config());
+
+    let upload = openapi.paths.paths["/api/upload"].post.as_ref().unwrap();
+    match &upload.responses.responses["413"] {
+        RefOr::T(response) => assert_eq!(response.description, "Custom description"),
+        RefOr::Ref(_) => panic!("unexpected reference"),
+    }
+}
+
+#[test]
+fn test_only_matching_paths_are_documented() {
+    let mut openapi = document();
+    document_size_limits_matching(&mut openapi, &config(), |path| path.starts_with("/api/"));
+
+    assert!(limit_of(&openapi, "/api/upload", "application/json").is_some());
+    assert!(limit_of(&openapi, "/internal/import", "text/csv").is_none());
+}
+
+#[test]
+fn test_limits_derived_from_document() {
+    use axum::http::{HeaderMap, Method};
+    use axum_jetpack::size_limit::{BufferStrategy, ContentTypeMatcher};
+    use utoipa::openapi::{extensions::Extensions, schema::ObjectBuilder};
+
+    // Documenting a configuration and deriving it back gives the same limits
+    let mut openapi = document();
+    document_size_limits(&mut openapi, &config());
+    let derived = SizeLimitConfig::from_openapi(&openapi).with_default_limit(1);
+    let matcher = ContentTypeMatcher::compile(&derived, &BufferStrategy::with_defaults());
+    let limit = |method: Method, path: &str, content_type: &str| {
+        matcher.lookup_with(&method, &[path], content_type, &HeaderMap::new()).limit
+    };
+    assert_eq!(limit(Method::POST, "/api/upload", "application/json"), 100_000);
+    assert_eq!(limit(Method::POST, "/api/upload", "image/png"), 5_000_000);
+    assert_eq!(limit(Method::PUT, "/internal/import", "text/csv"), 2_000_000);
+    // Other methods, paths and content types keep the configuration
+    assert_eq!(limit(Method::PUT, "/api/upload", "application/json"), 1);
+    assert_eq!(limit(Method::POST, "/api/other", "application/json"), 1);
+    assert_eq!(limit(Method::POST, "/api/upload", "text/plain"), 1);
+
+    // Human-readable extensions and schema maxLength
+    let mut csv = Content::default();
+    let mut extensions = Extensions::default();
+    extensions.insert(MAX_BODY_SIZE_EXTENSION.to_string(), "64kb".into());
+    csv.extensions = Some(extensions);
+    let binary = Content::new(Some(ObjectBuilder::new().max_length(Some(2048)).build()));
+    let body = RequestBodyBuilder::new()
+        .content("text/csv", csv)
+        .content("application/octet-stream", binary)
+        .content("application/json", Content::default())
+        .build();
+    let operation = OperationBuilder::new().request_body(Some(body)).build();
+    let openapi = OpenApiBuilder::new()
+        .paths(Paths::builder().path("/files/{id}", PathItem::new(HttpMethod::Post, operation)))
+        .build();
+
+    let derived = SizeLimitConfig::from_openapi(&openapi);
+    assert_eq!(derived.rules.len(), 2);
+    let matcher = ContentTypeMatcher::compile(&derived, &BufferStrategy::with_defaults());
+    let limit = |content_type: &str| {
+        matcher
+            .lookup_with(&Method::POST, &["/files/{id}"], content_type, &HeaderMap::new())
+            .limit
+    };
+    assert_eq!(limit("text/csv"), 64_000);
+    assert_eq!(limit("application/octet-stream"), 2048);
+    assert_eq!(limit("application/json"), 1_000_000);
+}
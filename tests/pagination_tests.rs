@@ -0,0 +1,136 @@
+// tests/pagination_tests.rs
+#![cfg(feature = "pagination")]
+#![allow(clippy::disallowed_methods)]
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::StatusCode,
+    response::Response,
+    routing::get,
+    Router,
+};
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+use axum_jetpack::error::ErrorFormat;
+use axum_jetpack::pagination::{with_pagination, Paginated, Pagination, PaginationConfig};
+
+async fn list(pagination: Pagination) -> Paginated<String> {
+    let body = format!("{}:{}", pagination.offset(), pagination.limit());
+    pagination.page_response(body, Some(95))
+}
+
+async fn feed(pagination: Pagination) -> Paginated<String> {
+    let next = match pagination.cursor() {
+        None => Some("abc=".to_string()),
+        Some(_) => None,
+    };
+    pagination.cursor_response(format!("{:?}", pagination.cursor()), next)
+}
+
+fn app(config: PaginationConfig) -> Router {
+    with_pagination(
+        Router::new().route("/items", get(list)).route("/feed", get(feed)),
+        config,
+    )
+}
+
+async fn call(app: Router, uri: &str) -> Response {
+    app.oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+async fn body_string(response: Response) -> String {
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_defaults_apply() {
+    let response = call(app(PaginationConfig::new()), "/items").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["x-total-count"], "95");
+    assert_eq!(body_string(response).await, "0:20");
+}
+
+#[tokio::test]
+async fn test_link_header_preserves_query() {
+    let response = call(app(PaginationConfig::new()), "/items?page=2&per_page=10&q=a%20b").await;
+
+    assert_eq!(
+        response.headers()["link"],
+        "</items?q=a%20b&page=1&per_page=10>; rel=\"first\", \
+         </items?q=a%20b&page=1&per_page=10>; rel=\"prev\", \
+         </items?q=a%20b&page=3&per_page=10>; rel=\"next\", \
+         </items?q=a%20b&page=10&per_page=10>; rel=\"last\""
+    );
+    assert_eq!(body_string(response).await, "10:10");
+}
+
+#[tokio::test]
+async fn test_last_page_has_no_next() {
+    let response = call(app(PaginationConfig::new()), "/items?page=10&per_page=10").await;
+
+    let link = response.headers()["link"].to_str().unwrap().to_string();
+    assert!(link.contains("rel=\"prev\""));
+    assert!(!link.contains("rel=\"next\""));
+}
+
+#[tokio::test]
+async fn test_out_of_bounds_rejected() {
+    let config = PaginationConfig::new().with_max_per_page(50).with_max_page(5);
+
+    for uri in [
+        "/items?per_page=51",
+        "/items?per_page=0",
+        "/items?page=0",
+        "/items?page=6",
+        "/items?page=abc",
+        "/feed?cursor=x&page=2",
+    ] {
+        let response = call(app(config.clone()), uri).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", uri);
+    }
+}
+
+#[tokio::test]
+async fn test_rejection_uses_error_format() {
+    let config = PaginationConfig::new().with_error_format(ErrorFormat::ProblemDetails);
+    let response = call(app(config), "/items?per_page=1000").await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(response.headers()["content-type"], "application/problem+json");
+    let body = body_string(response).await;
+    assert!(body.contains("\"detail\":\"per_page must be at most 100\""));
+    assert!(body.contains("\"status\":400"));
+}
+
+#[tokio::test]
+async fn test_cursor_pagination() {
+    let app = app(PaginationConfig::new());
+
+    let response = call(app.clone(), "/feed?per_page=5").await;
+    assert_eq!(
+        response.headers()["link"],
+        "</feed?per_page=5>; rel=\"first\", </feed?cursor=abc%3D&per_page=5>; rel=\"next\""
+    );
+
+    let response = call(app, "/feed?cursor=abc%3D&per_page=5").await;
+    assert!(!response.headers()["link"].to_str().unwrap().contains("next"));
+    assert_eq!(body_string(response).await, "Some(\"abc=\")");
+}
+
+#[tokio::test]
+async fn test_extractor_works_without_layer() {
+    let app = Router::new().route("/items", get(list));
+
+    let response = call(app.clone(), "/items?per_page=100").await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = call(app, "/items?per_page=101").await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(body_string(response).await, "per_page must be at most 100");
+}
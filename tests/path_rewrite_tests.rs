@@ -0,0 +1,126 @@
+// tests/path_rewrite_tests.rs
+#![cfg(feature = "path-rewrite")]
+#![allow(clippy::disallowed_methods)]
+
+use axum::{
+    body::Body,
+    extract::{OriginalUri, Request},
+    http::{StatusCode, Uri},
+    routing::get,
+    Extension, Router,
+};
+use http_body_util::BodyExt;
+use tower::ServiceExt;
+
+use axum_jetpack::path_rewrite::{
+    with_path_rewrite, OriginalPath, PathRewriteConfig, RewriteRule, TrailingSlash,
+};
+
+fn test_router() -> Router {
+    Router::new()
+        .route("/users", get(|uri: Uri| async move { format!("users {}", uri) }))
+        .route(
+            "/v2/orders",
+            get(|Extension(OriginalPath(path)): Extension<OriginalPath>| async move {
+                format!("orders from {}", path)
+            }),
+        )
+        .route(
+            "/original",
+            get(|OriginalUri(uri): OriginalUri| async move { uri.to_string() }),
+        )
+}
+
+async fn body_string(response: axum::response::Response) -> String {
+    let bytes = response.collect().await.unwrap().to_bytes();
+    String::from_utf8_lossy(&bytes).to_string()
+}
+
+#[tokio::test]
+async fn test_strip_prefix_before_routing() {
+    let app = with_path_rewrite(test_router(), PathRewriteConfig::new().with_strip_prefix("/api"));
+
+    let req = Request::builder()
+        .uri("/api/users?page=2")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    // Query string is preserved
+    assert_eq!(body_string(response).await, "users /users?page=2");
+}
+
+#[tokio::test]
+async fn test_regex_rewrite_preserves_original_path() {
+    let config = PathRewriteConfig::new()
+        .with_rule(RewriteRule::regex(r"^/v1/(.*)$", "/v2/$1").unwrap())
+        .with_trailing_slash(TrailingSlash::Trim);
+    let app = with_path_rewrite(test_router(), config);
+
+    let req = Request::builder()
+        .uri("/v1/orders/")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(body_string(response).await, "orders from /v1/orders/");
+}
+
+#[tokio::test]
+async fn test_original_uri_reflects_client_request() {
+    let app = with_path_rewrite(test_router(), PathRewriteConfig::new().with_strip_prefix("/api"));
+
+    let req = Request::builder()
+        .uri("/api/original")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(req).await.unwrap();
+    assert_eq!(body_string(response).await, "/api/original");
+}
+
+#[tokio::test]
+async fn test_original_path_header() {
+    let router = Router::new().route(
+        "/users",
+        get(|req: Request| async move {
+            req.headers()
+                .get("x-original-path")
+                .and_then(|h| h.to_str().ok())
+                .unwrap_or("missing")
+                .to_string()
+        }),
+    );
+    let config = PathRewriteConfig::new()
+        .with_strip_prefix("/api")
+        .with_original_path_header(true);
+    let app = with_path_rewrite(router, config);
+
+    let req = Request::builder()
+        .uri("/api/users")
+        .header("x-original-path", "/spoofed")
+        .body(Body::empty())
+        .unwrap();
+
+    let response = app.oneshot(req).await.unwrap();
+    assert_eq!(body_string(response).await, "/api/users");
+}
+
+#[tokio::test]
+async fn test_rewrite_cycle_is_rejected() {
+    // "/a" -> "/b" and "/b" -> "/a" across passes
+    let config = PathRewriteConfig::new()
+        .with_rule(RewriteRule::regex("^/a$", "/tmp-b").unwrap())
+        .with_rule(RewriteRule::regex("^/b$", "/a").unwrap())
+        .with_rule(RewriteRule::regex("^/tmp-(.*)$", "/$1").unwrap());
+
+    assert!(config.rewrite("/a").is_err());
+
+    let app = with_path_rewrite(test_router(), config);
+    let req = Request::builder().uri("/a").body(Body::empty()).unwrap();
+
+    let response = app.oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::LOOP_DETECTED);
+}
@@ -0,0 +1,115 @@
+// tests/query_tests.rs
+#![cfg(feature = "limited-query")]
+#![allow(clippy::disallowed_methods)]
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::StatusCode,
+    response::Response,
+    routing::get,
+    Router,
+};
+use http_body_util::BodyExt;
+use serde::Deserialize;
+use tower::ServiceExt;
+
+use axum_jetpack::error::ErrorFormat;
+use axum_jetpack::query::{with_query_limits, LimitedQuery, QueryLimits};
+
+#[derive(Deserialize)]
+#[cfg_attr(feature = "validation", derive(validator::Validate))]
+struct Search {
+    #[cfg_attr(feature = "validation", validate(length(min = 2)))]
+    q: String,
+    page: Option<u32>,
+}
+
+async fn search(LimitedQuery(search): LimitedQuery<Search>) -> String {
+    format!("{}:{:?}", search.q, search.page)
+}
+
+fn app(limits: QueryLimits) -> Router {
+    with_query_limits(Router::new().route("/search", get(search)), limits)
+}
+
+async fn call(app: Router, uri: &str) -> Response {
+    app.oneshot(Request::builder().uri(uri).body(Body::empty()).unwrap())
+        .await
+        .unwrap()
+}
+
+async fn body_string(response: Response) -> String {
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_valid_query_deserializes() {
+    let response = call(app(QueryLimits::new()), "/search?q=rust&page=2").await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(body_string(response).await, "rust:Some(2)");
+}
+
+#[tokio::test]
+async fn test_overlong_query_is_414() {
+    let uri = format!("/search?q={}", "a".repeat(100));
+    let response = call(app(QueryLimits::new().with_max_length(64)), &uri).await;
+
+    assert_eq!(response.status(), StatusCode::URI_TOO_LONG);
+}
+
+#[tokio::test]
+async fn test_too_many_params_rejected() {
+    let response = call(
+        app(QueryLimits::new().with_max_params(2)),
+        "/search?q=a&page=1&x=1",
+    )
+    .await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(
+        body_string(response).await,
+        "Query string has more than 2 parameters"
+    );
+}
+
+#[tokio::test]
+async fn test_long_value_rejected_with_error_format() {
+    let limits = QueryLimits::new()
+        .with_max_value_length(4)
+        .with_error_format(ErrorFormat::Json);
+    let response = call(app(limits), "/search?q=toolong").await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert_eq!(response.headers()["content-type"], "application/json");
+    assert!(body_string(response).await.contains("exceeds 4 bytes"));
+}
+
+#[tokio::test]
+async fn test_deserialization_failure_is_400() {
+    let response = call(app(QueryLimits::new()), "/search?page=x").await;
+
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[cfg(feature = "validation")]
+#[tokio::test]
+async fn test_validation_failure_is_422() {
+    use axum_jetpack::query::ValidatedQuery;
+
+    let app = with_query_limits(
+        Router::new().route(
+            "/search",
+            get(|ValidatedQuery(search): ValidatedQuery<Search>| async move { search.q }),
+        ),
+        QueryLimits::new(),
+    );
+
+    let response = call(app.clone(), "/search?q=ok").await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = call(app, "/search?q=x").await;
+    assert_eq!(response.status(), StatusCode::UNPROCESSABLE_ENTITY);
+}
@@ -0,0 +1,132 @@
+// tests/range_tests.rs
+#![cfg(feature = "range")]
+#![allow(clippy::disallowed_methods)]
+
+use axum::{
+    body::{Body, Bytes},
+    extract::Request,
+    http::{HeaderMap, StatusCode},
+    response::Response,
+    routing::get,
+    Router,
+};
+use http_body_util::BodyExt;
+use std::io::Write;
+use tower::ServiceExt;
+
+use axum_jetpack::range::{with_range_requests, FileSource, RangeConfig, Ranged};
+
+const CONTENT: &str = "0123456789abcdefghij";
+
+fn app() -> Router {
+    with_range_requests(
+        Router::new()
+            .route(
+                "/data",
+                get(|| async { ([("etag", "\"v1\"")], CONTENT) }),
+            )
+            .route(
+                "/ranged",
+                get(|headers: HeaderMap| async move {
+                    Ranged::new(Bytes::from_static(b"handled by source"))
+                        .respond(&headers)
+                        .await
+                }),
+            ),
+        RangeConfig::new(),
+    )
+}
+
+async fn get_with(uri: &str, headers: &[(&str, &str)]) -> Response {
+    let mut builder = Request::builder().uri(uri);
+    for (name, value) in headers {
+        builder = builder.header(*name, *value);
+    }
+    app().oneshot(builder.body(Body::empty()).unwrap()).await.unwrap()
+}
+
+async fn body_string(response: Response) -> String {
+    let bytes = response.into_body().collect().await.unwrap().to_bytes();
+    String::from_utf8(bytes.to_vec()).unwrap()
+}
+
+#[tokio::test]
+async fn test_full_response_advertises_ranges() {
+    let response = get_with("/data", &[]).await;
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["accept-ranges"], "bytes");
+    assert_eq!(body_string(response).await, CONTENT);
+}
+
+#[tokio::test]
+async fn test_single_range() {
+    let response = get_with("/data", &[("range", "bytes=2-5")]).await;
+
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(response.headers()["content-range"], "bytes 2-5/20");
+    assert_eq!(response.headers()["content-length"], "4");
+    assert_eq!(body_string(response).await, "2345");
+}
+
+#[tokio::test]
+async fn test_suffix_range() {
+    let response = get_with("/data", &[("range", "bytes=-3")]).await;
+
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(body_string(response).await, "hij");
+}
+
+#[tokio::test]
+async fn test_unsatisfiable_range() {
+    let response = get_with("/data", &[("range", "bytes=50-")]).await;
+
+    assert_eq!(response.status(), StatusCode::RANGE_NOT_SATISFIABLE);
+    assert_eq!(response.headers()["content-range"], "bytes */20");
+}
+
+#[tokio::test]
+async fn test_if_range_etag() {
+    let response = get_with("/data", &[("range", "bytes=0-1"), ("if-range", "\"v1\"")]).await;
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+
+    // A stale validator gets the full, current representation
+    let response = get_with("/data", &[("range", "bytes=0-1"), ("if-range", "\"v0\"")]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(body_string(response).await, CONTENT);
+
+    // Weak validators never match
+    let response = get_with("/data", &[("range", "bytes=0-1"), ("if-range", "W/\"v1\"")]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_handler_source_is_not_sliced_twice() {
+    let response = get_with("/ranged", &[("range", "bytes=0-6")]).await;
+
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(response.headers()["content-range"], "bytes 0-6/17");
+    assert_eq!(body_string(response).await, "handled");
+}
+
+#[tokio::test]
+async fn test_file_source_range() {
+    let path = std::env::temp_dir().join(format!("axum-jetpack-range-{}.txt", std::process::id()));
+    std::fs::File::create(&path)
+        .unwrap()
+        .write_all(CONTENT.as_bytes())
+        .unwrap();
+
+    let mut headers = HeaderMap::new();
+    headers.insert("range", "bytes=10-".parse().unwrap());
+    let response = Ranged::new(FileSource::open(&path).await.unwrap())
+        .with_content_type("text/plain")
+        .respond(&headers)
+        .await;
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(response.status(), StatusCode::PARTIAL_CONTENT);
+    assert_eq!(response.headers()["content-range"], "bytes 10-19/20");
+    assert_eq!(response.headers()["content-type"], "text/plain");
+    assert_eq!(body_string(response).await, "abcdefghij");
+}
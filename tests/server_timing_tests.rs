@@ -0,0 +1,132 @@
+// tests/server_timing_tests.rs
+#![cfg(feature = "server-timing")]
+#![allow(clippy::disallowed_methods)]
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::StatusCode,
+    routing::get,
+    Router,
+};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tower::ServiceExt;
+
+use axum_jetpack::server_timing::{with_server_timing, ServerTiming, ServerTimingConfig};
+
+async fn handler(timing: ServerTiming) -> &'static str {
+    timing.record_with_description("db", Duration::from_millis(5), "user \"lookup\"");
+    timing.mark("cache-miss");
+    "ok"
+}
+
+fn app(config: ServerTimingConfig) -> Router {
+    with_server_timing(Router::new().route("/", get(handler)), config)
+}
+
+fn header(response: &axum::response::Response) -> String {
+    response.headers()["server-timing"].to_str().unwrap().to_string()
+}
+
+#[tokio::test]
+async fn test_emits_handler_and_app_metrics() {
+    let response = app(ServerTimingConfig::new())
+        .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    let value = header(&response);
+    assert!(value.starts_with("db;dur=5.0;desc=\"user \\\"lookup\\\"\", cache-miss, app;dur="));
+}
+
+#[tokio::test]
+async fn test_app_metric_can_be_disabled() {
+    let response = app(ServerTimingConfig::new().without_app_metric())
+        .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert!(!header(&response).contains("app"));
+}
+
+#[tokio::test]
+async fn test_no_header_without_metrics() {
+    let app = with_server_timing(
+        Router::new().route("/", get(|| async { "ok" })),
+        ServerTimingConfig::new().without_app_metric(),
+    );
+
+    let response = app
+        .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert!(!response.headers().contains_key("server-timing"));
+}
+
+#[tokio::test]
+async fn test_queue_time_from_request_start_header() {
+    let received = SystemTime::now() - Duration::from_millis(250);
+    let millis = received.duration_since(UNIX_EPOCH).unwrap().as_millis();
+
+    let response = app(ServerTimingConfig::new().with_queue_header("X-Request-Start"))
+        .oneshot(
+            Request::builder()
+                .uri("/")
+                .header("x-request-start", format!("t={}", millis))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    let value = header(&response);
+    let queue: f64 = value
+        .split(", ")
+        .find_map(|entry| entry.strip_prefix("queue;dur="))
+        .unwrap()
+        .parse()
+        .unwrap();
+    assert!((249.0..5_000.0).contains(&queue));
+}
+
+#[tokio::test]
+async fn test_extractor_without_middleware_is_detached() {
+    let app = Router::new().route("/", get(handler));
+
+    let response = app
+        .oneshot(Request::builder().uri("/").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(!response.headers().contains_key("server-timing"));
+}
+
+#[cfg(feature = "size-limit")]
+#[tokio::test]
+async fn test_size_limiter_records_body_read() {
+    use axum::routing::post;
+    use axum_jetpack::size_limit::{with_size_limit, SizeLimitMiddlewareConfig};
+
+    let router = with_size_limit(
+        Router::new().route("/upload", post(|body: String| async move { body })),
+        SizeLimitMiddlewareConfig::default(),
+    );
+    let app = with_server_timing(router, ServerTimingConfig::new());
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/upload")
+                .header("content-type", "application/json")
+                .body(Body::from("{}"))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    assert!(header(&response).contains("body-read;dur="));
+}
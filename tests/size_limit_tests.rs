@@ -1,9 +1,11 @@
 // tests/size_limit_tests.rs
+#![cfg(feature = "size-limit")]
+#![allow(clippy::disallowed_methods)]
+
 use axum::{
     body::Body,
     extract::Request,
     http::StatusCode,
-    response::Response,
     routing::post,
     Router,
 };
@@ -83,7 +85,7 @@ async fn test_middleware_allows_small_buffered_requests() {
             match req.collect().await {
                 Ok(collected) => {
                     let body = collected.to_bytes();
-                    if body.len() > 0 {
+                    if !body.is_empty() {
                         (StatusCode::OK, format!("got {} bytes", body.len()))
                     } else {
                         (StatusCode::OK, String::from("empty body but ok"))
@@ -359,4 +361,3289 @@ async fn test_invalid_content_length_header() {
     assert_eq!(response.status(), StatusCode::OK);
 
     println!("✓ Invalid Content-Length header falls back to body reading");
-}
\ No newline at end of file
+}
+#[tokio::test]
+async fn test_fast_path_methods() {
+    use axum::routing::any;
+    use axum_jetpack::size_limit::{with_size_limit, SizeLimitMiddlewareConfig};
+
+    let config = SizeLimitMiddlewareConfig::new(
+        SizeLimitConfig::default().with_default_limit(SizeLimit::bytes(4)),
+    );
+    let app = with_size_limit(
+        Router::new().route("/test", any(|req: Request| async move {
+            let body = req.collect().await.unwrap().to_bytes();
+            (StatusCode::OK, format!("Size: {} bytes", body.len()))
+        })),
+        config,
+    );
+
+    // Body-less preflight passes straight through
+    let req = Request::builder()
+        .uri("/test")
+        .method("OPTIONS")
+        .header("origin", "https://app.example.com")
+        .header("access-control-request-method", "POST")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // A fast-path method carrying a body is still limited
+    let req = Request::builder()
+        .uri("/test")
+        .method("OPTIONS")
+        .body(Body::from("way too large"))
+        .unwrap();
+    let response = app.oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    println!("✓ Body-less OPTIONS/HEAD bypass the limiter, bodies are still checked");
+}
+
+#[tokio::test]
+async fn test_bodyless_methods_reject_bodies() {
+    use axum::http::Method;
+    use axum::routing::any;
+    use axum_jetpack::size_limit::{with_size_limit, SizeLimitMiddlewareConfig};
+
+    let config = SizeLimitMiddlewareConfig::default()
+        .with_bodyless_methods(&[Method::GET, Method::DELETE]);
+    let app = with_size_limit(
+        Router::new().route("/test", any(|| async { "ok" })),
+        config,
+    );
+
+    // Body-less GET passes
+    let req = Request::builder().uri("/test").body(Body::empty()).unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // GET with a body is refused
+    let req = Request::builder()
+        .uri("/test")
+        .body(Body::from("unexpected"))
+        .unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // DELETE announcing an empty body passes
+    let req = Request::builder()
+        .uri("/test")
+        .method("DELETE")
+        .header("content-length", "0")
+        .body(Body::empty())
+        .unwrap();
+    let response = app.clone().oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // POST is unaffected
+    let req = Request::builder()
+        .uri("/test")
+        .method("POST")
+        .body(Body::from("fine"))
+        .unwrap();
+    let response = app.oneshot(req).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    println!("✓ Bodies on GET/DELETE are rejected when configured");
+}
+
+#[tokio::test]
+async fn test_rejection_uses_error_format() {
+    use axum_jetpack::error::ErrorFormat;
+    use axum_jetpack::size_limit::{with_size_limit, SizeLimitMiddlewareConfig};
+
+    let config = SizeLimitMiddlewareConfig::new(
+        SizeLimitConfig::default().with_default_limit(SizeLimit::bytes(4)),
+    )
+    .with_error_format(ErrorFormat::Json);
+    let app = with_size_limit(
+        Router::new().route("/test", post(|| async { "ok" })),
+        config,
+    );
+
+    let req = Request::builder()
+        .uri("/test")
+        .method("POST")
+        .body(Body::from("way too large"))
+        .unwrap();
+    let response = app.oneshot(req).await.unwrap();
+
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(response.headers()["content-type"], "application/json");
+    let body = response.collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], br#"{"error":"Payload too large","status":413}"#);
+
+    println!("✓ Rejections are rendered through ErrorFormat");
+}
+
+#[tokio::test]
+async fn test_connection_quota() {
+    use axum::extract::connect_info::MockConnectInfo;
+    use axum_jetpack::size_limit::{with_connection_quota, ConnectionQuota, ConnectionUsage};
+
+    // MockConnectInfo shares one usage value across requests, like a single keep-alive connection
+    let app = with_connection_quota(
+        Router::new().route("/test", post(|body: Bytes| async move { body.len().to_string() })),
+        ConnectionQuota::new(SizeLimit::bytes(10)),
+    )
+    .layer(MockConnectInfo(ConnectionUsage::new(([127, 0, 0, 1], 4000).into())));
+
+    let send = |body: &'static str| {
+        Request::builder()
+            .uri("/test")
+            .method("POST")
+            .body(Body::from(body))
+            .unwrap()
+    };
+
+    let response = app.clone().oneshot(send("123456")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(!response.headers().contains_key("connection"));
+
+    // 6 + 6 bytes exceeds the 10-byte quota while reading
+    let response = app.clone().oneshot(send("123456")).await.unwrap();
+    assert_ne!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["connection"], "close");
+
+    // The connection stays exhausted
+    let response = app.oneshot(send("1")).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(response.headers()["connection"], "close");
+
+    println!("✓ Per-connection quota closes exhausted connections");
+}
+
+#[test]
+fn test_content_type_matcher_agrees_with_config() {
+    use axum_jetpack::size_limit::ContentTypeMatcher;
+
+    let limits = SizeLimitConfig::default()
+        .with_default_limit("2mb")
+        .with_specific_limit("application/json", "100kb")
+        .with_wildcard_limit("image/*", "5mb")
+        .with_wildcard_limit("text/*", "64kb");
+    let strategy = BufferStrategy::with_defaults().with_streamed_types(&["text/csv"]);
+    let matcher = ContentTypeMatcher::compile(&limits, &strategy);
+
+    for content_type in [
+        "application/json",
+        "APPLICATION/JSON; charset=utf-8",
+        "image/png",
+        "text/csv",
+        "text/html",
+        "video/mp4",
+        "multipart/form-data; boundary=x",
+        "no-slash",
+        "",
+    ] {
+        let rule = matcher.lookup(content_type);
+        assert_eq!(rule.limit, limits.get_limit_for_content_type(content_type), "{}", content_type);
+        assert_eq!(rule.buffer, strategy.should_buffer(content_type), "{}", content_type);
+    }
+
+    println!("✓ Compiled matcher agrees with the configuration");
+}
+
+#[tokio::test]
+async fn test_disconnect_hook() {
+    use axum_jetpack::size_limit::{with_size_limit, DisconnectInfo, SizeLimitMiddlewareConfig};
+    use std::sync::{Arc, Mutex};
+
+    let seen: Arc<Mutex<Vec<DisconnectInfo>>> = Arc::default();
+    let recorder = seen.clone();
+    let config = SizeLimitMiddlewareConfig::default().on_disconnect(move |info| {
+        recorder.lock().unwrap().push(info.clone());
+    });
+    let app = with_size_limit(Router::new().route("/upload", post(|_: Bytes| async { "ok" })), config);
+
+    for content_type in ["application/json", "video/mp4"] {
+        // One chunk arrives, then the connection drops
+        let chunks: Vec<Result<Bytes, std::io::Error>> = vec![
+            Ok(Bytes::from_static(b"partial")),
+            Err(std::io::Error::new(std::io::ErrorKind::ConnectionReset, "connection reset")),
+        ];
+        let req = Request::builder()
+            .uri("/upload")
+            .method("POST")
+            .header("content-type", content_type)
+            .header("content-length", "100")
+            .body(Body::from_stream(futures::stream::iter(chunks)))
+            .unwrap();
+        let response = app.clone().oneshot(req).await.unwrap();
+        assert_ne!(response.status(), StatusCode::OK);
+    }
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 2);
+    assert_eq!(seen[0].route, "/upload");
+    assert_eq!(seen[0].bytes_received, 7);
+    assert_eq!(seen[0].expected, Some(100));
+    assert!(seen[1].reason.contains("connection reset"));
+
+    println!("✓ Disconnect hook fires with bytes-received context");
+}
+
+#[tokio::test]
+async fn test_custom_limit_policy() {
+    use axum_jetpack::size_limit::{
+        with_size_limit_policy, Buffering, Decision, LimitPolicy, RequestContext, SizeLimitError,
+        SizeLimitMiddlewareConfig,
+    };
+
+    struct TenantPolicy;
+
+    impl LimitPolicy for TenantPolicy {
+        async fn decide(&self, ctx: RequestContext<'_>) -> Decision {
+            match ctx.parts.headers.get("x-tenant").and_then(|t| t.to_str().ok()) {
+                Some("internal") => Decision::Bypass,
+                Some("premium") => Decision::Accept { limit: 64, buffering: Buffering::Stream },
+                Some(_) => Decision::Accept { limit: 8, buffering: Buffering::Buffer },
+                None => Decision::Reject(SizeLimitError::UnexpectedBody),
+            }
+        }
+    }
+
+    let app = with_size_limit_policy(
+        Router::new().route("/upload", post(|body: Bytes| async move { body.len().to_string() })),
+        SizeLimitMiddlewareConfig::default(),
+        TenantPolicy,
+    );
+    let send = |tenant: Option<&'static str>, size: usize| {
+        let mut req = Request::builder().uri("/upload").method("POST");
+        if let Some(tenant) = tenant {
+            req = req.header("x-tenant", tenant);
+        }
+        app.clone().oneshot(req.body(Body::from(vec![b'x'; size])).unwrap())
+    };
+
+    assert_eq!(send(Some("basic"), 8).await.unwrap().status(), StatusCode::OK);
+    assert_eq!(send(Some("basic"), 16).await.unwrap().status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(send(Some("premium"), 16).await.unwrap().status(), StatusCode::OK);
+    assert_eq!(send(Some("internal"), 4096).await.unwrap().status(), StatusCode::OK);
+    assert_eq!(send(None, 1).await.unwrap().status(), StatusCode::BAD_REQUEST);
+
+    println!("✓ Custom LimitPolicy decides limits, rejections and bypasses");
+}
+
+#[tokio::test]
+async fn test_method_limits() {
+    use axum::http::Method;
+    use axum::routing::{patch, put};
+
+    let limits = SizeLimitConfig::default()
+        .with_default_limit(SizeLimit::bytes(100))
+        .with_method_limit(Method::PATCH, SizeLimit::bytes(10));
+    let app = with_size_limit_simple(
+        Router::new()
+            .route("/doc", patch(|| async { "patched" }))
+            .route("/doc/put", put(|| async { "put" })),
+        limits,
+    );
+    let send = |method: &str, uri: &str| {
+        let req = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::from(vec![b'x'; 50]))
+            .unwrap();
+        app.clone().oneshot(req)
+    };
+
+    assert_eq!(send("PATCH", "/doc").await.unwrap().status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(send("PUT", "/doc/put").await.unwrap().status(), StatusCode::OK);
+
+    println!("✓ Method limits cap content-type limits");
+}
+
+#[tokio::test]
+async fn test_ordered_rules() {
+    use axum::http::{HeaderName, HeaderValue};
+    use axum_jetpack::size_limit::LimitRule;
+
+    let limits = SizeLimitConfig::default()
+        .with_default_limit(SizeLimit::bytes(100))
+        .with_rule(
+            LimitRule::new(SizeLimit::bytes(64))
+                .for_content_type("image/*")
+                .with_header(HeaderName::from_static("x-tier"), Some(HeaderValue::from_static("gold"))),
+        )
+        .with_rule(LimitRule::new(SizeLimit::bytes(32)).for_content_type("image/*").for_path("/admin/*"))
+        .with_rule(LimitRule::new(SizeLimit::bytes(8)).for_content_type("image/*"));
+    let app = with_size_limit_simple(
+        Router::new()
+            .route("/admin/logo", post(|| async { "ok" }))
+            .route("/avatar", post(|| async { "ok" })),
+        limits,
+    );
+    let send = |uri: &str, content_type: &str, tier: Option<&str>, size: usize| {
+        let mut req = Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("content-type", content_type);
+        if let Some(tier) = tier {
+            req = req.header("x-tier", tier);
+        }
+        app.clone().oneshot(req.body(Body::from(vec![b'x'; size])).unwrap())
+    };
+
+    // Images on /admin get more than images elsewhere
+    assert_eq!(send("/admin/logo", "image/png", None, 20).await.unwrap().status(), StatusCode::OK);
+    assert_eq!(send("/avatar", "image/png", None, 20).await.unwrap().status(), StatusCode::PAYLOAD_TOO_LARGE);
+    // The first matching rule wins
+    assert_eq!(send("/avatar", "image/png", Some("gold"), 50).await.unwrap().status(), StatusCode::OK);
+    // Non-matching requests fall back to the default limit
+    assert_eq!(send("/avatar", "application/json", None, 50).await.unwrap().status(), StatusCode::OK);
+
+    println!("✓ Ordered rules match on content type, path and headers");
+}
+
+#[cfg(feature = "content-type-regex")]
+#[tokio::test]
+async fn test_regex_content_type_limits() {
+    use axum_jetpack::size_limit::ContentTypeMatcher;
+
+    let limits = SizeLimitConfig::default()
+        .with_default_limit(SizeLimit::bytes(100))
+        .with_specific_limit("application/vnd.exact+json", SizeLimit::bytes(50))
+        .with_regex_limit(r"^application/vnd\..*\+json$", SizeLimit::bytes(10))
+        .unwrap()
+        .with_wildcard_limit("application/*", SizeLimit::bytes(80));
+
+    // Exact beats regex, regex beats wildcard
+    assert_eq!(limits.get_limit_for_content_type("application/vnd.exact+json"), 50);
+    assert_eq!(limits.get_limit_for_content_type("application/vnd.api+json"), 10);
+    assert_eq!(limits.get_limit_for_content_type("application/xml"), 80);
+
+    let matcher = ContentTypeMatcher::compile(&limits, &BufferStrategy::with_defaults());
+    for content_type in ["application/vnd.exact+json", "APPLICATION/VND.API+JSON", "application/xml", "text/plain"] {
+        assert_eq!(matcher.limit_for(content_type), limits.get_limit_for_content_type(content_type));
+    }
+
+    let app = with_size_limit_simple(Router::new().route("/test", post(|| async { "ok" })), limits);
+    let req = Request::builder()
+        .method("POST")
+        .uri("/test")
+        .header("content-type", "application/vnd.api+json")
+        .body(Body::from(vec![b'x'; 20]))
+        .unwrap();
+    assert_eq!(app.oneshot(req).await.unwrap().status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    println!("✓ Regex content-type limits sit between exact and wildcard matches");
+}
+
+#[tokio::test]
+async fn test_glob_wildcard_limits() {
+    use axum_jetpack::size_limit::ContentTypeMatcher;
+
+    let limits = SizeLimitConfig::default()
+        .with_default_limit(SizeLimit::bytes(100))
+        .with_wildcard_limit("application/*", SizeLimit::bytes(80))
+        .with_wildcard_limit("application/vnd.*", SizeLimit::bytes(10))
+        .with_wildcard_limit("*/zip", SizeLimit::bytes(500));
+
+    // The most specific pattern wins
+    assert_eq!(limits.get_limit_for_content_type("application/vnd.api+json"), 10);
+    assert_eq!(limits.get_limit_for_content_type("application/xml"), 80);
+    assert_eq!(limits.get_limit_for_content_type("multipart/zip"), 500);
+    assert_eq!(limits.get_limit_for_content_type("text/plain"), 100);
+
+    let mut strategy = BufferStrategy::with_defaults();
+    strategy.streamed_types.push("application/vnd.*".to_string());
+    strategy.buffered_types.push("*/zip".to_string());
+    assert!(!strategy.should_buffer("application/vnd.api+json"));
+    assert!(strategy.should_buffer("application/zip"));
+
+    let matcher = ContentTypeMatcher::compile(&limits, &strategy);
+    for content_type in ["Application/VND.api+json", "application/xml", "multipart/zip", "text/plain", "image/png"] {
+        assert_eq!(matcher.limit_for(content_type), limits.get_limit_for_content_type(content_type));
+        assert_eq!(matcher.should_buffer(content_type), strategy.should_buffer(content_type));
+    }
+
+    let app = with_size_limit_simple(Router::new().route("/test", post(|| async { "ok" })), limits);
+    let req = Request::builder()
+        .method("POST")
+        .uri("/test")
+        .header("content-type", "application/vnd.api+json")
+        .body(Body::from(vec![b'x'; 20]))
+        .unwrap();
+    assert_eq!(app.oneshot(req).await.unwrap().status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    println!("✓ Glob wildcard limits prefer the most specific pattern");
+}
+
+#[tokio::test]
+async fn test_host_limits() {
+    use axum_jetpack::size_limit::{HostLimits, SizeLimitMiddlewareConfig, with_size_limit_policy};
+
+    let hosts = HostLimits::new(
+        SizeLimitConfig::default().with_default_limit(SizeLimit::bytes(50)),
+        BufferStrategy::with_defaults(),
+    )
+    .with_host("api.example.com", SizeLimitConfig::default().with_default_limit(SizeLimit::bytes(10)))
+    .with_host("*.cdn.example.com", SizeLimitConfig::default().with_default_limit(SizeLimit::bytes(1000)));
+
+    let app = with_size_limit_policy(
+        Router::new().route("/upload", post(|| async { "ok" })),
+        SizeLimitMiddlewareConfig::default(),
+        hosts,
+    );
+    let send = |host: &str, size: usize| {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .header("host", host)
+            .header("content-type", "application/octet-stream")
+            .body(Body::from(vec![b'x'; size]))
+            .unwrap();
+        app.clone().oneshot(req)
+    };
+
+    // Exact host, case-insensitive and without the port
+    assert_eq!(send("API.example.com:8443", 20).await.unwrap().status(), StatusCode::PAYLOAD_TOO_LARGE);
+    // Host pattern
+    assert_eq!(send("eu.cdn.example.com", 500).await.unwrap().status(), StatusCode::OK);
+    // Unknown hosts use the default limits
+    assert_eq!(send("other.example.com", 20).await.unwrap().status(), StatusCode::OK);
+    assert_eq!(send("other.example.com", 100).await.unwrap().status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    println!("✓ Host limits select a limit set by the Host header");
+}
+
+#[cfg(feature = "client-ip")]
+#[tokio::test]
+async fn test_client_network_rules() {
+    use axum::extract::ConnectInfo;
+    use axum_jetpack::client_ip::{ClientIpResolver, with_client_ip};
+    use axum_jetpack::size_limit::LimitRule;
+    use std::net::SocketAddr;
+
+    let limits = SizeLimitConfig::default()
+        .with_default_limit(SizeLimit::bytes(10))
+        .with_rule(LimitRule::new(SizeLimit::bytes(1000)).for_client_network("10.0.0.0/8").unwrap());
+    assert!(LimitRule::new(SizeLimit::bytes(1)).for_client_network("not-a-network").is_err());
+
+    // The client IP is resolved outside the size limiter, through the trusted proxy
+    let app = with_size_limit_simple(Router::new().route("/upload", post(|| async { "ok" })), limits.clone());
+    let app = with_client_ip(app, ClientIpResolver::new().with_trusted_proxy("127.0.0.1").unwrap());
+    let send = |peer: &str, forwarded_for: Option<&str>| {
+        let mut req = Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .header("content-type", "application/octet-stream");
+        if let Some(forwarded_for) = forwarded_for {
+            req = req.header("x-forwarded-for", forwarded_for);
+        }
+        let mut req = req.body(Body::from(vec![b'x'; 100])).unwrap();
+        req.extensions_mut().insert(ConnectInfo(peer.parse::<SocketAddr>().unwrap()));
+        app.clone().oneshot(req)
+    };
+
+    // Internal clients, directly or behind the proxy, get the larger limit
+    assert_eq!(send("10.1.2.3:5000", None).await.unwrap().status(), StatusCode::OK);
+    assert_eq!(send("127.0.0.1:5000", Some("10.9.9.9")).await.unwrap().status(), StatusCode::OK);
+    // Public clients, and spoofed headers from untrusted peers, do not
+    assert_eq!(send("127.0.0.1:5000", Some("203.0.113.7")).await.unwrap().status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(send("203.0.113.7:5000", Some("10.9.9.9")).await.unwrap().status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    // Without the client IP layer, `MockConnectInfo` stands in for the peer
+    let app = with_size_limit_simple(Router::new().route("/upload", post(|| async { "ok" })), limits)
+        .layer(axum::extract::connect_info::MockConnectInfo(SocketAddr::from(([10, 0, 0, 1], 4000))));
+    let req = Request::builder()
+        .method("POST")
+        .uri("/upload")
+        .header("content-type", "application/octet-stream")
+        .body(Body::from(vec![b'x'; 100]))
+        .unwrap();
+    assert_eq!(app.oneshot(req).await.unwrap().status(), StatusCode::OK);
+
+    println!("✓ Client network rules use the resolved client IP");
+}
+
+#[tokio::test]
+async fn test_allowlist_mode() {
+    use axum_jetpack::error::ErrorFormat;
+    use axum_jetpack::size_limit::{ContentTypeMatcher, SizeLimitMiddlewareConfig, with_size_limit};
+
+    let limits = SizeLimitConfig::default()
+        .with_specific_limit("application/json", SizeLimit::bytes(100))
+        .with_wildcard_limit("image/*", SizeLimit::bytes(100))
+        .with_allowlist_mode(true);
+
+    let matcher = ContentTypeMatcher::compile(&limits, &BufferStrategy::with_defaults());
+    for content_type in ["application/json", "IMAGE/png", "text/plain", "application/octet-stream", "video/mp4"] {
+        assert_eq!(matcher.is_allowed(content_type), limits.is_content_type_listed(content_type));
+    }
+
+    let app = with_size_limit(
+        Router::new().route("/upload", post(|| async { "ok" })),
+        SizeLimitMiddlewareConfig::new(limits).with_error_format(ErrorFormat::Json),
+    );
+    let send = |content_type: &str| {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .header("content-type", content_type)
+            .body(Body::from("{}"))
+            .unwrap();
+        app.clone().oneshot(req)
+    };
+
+    assert_eq!(send("application/json").await.unwrap().status(), StatusCode::OK);
+    assert_eq!(send("image/png").await.unwrap().status(), StatusCode::OK);
+
+    let response = send("text/plain").await.unwrap();
+    assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_eq!(response.headers()["content-type"], "application/json");
+
+    println!("✓ Allowlist mode rejects unlisted content types with 415");
+}
+
+#[tokio::test]
+async fn test_missing_content_type() {
+    let route = || Router::new().route("/upload", post(|| async { "ok" }));
+    let send = |app: Router, content_type: Option<&str>, size: usize| {
+        let mut req = Request::builder().method("POST").uri("/upload");
+        if let Some(content_type) = content_type {
+            req = req.header("content-type", content_type);
+        }
+        app.oneshot(req.body(Body::from(vec![b'x'; size])).unwrap())
+    };
+
+    // Header-less bodies get their own limit, genuine binary uploads keep theirs
+    let limits = SizeLimitConfig::default()
+        .with_specific_limit("application/octet-stream", SizeLimit::bytes(1000))
+        .with_missing_content_type_limit(SizeLimit::bytes(10));
+    let app = with_size_limit_simple(route(), limits);
+    assert_eq!(send(app.clone(), None, 20).await.unwrap().status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(send(app.clone(), None, 5).await.unwrap().status(), StatusCode::OK);
+    assert_eq!(send(app, Some("application/octet-stream"), 500).await.unwrap().status(), StatusCode::OK);
+
+    // Or are rejected outright
+    let app = with_size_limit_simple(route(), SizeLimitConfig::default().reject_missing_content_type());
+    assert_eq!(send(app.clone(), None, 5).await.unwrap().status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+    assert_eq!(send(app, Some("application/octet-stream"), 5).await.unwrap().status(), StatusCode::OK);
+
+    println!("✓ Requests without a Content-Type are handled separately");
+}
+
+#[tokio::test]
+async fn test_unlimited_limits() {
+    let limits = SizeLimitConfig::default()
+        .with_default_limit(SizeLimit::bytes(10))
+        .with_specific_limit("application/x-backup", SizeLimit::unlimited())
+        .with_specific_limit("application/json", "unlimited");
+    assert!(SizeLimit(limits.get_limit_for_content_type("application/json")).is_unlimited());
+
+    let app = with_size_limit_simple(
+        Router::new().route("/upload", post(|body: Bytes| async move { body.len().to_string() })),
+        limits,
+    );
+    let send = |content_type: &str| {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .header("content-type", content_type)
+            .body(Body::from(vec![b'x'; 100_000]))
+            .unwrap();
+        app.clone().oneshot(req)
+    };
+
+    let response = send("application/x-backup").await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"100000");
+    assert_eq!(send("application/json").await.unwrap().status(), StatusCode::OK);
+    assert_eq!(send("text/plain").await.unwrap().status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    println!("✓ Unlimited content types bypass the size limit");
+}
+
+#[tokio::test]
+async fn test_zero_limit_forbids_body() {
+    let app = with_size_limit_simple(
+        Router::new().route("/upload", post(|| async { "ok" })),
+        SizeLimitConfig::default().with_specific_limit("application/xml", 0),
+    );
+    let send = |body: &'static str| {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .header("content-type", "application/xml")
+            .body(Body::from(body))
+            .unwrap();
+        app.clone().oneshot(req)
+    };
+
+    let response = send("<a/>").await.unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(&body[..], b"Request body not allowed for this content type");
+    // Empty bodies are still fine
+    assert_eq!(send("").await.unwrap().status(), StatusCode::OK);
+
+    println!("✓ Zero limits forbid bodies with a dedicated error");
+}
+
+#[test]
+fn test_config_presets() {
+    let strict = SizeLimitConfig::strict();
+    let permissive = SizeLimitConfig::permissive();
+    let media = SizeLimitConfig::media_server();
+
+    for content_type in ["application/json", "multipart/form-data", "text/plain", "image/png", "video/mp4"] {
+        assert!(strict.get_limit_for_content_type(content_type) <= permissive.get_limit_for_content_type(content_type));
+    }
+    // Media servers keep structured payloads small and media large
+    assert!(media.get_limit_for_content_type("application/json") < SizeLimit::mb(1.0).0);
+    assert!(media.get_limit_for_content_type("video/mp4") > permissive.get_limit_for_content_type("video/mp4"));
+
+    println!("✓ Config presets are ordered from strict to media server");
+}
+
+#[test]
+fn test_bundle_limits() {
+    use axum_jetpack::size_limit::{Bundle, ContentTypeMatcher};
+
+    let limits = SizeLimitConfig::default()
+        .with_default_limit(SizeLimit::bytes(1))
+        .with_bundle_limit(Bundle::Images, SizeLimit::bytes(10))
+        .with_bundle_limit(Bundle::Archives, SizeLimit::bytes(20))
+        .with_bundle_limit(Bundle::Office, SizeLimit::bytes(30));
+    let strategy = BufferStrategy::new()
+        .with_buffered_bundle(Bundle::Office)
+        .with_streamed_bundle(Bundle::Archives);
+
+    assert_eq!(limits.get_limit_for_content_type("image/avif"), 10);
+    assert_eq!(limits.get_limit_for_content_type("application/x-7z-compressed"), 20);
+    assert_eq!(limits.get_limit_for_content_type("application/vnd.oasis.opendocument.spreadsheet"), 30);
+    assert_eq!(limits.get_limit_for_content_type("video/mp4"), 1);
+
+    let matcher = ContentTypeMatcher::compile(&limits, &strategy);
+    for content_type in ["image/avif", "application/zip", "application/vnd.ms-excel", "application/vnd.oasis.opendocument.text"] {
+        assert_eq!(matcher.limit_for(content_type), limits.get_limit_for_content_type(content_type));
+        assert_eq!(matcher.should_buffer(content_type), strategy.should_buffer(content_type));
+    }
+    assert!(strategy.should_buffer("application/vnd.ms-powerpoint"));
+    assert!(!strategy.should_buffer("application/gzip"));
+
+    println!("✓ Bundles expand to their content types for limits and buffering");
+}
+
+#[test]
+fn test_fallible_builders() {
+    use axum::http::Method;
+
+    let config = SizeLimitConfig::default()
+        .try_with_default_limit("2mb")
+        .and_then(|config| config.try_with_specific_limit("application/json", "100kb"))
+        .and_then(|config| config.try_with_method_limit(Method::PATCH, "64kb"))
+        .unwrap();
+    assert_eq!(config.default_limit, 2_000_000);
+    assert_eq!(config.get_limit_for_content_type("application/json"), 100_000);
+
+    let error = SizeLimitConfig::default()
+        .try_with_wildcard_limit("image/*", "5 megs")
+        .unwrap_err();
+    assert_eq!(error.input, "5 megs");
+    assert!(error.to_string().contains("5 megs"));
+    assert_eq!("10kb".parse::<SizeLimit>().unwrap().0, 10_000);
+
+    println!("✓ Fallible builders report invalid sizes as errors");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_config() {
+    use axum::http::{HeaderName, HeaderValue, Method, Request as HttpRequest};
+    use axum_jetpack::size_limit::{LimitRule, MissingContentType};
+
+    #[derive(serde::Deserialize)]
+    struct AppConfig {
+        limits: SizeLimitConfig,
+        #[serde(default)]
+        buffering: BufferStrategy,
+    }
+
+    let app: AppConfig = serde_json::from_str(
+        r#"{
+            "limits": {
+                "default_limit": "2mb",
+                "specific_limits": { "Application/JSON": "100kb", "application/x-backup": "unlimited" },
+                "wildcard_limits": { "image/*": 5000000 },
+                "method_limits": { "patch": "64kb" },
+                "rules": [{ "limit": "20mb", "methods": ["PUT"], "headers": { "x-tier": "gold" } }],
+                "missing_content_type": { "limit": "16kb" }
+            },
+            "buffering": { "default_is_buffered": true }
+        }"#,
+    )
+    .unwrap();
+
+    let limits = &app.limits;
+    assert_eq!(limits.default_limit, 2_000_000);
+    assert_eq!(limits.get_limit_for_content_type("application/json"), 100_000);
+    assert!(SizeLimit(limits.get_limit_for_content_type("application/x-backup")).is_unlimited());
+    assert_eq!(limits.method_limits[&Method::PATCH], 64_000);
+    assert_eq!(limits.missing_content_type, MissingContentType::Limit(16_000));
+    assert_eq!(
+        limits.rules,
+        vec![LimitRule::new("20mb")
+            .for_method(Method::PUT)
+            .with_header(HeaderName::from_static("x-tier"), Some(HeaderValue::from_static("gold")))]
+    );
+    // Unset buffering fields keep their defaults
+    assert!(app.buffering.default_is_buffered);
+    assert!(!app.buffering.buffered_types.is_empty());
+
+    // Round trip
+    let json = serde_json::to_string(limits).unwrap();
+    let again: SizeLimitConfig = serde_json::from_str(&json).unwrap();
+    let (parts, _) = HttpRequest::put("/x").header("x-tier", "gold").body(()).unwrap().into_parts();
+    assert_eq!(again.get_limit_for_request(&parts), 20_000_000);
+    assert_eq!(again.specific_limits, limits.specific_limits);
+
+    // Invalid sizes and methods are errors, not panics
+    assert!(serde_json::from_str::<SizeLimitConfig>(r#"{ "default_limit": "2 parsecs" }"#).is_err());
+    assert!(serde_json::from_str::<SizeLimitConfig>(r#"{ "method_limits": { "GET POST": 1 } }"#).is_err());
+    assert!(serde_json::from_str::<SizeLimitConfig>(r#"{ "default_limt": 1 }"#).is_err());
+
+    println!("✓ Size limit configuration deserializes from numbers and human-readable sizes");
+}
+
+#[cfg(feature = "config-file")]
+#[test]
+fn test_config_files() {
+    use axum::http::Method;
+    use axum_jetpack::error::ErrorFormat;
+    use axum_jetpack::size_limit::SizeLimitMiddlewareConfig;
+
+    let dir = std::env::temp_dir().join(format!("jetpack-size-limit-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    let toml_path = dir.join("limits.toml");
+    std::fs::write(
+        &toml_path,
+        r#"
+error_format = "problem_details"
+bodyless_methods = ["get", "DELETE"]
+
+[limits]
+default_limit = "1mb"
+
+[limits.specific_limits]
+"application/json" = "100kb"
+
+[limits.wildcard_limits]
+"image/*" = "10mb"
+
+[buffering]
+buffered_types = ["application/json"]
+streamed_types = ["image/*"]
+"#,
+    )
+    .unwrap();
+    let config = SizeLimitMiddlewareConfig::from_toml_file(&toml_path).unwrap();
+    assert_eq!(config.error_format, ErrorFormat::ProblemDetails);
+    assert_eq!(config.bodyless_methods, vec![Method::GET, Method::DELETE]);
+    assert_eq!(config.size_limits.default_limit, 1_000_000);
+    assert_eq!(config.size_limits.get_limit_for_content_type("application/json"), 100_000);
+    assert_eq!(config.size_limits.get_limit_for_content_type("image/png"), 10_000_000);
+    assert!(config.buffer_strategy.should_buffer("application/json"));
+    assert!(!config.buffer_strategy.should_buffer("image/png"));
+    // Unset keys keep their defaults
+    assert_eq!(config.fast_path_methods, SizeLimitMiddlewareConfig::default().fast_path_methods);
+
+    let yaml_path = dir.join("limits.yaml");
+    std::fs::write(
+        &yaml_path,
+        "default_limit: 2mb\nspecific_limits:\n  application/json: 100kb\nmissing_content_type: reject\n",
+    )
+    .unwrap();
+    let limits = SizeLimitConfig::from_yaml_file(&yaml_path).unwrap();
+    assert_eq!(limits.default_limit, 2_000_000);
+    assert_eq!(limits.get_limit_for_content_type("application/json"), 100_000);
+
+    // Errors point at the offending key
+    let Err(err) = SizeLimitMiddlewareConfig::from_toml_str("[limits.specific_limits]\n\"application/json\" = \"huge\"") else {
+        panic!("expected an error");
+    };
+    assert!(err.contains("limits.specific_limits.application/json"), "{}", err);
+    let Err(err) = SizeLimitMiddlewareConfig::from_yaml_str("error_format: xml") else {
+        panic!("expected an error");
+    };
+    assert!(err.contains("error_format"), "{}", err);
+    let err = SizeLimitConfig::from_yaml_str("default_limt: 1mb").unwrap_err();
+    assert!(err.contains("default_limt"), "{}", err);
+    let Err(err) = SizeLimitMiddlewareConfig::from_toml_str("bodyless_methods = [\"G E T\"]") else {
+        panic!("expected an error");
+    };
+    assert!(err.contains("bodyless_methods"), "{}", err);
+    let err = SizeLimitConfig::from_toml_file(dir.join("missing.toml")).unwrap_err();
+    assert!(err.starts_with("Failed to read"), "{}", err);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    println!("✓ Config files test passed");
+}
+
+#[test]
+fn test_config_from_env() {
+    use axum::http::Method;
+    use axum_jetpack::size_limit::{MissingContentType, SIZE_LIMIT_ENV_PREFIX};
+
+    let config = SizeLimitConfig::from_env_vars(
+        SIZE_LIMIT_ENV_PREFIX,
+        [
+            ("JETPACK_SIZE_LIMIT_DEFAULT", "10mb"),
+            ("JETPACK_SIZE_LIMIT_TYPE_application__json", "100kb"),
+            ("JETPACK_SIZE_LIMIT_TYPE_application__vnd.api+json", "50kb"),
+            ("JETPACK_SIZE_LIMIT_TYPE_video__*", "unlimited"),
+            ("JETPACK_SIZE_LIMIT_METHOD_patch", "64kb"),
+            ("JETPACK_SIZE_LIMIT_ALLOWLIST", "true"),
+            ("JETPACK_SIZE_LIMIT_MISSING_CONTENT_TYPE", "reject"),
+            ("HOME", "/root"),
+        ],
+    )
+    .unwrap();
+    assert_eq!(config.default_limit, 10_000_000);
+    assert_eq!(config.get_limit_for_content_type("application/json"), 100_000);
+    assert_eq!(config.get_limit_for_content_type("application/vnd.api+json"), 50_000);
+    assert!(SizeLimit(config.get_limit_for_content_type("video/mp4")).is_unlimited());
+    assert_eq!(config.method_limits[&Method::PATCH], 64_000);
+    assert!(config.allowlist_mode);
+    assert_eq!(config.missing_content_type, MissingContentType::Reject);
+
+    // Custom prefixes
+    let config = SizeLimitConfig::from_env_vars("UPLOADS_", [("UPLOADS_DEFAULT", "1mb")]).unwrap();
+    assert_eq!(config.default_limit, 1_000_000);
+
+    // Errors name the variable
+    let err = SizeLimitConfig::from_env_vars(SIZE_LIMIT_ENV_PREFIX, [("JETPACK_SIZE_LIMIT_DEFAULT", "lots")])
+        .unwrap_err();
+    assert!(err.starts_with("JETPACK_SIZE_LIMIT_DEFAULT:"), "{}", err);
+    let err = SizeLimitConfig::from_env_vars(SIZE_LIMIT_ENV_PREFIX, [("JETPACK_SIZE_LIMIT_TYPE_json", "1kb")])
+        .unwrap_err();
+    assert!(err.contains("__"), "{}", err);
+    assert!(SizeLimitConfig::from_env_vars(SIZE_LIMIT_ENV_PREFIX, [("JETPACK_SIZE_LIMIT_DEFALT", "1mb")]).is_err());
+
+    // Nothing set: defaults
+    let config = SizeLimitConfig::from_env_with_prefix("JETPACK_TEST_UNSET_PREFIX_").unwrap();
+    assert_eq!(config.default_limit, SizeLimitConfig::default().default_limit);
+
+    println!("✓ Config from env test passed");
+}
+
+#[cfg(feature = "hot-reload")]
+#[tokio::test]
+async fn test_hot_reload() {
+    use axum_jetpack::size_limit::{with_size_limit_watching, ConfigWatch};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    let dir = std::env::temp_dir().join(format!("jetpack-hot-reload-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let path = dir.join("limits.toml");
+    std::fs::write(&path, "[limits]\ndefault_limit = 100\n").unwrap();
+
+    let results = Arc::new(Mutex::new(Vec::new()));
+    let seen = results.clone();
+    let watch = ConfigWatch::new(&path)
+        .with_interval(Duration::from_millis(20))
+        .on_reload(move |result| seen.lock().unwrap().push(result.clone()));
+
+    let app = Router::new().route("/upload", post(|body: Bytes| async move { body.len().to_string() }));
+    let app = with_size_limit_watching(app, watch).unwrap();
+
+    let send = |app: Router, size: usize| async move {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .header("content-type", "application/x-test")
+            .body(Body::from(vec![0u8; size]))
+            .unwrap();
+        app.oneshot(req).await.unwrap().status()
+    };
+    assert_eq!(send(app.clone(), 80).await, StatusCode::OK);
+
+    // Lowering the limit takes effect without rebuilding the router
+    std::fs::write(&path, "[limits]\ndefault_limit = 50\n").unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(send(app.clone(), 80).await, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(results.lock().unwrap().as_slice(), &[Ok(())]);
+
+    // A broken edit is reported and the last good limits stay
+    std::fs::write(&path, "[limits]\ndefault_limit = \"fifty\"\n").unwrap();
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert_eq!(send(app.clone(), 40).await, StatusCode::OK);
+    assert_eq!(send(app.clone(), 80).await, StatusCode::PAYLOAD_TOO_LARGE);
+    {
+        let results = results.lock().unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results[1].as_ref().unwrap_err().contains("limits.default_limit"));
+    }
+
+    // Invalid files are rejected up front
+    std::fs::write(&path, "[limits]\ndefault_limt = 1\n").unwrap();
+    assert!(with_size_limit_watching(Router::new(), ConfigWatch::new(&path)).is_err());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+
+    println!("✓ Hot reload test passed");
+}
+
+#[cfg(feature = "runtime-limits")]
+#[tokio::test]
+async fn test_size_limit_handle() {
+    use axum_jetpack::size_limit::{with_size_limit_handle, SizeLimitMiddlewareConfig};
+
+    let app = Router::new().route("/upload", post(|body: Bytes| async move { body.len().to_string() }));
+    let config = SizeLimitMiddlewareConfig::new(SizeLimitConfig::default().with_default_limit(100));
+    let (app, limits) = with_size_limit_handle(app, config);
+
+    let send = |app: Router, content_type: &'static str, size: usize| async move {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .header("content-type", content_type)
+            .body(Body::from(vec![0u8; size]))
+            .unwrap();
+        app.oneshot(req).await.unwrap().status()
+    };
+    assert_eq!(send(app.clone(), "application/x-test", 80).await, StatusCode::OK);
+
+    // Changes apply to the next request, through any clone of the handle
+    limits.clone().set_default_limit(50);
+    assert_eq!(send(app.clone(), "application/x-test", 80).await, StatusCode::PAYLOAD_TOO_LARGE);
+
+    limits.set_specific_limit("application/x-test", 200);
+    limits.set_specific_limit("image/*", 10);
+    assert_eq!(send(app.clone(), "application/x-test", 150).await, StatusCode::OK);
+    assert_eq!(send(app.clone(), "image/png", 20).await, StatusCode::PAYLOAD_TOO_LARGE);
+
+    limits.remove_specific_limit("application/x-test");
+    assert_eq!(send(app.clone(), "application/x-test", 80).await, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(limits.config().get_limit_for_content_type("image/gif"), 10);
+
+    limits.set_config(SizeLimitConfig::default().with_default_limit("1kb"));
+    assert_eq!(send(app.clone(), "image/png", 500).await, StatusCode::OK);
+
+    println!("✓ Size limit handle test passed");
+}
+
+#[cfg(feature = "size-limit-admin")]
+#[tokio::test]
+async fn test_admin_router() {
+    use axum_jetpack::size_limit::{admin_router, with_size_limit_handle, SizeLimitMiddlewareConfig};
+
+    let api = Router::new().route("/upload", post(|body: Bytes| async move { body.len().to_string() }));
+    let config = SizeLimitMiddlewareConfig::new(SizeLimitConfig::default().with_default_limit(10));
+    let (api, limits) = with_size_limit_handle(api, config);
+    let app = api.nest(
+        "/admin",
+        admin_router(limits.clone(), |parts| {
+            parts.headers.get("authorization").is_some_and(|value| value == "Bearer s3cret")
+        }),
+    );
+
+    for content_type in ["image/png", "image/PNG; x=1", "application/x-test"] {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .header("content-type", content_type)
+            .body(Body::from(vec![0u8; 20]))
+            .unwrap();
+        assert_eq!(app.clone().oneshot(req).await.unwrap().status(), StatusCode::PAYLOAD_TOO_LARGE);
+    }
+    // Handler responses are not counted
+    let req = Request::builder()
+        .method("POST")
+        .uri("/upload")
+        .header("content-type", "image/png")
+        .body(Body::from(vec![0u8; 5]))
+        .unwrap();
+    assert_eq!(app.clone().oneshot(req).await.unwrap().status(), StatusCode::OK);
+
+    let get = |path: &'static str, token: Option<&'static str>| {
+        let app = app.clone();
+        async move {
+            let mut req = Request::builder().uri(path);
+            if let Some(token) = token {
+                req = req.header("authorization", token);
+            }
+            let response = app.oneshot(req.body(Body::empty()).unwrap()).await.unwrap();
+            let status = response.status();
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            (status, serde_json::from_slice::<serde_json::Value>(&body).unwrap_or_default())
+        }
+    };
+
+    let (status, stats) = get("/admin/limits/stats", Some("Bearer s3cret")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(stats["total"], 3);
+    assert_eq!(stats["by_content_type"]["image/png"], 2);
+    assert_eq!(stats["by_content_type"]["application/x-test"], 1);
+
+    limits.set_specific_limit("application/json", "100kb");
+    let (status, config) = get("/admin/limits", Some("Bearer s3cret")).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(config["limits"]["default_limit"], 10);
+    assert_eq!(config["limits"]["specific_limits"]["application/json"], 100_000);
+    assert!(config["buffering"]["buffered_types"].is_array());
+
+    // Authentication is required
+    assert_eq!(get("/admin/limits", None).await.0, StatusCode::UNAUTHORIZED);
+    assert_eq!(get("/admin/limits/stats", Some("Bearer wrong")).await.0, StatusCode::UNAUTHORIZED);
+
+    limits.reset_stats();
+    assert_eq!(limits.stats().total, 0);
+
+    println!("✓ Admin router test passed");
+}
+
+#[test]
+fn test_config_introspection() {
+    use axum_jetpack::size_limit::{EffectiveRule, LimitSource};
+
+    let config = SizeLimitConfig::default()
+        .with_default_limit("2mb")
+        .with_specific_limit("application/json", "100kb")
+        .with_specific_limit("application/csv", "5mb")
+        .with_wildcard_limit("image/*", "10mb")
+        .with_wildcard_limit("image/svg*", "100kb");
+
+    let rules: Vec<EffectiveRule> = config.rules().collect();
+    let patterns: Vec<&str> = rules.iter().map(|rule| rule.pattern.as_str()).collect();
+    assert_eq!(patterns, ["application/csv", "application/json", "image/svg*", "image/*", "*/*"]);
+    assert_eq!(rules[4], EffectiveRule { pattern: "*/*".to_string(), limit: 2_000_000, source: LimitSource::Default });
+
+    // explain agrees with the lookup for every kind of match
+    for content_type in ["application/json", "image/png", "image/svg+xml", "video/mp4", "Application/CSV; q=1"] {
+        let explanation = config.explain(content_type);
+        assert_eq!(explanation.limit, config.get_limit_for_content_type(content_type), "{}", content_type);
+    }
+
+    let explanation = config.explain("image/svg+xml");
+    assert_eq!(explanation.source, LimitSource::Wildcard);
+    assert_eq!(explanation.pattern.as_deref(), Some("image/svg*"));
+
+    let explanation = config.explain("video/mp4");
+    assert_eq!(explanation.source, LimitSource::Default);
+    assert_eq!(explanation.pattern, None);
+    assert!(!explanation.rejected);
+
+    // Allowlist mode rejects what only the default would cover
+    let explanation = config.clone().with_allowlist_mode(true).explain("video/mp4");
+    assert!(explanation.rejected);
+    assert!(explanation.to_string().contains("415"));
+
+    println!("✓ Config introspection test passed");
+}
+
+#[test]
+fn test_size_limits_macro() {
+    use axum_jetpack::size_limits;
+
+    let config = size_limits! {
+        default: "2mb",
+        "application/json": "256kb",
+        "Application/XML": "64 KiB",
+        "image/*": "1,5 MB",
+        "video/*": "unlimited",
+    };
+    assert_eq!(config.default_limit, 2_000_000);
+    assert_eq!(config.get_limit_for_content_type("application/json"), 256_000);
+    assert_eq!(config.get_limit_for_content_type("application/xml"), 65_536);
+    assert_eq!(config.get_limit_for_content_type("image/png"), 1_500_000);
+    assert!(SizeLimit(config.get_limit_for_content_type("video/mp4")).is_unlimited());
+
+    // Empty tables give the defaults
+    let config = size_limits! {};
+    assert_eq!(config.default_limit, SizeLimitConfig::default().default_limit);
+
+    // Compile-time parsing agrees with runtime parsing
+    for size in ["0", "1024", "1.5kb", "2,5 GB", "100 MiB", "8kbit", "1 TB", "3pib", "0.001 mb", " 7 B "] {
+        assert_eq!(SizeLimit::parse_const(size).0, SizeLimit::parse(size).unwrap().0, "{}", size);
+    }
+
+    println!("✓ size_limits! macro test passed");
+}
+
+#[tokio::test]
+async fn test_router_ext() {
+    use axum_jetpack::size_limit::{RouterExt, SizeLimitMiddlewareConfig};
+
+    let echo = |body: Bytes| async move { body.len().to_string() };
+    let app: Router = Router::new()
+        .route("/avatar", post(echo).size_limit(50))
+        .route("/comments", post(echo))
+        .size_limit_config(SizeLimitMiddlewareConfig::new(
+            SizeLimitConfig::default().with_default_limit(100).with_specific_limit("application/json", 20),
+        ));
+
+    let send = |uri: &'static str, content_type: &'static str, size: usize| {
+        let app = app.clone();
+        async move {
+            let req = Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("content-type", content_type)
+                .body(Body::from(vec![0u8; size]))
+                .unwrap();
+            app.oneshot(req).await.unwrap().status()
+        }
+    };
+
+    // Router-wide limits
+    assert_eq!(send("/comments", "text/plain", 80).await, StatusCode::OK);
+    assert_eq!(send("/comments", "text/plain", 120).await, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(send("/comments", "application/json", 30).await, StatusCode::PAYLOAD_TOO_LARGE);
+
+    // The route's own limit tightens the router-wide one
+    assert_eq!(send("/avatar", "image/png", 40).await, StatusCode::OK);
+    assert_eq!(send("/avatar", "image/png", 80).await, StatusCode::PAYLOAD_TOO_LARGE);
+
+    // Works on routers with state too
+    let _stateful: Router<u32> = Router::new().route("/x", post(echo)).size_limit("1mb");
+
+    println!("✓ RouterExt test passed");
+}
+
+#[tokio::test]
+async fn test_size_limit_override() {
+    use axum_jetpack::size_limit::{RouterExt, SizeLimitOverride};
+
+    let echo = |body: Bytes| async move { body.len().to_string() };
+    let limited: Router = Router::new()
+        .route("/comments", post(echo))
+        .route("/avatar", post(echo).layer(SizeLimitOverride::new(50)))
+        .size_limit(100);
+    let relaxed = limited.clone().layer(SizeLimitOverride::new(500));
+
+    let send = |app: Router, uri: &'static str, size: usize| async move {
+        let req = Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("content-type", "application/octet-stream")
+            .body(Body::from(vec![0u8; size]))
+            .unwrap();
+        app.oneshot(req).await.unwrap().status()
+    };
+
+    // The global limit, tightened on /avatar from inside the limiter
+    assert_eq!(send(limited.clone(), "/comments", 80).await, StatusCode::OK);
+    assert_eq!(send(limited.clone(), "/avatar", 40).await, StatusCode::OK);
+    assert_eq!(send(limited.clone(), "/avatar", 80).await, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(send(limited, "/comments", 200).await, StatusCode::PAYLOAD_TOO_LARGE);
+
+    // An override running before the limiter relaxes it
+    assert_eq!(send(relaxed.clone(), "/comments", 200).await, StatusCode::OK);
+    assert_eq!(send(relaxed.clone(), "/comments", 600).await, StatusCode::PAYLOAD_TOO_LARGE);
+    // The route's own override still applies on top
+    assert_eq!(send(relaxed, "/avatar", 80).await, StatusCode::PAYLOAD_TOO_LARGE);
+
+    println!("✓ SizeLimitOverride test passed");
+}
+
+#[tokio::test]
+async fn test_policy_resolver() {
+    use axum_jetpack::error::ErrorFormat;
+    use axum_jetpack::size_limit::{
+        BufferStrategy, PolicyResolver, RouteLimits, RoutePolicy, SizeLimitMiddlewareConfig, with_size_limit_resolver,
+    };
+
+    let echo = |body: Bytes| async move { body.len().to_string() };
+    let routes = RouteLimits::new()
+        .with_route(
+            "/videos/*",
+            RoutePolicy::new(SizeLimitConfig::default().with_default_limit(1000), BufferStrategy::all_streamed()),
+        )
+        .with_route(
+            "/api/users/{id}",
+            RoutePolicy::new(SizeLimitConfig::default().with_default_limit(10), BufferStrategy::all_buffered())
+                .with_error_format(ErrorFormat::Json),
+        );
+    assert!(routes.resolve(Some("/videos/upload")).is_some());
+    assert!(routes.resolve(Some("/other")).is_none());
+    assert!(routes.resolve(None).is_none());
+
+    let app = with_size_limit_resolver(
+        Router::new()
+            .route("/videos/upload", post(echo))
+            .route("/api/users/{id}", post(echo))
+            .route("/other", post(echo)),
+        SizeLimitMiddlewareConfig::new(SizeLimitConfig::default().with_default_limit(100)),
+        routes,
+    );
+
+    let send = |uri: &'static str, size: usize| {
+        let app = app.clone();
+        async move {
+            let req = Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("content-type", "application/octet-stream")
+                .body(Body::from(vec![0u8; size]))
+                .unwrap();
+            app.oneshot(req).await.unwrap()
+        }
+    };
+
+    assert_eq!(send("/videos/upload", 500).await.status(), StatusCode::OK);
+    assert_eq!(send("/other", 500).await.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(send("/other", 50).await.status(), StatusCode::OK);
+
+    // Per-route error format
+    let response = send("/api/users/7", 50).await;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(response.headers()["content-type"], "application/json");
+    let response = send("/other", 500).await;
+    assert_ne!(response.headers()["content-type"], "application/json");
+
+    println!("✓ PolicyResolver test passed");
+}
+
+#[tokio::test]
+async fn test_jetpack_policy() {
+    use axum::http::Method;
+    use axum_jetpack::error::ErrorFormat;
+    use axum_jetpack::size_limit::{JetpackPolicy, PolicyRule, PolicyTable, with_jetpack_policy};
+    use std::time::Duration;
+
+    let echo = |body: Bytes| async move { body.len().to_string() };
+    let slow = || async {
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        "done"
+    };
+    let table = PolicyTable::new(JetpackPolicy::buffered(100))
+        .with_rule(PolicyRule::new(JetpackPolicy::streamed(1000)).for_path("/videos/*"))
+        .with_rule(
+            PolicyRule::new(JetpackPolicy::buffered(10).with_error_format(ErrorFormat::Json))
+                .for_method(Method::PATCH),
+        )
+        .with_rule(PolicyRule::new(JetpackPolicy::default().with_timeout(Duration::from_millis(20))).for_path("/slow"));
+
+    let app = with_jetpack_policy(
+        Router::new()
+            .route("/videos/upload", post(echo))
+            .route("/comments", post(echo).patch(echo))
+            .route("/slow", post(slow)),
+        table,
+    );
+
+    let send = |method: Method, uri: &'static str, size: usize| {
+        let app = app.clone();
+        async move {
+            let req = Request::builder()
+                .method(method)
+                .uri(uri)
+                .header("content-type", "application/octet-stream")
+                .body(Body::from(vec![0u8; size]))
+                .unwrap();
+            app.oneshot(req).await.unwrap()
+        }
+    };
+
+    assert_eq!(send(Method::POST, "/videos/upload", 500).await.status(), StatusCode::OK);
+    assert_eq!(send(Method::POST, "/comments", 50).await.status(), StatusCode::OK);
+    assert_eq!(send(Method::POST, "/comments", 500).await.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    let response = send(Method::PATCH, "/comments", 50).await;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(response.headers()["content-type"], "application/json");
+
+    assert_eq!(send(Method::POST, "/slow", 0).await.status(), StatusCode::REQUEST_TIMEOUT);
+
+    println!("✓ JetpackPolicy test passed");
+}
+
+#[cfg(feature = "mime")]
+#[test]
+fn test_mime_configuration() {
+    use axum_jetpack::size_limit::BufferStrategy;
+
+    let png: mime::Mime = "image/png; q=1".parse().unwrap();
+    let config = SizeLimitConfig::default()
+        .with_specific_limit(mime::APPLICATION_JSON, "100kb")
+        .with_specific_limit(&png, "2mb")
+        .with_wildcard_limit(mime::IMAGE, "5mb")
+        .with_wildcard_limit(mime::VIDEO, "1gb");
+
+    assert_eq!(config.get_limit_for_content_type("application/json"), 100_000);
+    assert_eq!(config.get_limit_for_content_type("image/png"), 2_000_000);
+    assert_eq!(config.get_limit_for_content_type("image/gif"), 5_000_000);
+    assert_eq!(config.get_limit_for_content_type("video/mp4"), 1_000_000_000);
+
+    let strategy = BufferStrategy::new()
+        .with_buffered_types(&[mime::APPLICATION_JSON, mime::TEXT_PLAIN])
+        .with_streamed_types(&[mime::IMAGE, mime::VIDEO])
+        .with_default_buffered(false);
+    assert!(strategy.should_buffer("application/json"));
+    assert!(strategy.should_buffer("text/plain"));
+    assert!(!strategy.should_buffer("image/png"));
+
+    // Strings still work alongside
+    let strategy = strategy.with_buffered_types(&["application/xml"]);
+    assert!(strategy.should_buffer("application/xml"));
+
+    println!("✓ mime configuration test passed");
+}
+
+#[test]
+fn test_case_insensitive_content_type_matching() {
+    use axum_jetpack::size_limit::ContentTypeMatcher;
+
+    let limits = SizeLimitConfig::default()
+        .with_specific_limit("Application/JSON", "100kb")
+        .with_wildcard_limit("application/VND.*", "5mb");
+    let strategy = BufferStrategy::new()
+        .with_buffered_types(&["Text/CSV", "application/vnd.*"])
+        .with_streamed_types(&["Image/*"])
+        .with_default_buffered(false);
+    let matcher = ContentTypeMatcher::compile(&limits, &strategy);
+
+    for content_type in ["application/json", "APPLICATION/Json; charset=utf-8", " application/json "] {
+        assert_eq!(limits.get_limit_for_content_type(content_type), 100_000, "{}", content_type);
+        assert_eq!(matcher.lookup(content_type).limit, 100_000, "{}", content_type);
+    }
+    assert_eq!(limits.get_limit_for_content_type("Application/Vnd.Api+JSON"), 5_000_000);
+    assert!(limits.is_content_type_listed("APPLICATION/VND.X"));
+
+    for content_type in ["text/csv", "TEXT/CSV", "Application/VND.api+json"] {
+        assert!(strategy.should_buffer(content_type), "{}", content_type);
+        assert!(matcher.lookup(content_type).buffer, "{}", content_type);
+    }
+    for content_type in ["image/png", "IMAGE/PNG"] {
+        assert!(!strategy.should_buffer(content_type), "{}", content_type);
+        assert!(!matcher.lookup(content_type).buffer, "{}", content_type);
+    }
+
+    println!("✓ Case-insensitive content type matching test passed");
+}
+
+#[tokio::test]
+async fn test_content_type_lookup_cache() {
+    use axum_jetpack::size_limit::{ContentTypeMatcher, SizeLimitMiddlewareConfig, with_size_limit};
+
+    let limits = SizeLimitConfig::default()
+        .with_default_limit("2mb")
+        .with_specific_limit("application/json", "100kb")
+        .with_wildcard_limit("application/vnd.*", "5mb")
+        .with_wildcard_limit("image/*", "10mb");
+    let strategy = BufferStrategy::with_defaults();
+    let cached = ContentTypeMatcher::compile(&limits, &strategy).with_cache(2);
+    let uncached = ContentTypeMatcher::compile(&limits, &strategy);
+
+    // Repeated and evicted values resolve exactly like the uncached matcher
+    let long = format!("application/vnd.{}", "x".repeat(200));
+    for content_type in [
+        "application/json",
+        "application/vnd.api+json",
+        "application/json",
+        "image/png",
+        "Application/VND.api+json; charset=utf-8",
+        "video/mp4",
+        long.as_str(),
+        "application/json",
+        "image/png",
+    ] {
+        assert_eq!(cached.lookup(content_type), uncached.lookup(content_type), "{}", content_type);
+        assert_eq!(cached.is_allowed(content_type), uncached.is_allowed(content_type), "{}", content_type);
+    }
+
+    // Through the middleware configuration
+    let app = with_size_limit(
+        Router::new().route("/upload", post(|body: Bytes| async move { body.len().to_string() })),
+        SizeLimitMiddlewareConfig::new(SizeLimitConfig::default().with_default_limit(100)).with_lookup_cache(4),
+    );
+    for (size, status) in [(50, StatusCode::OK), (150, StatusCode::PAYLOAD_TOO_LARGE), (50, StatusCode::OK)] {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .header("content-type", "text/plain")
+            .body(Body::from(vec![0u8; size]))
+            .unwrap();
+        assert_eq!(app.clone().oneshot(req).await.unwrap().status(), status);
+    }
+
+    println!("✓ Content type lookup cache test passed");
+}
+
+#[tokio::test]
+async fn test_size_limit_layer() {
+    use axum_jetpack::size_limit::{SizeLimitLayer, SizeLimitMiddlewareConfig};
+    use tower::{Layer, ServiceBuilder};
+
+    let strategy = BufferStrategy::new()
+        .with_buffered_types(&["application/json"])
+        .with_streamed_types(&["video/*"]);
+    let config = SizeLimitMiddlewareConfig::new(SizeLimitConfig::default().with_default_limit(100))
+        .with_buffer_strategy(strategy);
+    let handler = post(|body: Bytes| async move { body.len().to_string() });
+
+    // On a router, in a ServiceBuilder stack
+    let router = Router::new()
+        .route("/upload", handler.clone())
+        .layer(ServiceBuilder::new().layer(SizeLimitLayer::new(config.clone())));
+    // Wrapping the whole router
+    let wrapped = SizeLimitLayer::new(config).layer(Router::new().route("/upload", handler));
+
+    // Buffered and streamed content types are both limited
+    for content_type in ["application/json", "video/mp4"] {
+        for (size, status) in [(100, StatusCode::OK), (101, StatusCode::PAYLOAD_TOO_LARGE)] {
+            let req = || {
+                Request::builder()
+                    .method("POST")
+                    .uri("/upload")
+                    .header("content-type", content_type)
+                    .body(Body::from(vec![0u8; size]))
+                    .unwrap()
+            };
+            let response = router.clone().oneshot(req()).await.unwrap();
+            assert_eq!(response.status(), status, "{} {}", content_type, size);
+            let response = wrapped.clone().oneshot(req()).await.unwrap();
+            assert_eq!(response.status(), status, "{} {}", content_type, size);
+            if status == StatusCode::OK {
+                let body = response.into_body().collect().await.unwrap().to_bytes();
+                assert_eq!(body, size.to_string());
+            }
+        }
+    }
+
+    println!("✓ Size limit layer test passed");
+}
+
+#[tokio::test]
+async fn test_incremental_streaming() {
+    use axum_jetpack::size_limit::{with_size_limit, SizeLimitMiddlewareConfig};
+    use std::time::Duration;
+    use tokio::sync::{mpsc, oneshot};
+    use tokio_stream::wrappers::ReceiverStream;
+
+    let (started_tx, started_rx) = oneshot::channel::<()>();
+    let started_tx = std::sync::Arc::new(std::sync::Mutex::new(Some(started_tx)));
+    let handler = post(move |body: Body| {
+        if let Some(started) = started_tx.lock().unwrap().take() {
+            let _ = started.send(());
+        }
+        async move {
+            match body.collect().await {
+                Ok(collected) => (StatusCode::OK, collected.to_bytes().len().to_string()),
+                Err(_) => (StatusCode::BAD_REQUEST, "truncated".to_string()),
+            }
+        }
+    });
+    let app = with_size_limit(
+        Router::new().route("/upload", handler),
+        SizeLimitMiddlewareConfig::new(SizeLimitConfig::default().with_default_limit(1000))
+            .with_buffer_strategy(BufferStrategy::all_streamed()),
+    );
+    let request = |chunks: mpsc::Receiver<Result<Bytes, std::io::Error>>| {
+        Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .header("content-type", "video/mp4")
+            .body(Body::from_stream(ReceiverStream::new(chunks)))
+            .unwrap()
+    };
+
+    // The handler starts before the body has fully arrived, and more chunks
+    // than the channel holds pass through
+    let (tx, rx) = mpsc::channel(1);
+    let response = tokio::spawn(app.clone().oneshot(request(rx)));
+    tx.send(Ok(Bytes::from(vec![0u8; 10]))).await.unwrap();
+    tokio::time::timeout(Duration::from_secs(5), started_rx).await.unwrap().unwrap();
+    for _ in 0..99 {
+        tx.send(Ok(Bytes::from(vec![0u8; 10]))).await.unwrap();
+    }
+    drop(tx);
+    let response = tokio::time::timeout(Duration::from_secs(5), response).await.unwrap().unwrap().unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.into_body().collect().await.unwrap().to_bytes(), "1000");
+
+    // Tripping the limit mid-stream gives 413 instead of the handler's response
+    let (tx, rx) = mpsc::channel(1);
+    let response = tokio::spawn(app.clone().oneshot(request(rx)));
+    for _ in 0..101 {
+        if tx.send(Ok(Bytes::from(vec![0u8; 10]))).await.is_err() {
+            break;
+        }
+    }
+    drop(tx);
+    let response = tokio::time::timeout(Duration::from_secs(5), response).await.unwrap().unwrap().unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    println!("✓ Incremental streaming test passed");
+}
+
+#[tokio::test]
+async fn test_streamed_body_dropped_by_handler() {
+    use axum_jetpack::size_limit::middleware::RejectedBody;
+    use axum_jetpack::size_limit::{with_size_limit, SizeLimitMiddlewareConfig};
+
+    // The handler answers without reading the body; its response always stands
+    let app = |rejected_body: RejectedBody| {
+        with_size_limit(
+            Router::new().route("/upload", post(|| async { "ok" })),
+            SizeLimitMiddlewareConfig::new(SizeLimitConfig::default().with_default_limit(1000))
+                .with_buffer_strategy(BufferStrategy::all_streamed())
+                .with_rejected_body(rejected_body),
+        )
+    };
+    let send = |app: Router, chunks: usize| {
+        let body = futures::stream::iter((0..chunks).map(|_| Ok::<_, std::io::Error>(Bytes::from(vec![0u8; 10]))));
+        let req = Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .header("content-type", "video/mp4")
+            .body(Body::from_stream(body))
+            .unwrap();
+        app.oneshot(req)
+    };
+    let cases = [
+        // Aborting leaves even a large upload unread and closes the connection
+        (RejectedBody::Abort, 100_000, true),
+        // Draining keeps the connection while the rest fits the limit
+        (RejectedBody::Drain, 100, false),
+        (RejectedBody::Drain, 101, true),
+        (RejectedBody::drain_up_to(500), 50, false),
+        (RejectedBody::drain_up_to(500), 51, true),
+    ];
+    for (rejected_body, chunks, closed) in cases {
+        let response = send(app(rejected_body), chunks).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{:?}, {} chunks", rejected_body, chunks);
+        assert_eq!(
+            response.headers().get("connection").is_some_and(|value| value == "close"),
+            closed,
+            "{:?}, {} chunks",
+            rejected_body,
+            chunks
+        );
+        assert_eq!(response.into_body().collect().await.unwrap().to_bytes(), "ok");
+    }
+
+    println!("✓ Streamed body dropped by handler test passed");
+}
+
+#[tokio::test]
+async fn test_typed_body_rejections() {
+    use axum_jetpack::size_limit::{with_size_limit, SizeLimitError, SizeLimitMiddlewareConfig};
+    use std::error::Error as _;
+    use std::sync::{Arc, Mutex};
+
+    // Records what streaming handlers see when reading the body
+    let seen: Arc<Mutex<Vec<String>>> = Arc::default();
+    let recorder = seen.clone();
+    let handler = post(move |body: Body| async move {
+        if let Err(e) = body.collect().await {
+            let error = std::iter::successors(e.source(), |&e| e.source())
+                .find_map(|e| e.downcast_ref::<SizeLimitError>())
+                .copied();
+            recorder.lock().unwrap().push(format!("{:?}", error));
+        }
+    });
+    let app = with_size_limit(
+        Router::new().route("/upload", handler),
+        SizeLimitMiddlewareConfig::with_default_buffer_strategy(SizeLimitConfig::default().with_default_limit(100)),
+    );
+    let send = |content_type: &'static str, chunks: Vec<Result<Bytes, std::io::Error>>| {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .header("content-type", content_type)
+            .body(Body::from_stream(futures::stream::iter(chunks)))
+            .unwrap();
+        app.clone().oneshot(req)
+    };
+    let reset = || std::io::Error::new(std::io::ErrorKind::ConnectionReset, "connection reset");
+
+    for content_type in ["application/json", "video/mp4"] {
+        // A failed transfer is not mistaken for an oversized body
+        let response = send(content_type, vec![Ok(Bytes::from_static(b"partial")), Err(reset())]).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", content_type);
+        assert_eq!(response.extensions().get(), Some(&SizeLimitError::BodyReadFailed));
+
+        // An error after the limit was crossed is never reached
+        let response = send(content_type, vec![Ok(Bytes::from(vec![0u8; 101])), Err(reset())]).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE, "{}", content_type);
+        assert_eq!(response.extensions().get(), Some(&SizeLimitError::PayloadTooLarge));
+    }
+
+    // Only the streamed requests reached the handler
+    assert_eq!(*seen.lock().unwrap(), ["Some(BodyReadFailed)", "Some(PayloadTooLarge)"]);
+
+    println!("✓ Typed body rejections test passed");
+}
+
+#[tokio::test]
+async fn test_limited_body_keeps_trailers_and_size_hint() {
+    use axum::body::HttpBody;
+    use axum::http::HeaderMap;
+    use axum_jetpack::size_limit::{with_size_limit, SizeLimitMiddlewareConfig};
+    use http_body::Frame;
+    use http_body_util::StreamBody;
+
+    let handler = post(|body: Body| async move {
+        let hint = body.size_hint().exact();
+        let collected = body.collect().await.unwrap();
+        let checksum = collected.trailers().and_then(|trailers| trailers.get("x-checksum").cloned());
+        format!("{:?} {:?} {}", hint, checksum, collected.to_bytes().len())
+    });
+    let app = with_size_limit(
+        Router::new().route("/upload", handler),
+        SizeLimitMiddlewareConfig::new(SizeLimitConfig::default().with_default_limit(100))
+            .with_buffer_strategy(BufferStrategy::all_streamed()),
+    );
+    let send = |body: Body| {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .header("content-type", "video/mp4")
+            .body(body)
+            .unwrap();
+        let app = app.clone();
+        async move {
+            let response = app.oneshot(req).await.unwrap();
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            String::from_utf8(body.to_vec()).unwrap()
+        }
+    };
+
+    // A body of known size keeps its size hint
+    assert_eq!(send(Body::from(vec![0u8; 50])).await, "Some(50) None 50");
+
+    // Trailers pass through the limiter
+    let mut trailers = HeaderMap::new();
+    trailers.insert("x-checksum", "abc".parse().unwrap());
+    let frames: Vec<Result<Frame<Bytes>, std::io::Error>> =
+        vec![Ok(Frame::data(Bytes::from(vec![0u8; 50]))), Ok(Frame::trailers(trailers))];
+    let body = Body::new(StreamBody::new(futures::stream::iter(frames)));
+    assert_eq!(send(body).await, "None Some(\"abc\") 50");
+
+    println!("✓ Limited body trailers and size hint test passed");
+}
+
+#[tokio::test]
+async fn test_bodyless_requests_skip_the_limiter() {
+    use axum::routing::any;
+    use axum_jetpack::size_limit::{
+        with_size_limit_policy, Buffering, Decision, LimitPolicy, RequestContext, SizeLimitMiddlewareConfig,
+    };
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    /// Forbids bodies, counting its decisions.
+    struct Counting(Arc<AtomicUsize>);
+
+    impl LimitPolicy for Counting {
+        async fn decide(&self, _ctx: RequestContext<'_>) -> Decision {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            Decision::Accept { limit: 0, buffering: Buffering::Stream }
+        }
+    }
+
+    let decisions = Arc::new(AtomicUsize::new(0));
+    let app = with_size_limit_policy(
+        Router::new().route("/items", any(|| async { "ok" })),
+        SizeLimitMiddlewareConfig::default(),
+        Counting(decisions.clone()),
+    );
+    let send = |method: &str, body: Body, content_length: Option<&str>| {
+        let mut req = Request::builder().uri("/items").method(method);
+        if let Some(length) = content_length {
+            req = req.header("content-length", length);
+        }
+        app.clone().oneshot(req.body(body).unwrap())
+    };
+
+    // Body-less reads never reach the policy
+    for method in ["GET", "HEAD", "OPTIONS"] {
+        assert_eq!(send(method, Body::empty(), None).await.unwrap().status(), StatusCode::OK);
+    }
+    assert_eq!(send("GET", Body::empty(), Some("0")).await.unwrap().status(), StatusCode::OK);
+    assert_eq!(decisions.load(Ordering::SeqCst), 0);
+
+    // Empty bodies elsewhere are decided on, but have nothing to limit
+    let empty_stream = Body::from_stream(futures::stream::empty::<Result<Bytes, std::io::Error>>());
+    assert_eq!(send("POST", empty_stream, Some("0")).await.unwrap().status(), StatusCode::OK);
+    assert_eq!(send("DELETE", Body::empty(), None).await.unwrap().status(), StatusCode::OK);
+    assert_eq!(decisions.load(Ordering::SeqCst), 2);
+
+    // Bodies are still limited, on fast-path methods too
+    assert_eq!(send("GET", Body::from("x"), None).await.unwrap().status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(send("POST", Body::from("x"), None).await.unwrap().status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    println!("✓ Body-less requests skip the limiter test passed");
+}
+
+#[tokio::test]
+async fn test_strict_content_length() {
+    use axum_jetpack::size_limit::{with_size_limit, SizeLimitError, SizeLimitMiddlewareConfig};
+
+    let app = |strict: bool| {
+        with_size_limit(
+            Router::new().route("/upload", post(|body: Bytes| async move { body.len().to_string() })),
+            SizeLimitMiddlewareConfig::with_default_buffer_strategy(SizeLimitConfig::default().with_default_limit(100))
+                .with_strict_content_length(strict),
+        )
+    };
+    let send = |strict: bool, content_type: &'static str, headers: &[(&'static str, &'static str)], size: usize| {
+        let mut req = Request::builder().method("POST").uri("/upload").header("content-type", content_type);
+        for (name, value) in headers {
+            req = req.header(*name, *value);
+        }
+        // Streamed so the declared length is not taken from the body itself
+        let body = Body::from_stream(futures::stream::iter([Ok::<_, std::io::Error>(Bytes::from(vec![0u8; size]))]));
+        app(strict).oneshot(req.body(body).unwrap())
+    };
+
+    for content_type in ["application/json", "video/mp4"] {
+        // Matching bodies pass
+        let response = send(true, content_type, &[("content-length", "10")], 10).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{}", content_type);
+
+        // Longer and shorter bodies are rejected
+        for size in [5, 20] {
+            let response = send(true, content_type, &[("content-length", "10")], size).await.unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{} {}", content_type, size);
+            assert_eq!(response.extensions().get(), Some(&SizeLimitError::ContentLengthMismatch));
+        }
+
+        // Without strict mode the mismatch goes unnoticed
+        let response = send(false, content_type, &[("content-length", "10")], 5).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{}", content_type);
+    }
+
+    // Ambiguous framing
+    for headers in [
+        &[("content-length", "10"), ("transfer-encoding", "chunked")][..],
+        &[("content-length", "ten")][..],
+        &[("content-length", "10"), ("content-length", "12")][..],
+    ] {
+        let response = send(true, "application/json", headers, 10).await.unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{:?}", headers);
+    }
+
+    // A body behind `Content-Length: 0`
+    let response = send(true, "application/json", &[("content-length", "0")], 10).await.unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    println!("✓ Strict Content-Length test passed");
+}
+
+#[tokio::test]
+async fn test_required_content_length() {
+    use axum_jetpack::error::ErrorFormat;
+    use axum_jetpack::size_limit::{with_size_limit, SizeLimitError, SizeLimitMiddlewareConfig};
+
+    let app = with_size_limit(
+        Router::new().route("/upload", post(|body: Bytes| async move { body.len().to_string() })),
+        SizeLimitMiddlewareConfig::default()
+            .with_required_content_length(true)
+            .with_error_format(ErrorFormat::Json),
+    );
+    let send = |content_length: Option<&'static str>, chunked: bool| {
+        let mut req = Request::builder().method("POST").uri("/upload").header("content-type", "application/json");
+        if let Some(length) = content_length {
+            req = req.header("content-length", length);
+        }
+        if chunked {
+            req = req.header("transfer-encoding", "chunked");
+        }
+        let body = Body::from_stream(futures::stream::iter([Ok::<_, std::io::Error>(Bytes::from_static(b"{}"))]));
+        app.clone().oneshot(req.body(body).unwrap())
+    };
+
+    // Declared lengths are accepted
+    assert_eq!(send(Some("2"), false).await.unwrap().status(), StatusCode::OK);
+
+    // Chunked and unknown-length bodies get 411 in the configured format
+    for chunked in [true, false] {
+        let response = send(None, chunked).await.unwrap();
+        assert_eq!(response.status(), StatusCode::LENGTH_REQUIRED);
+        assert_eq!(response.headers()["content-type"], "application/json");
+        assert_eq!(response.extensions().get(), Some(&SizeLimitError::LengthRequired));
+    }
+
+    // Requests without a body need no length
+    let req = Request::builder().method("POST").uri("/upload").body(Body::empty()).unwrap();
+    assert_eq!(app.clone().oneshot(req).await.unwrap().status(), StatusCode::OK);
+
+    println!("✓ Required Content-Length test passed");
+}
+
+#[tokio::test]
+async fn test_rejected_body_abort_or_drain() {
+    use axum::http::Version;
+    use axum::response::Response;
+    use axum_jetpack::size_limit::{with_size_limit, RejectedBody, SizeLimitMiddlewareConfig};
+    use futures::StreamExt;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    // Sends 100 chunks of 10 bytes against a 50 byte limit, counting the chunks pulled
+    async fn send(handling: RejectedBody, content_type: &str, version: Version, content_length: bool) -> (Response, usize) {
+        let app = with_size_limit(
+            Router::new().route("/upload", post(|body: Bytes| async move { body.len().to_string() })),
+            SizeLimitMiddlewareConfig::with_default_buffer_strategy(SizeLimitConfig::default().with_default_limit(50))
+                .with_rejected_body(handling),
+        );
+        let pulled = Arc::new(AtomicUsize::new(0));
+        let counter = pulled.clone();
+        let chunks = futures::stream::iter(0..100).map(move |_| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            Ok::<_, std::io::Error>(Bytes::from(vec![0u8; 10]))
+        });
+        let mut req = Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .version(version)
+            .header("content-type", content_type);
+        if content_length {
+            req = req.header("content-length", "1000");
+        }
+        let response = app.oneshot(req.body(Body::from_stream(chunks)).unwrap()).await.unwrap();
+        (response, pulled.load(Ordering::SeqCst))
+    }
+
+    for content_type in ["application/json", "video/mp4"] {
+        for content_length in [false, true] {
+            // Aborting stops reading and closes HTTP/1.1 connections
+            let (response, pulled) = send(RejectedBody::Abort, content_type, Version::HTTP_11, content_length).await;
+            assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+            assert_eq!(response.headers()["connection"], "close");
+            assert!(pulled <= 6, "{} {} pulled {}", content_type, content_length, pulled);
+
+            // Draining reads the whole upload and keeps the connection
+            let (response, pulled) = send(RejectedBody::Drain, content_type, Version::HTTP_11, content_length).await;
+            assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+            assert!(response.headers().get("connection").is_none());
+            assert_eq!(pulled, 100);
+        }
+    }
+
+    // A bounded drain keeps slightly oversized uploads' connections only
+    let (response, pulled) = send(RejectedBody::drain_up_to("2kb"), "video/mp4", Version::HTTP_11, false).await;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert!(response.headers().get("connection").is_none());
+    assert_eq!(pulled, 100);
+    let (response, pulled) = send(RejectedBody::drain_up_to(200), "video/mp4", Version::HTTP_11, false).await;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(response.headers()["connection"], "close");
+    assert!(pulled < 30, "pulled {}", pulled);
+
+    // HTTP/2 streams are reset by the server, not closed with a header
+    let (response, _) = send(RejectedBody::Abort, "video/mp4", Version::HTTP_2, false).await;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert!(response.headers().get("connection").is_none());
+
+    println!("✓ Rejected body abort/drain test passed");
+}
+
+#[tokio::test]
+async fn test_buffered_body_keeps_trailers() {
+    use axum::body::HttpBody;
+    use axum::http::HeaderMap;
+    use axum_jetpack::size_limit::{with_size_limit, SizeLimitMiddlewareConfig, SizeLimitOverride};
+    use http_body::Frame;
+    use http_body_util::StreamBody;
+
+    let handler = || {
+        post(|body: Body| async move {
+            let hint = body.size_hint().exact();
+            let collected = body.collect().await.unwrap();
+            let checksum = collected.trailers().and_then(|trailers| trailers.get("x-checksum").cloned());
+            format!("{:?} {:?} {}", hint, checksum, collected.to_bytes().len())
+        })
+    };
+    let app = with_size_limit(
+        Router::new()
+            .route("/upload", handler())
+            .route("/small", handler().layer(SizeLimitOverride::new(80))),
+        SizeLimitMiddlewareConfig::with_default_buffer_strategy(SizeLimitConfig::default().with_default_limit(100)),
+    );
+    let send = |uri: &'static str, with_trailers: bool| {
+        let mut frames: Vec<Result<Frame<Bytes>, std::io::Error>> =
+            vec![Ok(Frame::data(Bytes::from(vec![0u8; 30]))), Ok(Frame::data(Bytes::from(vec![0u8; 20])))];
+        if with_trailers {
+            let mut trailers = HeaderMap::new();
+            trailers.insert("x-checksum", "abc".parse().unwrap());
+            frames.push(Ok(Frame::trailers(trailers)));
+        }
+        let req = Request::builder()
+            .method("POST")
+            .uri(uri)
+            .header("content-type", "application/json")
+            .body(Body::new(StreamBody::new(futures::stream::iter(frames))))
+            .unwrap();
+        let app = app.clone();
+        async move {
+            let response = app.oneshot(req).await.unwrap();
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            String::from_utf8(body.to_vec()).unwrap()
+        }
+    };
+
+    // Buffered bodies keep their trailers and report their exact size
+    assert_eq!(send("/upload", true).await, "Some(50) Some(\"abc\") 50");
+    assert_eq!(send("/upload", false).await, "Some(50) None 50");
+    // So do bodies buffered again by a route-level override
+    assert_eq!(send("/small", true).await, "Some(50) Some(\"abc\") 50");
+
+    println!("✓ Buffered body trailers test passed");
+}
+
+#[tokio::test]
+async fn test_max_chunk_size() {
+    use axum_jetpack::size_limit::{with_size_limit, SizeLimitMiddlewareConfig, DEFAULT_MAX_CHUNK_SIZE};
+
+    assert_eq!(SizeLimitConfig::default().max_chunk_size, Some(DEFAULT_MAX_CHUNK_SIZE));
+
+    let app = |limits: SizeLimitConfig| {
+        with_size_limit(
+            Router::new().route("/upload", post(|body: Bytes| async move { body.len().to_string() })),
+            SizeLimitMiddlewareConfig::with_default_buffer_strategy(limits.with_default_limit(1000)),
+        )
+    };
+    let send = |app: Router, content_type: &'static str, chunks: Vec<usize>| {
+        let chunks = chunks.into_iter().map(|size| Ok::<_, std::io::Error>(Bytes::from(vec![0u8; size])));
+        let req = Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .header("content-type", content_type)
+            .body(Body::from_stream(futures::stream::iter(chunks)))
+            .unwrap();
+        app.oneshot(req)
+    };
+
+    for content_type in ["application/json", "video/mp4"] {
+        // Small chunks within the body limit pass
+        let capped = app(SizeLimitConfig::default().with_max_chunk_size(100));
+        let response = send(capped.clone(), content_type, vec![100, 100, 100]).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{}", content_type);
+
+        // One oversized chunk is rejected although the body fits
+        let response = send(capped, content_type, vec![10, 101]).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE, "{}", content_type);
+
+        // Without a cap only the body limit applies
+        let uncapped = app(SizeLimitConfig::default().with_max_chunk_size(100).without_max_chunk_size());
+        let response = send(uncapped, content_type, vec![500]).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{}", content_type);
+    }
+
+    // Configurable from the environment and serde
+    let config = SizeLimitConfig::from_env_vars("JETPACK_SIZE_LIMIT_", [("JETPACK_SIZE_LIMIT_MAX_CHUNK", "1mb")]).unwrap();
+    assert_eq!(config.max_chunk_size, Some(1_000_000));
+    let config = SizeLimitConfig::from_env_vars("JETPACK_SIZE_LIMIT_", [("JETPACK_SIZE_LIMIT_MAX_CHUNK", "off")]).unwrap();
+    assert_eq!(config.max_chunk_size, None);
+
+    println!("✓ Max chunk size test passed");
+}
+
+#[tokio::test]
+async fn test_exempt_methods() {
+    use axum::http::Method;
+    use axum::routing::any;
+    use axum_jetpack::size_limit::{with_size_limit, SizeLimitMiddlewareConfig};
+
+    let app = with_size_limit(
+        Router::new().route("/items", any(|body: Bytes| async move { body.len().to_string() })),
+        SizeLimitMiddlewareConfig::with_default_buffer_strategy(SizeLimitConfig::default().with_default_limit(10))
+            .with_bodyless_methods(&[Method::OPTIONS])
+            .with_exempt_methods(&[Method::OPTIONS, Method::PUT]),
+    );
+    let send = |method: Method, size: usize| {
+        let req = Request::builder()
+            .method(method)
+            .uri("/items")
+            .header("content-type", "application/json")
+            .body(Body::from(vec![0u8; size]))
+            .unwrap();
+        app.clone().oneshot(req)
+    };
+
+    // Exempt methods are neither limited nor checked for bodies
+    for method in [Method::PUT, Method::OPTIONS] {
+        let response = send(method.clone(), 100).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{}", method);
+        assert_eq!(&response.into_body().collect().await.unwrap().to_bytes()[..], b"100");
+    }
+
+    // Other methods still are
+    let response = send(Method::POST, 100).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    println!("✓ Exempt methods test passed");
+}
+
+#[tokio::test]
+async fn test_exempt_paths() {
+    use axum_jetpack::size_limit::{with_size_limit, SizeLimitMiddlewareConfig};
+
+    let handler = || post(|body: Bytes| async move { body.len().to_string() });
+    let app = with_size_limit(
+        Router::new()
+            .route("/health", handler())
+            .route("/internal/{*rest}", handler())
+            .route("/internalx", handler())
+            .route("/upload", handler()),
+        SizeLimitMiddlewareConfig::with_default_buffer_strategy(SizeLimitConfig::default().with_default_limit(10))
+            .with_exempt_paths(["/health", "/internal/*"]),
+    );
+    let send = |path: &'static str| {
+        let req = Request::builder()
+            .method("POST")
+            .uri(path)
+            .header("content-type", "application/json")
+            .body(Body::from(vec![0u8; 100]))
+            .unwrap();
+        app.clone().oneshot(req)
+    };
+
+    for path in ["/health", "/internal/jobs/run"] {
+        let response = send(path).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{}", path);
+    }
+    // Prefixes match on segment boundaries only
+    for path in ["/internalx", "/upload"] {
+        let response = send(path).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE, "{}", path);
+    }
+
+    println!("✓ Exempt paths test passed");
+}
+
+#[tokio::test]
+async fn test_report_only_mode() {
+    use axum_jetpack::size_limit::{with_size_limit, Enforcement, SizeLimitError, SizeLimitMiddlewareConfig, WouldReject};
+    use std::sync::{Arc, Mutex};
+
+    let reports: Arc<Mutex<Vec<WouldReject>>> = Arc::default();
+    let recorder = reports.clone();
+    let app = with_size_limit(
+        Router::new().route("/upload", post(|body: Bytes| async move { body.len().to_string() })),
+        SizeLimitMiddlewareConfig::with_default_buffer_strategy(SizeLimitConfig::default().with_default_limit(10))
+            .with_enforcement(Enforcement::ReportOnly)
+            .on_would_reject(move |report| recorder.lock().unwrap().push(report.clone())),
+    );
+    let send = |content_type: &'static str, size: usize, declared: bool| {
+        let mut req = Request::builder().method("POST").uri("/upload").header("content-type", content_type);
+        if declared {
+            req = req.header("content-length", size.to_string());
+        }
+        let body = Body::from_stream(futures::stream::iter([Ok::<_, std::io::Error>(Bytes::from(vec![0u8; size]))]));
+        app.clone().oneshot(req.body(body).unwrap())
+    };
+
+    for content_type in ["application/json", "video/mp4"] {
+        reports.lock().unwrap().clear();
+
+        // Bodies within the limit are not reported
+        let response = send(content_type, 5, false).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{}", content_type);
+        assert!(reports.lock().unwrap().is_empty());
+
+        // Oversized bodies pass, reported once, whether declared or measured
+        for declared in [true, false] {
+            let response = send(content_type, 100, declared).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK, "{}", content_type);
+            assert_eq!(&response.into_body().collect().await.unwrap().to_bytes()[..], b"100");
+        }
+        let reports = std::mem::take(&mut *reports.lock().unwrap());
+        assert_eq!(reports.len(), 2, "{}", content_type);
+        for report in &reports {
+            assert_eq!(report.error, SizeLimitError::PayloadTooLarge);
+            assert_eq!(report.limit, Some(10));
+            assert_eq!(report.route, "/upload");
+            assert_eq!(report.content_type, content_type);
+        }
+        assert_eq!((reports[0].content_length, reports[0].bytes_received), (Some(100), 0));
+        assert_eq!((reports[1].content_length, reports[1].bytes_received), (None, 100));
+    }
+
+    println!("✓ Report-only mode test passed");
+}
+
+#[tokio::test]
+async fn test_canary_enforcement() {
+    use axum::http::HeaderName;
+    use axum_jetpack::size_limit::{with_size_limit, SizeLimitMiddlewareConfig};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let reported = Arc::new(AtomicUsize::new(0));
+    let app = |percent: u8| {
+        let reported = reported.clone();
+        with_size_limit(
+            Router::new().route("/upload", post(|body: Bytes| async move { body.len().to_string() })),
+            SizeLimitMiddlewareConfig::with_default_buffer_strategy(SizeLimitConfig::default().with_default_limit(10))
+                .with_canary(percent, Some(HeaderName::from_static("x-request-id")))
+                .on_would_reject(move |_| {
+                    reported.fetch_add(1, Ordering::SeqCst);
+                }),
+        )
+    };
+    let send = |app: Router, content_type: &'static str, id: Option<String>| {
+        let mut req = Request::builder().method("POST").uri("/upload").header("content-type", content_type);
+        if let Some(id) = id {
+            req = req.header("x-request-id", id);
+        }
+        app.oneshot(req.body(Body::from(vec![0u8; 100])).unwrap())
+    };
+
+    for content_type in ["application/json", "video/mp4"] {
+        // 100% enforces everything, 0% only reports
+        let response = send(app(100), content_type, Some("a".into())).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE, "{}", content_type);
+        reported.store(0, Ordering::SeqCst);
+        let response = send(app(0), content_type, None).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{}", content_type);
+        assert_eq!(reported.swap(0, Ordering::SeqCst), 1);
+
+        // A share is enforced, the same request id always alike, the rest reported
+        let canary = app(30);
+        let mut enforced = 0;
+        for i in 0..400 {
+            let id = format!("request-{}", i);
+            let first = send(canary.clone(), content_type, Some(id.clone())).await.unwrap().status();
+            let again = send(canary.clone(), content_type, Some(id)).await.unwrap().status();
+            assert_eq!(first, again, "{}", content_type);
+            if first == StatusCode::PAYLOAD_TOO_LARGE {
+                enforced += 1;
+            }
+        }
+        assert!((80..=160).contains(&enforced), "{} enforced {}", content_type, enforced);
+        assert_eq!(reported.swap(0, Ordering::SeqCst), 2 * (400 - enforced));
+    }
+
+    println!("✓ Canary enforcement test passed");
+}
+
+#[tokio::test]
+async fn test_soft_limits() {
+    use axum::http::HeaderName;
+    use axum_jetpack::size_limit::{with_size_limit, SizeLimitMiddlewareConfig, SoftLimit, SoftLimitExceeded};
+    use std::sync::{Arc, Mutex};
+
+    let seen: Arc<Mutex<Vec<SoftLimitExceeded>>> = Arc::default();
+    let recorder = seen.clone();
+    let config = SizeLimitMiddlewareConfig::with_default_buffer_strategy(SizeLimitConfig::default().with_default_limit(100))
+        .with_soft_limit(SoftLimit::Percent(50))
+        .on_soft_limit(move |info| recorder.lock().unwrap().push(info.clone()));
+    let app = |config: SizeLimitMiddlewareConfig| {
+        with_size_limit(
+            Router::new().route("/upload", post(|body: Bytes| async move { body.len().to_string() })),
+            config,
+        )
+    };
+    let send = |app: Router, content_type: &'static str, size: usize| {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .header("content-type", content_type)
+            .body(Body::from_stream(futures::stream::iter([Ok::<_, std::io::Error>(Bytes::from(vec![0u8; size]))])))
+            .unwrap();
+        app.oneshot(req)
+    };
+
+    for content_type in ["application/json", "video/mp4"] {
+        seen.lock().unwrap().clear();
+
+        // At or below the soft limit: no warning
+        let response = send(app(config.clone()), content_type, 50).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{}", content_type);
+        assert!(response.headers().get("warning").is_none());
+
+        // Between soft and hard limit: accepted with a warning
+        let response = send(app(config.clone()), content_type, 80).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{}", content_type);
+        let warning = response.headers()["warning"].to_str().unwrap().to_string();
+        assert!(warning.starts_with("299 - "), "{}", warning);
+        assert!(warning.contains("80 bytes") && warning.contains("50 bytes"), "{}", warning);
+
+        // Above the hard limit: rejected, not warned about
+        let response = send(app(config.clone()), content_type, 150).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE, "{}", content_type);
+        assert!(response.headers().get("warning").is_none());
+
+        let seen = std::mem::take(&mut *seen.lock().unwrap());
+        assert_eq!(seen.len(), 1, "{}", content_type);
+        assert_eq!((seen[0].body_size, seen[0].soft_limit, seen[0].limit), (80, 50, 100));
+        assert_eq!(seen[0].content_type, content_type);
+
+        // A custom header
+        let custom = config.clone().with_soft_limit_header(HeaderName::from_static("x-size-warning"));
+        let response = send(app(custom), content_type, 80).await.unwrap();
+        assert!(response.headers().get("x-size-warning").is_some());
+        assert!(response.headers().get("warning").is_none());
+    }
+
+    println!("✓ Soft limits test passed");
+}
+
+#[tokio::test]
+async fn test_rejection_headers() {
+    use axum_jetpack::size_limit::{with_size_limit, RejectionHeaders, SizeLimitMiddlewareConfig};
+    use std::time::Duration;
+
+    let app = |headers: RejectionHeaders| {
+        with_size_limit(
+            Router::new().route("/upload", post(|body: Bytes| async move { body.len().to_string() })),
+            SizeLimitMiddlewareConfig::with_default_buffer_strategy(
+                SizeLimitConfig::default()
+                    .with_default_limit(100)
+                    .with_specific_limit("application/xml", 0)
+                    .with_rejection_headers(headers),
+            ),
+        )
+    };
+    let send = |app: Router, content_type: &'static str, size: usize, declared: bool| {
+        let mut req = Request::builder().method("POST").uri("/upload").header("content-type", content_type);
+        if declared {
+            req = req.header("content-length", size.to_string());
+        }
+        let body = Body::from_stream(futures::stream::iter([Ok::<_, std::io::Error>(Bytes::from(vec![0u8; size]))]));
+        app.oneshot(req.body(body).unwrap())
+    };
+
+    let all = RejectionHeaders::all().with_retry_after(Duration::from_secs(60));
+    for content_type in ["application/json", "video/mp4"] {
+        // Whether rejected up front or while reading
+        for declared in [true, false] {
+            let response = send(app(all), content_type, 150, declared).await.unwrap();
+            assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE, "{}", content_type);
+            assert_eq!(response.headers()["x-max-body-size"], "100");
+            assert_eq!(response.headers()["upload-limit"], "max-size=100");
+            assert_eq!(response.headers()["retry-after"], "60");
+        }
+
+        // Accepted requests get none
+        let response = send(app(all), content_type, 50, true).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(response.headers().get("x-max-body-size").is_none());
+
+        // Off by default
+        let response = send(app(RejectionHeaders::default()), content_type, 150, true).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert!(response.headers().get("x-max-body-size").is_none());
+        assert!(response.headers().get("retry-after").is_none());
+    }
+
+    // Forbidden bodies report a limit of zero
+    let response = send(app(RejectionHeaders::all()), "application/xml", 10, true).await.unwrap();
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(response.headers()["x-max-body-size"], "0");
+
+    println!("✓ Rejection headers test passed");
+}
+
+#[tokio::test]
+async fn test_advertised_limits() {
+    use axum::http::Method;
+    use axum::routing::any;
+    use axum_jetpack::size_limit::{with_size_limit, SizeLimitMiddlewareConfig};
+
+    let app = |advertise: bool| {
+        with_size_limit(
+            Router::new().route("/upload", any(|| async { "ok" })),
+            SizeLimitMiddlewareConfig::with_default_buffer_strategy(
+                SizeLimitConfig::default()
+                    .with_default_limit("1mb")
+                    .with_specific_limit("application/json", "100kb")
+                    .with_wildcard_limit("image/*", "5mb")
+                    .with_method_limit(Method::PATCH, "64kb"),
+            )
+            .with_advertised_limits(advertise),
+        )
+    };
+    let send = |app: Router, method: Method, headers: &[(&'static str, &'static str)]| {
+        let mut req = Request::builder().method(method).uri("/upload");
+        for (name, value) in headers {
+            req = req.header(*name, *value);
+        }
+        app.oneshot(req.body(Body::empty()).unwrap())
+    };
+
+    // The content-type table, in lookup order
+    let response = send(app(true), Method::OPTIONS, &[]).await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()["max-upload-size"],
+        "application/json=100000, image/*=5000000, */*=1000000"
+    );
+    assert_eq!(response.headers()["x-max-body-size"], "1000000");
+
+    // Preflights get the limit of the announced upload
+    for (method, content_type, limit) in [("POST", "image/png", "5000000"), ("PATCH", "image/png", "64000")] {
+        let headers = [("access-control-request-method", method), ("content-type", content_type)];
+        let response = send(app(true), Method::OPTIONS, &headers).await.unwrap();
+        assert_eq!(response.headers()["x-max-body-size"], limit, "{}", method);
+    }
+
+    // Only OPTIONS, and only when enabled
+    let response = send(app(true), Method::GET, &[]).await.unwrap();
+    assert!(response.headers().get("max-upload-size").is_none());
+    let response = send(app(false), Method::OPTIONS, &[]).await.unwrap();
+    assert!(response.headers().get("max-upload-size").is_none());
+    assert!(response.headers().get("x-max-body-size").is_none());
+
+    println!("✓ Advertised limits test passed");
+}
+
+#[tokio::test]
+async fn test_limits_endpoint() {
+    use axum::http::Method;
+    use axum_jetpack::size_limit::{limits_endpoint, SizeLimit};
+
+    let limits = SizeLimitConfig::default()
+        .with_default_limit("2mb")
+        .with_specific_limit("application/json", "100kb")
+        .with_wildcard_limit("image/*", "5mib")
+        .with_wildcard_limit("video/*", SizeLimit::unlimited())
+        .with_method_limit(Method::PATCH, "64kb");
+    let app: Router = Router::new().route("/limits", limits_endpoint(&limits));
+
+    let response = app
+        .oneshot(Request::builder().uri("/limits").body(Body::empty()).unwrap())
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()["content-type"], "application/json");
+    let body = response.into_body().collect().await.unwrap().to_bytes();
+    let table: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+    assert_eq!(table["default_limit"], serde_json::json!({ "bytes": 2_000_000, "human": "2 MB" }));
+    assert_eq!(table["allowlist_mode"], false);
+    assert_eq!(
+        table["content_types"],
+        serde_json::json!([
+            { "content_type": "application/json", "kind": "specific", "bytes": 100_000, "human": "100 KB" },
+            { "content_type": "image/*", "kind": "wildcard", "bytes": 5_242_880, "human": "5 MiB" },
+            { "content_type": "video/*", "kind": "wildcard", "bytes": null, "human": "unlimited" },
+        ])
+    );
+    assert_eq!(table["methods"]["PATCH"]["bytes"], 64_000);
+
+    println!("✓ Limits endpoint test passed");
+}
+
+#[tokio::test]
+async fn test_generic_response_bodies() {
+    use axum_jetpack::size_limit::{SizeLimitLayer, SizeLimitMiddlewareConfig};
+    use std::convert::Infallible;
+    use tower::Layer;
+
+    // A plain tower service answering with its own body type
+    let service = tower::service_fn(|req: Request<Body>| async move {
+        let body = req.into_body().collect().await.map(|body| body.to_bytes().len()).unwrap_or(0);
+        Ok::<_, Infallible>(axum::http::Response::new(format!("read {}", body)))
+    });
+    let config = SizeLimitMiddlewareConfig::with_default_buffer_strategy(SizeLimitConfig::default().with_default_limit(100));
+    let limited = SizeLimitLayer::new(config).layer(service);
+
+    for content_type in ["application/json", "video/mp4"] {
+        for (size, status) in [(100, StatusCode::OK), (101, StatusCode::PAYLOAD_TOO_LARGE)] {
+            let req = Request::builder()
+                .method("POST")
+                .uri("/upload")
+                .header("content-type", content_type)
+                .body(Body::from(vec![0u8; size]))
+                .unwrap();
+            let response: axum::response::Response = limited.clone().oneshot(req).await.unwrap();
+            assert_eq!(response.status(), status, "{} {}", content_type, size);
+            if status == StatusCode::OK {
+                let body = response.into_body().collect().await.unwrap().to_bytes();
+                assert_eq!(body, format!("read {}", size));
+            }
+        }
+    }
+
+    println!("✓ Generic response bodies test passed");
+}
+
+#[tokio::test]
+async fn test_inner_readiness_errors_surface() {
+    use axum_jetpack::size_limit::{SizeLimitLayer, SizeLimitMiddlewareConfig};
+    use std::task::{Context, Poll};
+    use tower::{Layer, Service};
+
+    /// A service whose readiness fails.
+    #[derive(Clone)]
+    struct Unavailable;
+
+    impl Service<Request<Body>> for Unavailable {
+        type Response = axum::response::Response;
+        type Error = &'static str;
+        type Future = std::future::Ready<Result<Self::Response, Self::Error>>;
+
+        fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+            Poll::Ready(Err("overloaded"))
+        }
+
+        fn call(&mut self, _req: Request<Body>) -> Self::Future {
+            std::future::ready(Err("called without readiness"))
+        }
+    }
+
+    let mut limited = SizeLimitLayer::new(SizeLimitMiddlewareConfig::default()).layer(Unavailable);
+    let ready = std::future::poll_fn(|cx| limited.poll_ready(cx)).await;
+    assert_eq!(ready.err(), Some("overloaded"));
+
+    let req = Request::builder().method("POST").uri("/").body(Body::from("data")).unwrap();
+    assert_eq!(limited.oneshot(req).await.err(), Some("overloaded"));
+
+    println!("✓ Inner readiness errors test passed");
+}
+
+#[tokio::test]
+async fn test_size_limit_info_extractor() {
+    use axum_jetpack::size_limit::{SizeLimitInfo, SizeLimitOverride};
+
+    let size_limits = SizeLimitConfig::default()
+        .with_default_limit(1000)
+        .with_specific_limit("application/json", 500)
+        .with_wildcard_limit("video/*", 2000);
+    let app = with_size_limit_simple(
+        Router::new()
+            .route(
+                "/info",
+                post(|info: SizeLimitInfo| async move {
+                    format!("{} {}", info.limit, info.content_type_rule.unwrap_or_else(|| "default".into()))
+                }),
+            )
+            .route(
+                "/small",
+                post(|info: SizeLimitInfo| async move { info.limit.to_string() }).layer(SizeLimitOverride::new(10)),
+            ),
+        size_limits,
+    );
+
+    for (content_type, expected) in [
+        ("application/json", "500 application/json"),
+        ("video/mp4", "2000 video/*"),
+        ("text/plain", "1000 default"),
+    ] {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/info")
+            .header("content-type", content_type)
+            .body(Body::from("data"))
+            .unwrap();
+        let response = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, expected);
+    }
+
+    for content_type in ["application/json", "video/mp4"] {
+        let req = Request::builder()
+            .method("POST")
+            .uri("/small")
+            .header("content-type", content_type)
+            .body(Body::from("data"))
+            .unwrap();
+        let response = app.clone().oneshot(req).await.unwrap();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, "10", "{}", content_type);
+    }
+
+    // Without the limiter the extractor rejects, the optional one yields `None`
+    let app = Router::new()
+        .route("/info", post(|_info: SizeLimitInfo| async { "ok" }))
+        .route("/optional", post(|info: Option<SizeLimitInfo>| async move { info.is_none().to_string() }));
+    let req = Request::builder().method("POST").uri("/info").body(Body::empty()).unwrap();
+    assert_eq!(app.clone().oneshot(req).await.unwrap().status(), StatusCode::INTERNAL_SERVER_ERROR);
+    let req = Request::builder().method("POST").uri("/optional").body(Body::empty()).unwrap();
+    let body = app.oneshot(req).await.unwrap().into_body().collect().await.unwrap().to_bytes();
+    assert_eq!(body, "true");
+
+    println!("✓ Size limit info extractor test passed");
+}
+
+#[tokio::test]
+async fn test_observed_body_size() {
+    use axum::http::HeaderName;
+    use axum_jetpack::size_limit::{ObservedBodySize, SizeLimitMiddlewareConfig, with_size_limit};
+
+    let header = HeaderName::from_static("x-request-body-size");
+    let config = SizeLimitMiddlewareConfig::default()
+        .with_body_size_header(header.clone())
+        .with_rejected_body(axum_jetpack::size_limit::RejectedBody::Drain);
+    let config = SizeLimitMiddlewareConfig {
+        size_limits: SizeLimitConfig::default().with_default_limit(100),
+        ..config
+    };
+    let app = with_size_limit(
+        Router::new()
+            .route("/read", post(|body: Bytes| async move { body.len().to_string() }))
+            .route("/ignore", post(|| async { "ignored" })),
+        config,
+    );
+
+    for content_type in ["application/json", "video/mp4"] {
+        for (uri, size, status) in [
+            ("/read", 60, StatusCode::OK),
+            // Dropped by the handler, then drained by the limiter
+            ("/ignore", 80, StatusCode::OK),
+            ("/read", 0, StatusCode::OK),
+        ] {
+            let req = Request::builder()
+                .method("POST")
+                .uri(uri)
+                .header("content-type", content_type)
+                .body(Body::from(vec![0u8; size]))
+                .unwrap();
+            let response = app.clone().oneshot(req).await.unwrap();
+            assert_eq!(response.status(), status, "{} {}", content_type, uri);
+            assert_eq!(response.extensions().get::<ObservedBodySize>().unwrap().bytes(), size as u64);
+            assert_eq!(response.headers()[&header], size.to_string().as_str());
+        }
+
+        // Chunked, without Content-Length: counted as it arrives, up to the rejection
+        let chunks: Vec<Result<Bytes, std::io::Error>> = vec![Ok(Bytes::from(vec![0u8; 70])), Ok(Bytes::from(vec![0u8; 70]))];
+        let req = Request::builder()
+            .method("POST")
+            .uri("/read")
+            .header("content-type", content_type)
+            .body(Body::from_stream(futures::stream::iter(chunks)))
+            .unwrap();
+        let response = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(response.headers()[&header], "140");
+    }
+
+    println!("✓ Observed body size test passed");
+}
+
+#[tokio::test]
+async fn test_buffer_budget() {
+    use axum_jetpack::size_limit::{BufferBudget, OnExhausted, SizeLimitMiddlewareConfig, with_size_limit};
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::Semaphore;
+
+    for (on_exhausted, refused) in [
+        (OnExhausted::Unavailable, StatusCode::SERVICE_UNAVAILABLE),
+        (OnExhausted::TooManyRequests, StatusCode::TOO_MANY_REQUESTS),
+        (OnExhausted::Wait(Duration::from_secs(5)), StatusCode::OK),
+    ] {
+        for content_type in ["application/json", "video/mp4"] {
+            let budget = BufferBudget::new(2048).with_on_exhausted(on_exhausted);
+            // Holds the first handler until released
+            let gate = Arc::new(Semaphore::new(0));
+            let handler_gate = gate.clone();
+            let config = SizeLimitMiddlewareConfig::default().with_buffer_budget(budget.clone());
+            let app = with_size_limit(
+                Router::new()
+                    .route(
+                        "/slow",
+                        post(move |body: Bytes| async move {
+                            let _ = handler_gate.acquire().await;
+                            body.len().to_string()
+                        }),
+                    )
+                    .route("/fast", post(|body: Bytes| async move { body.len().to_string() })),
+                config,
+            );
+            let request = |uri: &str| {
+                Request::builder()
+                    .method("POST")
+                    .uri(uri)
+                    .header("content-type", content_type)
+                    .body(Body::from(vec![0u8; 1500]))
+                    .unwrap()
+            };
+
+            let slow = tokio::spawn(app.clone().oneshot(request("/slow")));
+            // Let the slow request buffer its body and take the budget
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            let buffered = content_type == "application/json";
+            assert_eq!(budget.available(), if buffered { 0 } else { 2048 });
+
+            let fast = tokio::spawn(app.clone().oneshot(request("/fast")));
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            gate.add_permits(1);
+
+            assert_eq!(slow.await.unwrap().unwrap().status(), StatusCode::OK);
+            let fast = fast.await.unwrap().unwrap();
+            let expected = if buffered { refused } else { StatusCode::OK };
+            assert_eq!(fast.status(), expected, "{:?} {}", on_exhausted, content_type);
+            assert_eq!(budget.available(), 2048);
+        }
+    }
+
+    println!("✓ Buffer budget test passed");
+}
+
+#[tokio::test]
+async fn test_buffered_chunks_reassembled() {
+    let app = with_size_limit_simple(
+        Router::new().route("/echo", post(|body: Bytes| async move { body })),
+        SizeLimitConfig::default().with_default_limit(1000),
+    );
+
+    for content_type in ["application/json", "text/plain"] {
+        // With and without a declared length to size the buffer from
+        for declared in [true, false] {
+            let chunks: Vec<Result<Bytes, std::io::Error>> = (0u8..10)
+                .map(|i| Ok(Bytes::from(vec![b'a' + i; usize::from(i) * 7])))
+                .collect();
+            let expected: Vec<u8> = (0u8..10).flat_map(|i| vec![b'a' + i; usize::from(i) * 7]).collect();
+            let mut req = Request::builder().method("POST").uri("/echo").header("content-type", content_type);
+            if declared {
+                req = req.header("content-length", expected.len());
+            }
+            let req = req.body(Body::from_stream(futures::stream::iter(chunks))).unwrap();
+            let response = app.clone().oneshot(req).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            assert_eq!(body, expected, "{} {}", content_type, declared);
+        }
+    }
+
+    println!("✓ Buffered chunks reassembled test passed");
+}
+
+#[tokio::test]
+async fn test_progress_callbacks() {
+    use axum_jetpack::size_limit::{SizeLimitMiddlewareConfig, with_size_limit};
+    use std::ops::ControlFlow;
+    use std::sync::{Arc, Mutex};
+
+    for content_type in ["application/json", "video/mp4"] {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorded = seen.clone();
+        let config = SizeLimitMiddlewareConfig::default().on_progress(100, move |upload| {
+            assert_eq!(upload.limit, 1_000_000);
+            assert_eq!(upload.content_type, content_type);
+            recorded.lock().unwrap().push(upload.consumed);
+            // Custom abort: nothing above 500 bytes on this route
+            match upload.consumed > 500 {
+                true => ControlFlow::Break(()),
+                false => ControlFlow::Continue(()),
+            }
+        });
+        let app = with_size_limit(
+            Router::new().route("/upload", post(|body: Bytes| async move { body.len().to_string() })),
+            config,
+        );
+        let upload = |chunks: usize| {
+            let chunks: Vec<Result<Bytes, std::io::Error>> = (0..chunks).map(|_| Ok(Bytes::from(vec![0u8; 60]))).collect();
+            Request::builder()
+                .method("POST")
+                .uri("/upload")
+                .header("content-type", content_type)
+                .body(Body::from_stream(futures::stream::iter(chunks)))
+                .unwrap()
+        };
+
+        let response = app.clone().oneshot(upload(5)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        // First chunk, then on crossing 100, 200 and 300 bytes
+        assert_eq!(*seen.lock().unwrap(), vec![60, 120, 240, 300]);
+
+        seen.lock().unwrap().clear();
+        let response = app.clone().oneshot(upload(20)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE, "{}", content_type);
+        assert_eq!(seen.lock().unwrap().last(), Some(&540));
+    }
+
+    println!("✓ Progress callbacks test passed");
+}
+
+#[cfg(feature = "body-digest")]
+#[tokio::test]
+async fn test_body_digest() {
+    use axum::extract::Extension;
+    use axum_jetpack::size_limit::{BodyDigest, Sha256, SizeLimitMiddlewareConfig, with_size_limit};
+    use sha2::Digest;
+
+    let config = SizeLimitMiddlewareConfig {
+        size_limits: SizeLimitConfig::default().with_default_limit(100),
+        ..SizeLimitMiddlewareConfig::default()
+    }
+    .with_body_digest::<Sha256>();
+    let app = with_size_limit(
+        Router::new().route(
+            "/upload",
+            post(|Extension(digest): Extension<BodyDigest>, body: Bytes| async move {
+                format!("{} {}", body.len(), digest.hex().unwrap_or_default())
+            }),
+        ),
+        config,
+    );
+
+    for content_type in ["application/json", "video/mp4"] {
+        for size in [0, 1, 100] {
+            let chunks: Vec<Result<Bytes, std::io::Error>> = vec![
+                Ok(Bytes::from(vec![7u8; size / 2])),
+                Ok(Bytes::from(vec![7u8; size - size / 2])),
+            ];
+            let body = match size {
+                0 => Body::empty(),
+                _ => Body::from_stream(futures::stream::iter(chunks)),
+            };
+            let req = Request::builder()
+                .method("POST")
+                .uri("/upload")
+                .header("content-type", content_type)
+                .body(body)
+                .unwrap();
+            let response = app.clone().oneshot(req).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+            let expected: String = sha2::Sha256::digest(vec![7u8; size])
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect();
+            let digest = response.extensions().get::<BodyDigest>().unwrap().hex();
+            assert_eq!(digest.as_deref(), Some(expected.as_str()), "{} {}", content_type, size);
+            let body = response.into_body().collect().await.unwrap().to_bytes();
+            assert_eq!(body, format!("{} {}", size, expected));
+        }
+
+        // Rejected bodies have no digest
+        let req = Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .header("content-type", content_type)
+            .body(Body::from_stream(futures::stream::iter(vec![Ok::<_, std::io::Error>(Bytes::from(vec![0u8; 101]))])))
+            .unwrap();
+        let response = app.clone().oneshot(req).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(response.extensions().get::<BodyDigest>().unwrap().get(), None);
+    }
+
+    println!("✓ Body digest test passed");
+}
+
+#[tokio::test]
+async fn test_bandwidth_throttling() {
+    use axum::extract::connect_info::MockConnectInfo;
+    use axum_jetpack::size_limit::{RouterExt, ThrottleLayer};
+    use std::net::SocketAddr;
+    use std::time::{Duration, Instant};
+
+    let upload = |content_type: &str| {
+        let chunks: Vec<Result<Bytes, std::io::Error>> = (0..6).map(|_| Ok(Bytes::from(vec![0u8; 500]))).collect();
+        Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .header("content-type", content_type)
+            .body(Body::from_stream(futures::stream::iter(chunks)))
+            .unwrap()
+    };
+    let router = || {
+        Router::new()
+            .route("/upload", post(|body: Bytes| async move { body.len().to_string() }))
+            .size_limit("1mb")
+    };
+
+    for content_type in ["application/json", "video/mp4"] {
+        // 3000 bytes at 10 kB/s with a 1000 byte burst: about 200ms
+        let app = router().layer(ThrottleLayer::new("80 kbit").with_burst(1000));
+        let started = Instant::now();
+        let response = app.oneshot(upload(content_type)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, "3000");
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(180), "{:?}", elapsed);
+
+        // Two uploads of one client share its bucket: about 500ms
+        let app = router()
+            .layer(ThrottleLayer::new(10_000).with_burst(1000).per_client())
+            .layer(MockConnectInfo(SocketAddr::from(([10, 0, 0, 1], 4000))));
+        let started = Instant::now();
+        let (first, second) = tokio::join!(
+            app.clone().oneshot(upload(content_type)),
+            app.clone().oneshot(upload(content_type))
+        );
+        assert_eq!(first.unwrap().status(), StatusCode::OK);
+        assert_eq!(second.unwrap().status(), StatusCode::OK);
+        let elapsed = started.elapsed();
+        assert!(elapsed >= Duration::from_millis(450), "{:?}", elapsed);
+    }
+
+    println!("✓ Bandwidth throttling test passed");
+}
+
+#[cfg(feature = "client-ip")]
+#[tokio::test]
+async fn test_throttling_per_proxied_client() {
+    use axum::extract::ConnectInfo;
+    use axum::Extension;
+    use axum_jetpack::client_ip::{with_client_ip, ClientIpResolver};
+    use axum_jetpack::size_limit::{RouterExt, ThrottleLayer};
+    use std::net::SocketAddr;
+    use std::time::{Duration, Instant};
+
+    // Every request arrives through the same trusted proxy
+    let router = Router::new()
+        .route("/upload", post(|body: Bytes| async move { body.len().to_string() }))
+        .size_limit("1mb")
+        .layer(ThrottleLayer::new(10_000).with_burst(1000).per_client());
+    let app = with_client_ip(router, ClientIpResolver::new().with_trusted_proxy("10.0.0.0/8").unwrap())
+        .layer(Extension(ConnectInfo(SocketAddr::from(([10, 0, 0, 1], 4000)))));
+    let upload = |client: &str| {
+        let chunks: Vec<Result<Bytes, std::io::Error>> = (0..6).map(|_| Ok(Bytes::from(vec![0u8; 500]))).collect();
+        Request::builder()
+            .method("POST")
+            .uri("/upload")
+            .header("content-type", "video/mp4")
+            .header("x-forwarded-for", client)
+            .body(Body::from_stream(futures::stream::iter(chunks)))
+            .unwrap()
+    };
+
+    // Clients behind the proxy get a bucket each: about 200ms
+    let started = Instant::now();
+    let (first, second) = tokio::join!(
+        app.clone().oneshot(upload("203.0.113.1")),
+        app.clone().oneshot(upload("203.0.113.2"))
+    );
+    assert_eq!(first.unwrap().status(), StatusCode::OK);
+    assert_eq!(second.unwrap().status(), StatusCode::OK);
+    let elapsed = started.elapsed();
+    assert!(elapsed < Duration::from_millis(400), "{:?}", elapsed);
+
+    // Uploads of the same client share its bucket: about 500ms
+    let started = Instant::now();
+    let (first, second) = tokio::join!(
+        app.clone().oneshot(upload("203.0.113.1")),
+        app.clone().oneshot(upload("203.0.113.1"))
+    );
+    assert_eq!(first.unwrap().status(), StatusCode::OK);
+    assert_eq!(second.unwrap().status(), StatusCode::OK);
+    let elapsed = started.elapsed();
+    assert!(elapsed >= Duration::from_millis(450), "{:?}", elapsed);
+
+    println!("✓ Throttling per proxied client test passed");
+}
+
+#[tokio::test]
+async fn test_body_read_timeouts() {
+    use axum_jetpack::size_limit::{SizeLimitMiddlewareConfig, with_size_limit};
+    use futures::StreamExt;
+    use std::time::{Duration, Instant};
+
+    /// Sends `chunks` chunks of 10 bytes, `interval` apart.
+    fn trickle(chunks: usize, interval: Duration) -> Body {
+        let stream = futures::stream::unfold(0, move |sent| async move {
+            if sent == chunks {
+                return None;
+            }
+            if sent > 0 {
+                tokio::time::sleep(interval).await;
+            }
+            Some((Ok::<_, std::io::Error>(Bytes::from(vec![0u8; 10])), sent + 1))
+        });
+        Body::from_stream(stream)
+    }
+
+    for content_type in ["application/json", "video/mp4"] {
+        let app = |config: SizeLimitMiddlewareConfig| {
+            with_size_limit(
+                Router::new().route("/upload", post(|body: Bytes| async move { body.len().to_string() })),
+                config.with_error_format(axum_jetpack::error::ErrorFormat::Json),
+            )
+        };
+        let request = |body: Body| {
+            Request::builder()
+                .method("POST")
+                .uri("/upload")
+                .header("content-type", content_type)
+                .body(body)
+                .unwrap()
+        };
+
+        // One chunk, then nothing
+        let stalled = futures::stream::iter(vec![Ok::<_, std::io::Error>(Bytes::from_static(b"start"))])
+            .chain(futures::stream::pending());
+        let chunked = app(SizeLimitMiddlewareConfig::default().with_chunk_timeout(Duration::from_millis(100)));
+        let started = Instant::now();
+        let response = chunked.clone().oneshot(request(Body::from_stream(stalled))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::REQUEST_TIMEOUT, "{}", content_type);
+        assert!(started.elapsed() < Duration::from_secs(2));
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert!(String::from_utf8_lossy(&body).contains("\"status\":408"));
+
+        // Steady chunks within the chunk timeout pass
+        let response = chunked.oneshot(request(trickle(5, Duration::from_millis(20)))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        // ...unless the whole body takes too long
+        let total = app(SizeLimitMiddlewareConfig::default()
+            .with_chunk_timeout(Duration::from_millis(100))
+            .with_body_timeout(Duration::from_millis(150)));
+        let response = total.oneshot(request(trickle(100, Duration::from_millis(20)))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::REQUEST_TIMEOUT, "{}", content_type);
+    }
+
+    println!("✓ Body read timeouts test passed");
+}
+
+#[tokio::test]
+async fn test_byte_quotas() {
+    use axum_jetpack::size_limit::{
+        ByteQuota, MemoryQuotaStore, QuotaStore, SizeLimitMiddlewareConfig, with_size_limit, X_QUOTA_LIMIT,
+        X_QUOTA_REMAINING, X_QUOTA_RESET,
+    };
+    use std::time::Duration;
+
+    for content_type in ["application/json", "video/mp4"] {
+        let store = MemoryQuotaStore::new();
+        let quota = ByteQuota::new(1000, Duration::from_secs(60))
+            .with_header_key("x-api-key")
+            .with_store(store.clone());
+        let app = with_size_limit(
+            Router::new().route("/upload", post(|body: Bytes| async move { body.len().to_string() })),
+            SizeLimitMiddlewareConfig::default().with_quota(quota),
+        );
+        let request = |key: Option<&str>, body: Vec<u8>| {
+            let mut builder = Request::builder()
+                .method("POST")
+                .uri("/upload")
+                .header("content-type", content_type);
+            if let Some(key) = key {
+                builder = builder.header("x-api-key", key);
+            }
+            builder.body(Body::from(body)).unwrap()
+        };
+
+        // Accepted bodies are counted against their key
+        let response = app.clone().oneshot(request(Some("a"), vec![0; 600])).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "{}", content_type);
+        assert_eq!(response.headers()[X_QUOTA_LIMIT], "1000");
+        assert_eq!(response.headers()[X_QUOTA_REMAINING], "400");
+        assert!(response.headers().contains_key(X_QUOTA_RESET));
+        assert_eq!(store.usage("a", Duration::from_secs(60)).await.unwrap().used, 600);
+
+        // A declared length beyond what is left is refused before reading
+        let response = app.clone().oneshot(request(Some("a"), vec![0; 500])).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS, "{}", content_type);
+        assert!(response.headers().contains_key("retry-after"));
+        assert_eq!(response.headers()[X_QUOTA_REMAINING], "400");
+        assert_eq!(store.usage("a", Duration::from_secs(60)).await.unwrap().used, 600);
+
+        // ...while a body that fits uses the quota up, and then nothing passes
+        let response = app.clone().oneshot(request(Some("a"), vec![0; 400])).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[X_QUOTA_REMAINING], "0");
+        let response = app.clone().oneshot(request(Some("a"), vec![0; 1])).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+
+        // Other keys have their own quota; requests without a key are not accounted
+        let response = app.clone().oneshot(request(Some("b"), vec![0; 600])).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let response = app.oneshot(request(None, vec![0; 600])).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(!response.headers().contains_key(X_QUOTA_LIMIT));
+    }
+
+    println!("✓ Byte quotas test passed");
+}
+
+#[tokio::test]
+async fn test_upload_concurrency_cap() {
+    use axum_jetpack::size_limit::{RouterExt, UploadConcurrencyLayer};
+    use std::time::Duration;
+
+    for content_type in ["application/json", "video/mp4"] {
+        let cap = UploadConcurrencyLayer::new(1).with_header_key("x-api-key").with_min_size(100);
+        let app: Router = Router::new()
+            .route(
+                "/upload",
+                post(|body: Bytes| async move {
+                    tokio::time::sleep(Duration::from_millis(200)).await;
+                    body.len().to_string()
+                }),
+            )
+            .size_limit("1mb")
+            .layer(cap.clone());
+        let request = |key: &str, size: usize| {
+            Request::builder()
+                .method("POST")
+                .uri("/upload")
+                .header("content-type", content_type)
+                .header("x-api-key", key)
+                .body(Body::from(vec![0u8; size]))
+                .unwrap()
+        };
+
+        // A second upload of the same key while the first is in flight is refused
+        let first = tokio::spawn(app.clone().oneshot(request("a", 500)));
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert_eq!(cap.in_flight("a"), 1);
+        let response = app.clone().oneshot(request("a", 500)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS, "{}", content_type);
+
+        // ...while other keys and small bodies pass
+        let (other, small) = tokio::join!(app.clone().oneshot(request("b", 500)), app.clone().oneshot(request("a", 10)));
+        assert_eq!(other.unwrap().status(), StatusCode::OK);
+        assert_eq!(small.unwrap().status(), StatusCode::OK);
+
+        // The slot is freed once the response is ready
+        assert_eq!(first.await.unwrap().unwrap().status(), StatusCode::OK);
+        assert_eq!(cap.in_flight("a"), 0);
+        let response = app.oneshot(request("a", 500)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    println!("✓ Upload concurrency cap test passed");
+}
+
+#[tokio::test]
+async fn test_tenant_limits() {
+    use axum_jetpack::size_limit::{MemoryTenantStore, SizeLimitMiddlewareConfig, TenantLimits, with_size_limit_policy};
+
+    let store = MemoryTenantStore::new().with_tenant("acme", "premium").with_tenant("initech", "retired");
+    let tenants = TenantLimits::new(
+        SizeLimitConfig::default().with_default_limit(SizeLimit::bytes(50)),
+        BufferStrategy::with_defaults(),
+    )
+    .with_profile("premium", SizeLimitConfig::default().with_default_limit(SizeLimit::bytes(1000)))
+    .with_header_key("x-tenant-id")
+    .with_store(store.clone());
+
+    for content_type in ["application/json", "video/mp4"] {
+        let app = with_size_limit_policy(
+            Router::new().route("/upload", post(|body: Bytes| async move { body.len().to_string() })),
+            SizeLimitMiddlewareConfig::default(),
+            tenants.clone(),
+        );
+        let send = |tenant: Option<&str>, size: usize| {
+            let mut builder = Request::builder()
+                .method("POST")
+                .uri("/upload")
+                .header("content-type", content_type);
+            if let Some(tenant) = tenant {
+                builder = builder.header("x-tenant-id", tenant);
+            }
+            app.clone().oneshot(builder.body(Body::from(vec![b'x'; size])).unwrap())
+        };
+
+        // Premium tenants get the bigger cap
+        assert_eq!(send(Some("acme"), 500).await.unwrap().status(), StatusCode::OK, "{}", content_type);
+        // Unknown tenants, missing keys and unknown profiles get the default
+        assert_eq!(send(Some("globex"), 500).await.unwrap().status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(send(None, 500).await.unwrap().status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(send(Some("initech"), 500).await.unwrap().status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(send(None, 20).await.unwrap().status(), StatusCode::OK);
+
+        // Tenants move between profiles while serving
+        store.set_tenant("globex", "premium");
+        assert_eq!(send(Some("globex"), 500).await.unwrap().status(), StatusCode::OK);
+        store.remove_tenant("globex");
+    }
+
+    println!("✓ Tenant limits test passed");
+}
+
+#[tokio::test]
+async fn test_limits_from_user_claims() {
+    use axum::{middleware::Next, response::Response};
+    use axum_jetpack::size_limit::{SizeLimitMiddlewareConfig, TenantLimits, UserPlan, with_size_limit_policy};
+
+    /// Claims a JWT layer would insert.
+    #[derive(Clone)]
+    struct Claims {
+        role: String,
+    }
+
+    /// Stands in for an authentication layer: the bearer token is the plan.
+    async fn authenticate(mut req: Request, next: Next) -> Response {
+        let token = req
+            .headers()
+            .get("authorization")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .map(str::to_string);
+        if let Some(token) = token {
+            req.extensions_mut().insert(UserPlan::new(token.clone()));
+            req.extensions_mut().insert(Claims { role: token });
+        }
+        next.run(req).await
+    }
+
+    let limits = TenantLimits::new(
+        SizeLimitConfig::default().with_default_limit(SizeLimit::bytes(50)),
+        BufferStrategy::with_defaults(),
+    )
+    .with_profile("premium", SizeLimitConfig::default().with_default_limit(SizeLimit::bytes(1000)))
+    .with_profile("admin", SizeLimitConfig::default().with_default_limit(SizeLimit::bytes(5000)));
+
+    for content_type in ["application/json", "video/mp4"] {
+        let app = |limits: TenantLimits| {
+            with_size_limit_policy(
+                Router::new().route("/upload", post(|body: Bytes| async move { body.len().to_string() })),
+                SizeLimitMiddlewareConfig::default(),
+                limits,
+            )
+            // Runs before the size limiter
+            .layer(axum::middleware::from_fn(authenticate))
+        };
+        let send = |app: Router, token: Option<&str>, size: usize| {
+            let mut builder = Request::builder()
+                .method("POST")
+                .uri("/upload")
+                .header("content-type", content_type);
+            if let Some(token) = token {
+                builder = builder.header("authorization", format!("Bearer {}", token));
+            }
+            app.oneshot(builder.body(Body::from(vec![b'x'; size])).unwrap())
+        };
+
+        // The `UserPlan` extension picks the profile
+        let plans = app(limits.clone());
+        assert_eq!(send(plans.clone(), Some("premium"), 500).await.unwrap().status(), StatusCode::OK, "{}", content_type);
+        assert_eq!(send(plans.clone(), Some("premium"), 2000).await.unwrap().status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(send(plans.clone(), Some("free"), 500).await.unwrap().status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(send(plans, None, 500).await.unwrap().status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+        // ...or any claims type
+        let roles = app(limits.clone().with_plan(|claims: &Claims| Some(claims.role.clone())));
+        assert_eq!(send(roles, Some("admin"), 2000).await.unwrap().status(), StatusCode::OK);
+    }
+
+    println!("✓ Limits from user claims test passed");
+}
+
+#[tokio::test]
+async fn test_expansion_ratio_guard() {
+    use axum_jetpack::size_limit::{ExpansionGuardLayer, RouterExt, SizeLimitError};
+    use futures::StreamExt;
+    use tower::util::MapRequestLayer;
+
+    /// Stands in for a decompression layer: every byte decodes to `factor` bytes.
+    fn decoder(factor: usize) -> MapRequestLayer<impl Fn(Request<Body>) -> Request<Body> + Clone> {
+        MapRequestLayer::new(move |req: Request<Body>| {
+            let (parts, body) = req.into_parts();
+            let decoded = body
+                .into_data_stream()
+                .map(move |chunk| chunk.map(|chunk| Bytes::from(chunk.repeat(factor))));
+            Request::from_parts(parts, Body::from_stream(decoded))
+        })
+    }
+
+    for content_type in ["application/json", "video/mp4"] {
+        let app = |factor: usize| -> Router {
+            Router::new()
+                .route("/upload", post(|body: Bytes| async move { body.len().to_string() }))
+                .size_limit("100mb")
+                .layer(ExpansionGuardLayer::new(100, decoder(factor)).with_grace("1kb"))
+        };
+        let request = |encoding: Option<&str>| {
+            let mut builder = Request::builder()
+                .method("POST")
+                .uri("/upload")
+                .header("content-type", content_type);
+            if let Some(encoding) = encoding {
+                builder = builder.header("content-encoding", encoding);
+            }
+            let chunks = (0..5).map(|_| Ok::<_, std::io::Error>(Bytes::from(vec![b'x'; 20])));
+            builder.body(Body::from_stream(futures::stream::iter(chunks))).unwrap()
+        };
+
+        // A bomb is cut off well before the size limit
+        let response = app(1000).oneshot(request(Some("gzip"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE, "{}", content_type);
+        assert_eq!(response.extensions().get(), Some(&SizeLimitError::ExpansionRatioExceeded));
+
+        // Ordinary compression ratios pass
+        let response = app(10).oneshot(request(Some("gzip"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, "1000");
+
+        // Bodies without Content-Encoding are not checked
+        let response = app(500).oneshot(request(None)).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    println!("✓ Expansion ratio guard test passed");
+}
+
+#[tokio::test]
+async fn test_multipart_limits() {
+    use axum::extract::Multipart;
+    use axum_jetpack::size_limit::{
+        MultipartLimits, OversizedPart, SizeLimitError, SizeLimitMiddlewareConfig, with_size_limit,
+    };
+
+    async fn upload(mut multipart: Multipart) -> String {
+        let mut total = 0;
+        while let Ok(Some(field)) = multipart.next_field().await {
+            total += field.bytes().await.map_or(0, |bytes| bytes.len());
+        }
+        total.to_string()
+    }
+
+    /// A form with one text field and one file.
+    fn form(field: (&str, usize), file: (&str, usize)) -> Vec<u8> {
+        let mut body = Vec::new();
+        body.extend_from_slice(b"--XyZ\r\nContent-Disposition: form-data; name=\"");
+        body.extend_from_slice(field.0.as_bytes());
+        body.extend_from_slice(b"\"\r\n\r\n");
+        body.extend(std::iter::repeat_n(b'a', field.1));
+        body.extend_from_slice(b"\r\n--XyZ\r\nContent-Disposition: form-data; name=\"");
+        body.extend_from_slice(file.0.as_bytes());
+        body.extend_from_slice(b"\"; filename=\"f.bin\"\r\nContent-Type: application/octet-stream\r\n\r\n");
+        body.extend(std::iter::repeat_n(b'-', file.1));
+        body.extend_from_slice(b"\r\n--XyZ--\r\n");
+        body
+    }
+
+    let app = with_size_limit(
+        Router::new().route("/upload", post(upload)),
+        SizeLimitMiddlewareConfig::default().with_multipart_limits(
+            MultipartLimits::new()
+                .with_field_limit(SizeLimit::bytes(100))
+                .with_file_limit(SizeLimit::bytes(10_000))
+                .with_named_limit("avatar", SizeLimit::bytes(1_000)),
+        ),
+    );
+
+    // Small chunks split delimiters and headers across chunks
+    for chunk_size in [7, 64 * 1024] {
+        let send = |body: Vec<u8>| {
+            let chunks: Vec<Result<Bytes, std::io::Error>> =
+                body.chunks(chunk_size).map(|chunk| Ok(Bytes::copy_from_slice(chunk))).collect();
+            let req = Request::builder()
+                .method("POST")
+                .uri("/upload")
+                .header("content-type", "multipart/form-data; boundary=XyZ")
+                .body(Body::from_stream(futures::stream::iter(chunks)))
+                .unwrap();
+            app.clone().oneshot(req)
+        };
+
+        let response = send(form(("comment", 100), ("document", 10_000))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK, "chunks of {}", chunk_size);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, "10100");
+
+        // A text field over the field limit, even though the form is small
+        let response = send(form(("comment", 101), ("document", 10))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE, "chunks of {}", chunk_size);
+        assert_eq!(response.extensions().get(), Some(&SizeLimitError::FieldTooLarge));
+        let part = OversizedPart { name: "comment".to_string() };
+        assert_eq!(response.extensions().get(), Some(&part));
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, "Form field 'comment' too large");
+
+        // A file over the file limit, or over the limit of its field name
+        let response = send(form(("comment", 10), ("document", 10_001))).await.unwrap();
+        assert_eq!(response.extensions().get(), Some(&SizeLimitError::FileTooLarge));
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(body, "Uploaded file 'document' too large");
+        let response = send(form(("comment", 10), ("avatar", 1_001))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(response.extensions().get(), Some(&SizeLimitError::FileTooLarge));
+        let response = send(form(("comment", 10), ("avatar", 1_000))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    println!("✓ Multipart limits test passed");
+}
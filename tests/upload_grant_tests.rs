@@ -0,0 +1,112 @@
+// tests/upload_grant_tests.rs
+#![cfg(feature = "upload-grants")]
+#![allow(clippy::disallowed_methods)]
+
+use axum::{
+    body::Body,
+    extract::Request,
+    http::StatusCode,
+    routing::post,
+    Router,
+};
+use std::time::Duration;
+use tower::ServiceExt;
+
+use axum_jetpack::size_limit::{
+    with_size_limit, SizeLimit, SizeLimitConfig, SizeLimitMiddlewareConfig, UploadGrant,
+    UploadGrantKey, UPLOAD_GRANT_HEADER, UPLOAD_LIMIT_HEADER, UPLOAD_LIMIT_SIGNATURE_HEADER,
+};
+
+fn key() -> UploadGrantKey {
+    UploadGrantKey::new(b"test secret for upload grants".to_vec())
+}
+
+fn app() -> Router {
+    let router = Router::new()
+        .route("/videos/upload", post(|| async { "ok" }))
+        .route("/avatars", post(|| async { "ok" }));
+    let config = SizeLimitMiddlewareConfig::new(
+        SizeLimitConfig::default().with_default_limit(SizeLimit::bytes(8)),
+    )
+    .with_upload_grants(key());
+    with_size_limit(router, config)
+}
+
+async fn upload(uri: &str, token: Option<&str>) -> StatusCode {
+    let mut req = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-length", "32");
+    if let Some(token) = token {
+        req = req.header(UPLOAD_GRANT_HEADER, token);
+    }
+    let req = req.body(Body::from(vec![b'x'; 32])).unwrap();
+    app().oneshot(req).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_valid_grant_raises_limit() {
+    let token = key().mint(&UploadGrant::new("/videos/upload", SizeLimit::kib(1.0), Duration::from_secs(60)));
+
+    assert_eq!(upload("/videos/upload", None).await, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(upload("/videos/upload", Some(&token)).await, StatusCode::OK);
+}
+
+#[tokio::test]
+async fn test_grant_is_bound_to_route_expiry_and_key() {
+    let grant = UploadGrant::new("/videos/upload", SizeLimit::kib(1.0), Duration::from_secs(60));
+
+    // Other route
+    let token = key().mint(&grant);
+    assert_eq!(upload("/avatars", Some(&token)).await, StatusCode::PAYLOAD_TOO_LARGE);
+
+    // Expired
+    let expired = UploadGrant { expires_at: 1, ..grant.clone() };
+    assert_eq!(upload("/videos/upload", Some(&key().mint(&expired))).await, StatusCode::PAYLOAD_TOO_LARGE);
+
+    // Signed with another key
+    let forged = UploadGrantKey::new(b"attacker".to_vec()).mint(&grant);
+    assert_eq!(upload("/videos/upload", Some(&forged)).await, StatusCode::PAYLOAD_TOO_LARGE);
+
+    // Tampered payload
+    let (_, signature) = token.split_once('.').unwrap();
+    let tampered = format!("{}.{}", "OTk5OTk5OTk5Ojk5OTk5OTk5OTk6L3ZpZGVvcy91cGxvYWQ", signature);
+    assert!(key().verify(&tampered).is_err());
+}
+
+async fn upload_signed(uri: &str, limit: &str, signature: &str) -> StatusCode {
+    let req = Request::builder()
+        .method("POST")
+        .uri(uri)
+        .header("content-length", "32")
+        .header(UPLOAD_LIMIT_HEADER, limit)
+        .header(UPLOAD_LIMIT_SIGNATURE_HEADER, signature)
+        .body(Body::from(vec![b'x'; 32]))
+        .unwrap();
+    app().oneshot(req).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn test_signed_limit_header_raises_limit() {
+    let grant = UploadGrant::new("/videos/upload", SizeLimit::kib(1.0), Duration::from_secs(60));
+    let signature = key().sign_limit(&grant);
+
+    assert_eq!(upload_signed("/videos/upload", "1024", &signature).await, StatusCode::OK);
+
+    // Bound to the signed limit, path, expiry and key
+    assert_eq!(upload_signed("/videos/upload", "4096", &signature).await, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(upload_signed("/avatars", "1024", &signature).await, StatusCode::PAYLOAD_TOO_LARGE);
+    let expired = key().sign_limit(&UploadGrant { expires_at: 1, ..grant.clone() });
+    assert_eq!(upload_signed("/videos/upload", "1024", &expired).await, StatusCode::PAYLOAD_TOO_LARGE);
+    let forged = UploadGrantKey::new(b"attacker".to_vec()).sign_limit(&grant);
+    assert_eq!(upload_signed("/videos/upload", "1024", &forged).await, StatusCode::PAYLOAD_TOO_LARGE);
+    assert_eq!(upload_signed("/videos/upload", "1024", "garbage").await, StatusCode::PAYLOAD_TOO_LARGE);
+
+    // A grant token's signature does not pass as a signed limit, nor the other way round
+    let token = key().mint(&grant);
+    let (payload, grant_signature) = token.split_once('.').unwrap();
+    let borrowed = format!("{}.{}", grant.expires_at, grant_signature);
+    assert!(key().verify_limit("1024", &borrowed, "/videos/upload").is_err());
+    let (_, limit_signature) = signature.split_once('.').unwrap();
+    assert!(key().verify(&format!("{}.{}", payload, limit_signature)).is_err());
+}